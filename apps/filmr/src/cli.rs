@@ -3,8 +3,10 @@ use clap::{Parser, ValueEnum};
 use filmr::film::{FilmStock, FilmStockCollection};
 use filmr::presets;
 use filmr::processor::{
-    estimate_exposure_time, process_image, OutputMode, SimulationConfig, WhiteBalanceMode,
+    estimate_exposure_time, process_image, process_luma_image, LumaOutput, OutputMode,
+    SimulationConfig, WhiteBalanceMode,
 };
+use filmr::{color_transform_chart, color_transform_table, MaskConfig};
 use std::io::BufReader;
 use std::path::PathBuf;
 use std::time::Instant;
@@ -32,6 +34,11 @@ struct Args {
     #[arg(long)]
     load_preset: Option<PathBuf>,
 
+    /// Save how the primaries and a ColorChecker develop on the preset as a
+    /// labeled PNG, and print them as a table
+    #[arg(long)]
+    color_transform: Option<PathBuf>,
+
     /// Exposure time override (default: auto-estimated)
     #[arg(short, long)]
     exposure: Option<f32>,
@@ -40,19 +47,58 @@ struct Args {
     #[arg(short, long, default_value = "true")]
     grain: bool,
 
-    /// Output mode: positive or negative
+    /// Output mode: positive, negative, or negative scanned and inverted
     #[arg(short = 'm', long, value_enum, default_value_t = CliOutputMode::Positive)]
     mode: CliOutputMode,
 
     /// White balance mode
     #[arg(short = 'w', long, value_enum, default_value_t = CliWhiteBalance::Auto)]
     wb: CliWhiteBalance,
+
+    /// Output channels. Auto keeps grayscale input on a B&W stock single
+    /// channel, at the input bit depth
+    #[arg(short = 'c', long, value_enum, default_value_t = CliChannels::Auto)]
+    channels: CliChannels,
+
+    /// Skip the film and only round-trip the input through linear light,
+    /// for checking the pipeline
+    #[arg(long)]
+    bypass_film: bool,
+
+    /// Apply the film only within a luminance range, e.g. 0.5,1.0
+    #[arg(long, value_delimiter = ',', num_args = 2, conflicts_with = "mask_hue")]
+    mask_luminance: Option<Vec<f32>>,
+
+    /// Apply the film only within a hue range in degrees, e.g. 330,30
+    #[arg(long, value_delimiter = ',', num_args = 2)]
+    mask_hue: Option<Vec<f32>>,
+
+    /// Soft edge of the mask, in luminance (0-1) or degrees of hue
+    #[arg(long, default_value_t = 0.0)]
+    mask_feather: f32,
+
+    /// Apply the film outside the mask range instead
+    #[arg(long)]
+    mask_invert: bool,
+
+    /// Output size in pixels, e.g. 6000,4000. Grain is rendered for this size
+    #[arg(long, value_delimiter = ',', num_args = 2)]
+    target_size: Option<Vec<u32>>,
+
+    /// How much of the film look to apply over the original (0-1)
+    #[arg(long, default_value_t = 1.0)]
+    amount: f32,
+
+    /// Treat the input as linear light instead of sRGB-encoded
+    #[arg(long)]
+    linear_input: bool,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
 enum CliOutputMode {
     Positive,
     Negative,
+    Inverted,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -61,6 +107,14 @@ enum CliWhiteBalance {
     Off,
 }
 
+#[derive(ValueEnum, Clone, Debug)]
+enum CliChannels {
+    Auto,
+    Luma8,
+    Luma16,
+    Rgb,
+}
+
 pub fn run() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
@@ -76,18 +130,16 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         .unwrap_or(1);
 
     // Load and apply orientation
-    let img = {
+    let source = {
         let raw = image::open(&args.input)?;
-        apply_exif_orientation(raw, orientation).to_rgb8()
+        apply_exif_orientation(raw, orientation)
     };
+    let img = source.to_rgb8();
 
     let stock = if let Some(path) = &args.load_preset {
         println!("Loading custom preset from: {:?}", path);
         // Try to load as collection first
-        let file = std::fs::File::open(path)?;
-        let reader = std::io::BufReader::new(file);
-
-        if let Ok(collection) = serde_json::from_reader::<_, FilmStockCollection>(reader) {
+        if let Ok(collection) = FilmStockCollection::load_from_file(path) {
             println!("Detected preset collection.");
             // Try to find the preset specified by --preset argument
             if let Some(s) = collection.stocks.get(&args.preset) {
@@ -107,7 +159,7 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             std::rc::Rc::from(FilmStock::load_from_file(path)?)
         }
     } else {
-        find_preset(&args.preset).ok_or("Preset not found")?
+        presets::by_name(&args.preset).ok_or("Preset not found")?
     };
 
     if let Some(export_path) = &args.export_preset {
@@ -133,24 +185,74 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
     };
     println!("Exposure time: {:.4}s", exposure);
 
+    let mask = match (&args.mask_luminance, &args.mask_hue) {
+        (Some(range), _) => Some(MaskConfig::luminance(range[0], range[1], args.mask_feather)),
+        (None, Some(range)) => Some(MaskConfig::hue(range[0], range[1], args.mask_feather)),
+        (None, None) => None,
+    }
+    .map(|mask| {
+        if args.mask_invert {
+            mask.inverted()
+        } else {
+            mask
+        }
+    });
+
     let config = SimulationConfig {
         exposure_time: exposure,
         enable_grain: args.grain,
         output_mode: match args.mode {
             CliOutputMode::Positive => OutputMode::Positive,
             CliOutputMode::Negative => OutputMode::Negative,
+            CliOutputMode::Inverted => OutputMode::Inverted,
         },
         white_balance_mode: match args.wb {
             CliWhiteBalance::Auto => WhiteBalanceMode::Auto,
             CliWhiteBalance::Off => WhiteBalanceMode::Off,
         },
         white_balance_strength: 1.0,
+        bypass_film: args.bypass_film,
+        mask,
+        target_size: args.target_size.as_deref().map(|size| (size[0], size[1])),
+        effect_amount: args.amount,
+        input_is_linear: args.linear_input,
         ..Default::default()
     };
 
+    if let Some(path) = &args.color_transform {
+        println!("{}", color_transform_table(&stock, &config));
+        println!("Saving color transform chart to: {:?}", path);
+        color_transform_chart(&stock, &config).save(path)?;
+    }
+
+    // Grayscale input on a B&W stock skips the RGB conversion
+    let single_channel = stock.is_monochrome()
+        && matches!(
+            source,
+            image::DynamicImage::ImageLuma8(_) | image::DynamicImage::ImageLuma16(_)
+        );
+    let output = match args.channels {
+        CliChannels::Auto if single_channel => match source {
+            image::DynamicImage::ImageLuma8(_) => LumaOutput::Luma8,
+            _ => LumaOutput::Luma16,
+        },
+        CliChannels::Auto | CliChannels::Rgb => LumaOutput::Rgb8,
+        CliChannels::Luma8 => LumaOutput::Luma8,
+        CliChannels::Luma16 => LumaOutput::Luma16,
+    };
+
     println!("Processing...");
     let start = Instant::now();
-    let result = process_image(&img, &stock, &config);
+    let result = if single_channel {
+        process_luma_image(&source.to_luma16(), &stock, &config, output)
+    } else {
+        let rgb = image::DynamicImage::ImageRgb8(process_image(&img, &stock, &config));
+        match output {
+            LumaOutput::Luma8 => image::DynamicImage::ImageLuma8(rgb.to_luma8()),
+            LumaOutput::Luma16 => image::DynamicImage::ImageLuma16(rgb.to_luma16()),
+            LumaOutput::Rgb8 => rgb,
+        }
+    };
     let duration = start.elapsed();
     println!("Done in {:.2?}", duration);
 
@@ -159,18 +261,3 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
-
-fn find_preset(name: &str) -> Option<std::rc::Rc<FilmStock>> {
-    let stocks = presets::get_all_stocks();
-    let normalized_name = name.to_lowercase().replace("-", " ");
-
-    for stock in stocks {
-        let stock_name = stock.full_name();
-        if stock_name.to_lowercase() == normalized_name
-            || stock_name.to_lowercase().replace(" ", "-") == name.to_lowercase()
-        {
-            return Some(stock);
-        }
-    }
-    None
-}
@@ -1,5 +1,6 @@
 #[cfg(not(target_arch = "wasm32"))]
 use directories::UserDirs;
+use filmr::metrics::{HistogramAxis, HistogramSmoothing};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -16,19 +17,93 @@ pub enum UxMode {
     Professional,
 }
 
+/// Version written by this build. Version 1 files have no `ui` section;
+/// version 2 has an on/off `hist_smooth` instead of a smoothing kernel.
+pub const CONFIG_VERSION: u32 = 3;
+
+/// Kernel radius in histogram bins.
+pub const DEFAULT_HIST_SMOOTH_RADIUS: u32 = 2;
+
+/// Panel layout and metrics display options, restored on startup.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(default)]
+pub struct UiState {
+    pub show_metrics: bool,
+    pub split_view: bool,
+    pub hist_log_scale: bool,
+    pub hist_clamp_zeros: bool,
+    pub hist_smoothing: HistogramSmoothing,
+    pub hist_smooth_radius: u32,
+    /// Smoothing toggle of version 2 files, read to migrate them.
+    #[serde(skip_serializing)]
+    pub hist_smooth: bool,
+    pub hist_axis: HistogramAxis,
+    /// `FilmStock::stable_id` of the last selected stock.
+    pub last_stock: Option<String>,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            show_metrics: false,
+            split_view: false,
+            hist_log_scale: false,
+            hist_clamp_zeros: true,
+            hist_smoothing: HistogramSmoothing::default(),
+            hist_smooth_radius: DEFAULT_HIST_SMOOTH_RADIUS,
+            hist_smooth: false,
+            hist_axis: HistogramAxis::default(),
+            last_stock: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FilmrConfig {
+    #[serde(default = "legacy_config_version")]
+    pub version: u32,
     pub custom_stocks_path: PathBuf,
     #[serde(default = "default_ux_mode")]
     pub ux_mode: UxMode,
     #[serde(default)]
     pub suppress_model_prompt: bool,
+    /// Save develop settings next to each image as `<image>.filmr.json`.
+    #[serde(default = "default_write_sidecars")]
+    pub write_sidecars: bool,
+    #[serde(default)]
+    pub ui: UiState,
+    /// Recently opened images, most recent first.
+    #[serde(default)]
+    pub recent_files: Vec<PathBuf>,
+    /// Open the most recent image when the app starts.
+    #[serde(default)]
+    pub reopen_last_image: bool,
+}
+
+/// Longest `recent_files` list kept.
+pub const MAX_RECENT_FILES: usize = 10;
+
+impl FilmrConfig {
+    /// Move `path` to the front of the recent files.
+    pub fn add_recent_file(&mut self, path: PathBuf) {
+        self.recent_files.retain(|p| *p != path);
+        self.recent_files.insert(0, path);
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+}
+
+fn legacy_config_version() -> u32 {
+    1
 }
 
 fn default_ux_mode() -> UxMode {
     UxMode::Professional
 }
 
+fn default_write_sidecars() -> bool {
+    true
+}
+
 pub struct ConfigManager {
     pub config: FilmrConfig,
     pub root_path: PathBuf,
@@ -56,25 +131,40 @@ impl ConfigManager {
             let _ = fs::create_dir_all(&default_stocks_path);
         }
 
-        let config = if config_path.exists() {
+        let mut config = if config_path.exists() {
             if let Ok(content) = fs::read_to_string(&config_path) {
                 serde_json::from_str(&content).unwrap_or_else(|_| FilmrConfig {
+                    version: CONFIG_VERSION,
                     custom_stocks_path: default_stocks_path.clone(),
                     ux_mode: UxMode::Professional,
                     suppress_model_prompt: false,
+                    write_sidecars: true,
+                    ui: UiState::default(),
+                    recent_files: Vec::new(),
+                    reopen_last_image: false,
                 })
             } else {
                 FilmrConfig {
+                    version: CONFIG_VERSION,
                     custom_stocks_path: default_stocks_path.clone(),
                     ux_mode: UxMode::Professional,
                     suppress_model_prompt: false,
+                    write_sidecars: true,
+                    ui: UiState::default(),
+                    recent_files: Vec::new(),
+                    reopen_last_image: false,
                 }
             }
         } else {
             let config = FilmrConfig {
+                version: CONFIG_VERSION,
                 custom_stocks_path: default_stocks_path.clone(),
                 ux_mode: UxMode::Professional,
                 suppress_model_prompt: false,
+                write_sidecars: true,
+                ui: UiState::default(),
+                recent_files: Vec::new(),
+                reopen_last_image: false,
             };
             if let Ok(json) = serde_json::to_string_pretty(&config) {
                 let _ = fs::write(&config_path, json);
@@ -82,6 +172,14 @@ impl ConfigManager {
             config
         };
 
+        // Older files load with defaults for what they lack; the next save
+        // writes the current version
+        if config.version < 3 && config.ui.hist_smooth {
+            config.ui.hist_smoothing = HistogramSmoothing::Gaussian;
+            config.ui.hist_smooth = false;
+        }
+        config.version = CONFIG_VERSION;
+
         Some(Self {
             config,
             root_path,
@@ -0,0 +1,185 @@
+//! Non-destructive crop and straighten applied to the source before develop.
+//!
+//! The image is rotated about its center by [`CropSettings::angle`] and the
+//! crop rectangle is then taken from the rotated frame. Both happen before
+//! the film simulation so that resolution-dependent effects (grain, halation)
+//! see the final framing.
+
+use filmr::depth::DepthMap;
+use image::{ImageBuffer, Luma, Pixel, Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+
+/// Largest straighten angle in either direction, in degrees.
+pub const MAX_STRAIGHTEN_DEGREES: f32 = 15.0;
+
+/// Smallest crop edge, as a fraction of the frame.
+pub const MIN_CROP_FRACTION: f32 = 0.05;
+
+/// Crop rectangle in normalized (0-1) coordinates of the rotated frame,
+/// plus the straighten angle.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CropSettings {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    /// Clockwise rotation in degrees, within ±[`MAX_STRAIGHTEN_DEGREES`].
+    pub angle: f32,
+}
+
+impl Default for CropSettings {
+    fn default() -> Self {
+        Self {
+            left: 0.0,
+            top: 0.0,
+            right: 1.0,
+            bottom: 1.0,
+            angle: 0.0,
+        }
+    }
+}
+
+impl CropSettings {
+    /// True if applying the crop would return the image unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Clamp the rectangle into the frame, keep it at least
+    /// [`MIN_CROP_FRACTION`] wide and high, and limit the angle.
+    pub fn clamped(mut self) -> Self {
+        self.left = self.left.clamp(0.0, 1.0 - MIN_CROP_FRACTION);
+        self.top = self.top.clamp(0.0, 1.0 - MIN_CROP_FRACTION);
+        self.right = self.right.clamp(self.left + MIN_CROP_FRACTION, 1.0);
+        self.bottom = self.bottom.clamp(self.top + MIN_CROP_FRACTION, 1.0);
+        self.angle = self
+            .angle
+            .clamp(-MAX_STRAIGHTEN_DEGREES, MAX_STRAIGHTEN_DEGREES);
+        self
+    }
+
+    /// Size of the cropped output for a `width` x `height` source.
+    pub fn output_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let w = ((self.right - self.left) * width as f32).round() as u32;
+        let h = ((self.bottom - self.top) * height as f32).round() as u32;
+        (w.max(1), h.max(1))
+    }
+
+    /// Map a position inside the crop (0-1 on both axes) to normalized
+    /// source coordinates. Positions may fall outside 0-1 near rotated corners.
+    pub fn source_uv(&self, u: f32, v: f32, width: u32, height: u32) -> (f32, f32) {
+        let (w, h) = (width as f32, height as f32);
+        // Point on the rotated frame, in pixels relative to the center
+        let x = (self.left + u * (self.right - self.left) - 0.5) * w;
+        let y = (self.top + v * (self.bottom - self.top) - 0.5) * h;
+        // Undo the rotation to find where it came from in the source
+        let (sin, cos) = (-self.angle.to_radians()).sin_cos();
+        let sx = x * cos - y * sin;
+        let sy = x * sin + y * cos;
+        (sx / w + 0.5, sy / h + 0.5)
+    }
+
+    /// Rotate and crop `image`, sampling bilinearly with clamped edges.
+    pub fn apply(&self, image: &RgbImage) -> RgbImage {
+        if self.is_identity() {
+            return image.clone();
+        }
+        self.resample(image.dimensions(), |x, y| {
+            let rgb = sample_bilinear(image.dimensions(), x, y, |px, py| {
+                image.get_pixel(px, py).0.map(f32::from)
+            });
+            Rgb(rgb.map(|v| v.round() as u8))
+        })
+    }
+
+    /// [`apply`](Self::apply) for a 16-bit grayscale image.
+    pub fn apply_luma16(
+        &self,
+        image: &ImageBuffer<Luma<u16>, Vec<u16>>,
+    ) -> ImageBuffer<Luma<u16>, Vec<u16>> {
+        if self.is_identity() {
+            return image.clone();
+        }
+        self.resample(image.dimensions(), |x, y| {
+            let luma = sample_bilinear(image.dimensions(), x, y, |px, py| {
+                image.get_pixel(px, py).0.map(f32::from)
+            });
+            Luma(luma.map(|v| v.round() as u16))
+        })
+    }
+
+    /// Build the cropped image from `sample`, which takes pixel-center
+    /// coordinates in the `(src_w, src_h)` source.
+    fn resample<P: Pixel>(
+        &self,
+        (src_w, src_h): (u32, u32),
+        sample: impl Fn(f32, f32) -> P,
+    ) -> ImageBuffer<P, Vec<P::Subpixel>> {
+        let (out_w, out_h) = self.output_size(src_w, src_h);
+        ImageBuffer::from_fn(out_w, out_h, |x, y| {
+            let (u, v) = self.source_uv(
+                (x as f32 + 0.5) / out_w as f32,
+                (y as f32 + 0.5) / out_h as f32,
+                src_w,
+                src_h,
+            );
+            sample(u * src_w as f32 - 0.5, v * src_h as f32 - 0.5)
+        })
+    }
+
+    /// Rotate and crop a depth map the same way, so it stays aligned with
+    /// the cropped image.
+    pub fn apply_depth(&self, depth: &DepthMap) -> DepthMap {
+        if self.is_identity() {
+            return depth.clone();
+        }
+        let (out_w, out_h) = self.output_size(depth.width, depth.height);
+        let mut data = Vec::with_capacity((out_w * out_h) as usize);
+        for y in 0..out_h {
+            for x in 0..out_w {
+                let (u, v) = self.source_uv(
+                    (x as f32 + 0.5) / out_w as f32,
+                    (y as f32 + 0.5) / out_h as f32,
+                    depth.width,
+                    depth.height,
+                );
+                let sx = (u * depth.width as f32).clamp(0.0, depth.width as f32 - 1.0);
+                let sy = (v * depth.height as f32).clamp(0.0, depth.height as f32 - 1.0);
+                data.push(depth.get(sx as u32, sy as u32));
+            }
+        }
+        DepthMap {
+            data,
+            width: out_w,
+            height: out_h,
+        }
+    }
+}
+
+/// Bilinear sample at pixel-center coordinates, clamping to the edges.
+/// `pixel` reads the channels of the source pixel at `(x, y)`.
+fn sample_bilinear<const N: usize>(
+    (width, height): (u32, u32),
+    x: f32,
+    y: f32,
+    pixel: impl Fn(u32, u32) -> [f32; N],
+) -> [f32; N] {
+    let max_x = width as f32 - 1.0;
+    let max_y = height as f32 - 1.0;
+    let x = x.clamp(0.0, max_x);
+    let y = y.clamp(0.0, max_y);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (x1, y1) = ((x0 + 1.0).min(max_x), (y0 + 1.0).min(max_y));
+    let (fx, fy) = (x - x0, y - y0);
+
+    let p00 = pixel(x0 as u32, y0 as u32);
+    let p10 = pixel(x1 as u32, y0 as u32);
+    let p01 = pixel(x0 as u32, y1 as u32);
+    let p11 = pixel(x1 as u32, y1 as u32);
+
+    std::array::from_fn(|c| {
+        let top = p00[c] * (1.0 - fx) + p10[c] * fx;
+        let bottom = p01[c] * (1.0 - fx) + p11[c] * fx;
+        top * (1.0 - fy) + bottom * fy
+    })
+}
@@ -1,4 +1,5 @@
-//! EXIF orientation utilities for reading and applying image orientation transforms.
+//! EXIF utilities: reading and applying image orientation transforms, and
+//! recording the develop recipe in exported images.
 
 use image::DynamicImage;
 use std::io::Seek;
@@ -41,3 +42,97 @@ pub fn apply_exif_orientation(img: DynamicImage, orientation: u32) -> DynamicIma
         _ => img, // 1 or unknown: no transform
     }
 }
+
+/// Mark metadata copied from a source as upright (orientation 1).
+///
+/// Images are loaded through [`apply_exif_orientation`], so the pixels written
+/// out are already turned; keeping the source's tag would make viewers turn
+/// them a second time.
+pub fn reset_exif_orientation(metadata: &mut little_exif::metadata::Metadata) {
+    metadata.set_tag(little_exif::exif_tag::ExifTag::Orientation(vec![1u16]));
+}
+
+/// Character code that starts an EXIF UserComment in ASCII.
+const USER_COMMENT_ASCII: &[u8; 8] = b"ASCII\0\0\0";
+
+/// Record `recipe` as compact JSON in the UserComment tag, so the develop
+/// settings can be recovered from an exported file with [`read_exif_recipe`].
+pub fn embed_exif_recipe(metadata: &mut little_exif::metadata::Metadata, recipe: &filmr::Recipe) {
+    let Ok(json) = recipe.to_json() else {
+        return;
+    };
+    let mut comment = USER_COMMENT_ASCII.to_vec();
+    comment.extend_from_slice(json.as_bytes());
+    metadata.set_tag(little_exif::exif_tag::ExifTag::UserComment(comment));
+}
+
+/// The recipe stored by [`embed_exif_recipe`], or `None` if the image has no
+/// UserComment or it is not a Filmr recipe.
+pub fn read_exif_recipe<R: std::io::BufRead + Seek>(reader: &mut R) -> Option<filmr::Recipe> {
+    let exif = exif::Reader::new().read_from_container(reader).ok()?;
+    let field = exif.get_field(exif::Tag::UserComment, exif::In::PRIMARY)?;
+    let exif::Value::Undefined(bytes, _) = &field.value else {
+        return None;
+    };
+    let json = std::str::from_utf8(bytes.strip_prefix(USER_COMMENT_ASCII)?).ok()?;
+    filmr::Recipe::from_json(json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use little_exif::exif_tag::ExifTag;
+    use little_exif::filetype::FileExtension;
+    use little_exif::metadata::Metadata;
+
+    /// A JPEG carrying `metadata`.
+    fn written_jpeg(metadata: &Metadata) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        image::RgbImage::new(4, 2)
+            .write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Jpeg,
+            )
+            .unwrap();
+        metadata
+            .write_to_vec(&mut bytes, FileExtension::JPEG)
+            .unwrap();
+        bytes
+    }
+
+    /// Orientation tag of a JPEG carrying `metadata`.
+    fn written_orientation(metadata: &Metadata) -> u32 {
+        read_exif_orientation(&mut std::io::Cursor::new(written_jpeg(metadata)))
+    }
+
+    #[test]
+    fn test_reset_exif_orientation_on_rotated_source() {
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::Orientation(vec![6u16]));
+        assert_eq!(written_orientation(&metadata), 6);
+
+        reset_exif_orientation(&mut metadata);
+        assert_eq!(written_orientation(&metadata), 1);
+    }
+
+    #[test]
+    fn test_exif_recipe_round_trips() {
+        let mut metadata = Metadata::new();
+        metadata.set_tag(ExifTag::Orientation(vec![1u16]));
+        let read = |metadata: &Metadata| {
+            read_exif_recipe(&mut std::io::Cursor::new(written_jpeg(metadata)))
+        };
+        assert_eq!(read(&metadata), None);
+
+        let recipe = filmr::Recipe::new(
+            "Pushed",
+            &filmr::presets::kodak::KODAK_TRI_X_400(),
+            filmr::SimulationConfig::builder()
+                .exposure_time(2.0)
+                .build(),
+            filmr::StockOverrides::default(),
+        );
+        embed_exif_recipe(&mut metadata, &recipe);
+        assert_eq!(read(&metadata), Some(recipe));
+    }
+}
@@ -32,7 +32,7 @@ pub fn process_image_with_metrics(
     config: &SimulationConfig,
 ) -> (RgbImage, FilmMetrics) {
     let processed = process_image(image, film, config);
-    let metrics = FilmMetrics::analyze(&processed);
+    let metrics = FilmMetrics::analyze_with_stock(&processed, image, film, config);
     (processed, metrics)
 }
 
@@ -42,7 +42,7 @@ pub async fn process_image_with_metrics_async(
     config: &SimulationConfig,
 ) -> (RgbImage, FilmMetrics) {
     let processed = process_image_async(image, film, config).await;
-    let metrics = FilmMetrics::analyze(&processed);
+    let metrics = FilmMetrics::analyze_with_stock(&processed, image, film, config);
     (processed, metrics)
 }
 
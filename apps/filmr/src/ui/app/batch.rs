@@ -0,0 +1,175 @@
+//! Batch export: develop a list of images with shared or per-image settings.
+
+use super::io::{encode_output, filmr_exif_metadata};
+use super::workers::{luma_source, process_worker_logic, spawn_thread, ProcessRequest};
+use super::{DevelopParams, FilmrApp};
+use crate::crop::CropSettings;
+use filmr::{FilmStock, Recipe, SimulationConfig};
+use flume::{Receiver, Sender};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// One image of a batch export.
+pub struct BatchJob {
+    pub source: PathBuf,
+    pub output: PathBuf,
+    pub film: FilmStock,
+    pub config: SimulationConfig,
+    pub crop: CropSettings,
+    /// Embedded in the output's EXIF.
+    pub recipe: Recipe,
+}
+
+/// Outcome of one [`BatchJob`].
+pub struct BatchResult {
+    pub source: PathBuf,
+    pub result: Result<PathBuf, String>,
+}
+
+/// Progress of the running or last batch export.
+#[derive(Debug, Default)]
+pub struct BatchState {
+    pub total: usize,
+    pub done: usize,
+    pub failures: Vec<(PathBuf, String)>,
+    pub show_window: bool,
+}
+
+impl BatchState {
+    pub fn is_running(&self) -> bool {
+        self.done < self.total
+    }
+}
+
+/// Develop and save one image. Errors are reported, not propagated, so one
+/// bad file does not abort the batch.
+fn export_one(job: BatchJob) -> Result<PathBuf, String> {
+    use crate::exif_utils::{apply_exif_orientation, read_exif_orientation};
+
+    let orientation = std::fs::File::open(&job.source)
+        .map(|f| read_exif_orientation(&mut std::io::BufReader::new(f)))
+        .unwrap_or(1);
+    let img = image::open(&job.source).map_err(|e| e.to_string())?;
+    let img = apply_exif_orientation(img, orientation);
+    let luma = luma_source(&img, &job.film).map(|luma| Arc::new(job.crop.apply_luma16(&luma)));
+    let rgb = img.to_rgb8();
+    let rgb = if job.crop.is_identity() {
+        rgb
+    } else {
+        job.crop.apply(&rgb)
+    };
+
+    let processed = process_worker_logic(
+        ProcessRequest {
+            image: Arc::new(rgb),
+            film: job.film,
+            config: job.config,
+            is_preview: false,
+            depth_map: None,
+            luma,
+        },
+        None,
+    );
+
+    let ext = job
+        .output
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("jpg")
+        .to_lowercase();
+    let developed = match processed.luma {
+        Some(luma) => image::DynamicImage::ImageLuma16(luma),
+        None => image::DynamicImage::ImageRgb8(processed.image),
+    };
+    let bytes = encode_output(&developed, &ext, || {
+        let source_exif = little_exif::metadata::Metadata::new_from_path(&job.source).ok();
+        filmr_exif_metadata(source_exif, &job.recipe)
+    })
+    .map_err(|e| e.to_string())?;
+    std::fs::write(&job.output, bytes).map_err(|e| e.to_string())?;
+    Ok(job.output)
+}
+
+impl FilmrApp {
+    pub(crate) fn spawn_batch_worker(
+        rx_jobs: Receiver<BatchJob>,
+        tx_results: Sender<BatchResult>,
+        ctx: egui::Context,
+    ) {
+        spawn_thread(move || {
+            while let Ok(job) = rx_jobs.recv() {
+                let source = job.source.clone();
+                let result =
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| export_one(job)))
+                        .unwrap_or_else(|_| Err("processing panicked".to_string()));
+                let _ = tx_results.send(BatchResult { source, result });
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    /// Ask for images and an output folder, then queue them for export.
+    ///
+    /// Each image uses its sidecar session if present, otherwise the current
+    /// develop parameters.
+    pub fn start_batch_export(&mut self) {
+        if self.batch.is_running() {
+            return;
+        }
+        let Some(sources) = rfd::FileDialog::new()
+            .add_filter("Images", &["jpg", "jpeg", "png", "tif", "tiff", "webp"])
+            .pick_files()
+        else {
+            return;
+        };
+        let Some(folder) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+
+        let current = self.develop_params();
+        self.batch = BatchState {
+            total: sources.len(),
+            show_window: true,
+            ..Default::default()
+        };
+        for source in sources {
+            // The open image's crop does not carry over to other frames
+            let params = self.load_session(&source).unwrap_or_else(|| DevelopParams {
+                crop: CropSettings::default(),
+                ..current.clone()
+            });
+            let stem = source
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "filmr_output".to_string());
+            let job = BatchJob {
+                output: folder.join(format!("{}_FILMR.jpg", stem)),
+                recipe: self.params_recipe(&stem, &params),
+                film: self.develop_film(&params),
+                config: self.develop_config(&params),
+                crop: params.crop,
+                source,
+            };
+            let _ = self.tx_batch.send(job);
+        }
+        self.status_msg = format!("Exporting {} image(s)...", self.batch.total);
+    }
+
+    /// Collect finished batch jobs.
+    pub(crate) fn poll_batch(&mut self) {
+        while let Ok(res) = self.rx_batch.try_recv() {
+            self.batch.done += 1;
+            if let Err(e) = res.result {
+                log::warn!("Batch export of {:?} failed: {}", res.source, e);
+                self.batch.failures.push((res.source, e));
+            }
+            if !self.batch.is_running() {
+                self.status_msg = format!(
+                    "Exported {} of {} image(s)",
+                    self.batch.total - self.batch.failures.len(),
+                    self.batch.total
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! One-click exposure from the preview metrics, keyboard exposure and
+//! contrast steps, and clipping warnings.
+
+use super::FilmrApp;
+use egui::{Color32, ColorImage, Context, Key, Modifiers};
+use filmr::utils::{resize_to_fit_linear, PREVIEW_FILTER};
+use filmr::FilmMetrics;
+use image::RgbImage;
+
+/// Largest fraction of channel samples allowed at 255.
+const HIGHLIGHT_CLIP_TARGET: f32 = 0.005;
+
+/// Largest fraction of channel samples allowed at 0.
+const SHADOW_CLIP_TARGET: f32 = 0.02;
+
+/// Lowest p10 of the darkest channel before shadows count as too dark.
+const SHADOW_FLOOR: u8 = 12;
+
+/// p99 above which there is no headroom left to brighten into.
+const HIGHLIGHT_HEADROOM: u8 = 250;
+
+/// Maximum number of preview round trips per Auto click.
+const MAX_AUTO_EXPOSURE_STEPS: u8 = 8;
+
+/// Exposure time range of the exposure sliders, in seconds.
+pub const EXPOSURE_RANGE: std::ops::RangeInclusive<f32> = 0.001..=30.0;
+
+/// Contrast range of the contrast slider.
+pub const CONTRAST_RANGE: std::ops::RangeInclusive<f32> = 0.5..=2.0;
+
+/// Contrast change per `-` / `=` key press.
+const CONTRAST_STEP: f32 = 0.05;
+
+const BLOWN_COLOR: Color32 = Color32::from_rgb(255, 40, 40);
+const CRUSHED_COLOR: Color32 = Color32::from_rgb(40, 110, 255);
+const SHOULDER_COLOR: Color32 = Color32::from_rgb(255, 200, 0);
+
+/// Longest edge of the shoulder overlay; it is stretched over the image.
+const SHOULDER_OVERLAY_SIZE: u32 = 512;
+
+/// Progress of a running Auto exposure.
+#[derive(Debug, Default)]
+pub struct AutoExposure {
+    steps: u8,
+    darkened: bool,
+    brightened: bool,
+}
+
+/// Exposure change in EV suggested by `metrics`, or None if the preview
+/// already meets the clipping targets. Highlights take priority: shadows are
+/// only lifted while the brightest channel has headroom.
+fn exposure_correction(metrics: &FilmMetrics) -> Option<f32> {
+    let [crushed, blown] = metrics.clipping_ratio;
+    let p10 = metrics.quantiles_rgb.iter().map(|q| q[0]).min()?;
+    let p99 = metrics.quantiles_rgb.iter().map(|q| q[3]).max()?;
+
+    if blown > HIGHLIGHT_CLIP_TARGET {
+        // Bigger steps when far over the target
+        Some(if blown > 10.0 * HIGHLIGHT_CLIP_TARGET {
+            -1.0
+        } else {
+            -1.0 / 3.0
+        })
+    } else if (crushed > SHADOW_CLIP_TARGET || p10 < SHADOW_FLOOR) && p99 < HIGHLIGHT_HEADROOM {
+        Some(1.0 / 3.0)
+    } else {
+        None
+    }
+}
+
+/// `seconds` moved by `thirds` third stops, snapped to the third-stop scale
+/// through 1 s that camera shutter speeds follow.
+fn step_exposure_thirds(seconds: f32, thirds: i32) -> f32 {
+    let current = (3.0 * seconds.max(f32::MIN_POSITIVE).log2()).round() as i32;
+    2f32.powf((current + thirds) as f32 / 3.0)
+        .clamp(*EXPOSURE_RANGE.start(), *EXPOSURE_RANGE.end())
+}
+
+/// `contrast` moved by `steps` steps of [`CONTRAST_STEP`], snapped to them.
+fn step_contrast(contrast: f32, steps: i32) -> f32 {
+    let current = (contrast / CONTRAST_STEP).round() as i32;
+    ((current + steps) as f32 * CONTRAST_STEP).clamp(*CONTRAST_RANGE.start(), *CONTRAST_RANGE.end())
+}
+
+/// Overlay marking blown (any channel at 255) and crushed (any channel at 0)
+/// pixels, transparent elsewhere.
+fn clip_warning_image(image: &RgbImage) -> ColorImage {
+    let pixels: Vec<u8> = image
+        .pixels()
+        .flat_map(|p| {
+            let color = if p.0.contains(&255) {
+                BLOWN_COLOR.gamma_multiply(0.8)
+            } else if p.0.contains(&0) {
+                CRUSHED_COLOR.gamma_multiply(0.8)
+            } else {
+                Color32::TRANSPARENT
+            };
+            color.to_srgba_unmultiplied()
+        })
+        .collect();
+    ColorImage::from_rgba_unmultiplied([image.width() as usize, image.height() as usize], &pixels)
+}
+
+/// Overlay marking pixels past the stock's shoulder, more opaque toward
+/// d_max. Unlike [`clip_warning_image`] this follows the film curve, not the
+/// 8-bit output.
+fn shoulder_clip_image(overflow: &image::ImageBuffer<image::Luma<f32>, Vec<f32>>) -> ColorImage {
+    let pixels: Vec<u8> = overflow
+        .pixels()
+        .flat_map(|p| {
+            let color = if p.0[0] > 0.0 {
+                SHOULDER_COLOR.gamma_multiply(0.3 + 0.6 * p.0[0])
+            } else {
+                Color32::TRANSPARENT
+            };
+            color.to_srgba_unmultiplied()
+        })
+        .collect();
+    ColorImage::from_rgba_unmultiplied(
+        [overflow.width() as usize, overflow.height() as usize],
+        &pixels,
+    )
+}
+
+impl FilmrApp {
+    /// Start adjusting the exposure time from the preview metrics.
+    /// Each new preview moves the exposure until the targets are met.
+    pub fn start_auto_exposure(&mut self, ctx: &Context) {
+        if self.preview_image.is_none() {
+            return;
+        }
+        self.auto_exposure = Some(AutoExposure::default());
+        match self.metrics_preview.take() {
+            // Reuse the metrics of the current preview if there are any
+            Some(metrics) => {
+                self.step_auto_exposure(ctx, &metrics);
+                self.metrics_preview = Some(metrics);
+            }
+            None => self.process_and_update_texture(ctx),
+        }
+    }
+
+    /// Advance a running Auto exposure with the metrics of a new preview.
+    pub(crate) fn step_auto_exposure(&mut self, ctx: &Context, metrics: &FilmMetrics) {
+        let Some(mut state) = self.auto_exposure.take() else {
+            return;
+        };
+
+        let ev = match exposure_correction(metrics) {
+            // Overshot while lifting the shadows: back off half a step
+            Some(ev) if ev < 0.0 && state.brightened => Some(ev / 2.0),
+            // Never trade clipped highlights for shadows
+            Some(ev) if ev > 0.0 && state.darkened => None,
+            ev => ev,
+        };
+        let finished = match ev {
+            Some(ev) => {
+                state.steps += 1;
+                state.darkened |= ev < 0.0;
+                state.brightened |= ev > 0.0;
+                self.exposure_time = (self.exposure_time * 2f32.powf(ev))
+                    .clamp(*EXPOSURE_RANGE.start(), *EXPOSURE_RANGE.end());
+                self.process_and_update_texture(ctx);
+                state.steps >= MAX_AUTO_EXPOSURE_STEPS || (state.darkened && state.brightened)
+            }
+            None => true,
+        };
+
+        if !finished {
+            self.auto_exposure = Some(state);
+        } else {
+            self.status_msg = format!("Auto exposure: {:.2}\"", self.exposure_time);
+        }
+    }
+
+    /// `[` / `]` step the exposure by a third of a stop and `-` / `=` step
+    /// the contrast, re-developing after each press. Crop mode keeps the
+    /// brackets for the angle.
+    pub(crate) fn update_exposure_shortcuts(&mut self, ctx: &Context) {
+        if self.crop_mode || ctx.wants_keyboard_input() {
+            return;
+        }
+        let (darker, brighter, flatter, harder) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::OpenBracket),
+                i.consume_key(Modifiers::NONE, Key::CloseBracket),
+                i.consume_key(Modifiers::NONE, Key::Minus),
+                i.consume_key(Modifiers::NONE, Key::Equals),
+            )
+        });
+        let thirds = brighter as i32 - darker as i32;
+        let steps = harder as i32 - flatter as i32;
+        if thirds == 0 && steps == 0 {
+            return;
+        }
+
+        if thirds != 0 {
+            self.exposure_time = step_exposure_thirds(self.exposure_time, thirds);
+            self.status_msg = format!("Exposure: {:.3}\"", self.exposure_time);
+        }
+        if steps != 0 {
+            self.gamma_boost = step_contrast(self.gamma_boost, steps);
+            self.status_msg = format!("Contrast: {:.2}", self.gamma_boost);
+        }
+        self.process_and_update_texture(ctx);
+        self.regenerate_thumbnails();
+    }
+
+    /// Rebuild the clipping overlay for a new processed image.
+    pub(crate) fn update_clip_warnings(&mut self, ctx: &Context, image: &RgbImage) {
+        self.clip_texture = self.show_clip_warnings.then(|| {
+            ctx.load_texture(
+                "clip_warnings",
+                clip_warning_image(image),
+                egui::TextureOptions::NEAREST,
+            )
+        });
+        self.update_shoulder_clip(ctx);
+    }
+
+    /// Rebuild the shoulder overlay from the cropped source.
+    fn update_shoulder_clip(&mut self, ctx: &Context) {
+        self.shoulder_clip_texture = None;
+        let Some(source) = self
+            .preview_image
+            .as_ref()
+            .filter(|_| self.show_shoulder_clip)
+        else {
+            return;
+        };
+        let source = if self.crop.is_identity() {
+            source.as_ref().clone()
+        } else {
+            self.crop.apply(source)
+        };
+        let source = resize_to_fit_linear(&source, SHOULDER_OVERLAY_SIZE, PREVIEW_FILTER);
+        let overflow =
+            filmr::shoulder_overflow(&source, &self.preview_film(), &self.preview_config());
+        self.shoulder_clip_texture = Some(ctx.load_texture(
+            "shoulder_clip",
+            shoulder_clip_image(&overflow),
+            egui::TextureOptions::LINEAR,
+        ));
+    }
+}
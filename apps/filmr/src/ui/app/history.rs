@@ -0,0 +1,254 @@
+//! Undo/redo history for develop parameters.
+
+use super::FilmrApp;
+use crate::crop::CropSettings;
+use egui::Context;
+use filmr::{
+    light_leak::LightLeakConfig, BwFilter, DevelopmentParams, DitherMode, FilmStyle, GrainSpace,
+    LensConfig, NegativeInversion, OutputMode, OutputTransfer, ScannerProfile, ShutterSpeed,
+    Toning, WhiteBalanceMode, WorkingSpace,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Maximum number of undo steps kept.
+const MAX_HISTORY: usize = 50;
+
+/// Snapshot of every develop-relevant parameter of [`FilmrApp`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DevelopParams {
+    /// Index into the current stock list; sessions store the stock name instead.
+    #[serde(skip)]
+    pub selected_stock_idx: usize,
+    pub film_style: FilmStyle,
+    pub exposure_time: f32,
+    #[serde(default)]
+    pub shutter_speed: Option<ShutterSpeed>,
+    pub gamma_boost: f32,
+    pub warmth: f32,
+    pub saturation: f32,
+    #[serde(default)]
+    pub bw_filter: BwFilter,
+    #[serde(default)]
+    pub toning: Toning,
+    pub halation_strength: f32,
+    pub halation_threshold: f32,
+    pub halation_sigma: f32,
+    pub grain_alpha: f32,
+    pub grain_sigma: f32,
+    pub grain_roughness: f32,
+    pub grain_blur_radius: f32,
+    pub motion_blur_amount: f32,
+    pub motion_blur_seed: u64,
+    pub object_motion_amount: f32,
+    pub auto_levels: bool,
+    pub dof_amount: f32,
+    pub dof_focus: f32,
+    pub dof_swirl: f32,
+    pub rotational_blur_amount: f32,
+    #[serde(default)]
+    pub crop: CropSettings,
+    pub light_leak_config: LightLeakConfig,
+    pub output_mode: OutputMode,
+    #[serde(default)]
+    pub inversion: NegativeInversion,
+    #[serde(default)]
+    pub output_transfer: OutputTransfer,
+    #[serde(default)]
+    pub working_space: WorkingSpace,
+    #[serde(default)]
+    pub dither: DitherMode,
+    #[serde(default)]
+    pub grain_space: GrainSpace,
+    pub white_balance_mode: WhiteBalanceMode,
+    pub white_balance_strength: f32,
+    #[serde(default)]
+    pub development: DevelopmentParams,
+    #[serde(default)]
+    pub scanner: Option<ScannerProfile>,
+    #[serde(default)]
+    pub lens: LensConfig,
+    #[serde(default = "default_effect_amount")]
+    pub effect_amount: f32,
+}
+
+fn default_effect_amount() -> f32 {
+    1.0
+}
+
+/// Bounded undo/redo stacks of [`DevelopParams`].
+#[derive(Debug, Default)]
+pub struct ParamHistory {
+    undo: VecDeque<DevelopParams>,
+    redo: Vec<DevelopParams>,
+    /// Last state pushed to the history, i.e. what undo returns to.
+    committed: Option<DevelopParams>,
+}
+
+impl ParamHistory {
+    /// Record `current` as a new step if it differs from the last committed state.
+    /// Returns true if a step was recorded.
+    pub fn commit(&mut self, current: DevelopParams) -> bool {
+        if self.committed.as_ref() == Some(&current) {
+            return false;
+        }
+        match self.committed.replace(current) {
+            Some(previous) => {
+                self.undo.push_back(previous);
+                if self.undo.len() > MAX_HISTORY {
+                    self.undo.pop_front();
+                }
+                self.redo.clear();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop all steps and start over from `current`.
+    pub fn reset(&mut self, current: DevelopParams) {
+        self.undo.clear();
+        self.redo.clear();
+        self.committed = Some(current);
+    }
+
+    pub fn undo(&mut self) -> Option<DevelopParams> {
+        let previous = self.undo.pop_back()?;
+        if let Some(current) = self.committed.replace(previous.clone()) {
+            self.redo.push(current);
+        }
+        Some(previous)
+    }
+
+    pub fn redo(&mut self) -> Option<DevelopParams> {
+        let next = self.redo.pop()?;
+        if let Some(current) = self.committed.replace(next.clone()) {
+            self.undo.push_back(current);
+        }
+        Some(next)
+    }
+}
+
+impl FilmrApp {
+    /// Snapshot the current develop parameters.
+    pub fn develop_params(&self) -> DevelopParams {
+        DevelopParams {
+            selected_stock_idx: self.selected_stock_idx,
+            film_style: self.film_style,
+            exposure_time: self.exposure_time,
+            shutter_speed: self.shutter_speed,
+            gamma_boost: self.gamma_boost,
+            warmth: self.warmth,
+            saturation: self.saturation,
+            bw_filter: self.bw_filter,
+            toning: self.toning,
+            halation_strength: self.halation_strength,
+            halation_threshold: self.halation_threshold,
+            halation_sigma: self.halation_sigma,
+            grain_alpha: self.grain_alpha,
+            grain_sigma: self.grain_sigma,
+            grain_roughness: self.grain_roughness,
+            grain_blur_radius: self.grain_blur_radius,
+            motion_blur_amount: self.motion_blur_amount,
+            motion_blur_seed: self.motion_blur_seed,
+            object_motion_amount: self.object_motion_amount,
+            auto_levels: self.auto_levels,
+            dof_amount: self.dof_amount,
+            dof_focus: self.dof_focus,
+            dof_swirl: self.dof_swirl,
+            rotational_blur_amount: self.rotational_blur_amount,
+            crop: self.crop,
+            light_leak_config: self.light_leak_config.clone(),
+            output_mode: self.output_mode,
+            inversion: self.inversion,
+            output_transfer: self.output_transfer,
+            working_space: self.working_space,
+            dither: self.dither,
+            grain_space: self.grain_space,
+            white_balance_mode: self.white_balance_mode,
+            white_balance_strength: self.white_balance_strength,
+            development: self.development,
+            scanner: self.scanner,
+            lens: self.lens,
+            effect_amount: self.effect_amount,
+        }
+    }
+
+    /// Restore a snapshot taken by [`FilmrApp::develop_params`].
+    pub fn apply_develop_params(&mut self, params: DevelopParams) {
+        self.selected_stock_idx = params.selected_stock_idx.min(self.stocks.len() - 1);
+        self.film_style = params.film_style;
+        self.exposure_time = params.exposure_time;
+        self.shutter_speed = params.shutter_speed;
+        self.gamma_boost = params.gamma_boost;
+        self.warmth = params.warmth;
+        self.saturation = params.saturation;
+        self.bw_filter = params.bw_filter;
+        self.toning = params.toning;
+        self.halation_strength = params.halation_strength;
+        self.halation_threshold = params.halation_threshold;
+        self.halation_sigma = params.halation_sigma;
+        self.grain_alpha = params.grain_alpha;
+        self.grain_sigma = params.grain_sigma;
+        self.grain_roughness = params.grain_roughness;
+        self.grain_blur_radius = params.grain_blur_radius;
+        self.motion_blur_amount = params.motion_blur_amount;
+        self.motion_blur_seed = params.motion_blur_seed;
+        self.object_motion_amount = params.object_motion_amount;
+        self.auto_levels = params.auto_levels;
+        self.dof_amount = params.dof_amount;
+        self.dof_focus = params.dof_focus;
+        self.dof_swirl = params.dof_swirl;
+        self.rotational_blur_amount = params.rotational_blur_amount;
+        self.crop = params.crop;
+        self.light_leak_config = params.light_leak_config;
+        self.output_mode = params.output_mode;
+        self.inversion = params.inversion;
+        self.output_transfer = params.output_transfer;
+        self.working_space = params.working_space;
+        self.dither = params.dither;
+        self.grain_space = params.grain_space;
+        self.white_balance_mode = params.white_balance_mode;
+        self.white_balance_strength = params.white_balance_strength;
+        self.development = params.development;
+        self.scanner = params.scanner;
+        self.lens = params.lens;
+        self.effect_amount = params.effect_amount;
+    }
+
+    /// Handle Ctrl+Z / Ctrl+Shift+Z and record finished edits.
+    ///
+    /// A step is recorded once no pointer button is held, so a slider drag
+    /// becomes a single step when it is released.
+    pub(crate) fn update_history(&mut self, ctx: &Context) {
+        use egui::{Key, KeyboardShortcut, Modifiers};
+
+        // Leave text fields their own undo
+        if !ctx.wants_keyboard_input() {
+            let redo = KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z);
+            let undo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+            // Check redo first: the undo shortcut also matches with Shift held
+            let restored = if ctx.input_mut(|i| i.consume_shortcut(&redo)) {
+                self.history.redo()
+            } else if ctx.input_mut(|i| i.consume_shortcut(&undo)) {
+                self.history.undo()
+            } else {
+                None
+            };
+            if let Some(params) = restored {
+                self.apply_develop_params(params);
+                self.process_and_update_texture(ctx);
+                self.write_session();
+                return;
+            }
+        }
+
+        // Auto exposure becomes one step once it settles
+        if !ctx.input(|i| i.pointer.any_down())
+            && self.auto_exposure.is_none()
+            && self.history.commit(self.develop_params())
+        {
+            self.write_session();
+        }
+    }
+}
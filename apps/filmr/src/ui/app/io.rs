@@ -1,27 +1,134 @@
 //! File I/O methods for FilmrApp.
 
+use super::workers::LoadRequest;
 use super::FilmrApp;
+use crate::config::AppMode;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 impl FilmrApp {
-    /// Build EXIF metadata with Filmr processing info.
+    /// Load an image from a path or dropped bytes on the load worker.
+    pub(crate) fn load_image(&mut self, path: Option<PathBuf>, bytes: Option<Arc<[u8]>>) {
+        let path_str = path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| "dropped file".to_owned());
+
+        self.status_msg = format!("Loading {}...", path_str);
+        self.is_loading = true;
+        let stock = if self.mode == AppMode::Develop {
+            Some(self.get_current_stock().as_ref().clone())
+        } else {
+            None
+        };
+        let _ = self.tx_load.send(LoadRequest { path, bytes, stock });
+    }
+
+    /// Open an image from the recent files, dropping it from the list if it
+    /// is gone.
+    pub fn open_recent(&mut self, path: PathBuf) {
+        if path.is_file() {
+            self.load_image(Some(path), None);
+            return;
+        }
+        self.status_msg = format!("{} no longer exists", path.display());
+        if let Some(cm) = &mut self.config_manager {
+            cm.config.recent_files.retain(|p| *p != path);
+            cm.save();
+        }
+    }
+
+    /// Reopen the most recent image that still exists, if enabled.
+    pub(crate) fn reopen_last_image(&mut self) {
+        let Some(cm) = &self.config_manager else {
+            return;
+        };
+        if !cm.config.reopen_last_image {
+            return;
+        }
+        if let Some(path) = cm.config.recent_files.iter().find(|p| p.is_file()) {
+            self.load_image(Some(path.clone()), None);
+        }
+    }
+
+    /// Put a successfully loaded image at the front of the recent files.
+    pub(crate) fn remember_recent(&mut self, path: PathBuf) {
+        if let Some(cm) = &mut self.config_manager {
+            cm.config.add_recent_file(path);
+            cm.save();
+        }
+    }
+
+    /// Forget all recent files.
+    pub fn clear_recent(&mut self) {
+        if let Some(cm) = &mut self.config_manager {
+            cm.config.recent_files.clear();
+            cm.save();
+        }
+    }
+
+    /// Build EXIF metadata with Filmr processing info and the current recipe.
     pub fn build_exif_metadata(&self) -> little_exif::metadata::Metadata {
-        use little_exif::exif_tag::ExifTag;
-
-        let mut metadata = self.source_exif.clone().unwrap_or_default();
-
-        let stock_name = self.get_current_stock().name.clone();
-        metadata.set_tag(ExifTag::Software(
-            "Filmr - Physics-based Film Simulation".to_string(),
-        ));
-        metadata.set_tag(ExifTag::ImageDescription(format!(
-            "Processed with Filmr using {} film stock",
-            stock_name
-        )));
-        metadata.set_tag(ExifTag::Copyright(
-            "Processed by Filmr (https://github.com/W-Mai/filmr)".to_string(),
-        ));
-
-        metadata
+        let name = self
+            .source_path
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        filmr_exif_metadata(self.source_exif.clone(), &self.recipe(&name))
+    }
+
+    /// Load the recipe embedded in the open image into the develop controls.
+    pub fn restore_embedded_recipe(&mut self) {
+        let Some(recipe) = self.embedded_recipe.clone() else {
+            return;
+        };
+        self.status_msg = if self.apply_recipe(&recipe) {
+            format!("Restored settings from {}", recipe.name)
+        } else {
+            format!(
+                "Restored settings from {}; stock {} is not installed",
+                recipe.name, recipe.stock_name
+            )
+        };
+    }
+
+    /// Develop the current image on every stock and save the tiles as one
+    /// labeled PNG.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn export_stock_comparison(&mut self) {
+        const TILE_SIZE: u32 = 480;
+        const COLUMNS: u32 = 4;
+
+        let Some(img) = &self.original_image else {
+            return;
+        };
+        let default_name = self
+            .source_path
+            .as_ref()
+            .and_then(|p| p.file_stem())
+            .map(|s| format!("{}_FILM_TEST.png", s.to_string_lossy()))
+            .unwrap_or_else(|| "filmr_film_test.png".to_string());
+        let Some(path) = rfd::FileDialog::new()
+            .set_file_name(&default_name)
+            .add_filter("PNG Image", &["png"])
+            .save_file()
+        else {
+            return;
+        };
+
+        let source = self.crop.apply(&img.to_rgb8());
+        let config = self.develop_config(&self.develop_params());
+        let stocks: Vec<_> = self
+            .stocks
+            .iter()
+            .map(|stock| stock.as_ref().clone().with_style(self.film_style))
+            .collect();
+        let sheet = filmr::export_stock_comparison(&source, &stocks, &config, TILE_SIZE, COLUMNS);
+        self.status_msg = match sheet.save_with_format(&path, image::ImageFormat::Png) {
+            Ok(()) => format!("Saved stock comparison to {:?}", path),
+            Err(e) => format!("Failed to save stock comparison: {}", e),
+        };
     }
 
     /// Save the developed image to a file.
@@ -55,48 +162,7 @@ impl FilmrApp {
                 .unwrap_or("jpg")
                 .to_lowercase();
 
-            let result = match ext.as_str() {
-                "png" => {
-                    // PNG 8-bit with sRGB
-                    let mut bytes = Vec::new();
-                    let mut cursor = std::io::Cursor::new(&mut bytes);
-                    img.write_to(&mut cursor, image::ImageFormat::Png)
-                        .map(|_| bytes)
-                }
-                "tiff" | "tif" => {
-                    // TIFF 16-bit: convert 8-bit RGB to 16-bit
-                    let rgb8 = img.to_rgb8();
-                    let (w, h) = (rgb8.width(), rgb8.height());
-                    let pixels_16: Vec<u16> =
-                        rgb8.as_raw().iter().map(|&v| (v as u16) * 257).collect();
-                    let bytes_16: Vec<u8> =
-                        pixels_16.iter().flat_map(|v| v.to_ne_bytes()).collect();
-                    let mut bytes = Vec::new();
-                    let mut cursor = std::io::Cursor::new(&mut bytes);
-                    let encoder = image::codecs::tiff::TiffEncoder::new(&mut cursor);
-                    use image::ImageEncoder;
-                    encoder
-                        .write_image(&bytes_16, w, h, image::ExtendedColorType::Rgb16)
-                        .map(|_| bytes)
-                }
-                _ => {
-                    // JPEG (default)
-                    let mut bytes = Vec::new();
-                    let mut cursor = std::io::Cursor::new(&mut bytes);
-                    img.write_to(&mut cursor, image::ImageFormat::Jpeg)
-                        .map(|_| {
-                            // Embed EXIF with sRGB tag
-                            let mut metadata = self.build_exif_metadata();
-                            metadata
-                                .set_tag(little_exif::exif_tag::ExifTag::ColorSpace(vec![1u16]));
-                            let _ = metadata.write_to_vec(
-                                &mut bytes,
-                                little_exif::filetype::FileExtension::JPEG,
-                            );
-                            bytes
-                        })
-                }
-            };
+            let result = encode_output(img, &ext, || self.build_exif_metadata());
 
             match result {
                 Ok(bytes) => {
@@ -132,3 +198,88 @@ impl FilmrApp {
         }
     }
 }
+
+/// EXIF for a developed image: the source metadata plus Filmr processing info
+/// and the `recipe` it was developed with. The orientation is reset, as
+/// loading already applied it to the pixels.
+pub(crate) fn filmr_exif_metadata(
+    source: Option<little_exif::metadata::Metadata>,
+    recipe: &filmr::Recipe,
+) -> little_exif::metadata::Metadata {
+    use little_exif::exif_tag::ExifTag;
+
+    let mut metadata = source.unwrap_or_default();
+    crate::exif_utils::reset_exif_orientation(&mut metadata);
+    crate::exif_utils::embed_exif_recipe(&mut metadata, recipe);
+
+    metadata.set_tag(ExifTag::Software(
+        "Filmr - Physics-based Film Simulation".to_string(),
+    ));
+    metadata.set_tag(ExifTag::ImageDescription(format!(
+        "Processed with Filmr using {} film stock",
+        recipe.stock_name
+    )));
+    metadata.set_tag(ExifTag::Copyright(
+        "Processed by Filmr (https://github.com/W-Mai/filmr)".to_string(),
+    ));
+
+    metadata
+}
+
+/// Encode a developed image by file extension: PNG, 16-bit TIFF, or JPEG
+/// (the default) with the EXIF from `exif` embedded. Grayscale images stay
+/// single channel.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn encode_output(
+    img: &image::DynamicImage,
+    ext: &str,
+    exif: impl FnOnce() -> little_exif::metadata::Metadata,
+) -> Result<Vec<u8>, image::ImageError> {
+    match ext {
+        "png" => {
+            // PNG with sRGB, 16-bit for 16-bit grayscale
+            let mut bytes = Vec::new();
+            let mut cursor = std::io::Cursor::new(&mut bytes);
+            img.write_to(&mut cursor, image::ImageFormat::Png)
+                .map(|_| bytes)
+        }
+        "tiff" | "tif" => {
+            // TIFF 16-bit: widen 8-bit RGB, keep grayscale single channel
+            let (w, h) = (img.width(), img.height());
+            let (pixels_16, color_type) = match img {
+                image::DynamicImage::ImageLuma8(_) | image::DynamicImage::ImageLuma16(_) => {
+                    (img.to_luma16().into_raw(), image::ExtendedColorType::L16)
+                }
+                _ => (img.to_rgb16().into_raw(), image::ExtendedColorType::Rgb16),
+            };
+            let bytes_16: Vec<u8> = pixels_16.iter().flat_map(|v| v.to_ne_bytes()).collect();
+            let mut bytes = Vec::new();
+            let mut cursor = std::io::Cursor::new(&mut bytes);
+            let encoder = image::codecs::tiff::TiffEncoder::new(&mut cursor);
+            use image::ImageEncoder;
+            encoder
+                .write_image(&bytes_16, w, h, color_type)
+                .map(|_| bytes)
+        }
+        _ => {
+            // JPEG (default), 8-bit only
+            let img = match img {
+                image::DynamicImage::ImageLuma16(_) => {
+                    std::borrow::Cow::Owned(image::DynamicImage::ImageLuma8(img.to_luma8()))
+                }
+                _ => std::borrow::Cow::Borrowed(img),
+            };
+            let mut bytes = Vec::new();
+            let mut cursor = std::io::Cursor::new(&mut bytes);
+            img.write_to(&mut cursor, image::ImageFormat::Jpeg)
+                .map(|_| {
+                    // Embed EXIF with sRGB tag
+                    let mut metadata = exif();
+                    metadata.set_tag(little_exif::exif_tag::ExifTag::ColorSpace(vec![1u16]));
+                    let _ = metadata
+                        .write_to_vec(&mut bytes, little_exif::filetype::FileExtension::JPEG);
+                    bytes
+                })
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Grain loupe: the visible region regrained at native resolution.
+//!
+//! The preview is developed at most 1024px wide, where grain is mostly
+//! averaged away. At 100% zoom and above the loupe upsamples the visible part
+//! of the (grain-free) preview to the size it has in the full develop and
+//! adds grain there, sized for the whole frame.
+
+use super::workers::spawn_thread;
+use super::FilmrApp;
+use egui::{Context, Pos2, Rect, TextureHandle, Vec2};
+use filmr::{FilmStock, GrainSpace};
+use flume::{Receiver, Sender};
+use image::RgbImage;
+use std::sync::Arc;
+
+/// Largest loupe edge in native pixels.
+const MAX_LOUPE_SIZE: u32 = 768;
+
+pub struct LoupeRequest {
+    /// Grain-free develop of the visible region, at native resolution.
+    region: RgbImage,
+    film: FilmStock,
+    grain_space: GrainSpace,
+    /// Size of the full-resolution develop.
+    frame: (u32, u32),
+    uv: Rect,
+}
+
+pub struct LoupeResult {
+    image: RgbImage,
+    uv: Rect,
+    laplacian_variance: f32,
+}
+
+/// Rendered loupe, placed by its UV rectangle within the displayed image.
+pub struct GrainLoupe {
+    pub texture: TextureHandle,
+    pub uv: Rect,
+    pub laplacian_variance: f32,
+}
+
+impl FilmrApp {
+    pub(crate) fn spawn_loupe_worker(
+        rx: Receiver<LoupeRequest>,
+        tx: Sender<LoupeResult>,
+        ctx: egui::Context,
+    ) {
+        spawn_thread(move || {
+            while let Ok(mut req) = rx.recv() {
+                // Only the latest view matters
+                while let Ok(newer) = rx.try_recv() {
+                    req = newer;
+                }
+                let image = filmr::pipeline::apply_grain(
+                    &req.region,
+                    &req.film,
+                    req.frame.0,
+                    req.frame.1,
+                    req.grain_space,
+                );
+                let laplacian_variance = filmr::FilmMetrics::analyze(&image).laplacian_variance;
+                let _ = tx.send(LoupeResult {
+                    image,
+                    uv: req.uv,
+                    laplacian_variance,
+                });
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    /// Size of the full-resolution develop, after the crop.
+    fn frame_size(&self) -> Option<(u32, u32)> {
+        let original = self.original_image.as_ref()?;
+        Some(self.crop.output_size(original.width(), original.height()))
+    }
+
+    /// True if `image_rect` shows the frame at 100% or more.
+    pub fn loupe_zoom_reached(&self, ctx: &Context, image_rect: Rect) -> bool {
+        self.frame_size()
+            .is_some_and(|(w, _)| image_rect.width() * ctx.pixels_per_point() >= w as f32)
+    }
+
+    /// Ask for a new loupe if the visible part of `image_rect` within `canvas`
+    /// changed. Drops the loupe below 100% zoom.
+    pub(crate) fn request_grain_loupe(&mut self, ctx: &Context, canvas: Rect, image_rect: Rect) {
+        let (Some(preview), Some((frame_w, frame_h))) =
+            (self.preview_developed.clone(), self.frame_size())
+        else {
+            return;
+        };
+        let visible = image_rect.intersect(canvas);
+        if !self.loupe_zoom_reached(ctx, image_rect) || !visible.is_positive() {
+            self.grain_loupe = None;
+            self.loupe_requested = None;
+            return;
+        }
+
+        // Visible part in UV, capped around its center
+        let to_uv = |p: Pos2| ((p - image_rect.min) / image_rect.size()).to_pos2();
+        let visible_uv = Rect::from_min_max(to_uv(visible.min), to_uv(visible.max));
+        let max_uv = Vec2::new(
+            MAX_LOUPE_SIZE as f32 / frame_w as f32,
+            MAX_LOUPE_SIZE as f32 / frame_h as f32,
+        );
+        let wanted = Rect::from_center_size(visible_uv.center(), visible_uv.size().min(max_uv));
+        if self.loupe_requested == Some(wanted) {
+            return;
+        }
+        self.loupe_requested = Some(wanted);
+
+        // Snap to whole preview pixels
+        let (pw, ph) = (preview.width() as f32, preview.height() as f32);
+        let x0 = (wanted.min.x * pw).floor().clamp(0.0, pw - 1.0);
+        let y0 = (wanted.min.y * ph).floor().clamp(0.0, ph - 1.0);
+        let x1 = (wanted.max.x * pw).ceil().clamp(x0 + 1.0, pw);
+        let y1 = (wanted.max.y * ph).ceil().clamp(y0 + 1.0, ph);
+        let uv = Rect::from_min_max(Pos2::new(x0 / pw, y0 / ph), Pos2::new(x1 / pw, y1 / ph));
+
+        let sub = image::imageops::crop_imm(
+            preview.as_ref(),
+            x0 as u32,
+            y0 as u32,
+            (x1 - x0) as u32,
+            (y1 - y0) as u32,
+        )
+        .to_image();
+        let native_w = ((uv.width() * frame_w as f32).round() as u32).max(1);
+        let native_h = ((uv.height() * frame_h as f32).round() as u32).max(1);
+        let region = image::imageops::resize(
+            &sub,
+            native_w,
+            native_h,
+            image::imageops::FilterType::Triangle,
+        );
+
+        let _ = self.tx_loupe.send(LoupeRequest {
+            region,
+            film: self.preview_film(),
+            grain_space: self.grain_space,
+            frame: (frame_w, frame_h),
+            uv,
+        });
+    }
+
+    /// Collect a finished loupe.
+    pub(crate) fn poll_loupe(&mut self, ctx: &Context) {
+        while let Ok(res) = self.rx_loupe.try_recv() {
+            // Results can still arrive after the loupe was turned off
+            if !self.show_grain_loupe {
+                continue;
+            }
+            let size = [res.image.width() as usize, res.image.height() as usize];
+            let color_image = egui::ColorImage::from_rgb(size, res.image.as_raw());
+            self.grain_loupe = Some(GrainLoupe {
+                texture: ctx.load_texture(
+                    "grain_loupe",
+                    color_image,
+                    egui::TextureOptions::NEAREST,
+                ),
+                uv: res.uv,
+                laplacian_variance: res.laplacian_variance,
+            });
+        }
+    }
+
+    /// Keep the grain-free preview the loupe samples from.
+    pub(crate) fn set_preview_developed(&mut self, image: Arc<RgbImage>) {
+        self.preview_developed = Some(image);
+        // New develop parameters: render the loupe again
+        self.loupe_requested = None;
+    }
+}
@@ -0,0 +1,116 @@
+//! Region metering: metrics of a part of the develop.
+//!
+//! Shift-drag on the canvas draws the region. It is kept in image UV, so it
+//! follows the image through zoom, pan and a new develop, and is metered on
+//! the last developed image at whatever resolution that has.
+//!
+//! The waveform and vectorscope are measured on the same image, within the
+//! region if one is drawn, and only while they are shown.
+
+use super::FilmrApp;
+use egui::{Pos2, Rect};
+use filmr::metrics::{Vectorscope, Waveform};
+use filmr::{FilmMetrics, RoiRect};
+use image::RgbImage;
+use std::sync::Arc;
+
+/// Column bands of the waveform.
+const WAVEFORM_COLUMNS: usize = 256;
+
+/// Waveform and vectorscope of the metered image.
+pub struct Scopes {
+    pub waveform: Waveform,
+    pub vectorscope: Vectorscope,
+    /// Waveform and vectorscope textures, made when first drawn.
+    pub textures: Option<[egui::TextureHandle; 2]>,
+}
+
+/// Regions smaller than this (in UV) count as a click and clear the region.
+const MIN_REGION_UV: f32 = 0.005;
+
+impl FilmrApp {
+    /// Start drawing a region at `uv`.
+    pub(crate) fn begin_metering_drag(&mut self, uv: Pos2) {
+        self.metering_drag = Some((uv, uv));
+    }
+
+    /// Move the free corner of the region being drawn.
+    pub(crate) fn update_metering_drag(&mut self, uv: Pos2) {
+        if let Some((_, end)) = &mut self.metering_drag {
+            *end = uv;
+        }
+    }
+
+    /// Finish drawing: meter the region, or clear it after a plain click.
+    pub(crate) fn end_metering_drag(&mut self) {
+        let Some((start, end)) = self.metering_drag.take() else {
+            return;
+        };
+        let region = Rect::from_two_pos(start, end)
+            .intersect(Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)));
+        let region =
+            (region.width() >= MIN_REGION_UV && region.height() >= MIN_REGION_UV).then_some(region);
+        self.set_metering_region(region);
+    }
+
+    /// Region being drawn or metered, in image UV.
+    pub fn visible_metering_region(&self) -> Option<Rect> {
+        self.metering_drag
+            .map(|(start, end)| Rect::from_two_pos(start, end))
+            .or(self.metering_region)
+    }
+
+    pub fn set_metering_region(&mut self, region: Option<Rect>) {
+        self.metering_region = region;
+        self.update_region_metrics();
+    }
+
+    /// Keep the develop that regions are metered on.
+    pub(crate) fn set_metering_source(&mut self, image: Arc<RgbImage>) {
+        self.metering_source = Some(image);
+        self.update_region_metrics();
+    }
+
+    /// Show or hide the waveform and vectorscope.
+    pub fn set_show_scopes(&mut self, show: bool) {
+        self.show_scopes = show;
+        self.update_region_metrics();
+    }
+
+    /// Part of the metering source within the region, in pixels.
+    fn metering_rect(&self, image: &RgbImage) -> RoiRect {
+        match self.metering_region {
+            Some(region) => RoiRect::from_normalized(
+                region.min.x,
+                region.min.y,
+                region.max.x,
+                region.max.y,
+                image.width(),
+                image.height(),
+            ),
+            None => RoiRect::full(image),
+        }
+    }
+
+    fn update_region_metrics(&mut self) {
+        let Some(image) = self.metering_source.clone() else {
+            self.metrics_region = None;
+            self.scopes = None;
+            return;
+        };
+        let rect = self.metering_rect(&image);
+        self.metrics_region = self
+            .metering_region
+            .map(|_| FilmMetrics::analyze_roi(&image, rect));
+        self.scopes = self.show_scopes.then(|| {
+            let region =
+                image::imageops::crop_imm(image.as_ref(), rect.x, rect.y, rect.width, rect.height)
+                    .to_image();
+            Scopes {
+                waveform: Waveform::measure(&region, WAVEFORM_COLUMNS),
+                vectorscope: Vectorscope::measure(&region),
+                textures: None,
+            }
+        });
+    }
+}
@@ -1,11 +1,28 @@
 //! FilmrApp - Main application state and initialization.
 
+#[cfg(not(target_arch = "wasm32"))]
+mod batch;
+mod exposure;
+mod history;
 mod io;
+mod loupe;
+mod metering;
 mod processing;
+mod recipe;
+mod reference;
+mod session;
+mod stock_nav;
 mod update;
 pub mod workers;
 
-pub use crate::config::{AppMode, ConfigManager, UxMode};
+pub use crate::config::{AppMode, ConfigManager, UiState, UxMode};
+#[cfg(not(target_arch = "wasm32"))]
+pub use batch::BatchState;
+pub use exposure::{CONTRAST_RANGE, EXPOSURE_RANGE};
+pub use history::{DevelopParams, ParamHistory};
+pub use metering::Scopes;
+pub use session::sidecar_path;
+pub use stock_nav::AbSide;
 
 /// Right panel tab selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,11 +32,14 @@ pub enum RightTab {
     Detail,
 }
 
+use crate::crop::CropSettings;
 use egui::{TextureHandle, Vec2};
 use filmr::film::FilmStockCollection;
+use filmr::metrics::{HistogramAxis, HistogramSmoothing};
 use filmr::{
-    light_leak::LightLeakConfig, presets, FilmMetrics, FilmStock, OutputMode, SimulationConfig,
-    WhiteBalanceMode,
+    light_leak::LightLeakConfig, presets, BwFilter, DevelopmentParams, DitherMode, FilmMetrics,
+    FilmStock, GrainSpace, LensConfig, NegativeInversion, OutputMode, OutputTransfer,
+    ScannerProfile, ShutterSpeed, SimulationConfig, Toning, WhiteBalanceMode, WorkingSpace,
 };
 use flume::{unbounded, Receiver, Sender};
 use image::{DynamicImage, RgbImage};
@@ -40,14 +60,25 @@ pub struct FilmrApp {
     // State
     pub original_image: Option<DynamicImage>,
     pub preview_image: Option<Arc<RgbImage>>,
+    /// Cropped, downscaled develop source for `preview_image` and the crop
+    /// it was made with. Reused while both are unchanged, so the process
+    /// worker keeps its linearized copy.
+    pub(crate) preview_source: Option<(Arc<RgbImage>, CropSettings, Arc<RgbImage>)>,
     pub developed_image: Option<DynamicImage>,
     pub processed_texture: Option<TextureHandle>,
     pub original_texture: Option<TextureHandle>,
     pub metrics_original: Option<FilmMetrics>,
     pub metrics_preview: Option<FilmMetrics>,
     pub metrics_developed: Option<FilmMetrics>,
+    /// Downscaled reference image to match the look of, and its metrics.
+    pub reference: Option<(Arc<RgbImage>, FilmMetrics)>,
+    pub reference_name: Option<String>,
+    /// The last develop compared with `reference`.
+    pub target_match: Option<filmr::TargetMatch>,
     pub source_path: Option<PathBuf>,
     pub source_exif: Option<little_exif::metadata::Metadata>,
+    /// Recipe a previous Filmr export stored in the open image's EXIF.
+    pub embedded_recipe: Option<filmr::Recipe>,
 
     // Async Processing
     pub(crate) tx_req: Sender<ProcessRequest>,
@@ -66,12 +97,33 @@ pub struct FilmrApp {
     pub show_metrics: bool,
     pub split_view: bool,
     pub split_pos: f32,
+    /// Show source, linear and density values under the cursor.
+    pub show_readout: bool,
+    /// Show the strip of stock thumbnails below the canvas.
+    pub show_stock_strip: bool,
+    /// Paint blown highlights and crushed shadows over the image.
+    pub show_clip_warnings: bool,
+    pub clip_texture: Option<TextureHandle>,
+    /// Paint where the density runs past the stock's shoulder.
+    pub show_shoulder_clip: bool,
+    pub shoulder_clip_texture: Option<TextureHandle>,
+    /// Editing the crop: the canvas shows the uncropped source with handles.
+    pub crop_mode: bool,
 
     // Parameters
     pub exposure_time: f32,
+    /// Shutter speed of the simulated shot, for reciprocity failure.
+    /// None leaves reciprocity out.
+    pub shutter_speed: Option<ShutterSpeed>,
     pub gamma_boost: f32,
     pub warmth: f32,
     pub saturation: f32,
+    /// How much of the film look to mix over the original (0-1).
+    pub effect_amount: f32,
+    /// Lens filter for B&W stocks.
+    pub bw_filter: BwFilter,
+    /// Print toning for B&W stocks.
+    pub toning: Toning,
 
     // Halation Parameters
     pub halation_strength: f32,
@@ -91,6 +143,8 @@ pub struct FilmrApp {
     pub dof_focus: f32,
     pub dof_swirl: f32,
     pub rotational_blur_amount: f32,
+    /// Crop and straighten applied to the source before develop.
+    pub crop: CropSettings,
     pub depth_map: Option<filmr::depth::DepthMap>,
     /// Model download state
     pub model_download_progress: Option<(u64, u64)>, // (downloaded, total)
@@ -106,8 +160,32 @@ pub struct FilmrApp {
     pub film_style: filmr::FilmStyle,
 
     pub output_mode: OutputMode,
+    /// Mask removal of the inverted output mode.
+    pub inversion: NegativeInversion,
+    /// Transfer function of the exported file.
+    pub output_transfer: OutputTransfer,
+    /// Primaries of the stock's dye channels.
+    pub working_space: WorkingSpace,
+    /// Dithering of the 8-bit output.
+    pub dither: DitherMode,
+    /// Space the grain is added in.
+    pub grain_space: GrainSpace,
     pub white_balance_mode: WhiteBalanceMode,
     pub white_balance_strength: f32,
+    /// Developer temperature and time.
+    pub development: DevelopmentParams,
+    /// Scanner look applied after grain, if any.
+    pub scanner: Option<ScannerProfile>,
+    /// Taking lens effects.
+    pub lens: LensConfig,
+
+    /// Undo/redo of the develop parameters above.
+    pub history: ParamHistory,
+    /// Running Auto exposure, if any.
+    pub auto_exposure: Option<exposure::AutoExposure>,
+    /// The look not on screen in the A/B comparison.
+    pub ab_snapshot: Option<DevelopParams>,
+    pub ab_side: AbSide,
 
     // Status
     pub status_msg: String,
@@ -115,7 +193,9 @@ pub struct FilmrApp {
     // Metrics Display Options
     pub hist_log_scale: bool,
     pub hist_clamp_zeros: bool,
-    pub hist_smooth: bool,
+    pub hist_smoothing: HistogramSmoothing,
+    pub hist_smooth_radius: u32,
+    pub hist_axis: HistogramAxis,
 
     // App Mode
     pub mode: AppMode,
@@ -125,6 +205,11 @@ pub struct FilmrApp {
 
     // Studio State
     pub studio_stock_idx: Option<usize>,
+    /// Step wedge readings pasted or imported in the studio, one
+    /// `log E, density` pair per line.
+    pub studio_wedge_text: String,
+    /// RMS density error of the last wedge fit and the points it used.
+    pub studio_wedge_fit: Option<(f32, usize)>,
     pub has_unsaved_changes: bool,
     pub show_exit_dialog: bool,
     pub show_settings: bool,
@@ -137,6 +222,37 @@ pub struct FilmrApp {
     pub(crate) tx_thumb: Sender<(String, RgbImage, SimulationConfig, FilmStock)>,
     pub(crate) rx_thumb: Receiver<(String, RgbImage)>,
 
+    // Grain loupe
+    /// Regrain the visible region at native resolution from 100% zoom.
+    pub show_grain_loupe: bool,
+    pub grain_loupe: Option<loupe::GrainLoupe>,
+    /// View region of the last loupe request, in image UV.
+    pub(crate) loupe_requested: Option<egui::Rect>,
+    /// Last preview develop, grain-free while the loupe is on.
+    pub(crate) preview_developed: Option<Arc<RgbImage>>,
+    pub(crate) tx_loupe: Sender<loupe::LoupeRequest>,
+    pub(crate) rx_loupe: Receiver<loupe::LoupeResult>,
+
+    // Region metering
+    /// Region the metrics panel meters, in image UV.
+    pub metering_region: Option<egui::Rect>,
+    /// Start and current corner of the region being drawn, in image UV.
+    pub(crate) metering_drag: Option<(egui::Pos2, egui::Pos2)>,
+    /// Last displayed develop, metered within `metering_region`.
+    pub(crate) metering_source: Option<Arc<RgbImage>>,
+    pub metrics_region: Option<FilmMetrics>,
+    /// Show the waveform and vectorscope in the metrics panel.
+    pub show_scopes: bool,
+    pub scopes: Option<Scopes>,
+
+    // Batch Export
+    #[cfg(not(target_arch = "wasm32"))]
+    pub batch: BatchState,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) tx_batch: Sender<batch::BatchJob>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) rx_batch: Receiver<batch::BatchResult>,
+
     // Preset Loading (WASM)
     #[cfg(target_arch = "wasm32")]
     pub tx_preset: Sender<Vec<u8>>,
@@ -153,12 +269,24 @@ impl FilmrApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         Self::setup_fonts(cc);
 
-        let mut stocks = presets::get_all_stocks();
+        let mut stocks = presets::all_unique();
         let builtin_stock_count = stocks.len();
         let config_manager = ConfigManager::init();
 
         // Load custom stocks
-        Self::load_custom_stocks(&config_manager, &mut stocks);
+        let stock_errors = Self::load_custom_stocks(&config_manager, &mut stocks);
+        for err in &stock_errors {
+            log::warn!("Failed to load custom stock {}", err);
+        }
+        let status_msg = match stock_errors.as_slice() {
+            [] => "Drag and drop an image here to start.".to_owned(),
+            [err] => format!("Failed to load custom stock {}", err),
+            [err, rest @ ..] => format!(
+                "Failed to load custom stock {} (and {} more, see log)",
+                err,
+                rest.len()
+            ),
+        };
 
         // Setup channels
         let (tx_req, rx_req) = unbounded::<ProcessRequest>();
@@ -179,6 +307,21 @@ impl FilmrApp {
         Self::spawn_load_worker(rx_load, tx_load_res, ctx_load);
         Self::spawn_thumbnail_worker(rx_thumb_internal, tx_thumb_res, ctx_thumb);
 
+        let (tx_loupe, rx_loupe) = {
+            let (tx_req, rx_req) = unbounded();
+            let (tx_res, rx_res) = unbounded();
+            Self::spawn_loupe_worker(rx_req, tx_res, cc.egui_ctx.clone());
+            (tx_req, rx_res)
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let (tx_batch, rx_batch) = {
+            let (tx_jobs, rx_jobs) = unbounded();
+            let (tx_results, rx_results) = unbounded();
+            Self::spawn_batch_worker(rx_jobs, tx_results, cc.egui_ctx.clone());
+            (tx_jobs, rx_results)
+        };
+
         #[cfg(target_arch = "wasm32")]
         let (tx_preset, rx_preset) = unbounded();
         let (tx_model_dl, rx_model_dl) = unbounded();
@@ -187,18 +330,32 @@ impl FilmrApp {
             .as_ref()
             .map(|cm| cm.config.ux_mode)
             .unwrap_or(UxMode::Simple);
-
-        Self {
+        let ui_state = config_manager
+            .as_ref()
+            .map(|cm| cm.config.ui.clone())
+            .unwrap_or_default();
+        let selected_stock_idx = ui_state
+            .last_stock
+            .as_deref()
+            .and_then(|id| stocks.iter().position(|s| s.stable_id() == id))
+            .unwrap_or(0);
+
+        let mut app = Self {
             original_image: None,
             preview_image: None,
+            preview_source: None,
             developed_image: None,
             processed_texture: None,
             original_texture: None,
             metrics_original: None,
             metrics_preview: None,
             metrics_developed: None,
+            reference: None,
+            reference_name: None,
+            target_match: None,
             source_path: None,
             source_exif: None,
+            embedded_recipe: None,
 
             tx_req,
             rx_res,
@@ -211,17 +368,40 @@ impl FilmrApp {
             preset_thumbnails: std::collections::HashMap::new(),
             tx_thumb,
             rx_thumb: rx_thumb_res,
+            show_grain_loupe: false,
+            grain_loupe: None,
+            loupe_requested: None,
+            preview_developed: None,
+            tx_loupe,
+            rx_loupe,
+            metering_region: None,
+            metering_drag: None,
+            metering_source: None,
+            metrics_region: None,
+            show_scopes: false,
+            scopes: None,
 
             zoom: 1.0,
             offset: Vec2::ZERO,
             show_original: false,
-            show_metrics: false,
-            split_view: false,
+            show_metrics: ui_state.show_metrics,
+            split_view: ui_state.split_view,
             split_pos: 0.5,
+            show_readout: false,
+            show_stock_strip: true,
+            show_clip_warnings: false,
+            clip_texture: None,
+            show_shoulder_clip: false,
+            shoulder_clip_texture: None,
+            crop_mode: false,
             exposure_time: 1.0,
+            shutter_speed: None,
             gamma_boost: 1.0,
             warmth: 0.0,
             saturation: 1.0,
+            effect_amount: 1.0,
+            bw_filter: BwFilter::None,
+            toning: Toning::None,
 
             // Default Halation params
             halation_strength: 0.0,
@@ -241,6 +421,7 @@ impl FilmrApp {
             dof_focus: 0.5,
             dof_swirl: 0.0,
             rotational_blur_amount: 0.0,
+            crop: CropSettings::default(),
             depth_map: None,
             model_download_progress: None,
             model_download_error: None,
@@ -249,16 +430,30 @@ impl FilmrApp {
             light_leak_config: LightLeakConfig::default(),
 
             stocks,
-            selected_stock_idx: 0, // Default to first
+            selected_stock_idx,
             film_style: filmr::FilmStyle::Accurate,
             output_mode: OutputMode::Positive,
+            inversion: NegativeInversion::default(),
+            output_transfer: OutputTransfer::Srgb,
+            working_space: WorkingSpace::default(),
+            dither: DitherMode::default(),
+            grain_space: GrainSpace::default(),
             white_balance_mode: WhiteBalanceMode::Auto,
             white_balance_strength: 1.0,
-            status_msg: "Drag and drop an image here to start.".to_owned(),
-
-            hist_log_scale: false,
-            hist_clamp_zeros: true,
-            hist_smooth: false,
+            development: DevelopmentParams::default(),
+            scanner: None,
+            lens: LensConfig::default(),
+            history: ParamHistory::default(),
+            auto_exposure: None,
+            ab_snapshot: None,
+            ab_side: AbSide::A,
+            status_msg,
+
+            hist_log_scale: ui_state.hist_log_scale,
+            hist_clamp_zeros: ui_state.hist_clamp_zeros,
+            hist_smoothing: ui_state.hist_smoothing,
+            hist_smooth_radius: ui_state.hist_smooth_radius,
+            hist_axis: ui_state.hist_axis,
 
             mode: AppMode::Develop,
             ux_mode,
@@ -266,6 +461,8 @@ impl FilmrApp {
             builtin_stock_count,
 
             studio_stock_idx: None,
+            studio_wedge_text: String::new(),
+            studio_wedge_fit: None,
             has_unsaved_changes: false,
             show_exit_dialog: false,
             show_settings: false,
@@ -274,13 +471,22 @@ impl FilmrApp {
 
             config_manager,
 
+            #[cfg(not(target_arch = "wasm32"))]
+            batch: BatchState::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            tx_batch,
+            #[cfg(not(target_arch = "wasm32"))]
+            rx_batch,
+
             #[cfg(target_arch = "wasm32")]
             tx_preset,
             #[cfg(target_arch = "wasm32")]
             rx_preset,
             rx_model_dl,
             tx_model_dl,
-        }
+        };
+        app.reopen_last_image();
+        app
     }
 
     /// Get the currently selected film stock with applied style.
@@ -296,6 +502,35 @@ impl FilmrApp {
         std::rc::Rc::new(styled_stock)
     }
 
+    /// Panel layout and metrics options to restore on the next launch.
+    fn ui_state(&self) -> UiState {
+        UiState {
+            show_metrics: self.show_metrics,
+            split_view: self.split_view,
+            hist_log_scale: self.hist_log_scale,
+            hist_clamp_zeros: self.hist_clamp_zeros,
+            hist_smoothing: self.hist_smoothing,
+            hist_smooth_radius: self.hist_smooth_radius,
+            hist_smooth: false,
+            hist_axis: self.hist_axis,
+            last_stock: self
+                .stocks
+                .get(self.selected_stock_idx)
+                .map(|s| s.stable_id()),
+        }
+    }
+
+    /// Save the UI state to the config when it changed.
+    pub(crate) fn persist_ui_state(&mut self) {
+        let state = self.ui_state();
+        if let Some(cm) = &mut self.config_manager {
+            if cm.config.ui != state {
+                cm.config.ui = state;
+                cm.save();
+            }
+        }
+    }
+
     // --- Private helper methods ---
 
     fn setup_fonts(cc: &eframe::CreationContext<'_>) {
@@ -320,39 +555,73 @@ impl FilmrApp {
         crate::ui::theme::apply_dark_pro_theme(&cc.egui_ctx);
     }
 
+    /// Load every `*.json` stock or collection from the custom stocks directory.
+    ///
+    /// Returns one message per file or stock that failed to parse or validate.
     fn load_custom_stocks(
         config_manager: &Option<ConfigManager>,
         stocks: &mut Vec<std::rc::Rc<FilmStock>>,
-    ) {
+    ) -> Vec<String> {
+        let mut errors = Vec::new();
         if let Some(cm) = config_manager {
             if let Ok(entries) = std::fs::read_dir(&cm.config.custom_stocks_path) {
                 for entry in entries.flatten() {
                     let path = entry.path();
                     if path.extension().is_some_and(|ext| ext == "json") {
-                        // Try collection first
-                        if let Ok(file) = std::fs::File::open(&path) {
-                            let reader = std::io::BufReader::new(file);
-                            if let Ok(collection) =
-                                serde_json::from_reader::<_, FilmStockCollection>(reader)
-                            {
-                                for (name, mut stock) in collection.stocks {
-                                    if stock.name.is_empty() {
-                                        stock.name = name;
-                                    }
-                                    stocks.push(std::rc::Rc::from(stock));
-                                }
-                            } else if let Ok(mut stock) = FilmStock::load_from_file(&path) {
-                                let name = path.file_stem().unwrap().to_string_lossy().to_string();
-                                if stock.name.is_empty() {
-                                    stock.name = name;
-                                }
-                                stocks.push(std::rc::Rc::from(stock));
+                        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+                        let bytes = match std::fs::read(&path) {
+                            Ok(bytes) => bytes,
+                            Err(e) => {
+                                errors.push(format!("{}: {}", file_name, e));
+                                continue;
                             }
-                        }
+                        };
+                        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+                        let (loaded, failed) = parse_stock_json(&bytes, &name);
+                        stocks.extend(loaded.into_iter().map(std::rc::Rc::from));
+                        errors.extend(failed.into_iter().map(|e| format!("{}: {}", file_name, e)));
                     }
                 }
             }
         }
+        errors
+    }
+
+    /// Add stocks produced by [`parse_stock_json`] and report the outcome in the status bar.
+    ///
+    /// A single imported stock is selected immediately. Returns true if any stock was added.
+    pub(crate) fn add_imported_stocks(
+        &mut self,
+        file_name: &str,
+        loaded: Vec<FilmStock>,
+        errors: &[String],
+    ) -> bool {
+        for err in errors {
+            log::warn!("Preset import {}: {}", file_name, err);
+        }
+        self.status_msg = match (loaded.len(), errors.first()) {
+            (n, Some(err)) => format!(
+                "Loaded {} stock(s) from {}, {} error(s): {}",
+                n,
+                file_name,
+                errors.len(),
+                err
+            ),
+            (1, None) => format!("Loaded preset: {}", loaded[0].full_name()),
+            (n, None) => format!("Loaded {} stocks from {}", n, file_name),
+        };
+
+        if loaded.is_empty() {
+            return false;
+        }
+        let single = loaded.len() == 1;
+        self.stocks
+            .extend(loaded.into_iter().map(std::rc::Rc::from));
+        if single {
+            self.selected_stock_idx = self.stocks.len() - 1;
+            self.load_preset_values();
+        }
+        true
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -361,9 +630,10 @@ impl FilmrApp {
         tx_res: Sender<ProcessResult>,
         ctx: egui::Context,
     ) {
-        use workers::process_worker_logic;
+        use workers::{process_worker_logic, PreviewCache};
 
         spawn_thread(move || {
+            let mut preview = PreviewCache::default();
             while let Ok(mut req) = rx_req.recv() {
                 let mut skipped = 0u32;
                 while let Ok(newer) = rx_req.try_recv() {
@@ -384,7 +654,7 @@ impl FilmrApp {
 
                 let t0 = std::time::Instant::now();
                 match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                    process_worker_logic(req)
+                    process_worker_logic(req, Some(&mut preview))
                 })) {
                     Ok(res) => {
                         log::info!(
@@ -402,6 +672,7 @@ impl FilmrApp {
                             image: image::RgbImage::new(1, 1),
                             metrics: filmr::FilmMetrics::empty(),
                             is_preview,
+                            luma: None,
                         };
                         let _ = tx_res.send(fallback);
                     }
@@ -471,6 +742,7 @@ impl FilmrApp {
                                 image: img,
                                 metrics: *metrics,
                                 is_preview,
+                                luma: None,
                             };
                             let _ = tx_res.send(res);
                             ctx.request_repaint();
@@ -503,7 +775,7 @@ impl FilmrApp {
         tx_thumb_res: Sender<(String, RgbImage)>,
         ctx: egui::Context,
     ) {
-        use filmr::process_image;
+        use filmr::process_images_batch;
 
         spawn_thread(move || {
             while let Ok(first) = rx_thumb.recv() {
@@ -517,13 +789,84 @@ impl FilmrApp {
                     latest.insert(name, (img, config, stock));
                 }
 
-                for (name, (base_img, mut config, stock)) in latest {
-                    config.exposure_time = 1.0; // Accurate mode: norm handles exposure
-                    let processed = process_image(&base_img, &stock, &config);
-                    let _ = tx_thumb_res.send((name, processed));
+                let mut jobs: Vec<(String, RgbImage, SimulationConfig, FilmStock)> = latest
+                    .into_iter()
+                    .map(|(name, (base_img, mut config, stock))| {
+                        config.exposure_time = 1.0; // Accurate mode: norm handles exposure
+                        (name, base_img, config, stock)
+                    })
+                    .collect();
+                jobs.sort_by(|a, b| a.0.cmp(&b.0));
+
+                // One GPU submission for the whole batch instead of a round trip per stock
+                let items: Vec<_> = jobs
+                    .iter()
+                    .map(|(_, base_img, config, stock)| (base_img, stock, config))
+                    .collect();
+                let processed = process_images_batch(&items);
+                for ((name, ..), image) in jobs.into_iter().zip(processed) {
+                    let _ = tx_thumb_res.send((name, image));
                 }
                 ctx.request_repaint();
             }
         });
     }
 }
+
+/// Parse a JSON preset that is either a [`FilmStockCollection`] or a single [`FilmStock`].
+///
+/// Stocks that fail [`FilmStock::validate`] are skipped and reported with the
+/// offending field. Unnamed stocks take the collection key or `fallback_name`.
+pub(crate) fn parse_stock_json(bytes: &[u8], fallback_name: &str) -> (Vec<FilmStock>, Vec<String>) {
+    let mut errors = Vec::new();
+
+    let parsed: Vec<FilmStock> = match serde_json::from_slice::<FilmStockCollection>(bytes) {
+        Ok(collection) => collection
+            .stocks
+            .into_iter()
+            .map(|(name, mut stock)| {
+                if stock.name.is_empty() {
+                    stock.name = name;
+                }
+                stock
+            })
+            .collect(),
+        Err(collection_err) => {
+            // Report the collection error only when the file actually looks like one,
+            // otherwise the single-stock error points at the real typo.
+            let is_collection = serde_json::from_slice::<serde_json::Value>(bytes)
+                .is_ok_and(|v| v.get("stocks").is_some());
+            match serde_json::from_slice::<FilmStock>(bytes) {
+                Ok(mut stock) => {
+                    if stock.name.is_empty() {
+                        stock.name = fallback_name.to_string();
+                    }
+                    vec![stock]
+                }
+                Err(_) if is_collection => {
+                    errors.push(collection_err.to_string());
+                    Vec::new()
+                }
+                Err(stock_err) => {
+                    errors.push(stock_err.to_string());
+                    Vec::new()
+                }
+            }
+        }
+    };
+
+    let mut stocks = Vec::with_capacity(parsed.len());
+    for mut stock in parsed {
+        stock.migrate();
+        match stock.validate() {
+            Ok(()) => stocks.push(stock),
+            Err(field_errors) => {
+                for e in field_errors {
+                    errors.push(format!("'{}' {}", stock.full_name(), e));
+                }
+            }
+        }
+    }
+
+    (stocks, errors)
+}
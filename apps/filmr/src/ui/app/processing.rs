@@ -1,10 +1,15 @@
 //! Image processing methods for FilmrApp.
 
-use super::workers::ProcessRequest;
-use super::FilmrApp;
+use super::workers::{luma_source, ProcessRequest};
+use super::{DevelopParams, FilmrApp};
 use crate::config::AppMode;
 use egui::Context;
-use filmr::{light_leak::LightLeakConfig, SimulationConfig, SimulationMode};
+use filmr::utils::{resize_to_fit_linear, PREVIEW_FILTER};
+use filmr::{
+    light_leak::LightLeakConfig, FilmStock, GpuPowerPreference, OutputTransfer, SimulationConfig,
+    SimulationMode,
+};
+use image::RgbImage;
 use std::sync::Arc;
 
 impl FilmrApp {
@@ -12,89 +17,18 @@ impl FilmrApp {
     pub fn process_and_update_texture(&mut self, _ctx: &Context) {
         // Use preview image for GUI display to maintain responsiveness
         // For preview, we use the pre-converted Arc<RgbImage>
-        let source_image = self.preview_image.as_ref();
-
-        if let Some(img) = source_image {
-            // Construct params
-            // Use preset as base and modify
-            let base_film = if self.mode == AppMode::StockStudio {
-                self.studio_stock.clone()
-            } else {
-                self.get_current_stock().as_ref().clone()
-            };
-
-            let mut film = base_film;
-            if self.mode == AppMode::Develop {
-                // Only apply UI overrides in Develop mode
-                film.halation_strength = self.halation_strength;
-                film.halation_threshold = self.halation_threshold;
-                film.halation_sigma = self.halation_sigma;
-
-                film.grain_model.alpha = self.grain_alpha;
-                film.grain_model.sigma_read = self.grain_sigma;
-                film.grain_model.roughness = self.grain_roughness;
-                film.grain_model.blur_radius = self.grain_blur_radius;
-
-                // Apply gamma boost to all channels
-                film.r_curve.gamma *= self.gamma_boost;
-                film.g_curve.gamma *= self.gamma_boost;
-                film.b_curve.gamma *= self.gamma_boost;
-            }
-
-            let config = SimulationConfig {
-                simulation_mode: SimulationMode::default(),
-                exposure_time: self.exposure_time,
-                enable_grain: true,
-                use_gpu: true,
-                output_mode: self.output_mode,
-                white_balance_mode: if self.ux_mode == crate::config::UxMode::Simple {
-                    filmr::WhiteBalanceMode::Off
-                } else {
-                    self.white_balance_mode
-                },
-                white_balance_strength: self.white_balance_strength,
-                warmth: self.warmth,
-                saturation: self.saturation,
-                light_leak: self.light_leak_config.clone(),
-                motion_blur_amount: self.motion_blur_amount,
-                motion_blur_seed: self.motion_blur_seed,
-                object_motion_amount: self.object_motion_amount,
-                auto_levels: self.auto_levels,
-                dof_amount: self.dof_amount,
-                dof_focus: self.dof_focus,
-                dof_swirl: self.dof_swirl,
-                rotational_blur_amount: self.rotational_blur_amount,
-            };
-
-            // Send request to worker
-            // Downscale for preview to keep processing fast
-            let preview_img = {
-                let (w, h) = (img.width(), img.height());
-                let max_side = 1024u32;
-                if w > max_side || h > max_side {
-                    let scale = max_side as f32 / w.max(h) as f32;
-                    let nw = (w as f32 * scale) as u32;
-                    let nh = (h as f32 * scale) as u32;
-                    std::sync::Arc::new(image::imageops::resize(
-                        img.as_ref(),
-                        nw,
-                        nh,
-                        image::imageops::FilterType::Triangle,
-                    ))
-                } else {
-                    Arc::clone(img)
-                }
-            };
+        if let Some(img) = self.preview_image.clone() {
+            let film = self.preview_film();
+            let config = self.preview_config();
+            let preview_img = self.preview_source(&img);
 
             let request = ProcessRequest {
                 image: preview_img,
                 film,
                 config,
                 is_preview: true,
-                depth_map: self
-                    .depth_map
-                    .as_ref()
-                    .map(|dm| std::sync::Arc::new(dm.clone())),
+                depth_map: self.cropped_depth_map(),
+                luma: None,
             };
 
             log::info!("[UI] Sent PREVIEW request");
@@ -103,16 +37,253 @@ impl FilmrApp {
         }
     }
 
+    /// Develop source for the preview: `img` cropped and downscaled.
+    /// The same `Arc` is returned until the image or the crop changes.
+    fn preview_source(&mut self, img: &Arc<RgbImage>) -> Arc<RgbImage> {
+        if let Some((cached_img, crop, source)) = &self.preview_source {
+            if Arc::ptr_eq(cached_img, img) && *crop == self.crop {
+                return Arc::clone(source);
+            }
+        }
+
+        // Crop first so grain and halation see the final framing
+        let cropped = if self.crop.is_identity() {
+            Arc::clone(img)
+        } else {
+            Arc::new(self.crop.apply(img))
+        };
+
+        // Downscale for preview to keep processing fast
+        let max_side = 1024u32;
+        let source = if cropped.width() > max_side || cropped.height() > max_side {
+            Arc::new(resize_to_fit_linear(
+                cropped.as_ref(),
+                max_side,
+                PREVIEW_FILTER,
+            ))
+        } else {
+            cropped
+        };
+        self.preview_source = Some((Arc::clone(img), self.crop, Arc::clone(&source)));
+        source
+    }
+
+    /// Film stock for the preview: the studio stock in Stock Studio,
+    /// otherwise the selected stock with the develop overrides applied.
+    pub(crate) fn preview_film(&self) -> FilmStock {
+        // Use preset as base and modify
+        let base_film = if self.mode == AppMode::StockStudio {
+            self.studio_stock.clone()
+        } else {
+            self.get_current_stock().as_ref().clone()
+        };
+
+        let mut film = base_film;
+        if self.mode == AppMode::Develop {
+            // Only apply UI overrides in Develop mode
+            film.halation_strength = self.halation_strength;
+            film.halation_threshold = self.halation_threshold;
+            film.halation_sigma = self.halation_sigma;
+
+            film.grain_model.alpha = self.grain_alpha;
+            film.grain_model.sigma_read = self.grain_sigma;
+            film.grain_model.roughness = self.grain_roughness;
+            film.grain_model.blur_radius = self.grain_blur_radius;
+
+            // Apply gamma boost to all channels
+            film.r_curve.gamma *= self.gamma_boost;
+            film.g_curve.gamma *= self.gamma_boost;
+            film.b_curve.gamma *= self.gamma_boost;
+        }
+        film
+    }
+
+    /// Simulation config for the preview.
+    pub(crate) fn preview_config(&self) -> SimulationConfig {
+        SimulationConfig {
+            simulation_mode: SimulationMode::default(),
+            exposure_time: self.exposure_time,
+            shutter_speed: self.shutter_speed,
+            // The grain loupe adds grain at native resolution instead
+            enable_grain: !self.show_grain_loupe,
+            grain_space: self.grain_space,
+            use_gpu: true,
+            gpu_power_preference: GpuPowerPreference::default(),
+            output_mode: self.output_mode,
+            inversion: self.inversion,
+            // The preview is shown on screen
+            output_transfer: OutputTransfer::Srgb,
+            working_space: self.working_space,
+            dither: self.dither,
+            white_balance_mode: if self.ux_mode == crate::config::UxMode::Simple {
+                filmr::WhiteBalanceMode::Off
+            } else {
+                self.white_balance_mode
+            },
+            white_balance_strength: self.white_balance_strength,
+            warmth: self.warmth,
+            saturation: self.saturation,
+            bw_filter: self.bw_filter,
+            toning: self.toning,
+            development: self.development,
+            lens: self.lens,
+            scanner: self.scanner,
+            light_leak: self.light_leak_config.clone(),
+            motion_blur_amount: self.motion_blur_amount,
+            motion_blur_seed: self.motion_blur_seed,
+            grain_seed: None,
+            object_motion_amount: self.object_motion_amount,
+            auto_levels: self.auto_levels,
+            dof_amount: self.dof_amount,
+            dof_focus: self.dof_focus,
+            dof_swirl: self.dof_swirl,
+            rotational_blur_amount: self.rotational_blur_amount,
+            // Let grain weigh the preview as a downscaled full-res develop
+            source_width: self
+                .original_image
+                .as_ref()
+                .map(|i| self.crop.output_size(i.width(), i.height()).0),
+            target_size: None,
+            pipeline: SimulationConfig::default_pipeline(),
+            bypass_film: false,
+            mask: None,
+            effect_amount: self.effect_amount,
+            input_is_linear: false,
+        }
+    }
+
+    /// Full-resolution film stock for `params`: the selected stock with the
+    /// halation and gamma overrides applied.
+    pub(crate) fn develop_film(&self, params: &DevelopParams) -> FilmStock {
+        let index = params.selected_stock_idx.min(self.stocks.len() - 1);
+        let mut film = self.stocks[index]
+            .as_ref()
+            .clone()
+            .with_style(params.film_style);
+        film.halation_strength = params.halation_strength;
+        film.halation_threshold = params.halation_threshold;
+        film.halation_sigma = params.halation_sigma;
+        film.r_curve.gamma *= params.gamma_boost;
+        film.g_curve.gamma *= params.gamma_boost;
+        film.b_curve.gamma *= params.gamma_boost;
+        film
+    }
+
+    /// Full-resolution simulation config for `params`.
+    pub(crate) fn develop_config(&self, params: &DevelopParams) -> SimulationConfig {
+        SimulationConfig {
+            simulation_mode: SimulationMode::Accurate,
+            exposure_time: params.exposure_time,
+            shutter_speed: params.shutter_speed,
+            enable_grain: true,
+            grain_space: params.grain_space,
+            use_gpu: true,
+            gpu_power_preference: GpuPowerPreference::default(),
+            output_mode: params.output_mode,
+            inversion: params.inversion,
+            output_transfer: params.output_transfer,
+            working_space: params.working_space,
+            dither: params.dither,
+            white_balance_mode: if self.ux_mode == crate::config::UxMode::Simple {
+                filmr::WhiteBalanceMode::Off
+            } else {
+                params.white_balance_mode
+            },
+            white_balance_strength: params.white_balance_strength,
+            warmth: params.warmth,
+            saturation: params.saturation,
+            bw_filter: params.bw_filter,
+            toning: params.toning,
+            development: params.development,
+            lens: params.lens,
+            scanner: params.scanner,
+            light_leak: params.light_leak_config.clone(),
+            motion_blur_amount: params.motion_blur_amount,
+            motion_blur_seed: params.motion_blur_seed,
+            grain_seed: None,
+            object_motion_amount: params.object_motion_amount,
+            auto_levels: params.auto_levels,
+            dof_amount: params.dof_amount,
+            dof_focus: params.dof_focus,
+            dof_swirl: params.dof_swirl,
+            rotational_blur_amount: params.rotational_blur_amount,
+            source_width: None,
+            target_size: None,
+            pipeline: SimulationConfig::default_pipeline(),
+            bypass_film: false,
+            mask: None,
+            effect_amount: params.effect_amount,
+            input_is_linear: false,
+        }
+    }
+
+    /// Enter or leave crop editing.
+    /// Leaving re-renders the preview with the new framing.
+    pub fn set_crop_mode(&mut self, ctx: &Context, enabled: bool) {
+        if self.crop_mode && !enabled {
+            self.process_and_update_texture(ctx);
+        }
+        self.crop_mode = enabled;
+    }
+
+    /// `stock` styled and adjusted the way the develop overrides adjust
+    /// `selected`. The halation sliders start at the selected stock's
+    /// values, so they carry over as a change from that preset rather than
+    /// as absolute values, which would give every thumbnail the same glow.
+    fn thumbnail_film(&self, stock: &FilmStock, selected: &FilmStock) -> FilmStock {
+        // Scale by the slider's ratio to the preset, or add it when the
+        // preset has none to scale
+        let relative = |value: f32, preset: f32, slider: f32| {
+            if preset > 0.0 {
+                value * slider / preset
+            } else {
+                value + slider
+            }
+        };
+
+        let mut film = stock.clone().with_style(self.film_style);
+        if self.mode == AppMode::Develop {
+            film.halation_strength = relative(
+                film.halation_strength,
+                selected.halation_strength,
+                self.halation_strength,
+            );
+            film.halation_threshold = (film.halation_threshold + self.halation_threshold
+                - selected.halation_threshold)
+                .clamp(0.0, 1.0);
+            film.halation_sigma = relative(
+                film.halation_sigma,
+                selected.halation_sigma,
+                self.halation_sigma,
+            );
+            film.r_curve.gamma *= self.gamma_boost;
+            film.g_curve.gamma *= self.gamma_boost;
+            film.b_curve.gamma *= self.gamma_boost;
+        }
+        film
+    }
+
     /// Regenerate thumbnails for all film stocks.
     pub fn regenerate_thumbnails(&self) {
         if let Some(img) = &self.original_image {
-            let thumb_base = img.thumbnail(128, 128).to_rgb8();
+            // Start from the preview-sized copy when there is one
+            let thumb_base = match &self.preview_image {
+                Some(preview) => resize_to_fit_linear(preview, 128, PREVIEW_FILTER),
+                None => resize_to_fit_linear(&img.to_rgb8(), 128, PREVIEW_FILTER),
+            };
             let thumb_config = SimulationConfig {
                 simulation_mode: SimulationMode::default(),
-                exposure_time: 1.0,
+                exposure_time: self.exposure_time,
+                shutter_speed: self.shutter_speed,
                 enable_grain: false,
-                use_gpu: false,
+                grain_space: self.grain_space,
+                use_gpu: true,
+                gpu_power_preference: GpuPowerPreference::default(),
                 output_mode: self.output_mode,
+                inversion: self.inversion,
+                output_transfer: OutputTransfer::Srgb,
+                working_space: self.working_space,
+                dither: self.dither,
                 white_balance_mode: if self.ux_mode == crate::config::UxMode::Simple {
                     filmr::WhiteBalanceMode::Off
                 } else {
@@ -121,27 +292,36 @@ impl FilmrApp {
                 white_balance_strength: self.white_balance_strength,
                 warmth: self.warmth,
                 saturation: self.saturation,
+                bw_filter: self.bw_filter,
+                toning: self.toning,
+                development: self.development,
+                lens: self.lens,
+                scanner: None,
                 light_leak: LightLeakConfig::default(),
                 motion_blur_amount: 0.0,
                 motion_blur_seed: 0,
+                grain_seed: None,
                 object_motion_amount: 0.0,
                 auto_levels: false,
                 dof_amount: 0.0,
                 dof_focus: 0.5,
                 dof_swirl: 0.0,
                 rotational_blur_amount: 0.0,
+                source_width: None,
+                target_size: None,
+                pipeline: SimulationConfig::default_pipeline(),
+                bypass_film: false,
+                mask: None,
+                effect_amount: self.effect_amount,
+                input_is_linear: false,
             };
+            let selected = self.get_current_stock();
             for stock in &self.stocks {
-                let mut thumb_stock = stock.as_ref().clone();
-                // Apply gamma boost to thumbnail
-                thumb_stock.r_curve.gamma *= self.gamma_boost;
-                thumb_stock.g_curve.gamma *= self.gamma_boost;
-                thumb_stock.b_curve.gamma *= self.gamma_boost;
                 let _ = self.tx_thumb.send((
-                    stock.full_name(),
+                    stock.stable_id(),
                     thumb_base.clone(),
                     thumb_config.clone(),
-                    thumb_stock,
+                    self.thumbnail_film(stock, &selected),
                 ));
             }
         }
@@ -154,58 +334,30 @@ impl FilmrApp {
 
             // This might still take a bit of time to clone/convert, but it's unavoidable for full-res develop
             // unless we also keep full-res as RgbImage (memory intensive).
-            let rgb_img = Arc::new(img.to_rgb8());
+            let rgb = img.to_rgb8();
+            let rgb_img = Arc::new(if self.crop.is_identity() {
+                rgb
+            } else {
+                self.crop.apply(&rgb)
+            });
 
-            let base_film = if self.mode == AppMode::StockStudio {
+            let params = self.develop_params();
+            let film = if self.mode == AppMode::StockStudio {
                 self.studio_stock.clone()
             } else {
-                self.get_current_stock().as_ref().clone()
-            };
-            let mut film = base_film;
-
-            if self.mode == AppMode::Develop {
-                film.halation_strength = self.halation_strength;
-                film.halation_threshold = self.halation_threshold;
-                film.halation_sigma = self.halation_sigma;
-                film.r_curve.gamma *= self.gamma_boost;
-                film.g_curve.gamma *= self.gamma_boost;
-                film.b_curve.gamma *= self.gamma_boost;
-            }
-
-            let config = SimulationConfig {
-                simulation_mode: SimulationMode::Accurate,
-                exposure_time: self.exposure_time,
-                enable_grain: true,
-                use_gpu: true,
-                output_mode: self.output_mode,
-                white_balance_mode: if self.ux_mode == crate::config::UxMode::Simple {
-                    filmr::WhiteBalanceMode::Off
-                } else {
-                    self.white_balance_mode
-                },
-                white_balance_strength: self.white_balance_strength,
-                warmth: self.warmth,
-                saturation: self.saturation,
-                light_leak: self.light_leak_config.clone(),
-                motion_blur_amount: self.motion_blur_amount,
-                motion_blur_seed: self.motion_blur_seed,
-                object_motion_amount: self.object_motion_amount,
-                auto_levels: self.auto_levels,
-                dof_amount: self.dof_amount,
-                dof_focus: self.dof_focus,
-                dof_swirl: self.dof_swirl,
-                rotational_blur_amount: self.rotational_blur_amount,
+                self.develop_film(&params)
             };
+            let config = self.develop_config(&params);
+            // Grayscale scans on a B&W stock develop from 16-bit gray
+            let luma = luma_source(img, &film).map(|luma| Arc::new(self.crop.apply_luma16(&luma)));
 
             let request = ProcessRequest {
                 image: rgb_img,
                 film,
                 config,
                 is_preview: false,
-                depth_map: self
-                    .depth_map
-                    .as_ref()
-                    .map(|dm| std::sync::Arc::new(dm.clone())),
+                depth_map: self.cropped_depth_map(),
+                luma,
             };
 
             log::info!("[UI] Sent DEVELOP request");
@@ -214,6 +366,13 @@ impl FilmrApp {
         }
     }
 
+    /// The depth map aligned with the cropped source.
+    fn cropped_depth_map(&self) -> Option<Arc<filmr::depth::DepthMap>> {
+        self.depth_map
+            .as_ref()
+            .map(|dm| Arc::new(self.crop.apply_depth(dm)))
+    }
+
     /// Load preset values from the current stock into UI sliders.
     pub fn load_preset_values(&mut self) {
         let preset = self.get_current_stock();
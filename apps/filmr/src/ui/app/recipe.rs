@@ -0,0 +1,110 @@
+//! Saving and applying develop recipes, see [`filmr::Recipe`].
+
+use super::{DevelopParams, FilmrApp};
+use filmr::light_leak::LightLeakConfig;
+use filmr::{Recipe, StockOverrides};
+use std::sync::Arc;
+
+/// Decode the overlay images a loaded config refers to by path. Overlays
+/// whose file cannot be read stay unloaded and are skipped when developing.
+pub(crate) fn load_leak_overlays(config: &mut LightLeakConfig) {
+    for overlay in config.overlays.iter_mut().filter(|o| o.image.is_none()) {
+        match image::open(&overlay.source) {
+            Ok(img) => overlay.image = Some(Arc::new(img.to_rgba8())),
+            Err(e) => log::warn!("Failed to load leak image {:?}: {}", overlay.source, e),
+        }
+    }
+}
+
+impl FilmrApp {
+    /// The selected stock and current develop parameters as a recipe.
+    pub(crate) fn recipe(&self, name: &str) -> Recipe {
+        self.params_recipe(name, &self.develop_params())
+    }
+
+    /// `params` and the stock they select as a recipe.
+    pub(crate) fn params_recipe(&self, name: &str, params: &DevelopParams) -> Recipe {
+        let overrides = StockOverrides {
+            style: params.film_style,
+            gamma_scale: params.gamma_boost,
+            halation_strength: Some(params.halation_strength),
+            halation_threshold: Some(params.halation_threshold),
+            halation_sigma: Some(params.halation_sigma),
+            grain_alpha: Some(params.grain_alpha),
+            grain_sigma_read: Some(params.grain_sigma),
+            grain_roughness: Some(params.grain_roughness),
+            grain_blur_radius: Some(params.grain_blur_radius),
+        };
+        let stock = &self.stocks[params.selected_stock_idx.min(self.stocks.len() - 1)];
+        Recipe::new(name, stock, self.develop_config(params), overrides)
+    }
+
+    /// Load `recipe` into the develop controls. Returns false if its stock is
+    /// not installed, in which case the current stock is kept.
+    pub(crate) fn apply_recipe(&mut self, recipe: &Recipe) -> bool {
+        let mut params = self.develop_params();
+        let found = self
+            .stocks
+            .iter()
+            .position(|stock| stock.full_name() == recipe.stock_name);
+        if let Some(idx) = found {
+            params.selected_stock_idx = idx;
+        }
+
+        // Overrides the recipe leaves unset fall back to the stock's values
+        let stock = self.stocks[params.selected_stock_idx.min(self.stocks.len() - 1)].clone();
+        let overrides = &recipe.overrides;
+        params.film_style = overrides.style;
+        params.gamma_boost = overrides.gamma_scale;
+        params.halation_strength = overrides
+            .halation_strength
+            .unwrap_or(stock.halation_strength);
+        params.halation_threshold = overrides
+            .halation_threshold
+            .unwrap_or(stock.halation_threshold);
+        params.halation_sigma = overrides.halation_sigma.unwrap_or(stock.halation_sigma);
+        params.grain_alpha = overrides.grain_alpha.unwrap_or(stock.grain_model.alpha);
+        params.grain_sigma = overrides
+            .grain_sigma_read
+            .unwrap_or(stock.grain_model.sigma_read);
+        params.grain_roughness = overrides
+            .grain_roughness
+            .unwrap_or(stock.grain_model.roughness);
+        params.grain_blur_radius = overrides
+            .grain_blur_radius
+            .unwrap_or(stock.grain_model.blur_radius);
+
+        let config = &recipe.config;
+        params.exposure_time = config.exposure_time;
+        params.shutter_speed = config.shutter_speed;
+        params.warmth = config.warmth;
+        params.saturation = config.saturation;
+        params.bw_filter = config.bw_filter;
+        params.toning = config.toning;
+        params.motion_blur_amount = config.motion_blur_amount;
+        params.motion_blur_seed = config.motion_blur_seed;
+        params.object_motion_amount = config.object_motion_amount;
+        params.auto_levels = config.auto_levels;
+        params.dof_amount = config.dof_amount;
+        params.dof_focus = config.dof_focus;
+        params.dof_swirl = config.dof_swirl;
+        params.rotational_blur_amount = config.rotational_blur_amount;
+        params.light_leak_config = config.light_leak.clone();
+        load_leak_overlays(&mut params.light_leak_config);
+        params.output_mode = config.output_mode;
+        params.inversion = config.inversion;
+        params.output_transfer = config.output_transfer;
+        params.working_space = config.working_space;
+        params.dither = config.dither;
+        params.grain_space = config.grain_space;
+        params.white_balance_mode = config.white_balance_mode;
+        params.white_balance_strength = config.white_balance_strength;
+        params.development = config.development;
+        params.scanner = config.scanner;
+        params.lens = config.lens;
+        params.effect_amount = config.effect_amount;
+
+        self.apply_develop_params(params);
+        found.is_some()
+    }
+}
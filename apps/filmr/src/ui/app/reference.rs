@@ -0,0 +1,79 @@
+//! Reference image to match a look against.
+//!
+//! The reference is kept downscaled with its metrics. Every new develop is
+//! compared with it, and the suggested exposure, warmth and saturation
+//! changes can be applied in one step.
+
+use super::{FilmrApp, EXPOSURE_RANGE};
+use egui::Context;
+use filmr::{FilmMetrics, TargetMatch};
+use image::RgbImage;
+
+/// Longest edge the reference is kept at.
+#[cfg(not(target_arch = "wasm32"))]
+const REFERENCE_SIZE: u32 = 1024;
+
+impl FilmrApp {
+    /// Pick a reference image and compare the current develop with it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_reference(&mut self) {
+        use filmr::utils::{resize_to_fit_linear, PREVIEW_FILTER};
+        use std::sync::Arc;
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Image", &["png", "jpg", "jpeg", "tif", "tiff", "webp"])
+            .pick_file()
+        else {
+            return;
+        };
+        match image::open(&path) {
+            Ok(img) => {
+                let img = resize_to_fit_linear(&img.to_rgb8(), REFERENCE_SIZE, PREVIEW_FILTER);
+                let metrics = FilmMetrics::analyze(&img);
+                self.reference = Some((Arc::new(img), metrics));
+                self.reference_name = path.file_name().map(|n| n.to_string_lossy().to_string());
+                // Compare the develop on screen right away
+                let metrics = if self.developed_image.is_some() {
+                    self.metrics_developed.clone()
+                } else {
+                    self.metrics_preview.clone()
+                };
+                self.target_match = None;
+                if let (Some(image), Some(metrics)) = (self.metering_source.clone(), metrics) {
+                    self.update_target_match(&image, &metrics);
+                }
+            }
+            Err(e) => self.status_msg = format!("Failed to load reference: {}", e),
+        }
+    }
+
+    /// Stop matching against the reference.
+    pub fn clear_reference(&mut self) {
+        self.reference = None;
+        self.reference_name = None;
+        self.target_match = None;
+    }
+
+    /// Compare a new develop and its metrics with the reference.
+    pub(crate) fn update_target_match(&mut self, image: &RgbImage, metrics: &FilmMetrics) {
+        self.target_match = self
+            .reference
+            .as_ref()
+            .map(|(reference, reference_metrics)| {
+                TargetMatch::measure(metrics, reference_metrics).with_frame(image, reference)
+            });
+    }
+
+    /// Apply the suggested changes and develop again.
+    pub fn apply_match_nudges(&mut self, ctx: &Context) {
+        let Some(nudges) = self.target_match.map(|m| m.nudges) else {
+            return;
+        };
+        self.exposure_time = (self.exposure_time * 2f32.powf(nudges.exposure_ev))
+            .clamp(*EXPOSURE_RANGE.start(), *EXPOSURE_RANGE.end());
+        self.warmth = (self.warmth + nudges.warmth).clamp(-1.0, 1.0);
+        self.saturation = (self.saturation * nudges.saturation_scale).clamp(0.0, 2.0);
+        self.process_and_update_texture(ctx);
+        self.regenerate_thumbnails();
+    }
+}
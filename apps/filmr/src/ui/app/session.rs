@@ -0,0 +1,108 @@
+//! Per-image develop sessions stored next to the photo as `<image>.filmr.json`.
+
+use super::{DevelopParams, FilmrApp};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Schema version of the sidecar. Bump it only together with a conversion
+/// in [`FilmrApp::load_session`].
+const SESSION_VERSION: u32 = 1;
+
+/// Sidecars written before versioning are version 1.
+fn legacy_session_version() -> u32 {
+    1
+}
+
+/// Develop settings tied to one image.
+#[derive(Debug, Serialize, Deserialize)]
+struct DevelopSession {
+    #[serde(default = "legacy_session_version")]
+    version: u32,
+    /// Full name of the selected stock, see [`filmr::FilmStock::full_name`].
+    stock: String,
+    #[serde(flatten)]
+    params: DevelopParams,
+}
+
+/// Sidecar path for `image`, e.g. `IMG_0001.jpg.filmr.json`.
+pub fn sidecar_path(image: &Path) -> PathBuf {
+    let mut name = image.as_os_str().to_owned();
+    name.push(".filmr.json");
+    PathBuf::from(name)
+}
+
+impl FilmrApp {
+    fn sidecars_enabled(&self) -> bool {
+        self.config_manager
+            .as_ref()
+            .is_none_or(|cm| cm.config.write_sidecars)
+    }
+
+    /// Write the current develop parameters to the sidecar of the open image.
+    pub(crate) fn write_session(&mut self) {
+        if !self.sidecars_enabled() {
+            return;
+        }
+        let Some(path) = self.source_path.as_deref().map(sidecar_path) else {
+            return;
+        };
+
+        let session = DevelopSession {
+            version: SESSION_VERSION,
+            stock: self.get_current_stock().full_name(),
+            params: self.develop_params(),
+        };
+        let result = serde_json::to_string_pretty(&session)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            log::warn!("Failed to write session {:?}: {}", path, e);
+        }
+    }
+
+    /// Restore the develop parameters from the sidecar of the open image.
+    /// Returns false if there is no readable sidecar.
+    pub(crate) fn restore_session(&mut self) -> bool {
+        let Some(params) = self
+            .source_path
+            .as_deref()
+            .and_then(|path| self.load_session(path))
+        else {
+            return false;
+        };
+        self.apply_develop_params(params);
+        true
+    }
+
+    /// Develop parameters saved in the sidecar of `image`, or None if there
+    /// is no readable sidecar. A stock that is no longer installed falls back
+    /// to the current selection.
+    pub(crate) fn load_session(&self, image: &Path) -> Option<DevelopParams> {
+        let path = sidecar_path(image);
+        let json = std::fs::read_to_string(&path).ok()?;
+        let session: DevelopSession = match serde_json::from_str(&json) {
+            Ok(session) => session,
+            Err(e) => {
+                log::warn!("Ignoring invalid session {:?}: {}", path, e);
+                return None;
+            }
+        };
+        if session.version > SESSION_VERSION {
+            log::warn!(
+                "Session {:?} is version {}, newer than {}",
+                path,
+                session.version,
+                SESSION_VERSION
+            );
+        }
+
+        let mut params = session.params;
+        super::recipe::load_leak_overlays(&mut params.light_leak_config);
+        params.selected_stock_idx = self
+            .stocks
+            .iter()
+            .position(|stock| stock.full_name() == session.stock)
+            .unwrap_or(self.selected_stock_idx);
+        Some(params)
+    }
+}
@@ -0,0 +1,139 @@
+//! Keyboard stock cycling and the A/B look snapshot.
+
+use super::{DevelopParams, FilmrApp};
+use egui::{Context, Key, Modifiers};
+use filmr::presets;
+
+/// Which side of the A/B comparison is on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AbSide {
+    #[default]
+    A,
+    B,
+}
+
+impl AbSide {
+    pub fn label(self) -> &'static str {
+        match self {
+            AbSide::A => "A",
+            AbSide::B => "B",
+        }
+    }
+}
+
+impl FilmrApp {
+    /// Stock indices grouped by [`presets::group_key`], in display order.
+    pub fn stock_groups(&self) -> Vec<(String, Vec<usize>)> {
+        let mut groups: std::collections::BTreeMap<String, Vec<usize>> = Default::default();
+        for (idx, stock) in self.stocks.iter().enumerate() {
+            groups
+                .entry(presets::group_key(stock))
+                .or_default()
+                .push(idx);
+        }
+        groups.into_iter().collect()
+    }
+
+    /// Select a stock the way the film list does: load its defaults and
+    /// re-render the preview.
+    pub fn select_stock(&mut self, ctx: &Context, idx: usize) {
+        self.selected_stock_idx = idx.min(self.stocks.len() - 1);
+        self.load_preset_values();
+        self.process_and_update_texture(ctx);
+        self.regenerate_thumbnails();
+    }
+
+    /// Move to the next or previous stock in display order, wrapping at the
+    /// ends. With `by_group`, jump to the first stock of the next brand.
+    fn cycle_stock(&mut self, ctx: &Context, forward: bool, by_group: bool) {
+        let groups = self.stock_groups();
+        let current = self.selected_stock_idx;
+        let group_pos = groups
+            .iter()
+            .position(|(_, indices)| indices.contains(&current))
+            .unwrap_or(0);
+
+        let next = if by_group {
+            let n = groups.len();
+            let pos = if forward {
+                (group_pos + 1) % n
+            } else {
+                (group_pos + n - 1) % n
+            };
+            groups[pos].1[0]
+        } else {
+            let order: Vec<usize> = groups.into_iter().flat_map(|(_, i)| i).collect();
+            let n = order.len();
+            let pos = order.iter().position(|&i| i == current).unwrap_or(0);
+            order[if forward {
+                (pos + 1) % n
+            } else {
+                (pos + n - 1) % n
+            }]
+        };
+        self.select_stock(ctx, next);
+    }
+
+    /// Store the current look as the other side of the A/B comparison.
+    fn store_ab_snapshot(&mut self) {
+        self.ab_snapshot = Some(self.develop_params());
+        self.status_msg = format!(
+            "Stored look {}",
+            match self.ab_side {
+                AbSide::A => AbSide::B.label(),
+                AbSide::B => AbSide::A.label(),
+            }
+        );
+    }
+
+    /// Flip to the other look. The crop is framing, not part of the look,
+    /// and stays as it is.
+    fn swap_ab(&mut self, ctx: &Context) {
+        let Some(other) = self.ab_snapshot.take() else {
+            self.store_ab_snapshot();
+            return;
+        };
+        self.ab_snapshot = Some(self.develop_params());
+        self.apply_develop_params(DevelopParams {
+            crop: self.crop,
+            ..other
+        });
+        self.ab_side = match self.ab_side {
+            AbSide::A => AbSide::B,
+            AbSide::B => AbSide::A,
+        };
+        self.process_and_update_texture(ctx);
+        self.status_msg = format!("Showing look {}", self.ab_side.label());
+    }
+
+    /// Up/Down cycle stocks (Shift jumps brands), S stores the A/B
+    /// snapshot and X flips between the two looks.
+    pub(crate) fn update_stock_shortcuts(&mut self, ctx: &Context) {
+        // Leave arrow keys to text fields and focused widgets
+        if ctx.wants_keyboard_input() || ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        // Shift first: the plain shortcuts also match with Shift held
+        let (shift_up, shift_down, up, down, store, swap) = ctx.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::SHIFT, Key::ArrowUp),
+                i.consume_key(Modifiers::SHIFT, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::S),
+                i.consume_key(Modifiers::NONE, Key::X),
+            )
+        });
+
+        if shift_up || shift_down {
+            self.cycle_stock(ctx, shift_down, true);
+        } else if up || down {
+            self.cycle_stock(ctx, down, false);
+        }
+        if store {
+            self.store_ab_snapshot();
+        } else if swap {
+            self.swap_ab(ctx);
+        }
+    }
+}
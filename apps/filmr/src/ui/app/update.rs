@@ -1,16 +1,55 @@
 //! App trait implementation for FilmrApp.
 
-use super::workers::LoadRequest;
 use super::FilmrApp;
 use crate::config::AppMode;
 use crate::ui::panels;
 use eframe::{App, Frame};
 use egui::{ColorImage, Context};
-#[cfg(target_arch = "wasm32")]
-use filmr::film::FilmStockCollection;
-#[cfg(target_arch = "wasm32")]
-use filmr::FilmStock;
 use image::DynamicImage;
+use std::sync::Arc;
+
+impl FilmrApp {
+    /// Toolbar menu of recently opened images.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_recent_menu(&mut self, ui: &mut egui::Ui) {
+        let recent = self
+            .config_manager
+            .as_ref()
+            .map(|cm| cm.config.recent_files.clone())
+            .unwrap_or_default();
+        ui.add_enabled_ui(!recent.is_empty(), |ui| {
+            ui.menu_button("🕘 Recent", |ui| {
+                for path in recent {
+                    let name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| path.to_string_lossy().to_string());
+                    let exists = path.is_file();
+                    let label = if exists {
+                        egui::RichText::new(name)
+                    } else {
+                        egui::RichText::new(name).weak().strikethrough()
+                    };
+                    if ui
+                        .button(label)
+                        .on_hover_text(path.to_string_lossy())
+                        .clicked()
+                    {
+                        self.open_recent(path);
+                        ui.close();
+                    }
+                }
+                ui.separator();
+                if ui.button("Clear Recent").clicked() {
+                    self.clear_recent();
+                    ui.close();
+                }
+            })
+            .response
+            .on_hover_text("Reopen a recent image");
+        });
+    }
+}
 
 impl App for FilmrApp {
     #[allow(deprecated)]
@@ -114,19 +153,7 @@ impl App for FilmrApp {
                 let bytes = file.bytes.clone();
 
                 if path.is_some() || bytes.is_some() {
-                    let path_str = path
-                        .as_ref()
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_else(|| "dropped file".to_owned());
-
-                    self.status_msg = format!("Loading {}...", path_str);
-                    self.is_loading = true;
-                    let stock = if self.mode == AppMode::Develop {
-                        Some(self.get_current_stock().as_ref().clone())
-                    } else {
-                        None
-                    };
-                    let _ = self.tx_load.send(LoadRequest { path, bytes, stock });
+                    self.load_image(path, bytes);
                 }
             }
         }
@@ -138,6 +165,9 @@ impl App for FilmrApp {
                 Ok(data) => {
                     self.original_image = Some(data.image);
                     self.source_path = result.path.clone();
+                    if let Some(path) = &result.path {
+                        self.remember_recent(path.clone());
+                    }
                     self.status_msg = format!("Loaded {:?}", result.path);
 
                     // Read EXIF metadata from source file
@@ -160,6 +190,8 @@ impl App for FilmrApp {
                         egui::TextureOptions::LINEAR,
                     ));
                     self.metrics_original = Some(data.metrics);
+                    self.metering_source = None;
+                    self.set_metering_region(None);
 
                     // Reset developed status on new image load
                     self.developed_image = None;
@@ -203,6 +235,22 @@ impl App for FilmrApp {
                         self.exposure_time = 1.0;
                     }
 
+                    // Framing belongs to the previous image
+                    self.crop = Default::default();
+                    self.crop_mode = false;
+
+                    // Pick up the settings this image was last developed with
+                    if self.mode == AppMode::Develop && self.restore_session() {
+                        self.status_msg += " | Restored develop session";
+                    }
+                    self.embedded_recipe = data.recipe;
+                    if self.embedded_recipe.is_some() {
+                        self.status_msg += " | Contains Filmr settings";
+                    }
+
+                    // A new image starts a fresh undo history
+                    self.history.reset(self.develop_params());
+
                     // Auto-process logic: Immediately process the preview after loading
                     self.process_and_update_texture(ctx);
 
@@ -218,27 +266,9 @@ impl App for FilmrApp {
         // Check for async results
         #[cfg(target_arch = "wasm32")]
         if let Ok(bytes) = self.rx_preset.try_recv() {
-            if let Ok(collection) = serde_json::from_slice::<FilmStockCollection>(&bytes) {
-                for (name, mut stock) in collection.stocks {
-                    if stock.name.is_empty() {
-                        stock.name = name;
-                    }
-                    self.stocks.push(std::rc::Rc::from(stock));
-                }
-                self.status_msg = "Loaded preset collection".to_string();
-            } else if let Ok(stock) = serde_json::from_slice::<FilmStock>(&bytes) {
-                let name = format!("Imported Stock {}", self.stocks.len());
-                let mut stock = stock;
-                if stock.name.is_empty() {
-                    stock.name = name;
-                }
-                self.stocks.push(std::rc::Rc::from(stock));
-                self.selected_stock_idx = self.stocks.len() - 1;
-                self.load_preset_values();
-                self.status_msg = "Loaded imported preset".to_string();
-            } else {
-                self.status_msg = "Failed to parse preset file".to_string();
-            }
+            let name = format!("Imported Stock {}", self.stocks.len());
+            let (loaded, errors) = super::parse_stock_json(&bytes, &name);
+            self.add_imported_stocks("preset file", loaded, &errors);
         }
 
         if let Ok(result) = self.rx_res.try_recv() {
@@ -259,9 +289,17 @@ impl App for FilmrApp {
                     color_image,
                     egui::TextureOptions::LINEAR,
                 ));
+                self.update_clip_warnings(ctx, &result.image);
+                let developed = Arc::new(result.image);
+                self.update_target_match(&developed, &result.metrics);
+                if self.show_grain_loupe {
+                    self.set_preview_developed(developed.clone());
+                }
+                self.set_metering_source(developed);
                 self.developed_image = None;
-                self.metrics_preview = Some(result.metrics);
                 self.is_processing = false;
+                self.step_auto_exposure(ctx, &result.metrics);
+                self.metrics_preview = Some(result.metrics);
             } else {
                 let img = result.image;
                 // Convert to egui texture for display (Full Resolution)
@@ -274,14 +312,24 @@ impl App for FilmrApp {
                     color_image,
                     egui::TextureOptions::LINEAR,
                 ));
+                self.update_clip_warnings(ctx, &img);
+                self.update_target_match(&img, &result.metrics);
+                self.set_metering_source(Arc::new(img.clone()));
 
-                self.developed_image = Some(DynamicImage::ImageRgb8(img));
+                self.developed_image = Some(match result.luma {
+                    Some(luma) => DynamicImage::ImageLuma16(luma),
+                    None => DynamicImage::ImageRgb8(img),
+                });
                 self.metrics_developed = Some(result.metrics);
                 self.is_processing = false;
                 self.status_msg = "Development complete.".to_owned();
             }
         }
 
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_batch();
+        self.poll_loupe(ctx);
+
         // Handle Thumbnail Results
         while let Ok((name, img)) = self.rx_thumb.try_recv() {
             let size = [img.width() as _, img.height() as _];
@@ -385,7 +433,15 @@ impl App for FilmrApp {
                         ui.painter().rect_filled(r, 1.0, sep_color);
                         ui.add_space(6.0);
 
-                        // ── Action group: Save, Develop ──
+                        // ── Action group: Batch, Save, Develop ──
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui
+                            .add_enabled(!self.batch.is_running(), tb_btn("📦 Batch", false))
+                            .on_hover_text("Export several images with the current settings")
+                            .clicked()
+                        {
+                            self.start_batch_export();
+                        }
                         if ui
                             .add_enabled(self.developed_image.is_some(), tb_btn("💾 Save", false))
                             .on_hover_text("Save developed image")
@@ -393,6 +449,21 @@ impl App for FilmrApp {
                         {
                             self.save_image();
                         }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui
+                            .add_enabled(
+                                self.original_image.is_some(),
+                                tb_btn("🎞 Film Test", false),
+                            )
+                            .on_hover_text(
+                                "Save the image developed on every stock as one labeled sheet",
+                            )
+                            .clicked()
+                        {
+                            self.export_stock_comparison();
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        self.render_recent_menu(ui);
 
                         // Develop — primary action, accent fill
                         let dev_btn = egui::Button::new(
@@ -420,7 +491,17 @@ impl App for FilmrApp {
                         ui.painter().rect_filled(r, 1.0, sep_color);
                         ui.add_space(6.0);
 
-                        // ── View group: Split, Compare ──
+                        // ── View group: Crop, Split, Strip, Probe, Compare ──
+                        if ui
+                            .add_enabled(
+                                self.original_texture.is_some(),
+                                tb_btn("✂ Crop", self.crop_mode),
+                            )
+                            .on_hover_text("Crop and straighten before develop")
+                            .clicked()
+                        {
+                            self.set_crop_mode(ctx, !self.crop_mode);
+                        }
                         if ui
                             .add(tb_btn("🌓 Split", self.split_view))
                             .on_hover_text("Toggle split view comparison")
@@ -428,6 +509,20 @@ impl App for FilmrApp {
                         {
                             self.split_view = !self.split_view;
                         }
+                        if ui
+                            .add(tb_btn("🎞 Strip", self.show_stock_strip))
+                            .on_hover_text("Show every stock applied to this image")
+                            .clicked()
+                        {
+                            self.show_stock_strip = !self.show_stock_strip;
+                        }
+                        if ui
+                            .add(tb_btn("🎯 Probe", self.show_readout))
+                            .on_hover_text("Show pixel values under the cursor")
+                            .clicked()
+                        {
+                            self.show_readout = !self.show_readout;
+                        }
                         self.show_original = ui
                             .add(tb_btn("👋 Compare", false))
                             .on_hover_text("Hold to show original")
@@ -455,6 +550,13 @@ impl App for FilmrApp {
             });
         });
 
+        // Stock, A/B and exposure shortcuts, undo/redo and history recording
+        if self.mode == AppMode::Develop {
+            self.update_stock_shortcuts(ctx);
+            self.update_exposure_shortcuts(ctx);
+            self.update_history(ctx);
+        }
+
         // Left + Right panels (controls)
         panels::controls::render_controls(self, ctx);
 
@@ -473,7 +575,21 @@ impl App for FilmrApp {
             panels::settings::render_settings_window(self, ctx);
         }
 
+        // Batch export progress
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.batch.show_window {
+            panels::batch::render_batch_window(self, ctx);
+        }
+
+        // Stock thumbnails below the canvas
+        if self.mode == AppMode::Develop && self.show_stock_strip && self.original_image.is_some() {
+            panels::stock_strip::render_stock_strip(self, ctx);
+        }
+
         // Central panel (image canvas only, toolbar moved to top)
         panels::central::render_central_panel(self, ctx);
+
+        // Remember the layout for the next launch
+        self.persist_ui_state();
     }
 }
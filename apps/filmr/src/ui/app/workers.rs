@@ -1,7 +1,7 @@
 //! Worker thread types and logic for async image processing and loading.
 
 use filmr::{FilmMetrics, FilmStock, SimulationConfig};
-use image::{DynamicImage, RgbImage};
+use image::{DynamicImage, ImageBuffer, Luma, RgbImage};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -37,6 +37,9 @@ pub struct ProcessRequest {
     pub config: SimulationConfig,
     pub is_preview: bool,
     pub depth_map: Option<Arc<filmr::depth::DepthMap>>,
+    /// Single-channel source from [`luma_source`]. When set, the native
+    /// worker develops this instead of `image`.
+    pub luma: Option<Arc<ImageBuffer<Luma<u16>, Vec<u16>>>>,
 }
 
 /// Result of image processing.
@@ -44,6 +47,21 @@ pub struct ProcessResult {
     pub image: RgbImage,
     pub metrics: FilmMetrics,
     pub is_preview: bool,
+    /// The 16-bit grayscale develop, for requests with a `luma` source.
+    pub luma: Option<ImageBuffer<Luma<u16>, Vec<u16>>>,
+}
+
+/// The 16-bit single-channel source for grayscale images developed on a
+/// monochrome stock, which skip the RGB conversion; `None` otherwise.
+pub fn luma_source(
+    img: &DynamicImage,
+    film: &FilmStock,
+) -> Option<ImageBuffer<Luma<u16>, Vec<u16>>> {
+    let is_luma = matches!(
+        img,
+        DynamicImage::ImageLuma8(_) | DynamicImage::ImageLuma16(_)
+    );
+    (is_luma && film.is_monochrome()).then(|| img.to_luma16())
 }
 
 /// Request to load an image from file or bytes.
@@ -61,6 +79,8 @@ pub struct LoadResultData {
     pub preview: Arc<RgbImage>,
     pub preview_texture_data: egui::ColorImage,
     pub estimated_exposure: Option<f32>,
+    /// Develop recipe embedded by a previous Filmr export.
+    pub recipe: Option<filmr::Recipe>,
 }
 
 /// Result of image loading operation.
@@ -69,25 +89,83 @@ pub struct LoadResult {
     pub result: Result<LoadResultData, String>,
 }
 
-/// Process worker logic for native builds.
+/// Preview session of the process worker. Keeps the linearized preview
+/// source while requests keep sending the same `Arc`, so slider drags skip
+/// the upload and linearization.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+pub struct PreviewCache {
+    session: Option<(Arc<RgbImage>, filmr::PreviewSession)>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PreviewCache {
+    fn render(
+        &mut self,
+        image: &Arc<RgbImage>,
+        film: &FilmStock,
+        config: &SimulationConfig,
+        depth_map: Option<&filmr::depth::DepthMap>,
+    ) -> RgbImage {
+        let cached = self
+            .session
+            .as_ref()
+            .is_some_and(|(source, _)| Arc::ptr_eq(source, image));
+        if !cached {
+            let session = filmr::PreviewSession::new(image.as_ref().clone());
+            self.session = Some((Arc::clone(image), session));
+        }
+        let (_, session) = self.session.as_mut().expect("session was just created");
+        session.render_with_depth(film, config, depth_map)
+    }
+}
+
+/// Process worker logic for native builds. Preview requests go through
+/// `preview` when given.
 #[cfg(not(target_arch = "wasm32"))]
-pub fn process_worker_logic(req: ProcessRequest) -> ProcessResult {
+pub fn process_worker_logic(
+    req: ProcessRequest,
+    preview: Option<&mut PreviewCache>,
+) -> ProcessResult {
     let dm_ref = req.depth_map.as_deref();
-    let processed = filmr::process_image_with_depth(&req.image, &req.film, &req.config, dm_ref);
-    let metrics = filmr::FilmMetrics::analyze(&processed);
+    let (processed, luma) = match &req.luma {
+        Some(source) => {
+            let luma = filmr::process_luma_image_with_depth(
+                source,
+                &req.film,
+                &req.config,
+                dm_ref,
+                filmr::LumaOutput::Luma16,
+            );
+            (luma.to_rgb8(), Some(luma.into_luma16()))
+        }
+        None => match preview.filter(|_| req.is_preview) {
+            Some(cache) => (
+                cache.render(&req.image, &req.film, &req.config, dm_ref),
+                None,
+            ),
+            None => (
+                filmr::process_image_with_depth(&req.image, &req.film, &req.config, dm_ref),
+                None,
+            ),
+        },
+    };
+    let metrics =
+        filmr::FilmMetrics::analyze_with_stock(&processed, &req.image, &req.film, &req.config);
     ProcessResult {
         image: processed,
         metrics,
         is_preview: req.is_preview,
+        luma,
     }
 }
 
 /// Load worker logic - handles image loading with EXIF orientation.
 pub fn load_worker_logic(req: LoadRequest) -> LoadResult {
-    use crate::exif_utils::{apply_exif_orientation, read_exif_orientation};
+    use crate::exif_utils::{apply_exif_orientation, read_exif_orientation, read_exif_recipe};
     use egui::ColorImage;
     use filmr::estimate_exposure_time;
-    use image::imageops::FilterType;
+    use filmr::utils::{resize_to_fit_linear, PREVIEW_FILTER};
     use std::io::{BufReader, Cursor};
 
     // Read EXIF orientation before loading image
@@ -105,6 +183,15 @@ pub fn load_worker_logic(req: LoadRequest) -> LoadResult {
     } else {
         1
     };
+    let recipe = if let Some(bytes) = &req.bytes {
+        read_exif_recipe(&mut Cursor::new(bytes.as_ref()))
+    } else if let Some(path) = &req.path {
+        std::fs::File::open(path)
+            .ok()
+            .and_then(|f| read_exif_recipe(&mut BufReader::new(f)))
+    } else {
+        None
+    };
 
     let img_result = if let Some(bytes) = &req.bytes {
         image::load_from_memory(bytes)
@@ -132,7 +219,7 @@ pub fn load_worker_logic(req: LoadRequest) -> LoadResult {
             let width = img.width();
             let height = img.height();
             let preview_rgb = if width > 2048 || height > 2048 {
-                img.resize(2048, 2048, FilterType::Lanczos3).to_rgb8()
+                resize_to_fit_linear(&rgb, 2048, PREVIEW_FILTER)
             } else {
                 rgb.clone()
             };
@@ -152,6 +239,7 @@ pub fn load_worker_logic(req: LoadRequest) -> LoadResult {
                 preview: Arc::new(preview_rgb),
                 preview_texture_data,
                 estimated_exposure,
+                recipe,
             })
         }
         Err(e) => Err(e.to_string()),
@@ -4,7 +4,7 @@ use super::tokens::*;
 use egui::{self, Layout, RichText};
 
 /// Labeled slider: label+value top row (justify-between), full-width slider below.
-/// Returns `true` if the value changed.
+/// Click the value to type an exact number. Returns `true` if the value changed.
 pub fn labeled_slider(
     ui: &mut egui::Ui,
     label: &str,
@@ -12,21 +12,30 @@ pub fn labeled_slider(
     range: std::ops::RangeInclusive<f32>,
     logarithmic: bool,
 ) -> bool {
-    // Top row: label left, value right
-    ui.horizontal(|ui| {
-        ui.label(
-            RichText::new(label)
-                .size(BODY_FONT_SIZE)
-                .color(TEXT_SECONDARY),
-        );
-        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+    // Top row: label left, editable value right
+    let typed = ui
+        .horizontal(|ui| {
             ui.label(
-                RichText::new(format!("{:.2}", *value))
+                RichText::new(label)
                     .size(BODY_FONT_SIZE)
-                    .color(TEXT_PRIMARY),
+                    .color(TEXT_SECONDARY),
             );
-        });
-    });
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.style_mut().override_font_id = Some(egui::FontId::proportional(BODY_FONT_SIZE));
+                ui.visuals_mut().override_text_color = Some(TEXT_PRIMARY);
+                let speed = (range.end() - range.start()) as f64 / 200.0;
+                ui.add(
+                    egui::DragValue::new(value)
+                        .range(range.clone())
+                        .speed(speed)
+                        .min_decimals(2)
+                        .max_decimals(3),
+                )
+                .changed()
+            })
+            .inner
+        })
+        .inner;
 
     // Slider full width, no text
     let mut slider = egui::Slider::new(value, range).show_value(false);
@@ -36,7 +45,7 @@ pub fn labeled_slider(
     ui.spacing_mut().slider_width = ui.available_width();
     let changed = ui.add(slider).changed();
     ui.add_space(4.0);
-    changed
+    typed || changed
 }
 
 /// Section header — uppercase, muted color.
@@ -0,0 +1,48 @@
+use crate::ui::app::FilmrApp;
+use egui::Context;
+
+pub fn render_batch_window(app: &mut FilmrApp, ctx: &Context) {
+    let mut open = app.batch.show_window;
+    let running = app.batch.is_running();
+    egui::Window::new("📦 Batch Export")
+        .open(&mut open)
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.set_min_width(400.0);
+            let batch = &app.batch;
+            let progress = batch.done as f32 / batch.total.max(1) as f32;
+            ui.add(
+                egui::ProgressBar::new(progress)
+                    .text(format!("{} / {}", batch.done, batch.total))
+                    .animate(running),
+            );
+
+            if !batch.failures.is_empty() {
+                ui.add_space(8.0);
+                ui.colored_label(
+                    egui::Color32::RED,
+                    format!("{} failed:", batch.failures.len()),
+                );
+                egui::ScrollArea::vertical()
+                    .max_height(160.0)
+                    .show(ui, |ui| {
+                        for (path, err) in &batch.failures {
+                            let name = path
+                                .file_name()
+                                .map(|n| n.to_string_lossy().to_string())
+                                .unwrap_or_default();
+                            ui.label(format!("{}: {}", name, err));
+                        }
+                    });
+            }
+
+            if !running {
+                ui.add_space(8.0);
+                ui.label("Done.");
+            }
+        });
+    // Keep the window up until the batch has finished
+    app.batch.show_window = open || running;
+}
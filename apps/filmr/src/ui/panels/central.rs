@@ -1,4 +1,5 @@
-use crate::ui::app::FilmrApp;
+use crate::crop::{CropSettings, MAX_STRAIGHTEN_DEGREES};
+use crate::ui::app::{AppMode, FilmrApp};
 use egui::{Color32, Context, Pos2, Rect, RichText, Sense, Vec2};
 
 pub fn render_central_panel(app: &mut FilmrApp, ctx: &Context) {
@@ -38,7 +39,9 @@ fn render_image_canvas(app: &mut FilmrApp, ui: &mut egui::Ui, ctx: &Context) {
             app.offset += scroll;
         }
     }
-    if response.dragged() {
+    // Shift-drag draws the metering region instead of panning
+    let metering = app.metering_drag.is_some() || ctx.input(|i| i.modifiers.shift);
+    if response.dragged() && !metering {
         app.offset += response.drag_delta();
     }
     if response.double_clicked() {
@@ -46,6 +49,11 @@ fn render_image_canvas(app: &mut FilmrApp, ui: &mut egui::Ui, ctx: &Context) {
         app.offset = Vec2::ZERO;
     }
 
+    if app.crop_mode {
+        render_crop_editor(app, ui, ctx, rect);
+        return;
+    }
+
     // Rendering
     if let Some(processed) = &app.processed_texture {
         let image_size = processed.size_vec2();
@@ -67,14 +75,15 @@ fn render_image_canvas(app: &mut FilmrApp, ui: &mut egui::Ui, ctx: &Context) {
             if let Some(original) = &app.original_texture {
                 let split_x = rect.min.x + rect.width() * app.split_pos;
 
-                painter
-                    .with_clip_rect(Rect::from_min_max(rect.min, Pos2::new(split_x, rect.max.y)))
-                    .image(
-                        original.id(),
-                        image_rect,
-                        Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
-                        Color32::WHITE,
-                    );
+                paint_cropped(
+                    &painter.with_clip_rect(Rect::from_min_max(
+                        rect.min,
+                        Pos2::new(split_x, rect.max.y),
+                    )),
+                    original,
+                    image_rect,
+                    &app.crop,
+                );
 
                 painter
                     .with_clip_rect(Rect::from_min_max(Pos2::new(split_x, rect.min.y), rect.max))
@@ -91,9 +100,16 @@ fn render_image_canvas(app: &mut FilmrApp, ui: &mut egui::Ui, ctx: &Context) {
                 );
                 painter.rect_filled(line_rect, 0.0, Color32::WHITE.gamma_multiply(0.5));
 
+                let grip_rect = Rect::from_center_size(
+                    Pos2::new(split_x, rect.center().y),
+                    Vec2::new(8.0, 40.0),
+                );
+                painter.rect_filled(grip_rect, 4.0, Color32::WHITE.gamma_multiply(0.8));
+
+                // The whole divider is draggable, not just the grip
                 let handle_rect = Rect::from_center_size(
                     Pos2::new(split_x, rect.center().y),
-                    Vec2::new(20.0, 40.0),
+                    Vec2::new(16.0, rect.height()),
                 );
                 let handle_res =
                     ui.interact(handle_rect, ui.id().with("split_handle"), Sense::drag());
@@ -107,23 +123,448 @@ fn render_image_canvas(app: &mut FilmrApp, ui: &mut egui::Ui, ctx: &Context) {
                 }
             }
         } else {
-            let texture = if app.show_original {
-                app.original_texture.as_ref()
+            if app.show_original {
+                if let Some(original) = &app.original_texture {
+                    paint_cropped(&painter, original, image_rect, &app.crop);
+                }
             } else {
-                Some(processed)
-            };
-            if let Some(tex) = texture {
                 painter.image(
-                    tex.id(),
+                    processed.id(),
                     image_rect,
                     Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
                     Color32::WHITE,
                 );
             }
         }
+
+        // Overlays cover the developed side only
+        let developed_rect = if app.split_view && app.original_texture.is_some() {
+            Rect::from_min_max(
+                Pos2::new(rect.min.x + rect.width() * app.split_pos, rect.min.y),
+                rect.max,
+            )
+        } else {
+            rect
+        };
+
+        if app.show_grain_loupe && !app.show_original {
+            app.request_grain_loupe(ctx, rect, image_rect);
+            render_grain_loupe(
+                app,
+                &painter.with_clip_rect(developed_rect),
+                image_rect,
+                ctx,
+            );
+        }
+
+        let overlays = [&app.shoulder_clip_texture, &app.clip_texture];
+        for overlay in overlays
+            .into_iter()
+            .flatten()
+            .filter(|_| !app.show_original)
+        {
+            painter.with_clip_rect(developed_rect).image(
+                overlay.id(),
+                image_rect,
+                Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                Color32::WHITE,
+            );
+        }
+
+        if !app.show_original {
+            update_metering_region(app, &response, image_rect, ctx);
+            render_metering_region(app, &painter.with_clip_rect(developed_rect), image_rect);
+        }
+
+        if app.mode == AppMode::Develop {
+            render_stock_badge(app, &painter, rect);
+        }
+
+        if app.show_readout {
+            render_readout(app, &painter, image_rect, ctx);
+        }
     }
 }
 
+/// Native-resolution grain over its region of the image, or a hint to zoom in.
+fn render_grain_loupe(app: &FilmrApp, painter: &egui::Painter, image_rect: Rect, ctx: &Context) {
+    let Some(loupe) = &app.grain_loupe else {
+        if !app.loupe_zoom_reached(ctx, image_rect) {
+            painter.text(
+                painter.clip_rect().center_bottom() - Vec2::new(0.0, 16.0),
+                egui::Align2::CENTER_BOTTOM,
+                "Zoom to 100% to inspect grain",
+                egui::FontId::proportional(12.0),
+                Color32::from_white_alpha(180),
+            );
+        }
+        return;
+    };
+
+    let loupe_rect = Rect::from_min_max(
+        image_rect.lerp_inside(loupe.uv.min.to_vec2()),
+        image_rect.lerp_inside(loupe.uv.max.to_vec2()),
+    );
+    painter.image(
+        loupe.texture.id(),
+        loupe_rect,
+        Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+        Color32::WHITE,
+    );
+    painter.rect_stroke(
+        loupe_rect,
+        0.0,
+        egui::Stroke::new(1.0, Color32::from_white_alpha(60)),
+        egui::StrokeKind::Outside,
+    );
+
+    let label = painter.layout_no_wrap(
+        format!("Grain 1:1 · Laplacian var {:.1}", loupe.laplacian_variance),
+        egui::FontId::monospace(11.0),
+        Color32::from_gray(230),
+    );
+    // Bottom-left corner of the visible part of the loupe
+    let clip = painter.clip_rect();
+    let corner = Pos2::new(
+        loupe_rect.min.x.max(clip.min.x),
+        loupe_rect.max.y.min(clip.max.y),
+    );
+    let size = label.size() + Vec2::new(12.0, 8.0);
+    let bg = Rect::from_min_size(corner - Vec2::new(0.0, size.y), size);
+    painter.rect_filled(bg, 4.0, Color32::from_black_alpha(180));
+    painter.galley(bg.min + Vec2::new(6.0, 4.0), label, Color32::WHITE);
+}
+
+/// Draw the metering region with shift-drag; a shift-click clears it.
+fn update_metering_region(
+    app: &mut FilmrApp,
+    response: &egui::Response,
+    image_rect: Rect,
+    ctx: &Context,
+) {
+    let to_uv = |p: Pos2| ((p - image_rect.min) / image_rect.size()).to_pos2();
+    let pointer_uv = response.interact_pointer_pos().map(to_uv);
+    if response.drag_started() && ctx.input(|i| i.modifiers.shift) {
+        if let Some(uv) = pointer_uv {
+            app.begin_metering_drag(uv);
+        }
+    } else if response.dragged() {
+        if let Some(uv) = pointer_uv {
+            app.update_metering_drag(uv);
+        }
+    }
+    if response.drag_stopped() {
+        app.end_metering_drag();
+    } else if response.clicked() && ctx.input(|i| i.modifiers.shift) {
+        app.set_metering_region(None);
+    }
+}
+
+/// Outline of the metering region.
+fn render_metering_region(app: &FilmrApp, painter: &egui::Painter, image_rect: Rect) {
+    let Some(region) = app.visible_metering_region() else {
+        return;
+    };
+    let region_rect = Rect::from_min_max(
+        image_rect.lerp_inside(region.min.to_vec2()),
+        image_rect.lerp_inside(region.max.to_vec2()),
+    );
+    painter.rect_stroke(
+        region_rect,
+        0.0,
+        egui::Stroke::new(1.5, Color32::from_rgb(255, 200, 60)),
+        egui::StrokeKind::Outside,
+    );
+    painter.text(
+        region_rect.left_top() + Vec2::new(4.0, 4.0),
+        egui::Align2::LEFT_TOP,
+        "Metering",
+        egui::FontId::proportional(11.0),
+        Color32::from_rgb(255, 200, 60),
+    );
+}
+
+/// Current stock name in the top-left corner of the canvas, with the A/B
+/// side once a snapshot is stored.
+fn render_stock_badge(app: &FilmrApp, painter: &egui::Painter, rect: Rect) {
+    let mut text = app.get_current_stock().full_name();
+    if app.ab_snapshot.is_some() {
+        text = format!("{}  ·  {}", app.ab_side.label(), text);
+    }
+    let galley = painter.layout_no_wrap(
+        text,
+        egui::FontId::proportional(16.0),
+        Color32::from_gray(235),
+    );
+    let bg = Rect::from_min_size(
+        rect.min + Vec2::new(12.0, 12.0),
+        galley.size() + Vec2::new(16.0, 8.0),
+    );
+    painter.rect_filled(bg, 6.0, Color32::from_black_alpha(150));
+    painter.galley(bg.min + Vec2::new(8.0, 4.0), galley, Color32::WHITE);
+}
+
+/// Hover readout: source sRGB, linearized scene value, film density and
+/// output at the cursor, computed with [`filmr::develop_pixel`].
+fn render_readout(app: &FilmrApp, painter: &egui::Painter, image_rect: Rect, ctx: &Context) {
+    let Some(pos) = ctx
+        .pointer_hover_pos()
+        .filter(|p| image_rect.contains(*p) && painter.clip_rect().contains(*p))
+    else {
+        return;
+    };
+    let Some(source) = &app.preview_image else {
+        return;
+    };
+
+    // The canvas shows the cropped frame; find the pixel in the uncropped source
+    let uv = (pos - image_rect.min) / image_rect.size();
+    let (width, height) = source.dimensions();
+    let (su, sv) = app.crop.source_uv(uv.x, uv.y, width, height);
+    let x = (su * width as f32).clamp(0.0, width as f32 - 1.0) as u32;
+    let y = (sv * height as f32).clamp(0.0, height as f32 - 1.0) as u32;
+    let rgb = source.get_pixel(x, y).0;
+
+    let (linear, density, out) =
+        filmr::develop_pixel(&app.preview_film(), &app.preview_config(), rgb);
+    let text = format!(
+        "sRGB     {:>5} {:>5} {:>5}\n\
+         Linear   {:>5.3} {:>5.3} {:>5.3}\n\
+         Density  {:>5.2} {:>5.2} {:>5.2}\n\
+         Output   {:>5} {:>5} {:>5}",
+        rgb[0],
+        rgb[1],
+        rgb[2],
+        linear[0],
+        linear[1],
+        linear[2],
+        density[0],
+        density[1],
+        density[2],
+        out[0],
+        out[1],
+        out[2],
+    );
+
+    let galley =
+        painter.layout_no_wrap(text, egui::FontId::monospace(11.0), Color32::from_gray(230));
+    let swatch = 12.0;
+    let size = galley.size() + Vec2::new(swatch + 18.0, 8.0);
+    // Offset from the cursor, flipped to stay inside the canvas
+    let clip = painter.clip_rect();
+    let mut min = pos + Vec2::new(16.0, 16.0);
+    if min.x + size.x > clip.max.x {
+        min.x = pos.x - 16.0 - size.x;
+    }
+    if min.y + size.y > clip.max.y {
+        min.y = pos.y - 16.0 - size.y;
+    }
+    let bg = Rect::from_min_size(min, size);
+
+    painter.rect_filled(bg, 4.0, Color32::from_black_alpha(200));
+    painter.galley(bg.min + Vec2::new(6.0, 4.0), galley, Color32::WHITE);
+    let swatch_rect = Rect::from_min_size(
+        Pos2::new(bg.max.x - swatch - 6.0, bg.min.y + 6.0),
+        Vec2::splat(swatch),
+    );
+    painter.rect_filled(swatch_rect, 2.0, Color32::from_rgb(out[0], out[1], out[2]));
+}
+
+/// Paint the region of the uncropped source `texture` selected by `crop` into `rect`.
+fn paint_cropped(
+    painter: &egui::Painter,
+    texture: &egui::TextureHandle,
+    rect: Rect,
+    crop: &CropSettings,
+) {
+    let [width, height] = texture.size();
+    let mut mesh = egui::Mesh::with_texture(texture.id());
+    for (u, v) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+        let (su, sv) = crop.source_uv(u, v, width as u32, height as u32);
+        mesh.vertices.push(egui::epaint::Vertex {
+            pos: rect.lerp_inside(Vec2::new(u, v)),
+            uv: Pos2::new(su, sv),
+            color: Color32::WHITE,
+        });
+    }
+    mesh.add_triangle(0, 1, 2);
+    mesh.add_triangle(0, 2, 3);
+    painter.add(mesh);
+}
+
+/// Crop mode: the straightened source with the crop rectangle, corner
+/// handles and an angle bar. `[` / `]` nudge the angle (hold Shift for 1°).
+fn render_crop_editor(app: &mut FilmrApp, ui: &mut egui::Ui, ctx: &Context, rect: Rect) {
+    let Some(original) = app.original_texture.clone() else {
+        return;
+    };
+
+    // Keyboard: nudge the angle, Enter to finish
+    if !ctx.wants_keyboard_input() {
+        let (step, nudge_left, nudge_right, done) = ctx.input(|i| {
+            (
+                if i.modifiers.shift { 1.0 } else { 0.1 },
+                i.key_pressed(egui::Key::OpenBracket),
+                i.key_pressed(egui::Key::CloseBracket),
+                i.key_pressed(egui::Key::Enter),
+            )
+        });
+        if nudge_left {
+            app.crop.angle -= step;
+        }
+        if nudge_right {
+            app.crop.angle += step;
+        }
+        app.crop = app.crop.clamped();
+        if done {
+            app.set_crop_mode(ctx, false);
+            return;
+        }
+    }
+
+    let image_size = original.size_vec2();
+    let base_scale = (rect.width() / image_size.x).min(rect.height() / image_size.y);
+    let image_rect = Rect::from_center_size(
+        rect.center() + app.offset,
+        image_size * base_scale * app.zoom,
+    );
+
+    // Straightened source
+    let painter = ui.painter_at(rect);
+    let mut mesh = egui::Mesh::with_texture(original.id());
+    mesh.add_rect_with_uv(
+        image_rect,
+        Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+        Color32::WHITE,
+    );
+    mesh.rotate(
+        egui::emath::Rot2::from_angle(app.crop.angle.to_radians()),
+        image_rect.center(),
+    );
+    painter.add(mesh);
+
+    // Dim everything outside the crop
+    let crop_rect = Rect::from_min_max(
+        image_rect.lerp_inside(Vec2::new(app.crop.left, app.crop.top)),
+        image_rect.lerp_inside(Vec2::new(app.crop.right, app.crop.bottom)),
+    );
+    let shade = Color32::from_black_alpha(160);
+    for outside in [
+        Rect::from_min_max(rect.min, Pos2::new(rect.max.x, crop_rect.min.y)),
+        Rect::from_min_max(Pos2::new(rect.min.x, crop_rect.max.y), rect.max),
+        Rect::from_min_max(
+            Pos2::new(rect.min.x, crop_rect.min.y),
+            Pos2::new(crop_rect.min.x, crop_rect.max.y),
+        ),
+        Rect::from_min_max(
+            Pos2::new(crop_rect.max.x, crop_rect.min.y),
+            Pos2::new(rect.max.x, crop_rect.max.y),
+        ),
+    ] {
+        painter.rect_filled(outside, 0.0, shade);
+    }
+
+    // Border and rule-of-thirds guides
+    let guide = egui::Stroke::new(1.0, Color32::WHITE.gamma_multiply(0.35));
+    for t in [1.0 / 3.0, 2.0 / 3.0] {
+        let x = crop_rect.min.x + crop_rect.width() * t;
+        let y = crop_rect.min.y + crop_rect.height() * t;
+        painter.line_segment(
+            [Pos2::new(x, crop_rect.min.y), Pos2::new(x, crop_rect.max.y)],
+            guide,
+        );
+        painter.line_segment(
+            [Pos2::new(crop_rect.min.x, y), Pos2::new(crop_rect.max.x, y)],
+            guide,
+        );
+    }
+    painter.rect_stroke(
+        crop_rect,
+        0.0,
+        egui::Stroke::new(1.5, Color32::WHITE),
+        egui::StrokeKind::Inside,
+    );
+
+    // Drag inside to move, drag a corner to resize
+    let to_normalized = |delta: Vec2| delta / image_rect.size();
+    let move_res = ui.interact(crop_rect, ui.id().with("crop_move"), Sense::drag());
+    if move_res.dragged() {
+        let d = to_normalized(move_res.drag_delta());
+        let crop = &mut app.crop;
+        let dx = d.x.clamp(-crop.left, 1.0 - crop.right);
+        let dy = d.y.clamp(-crop.top, 1.0 - crop.bottom);
+        crop.left += dx;
+        crop.right += dx;
+        crop.top += dy;
+        crop.bottom += dy;
+    }
+    if move_res.hovered() {
+        ctx.set_cursor_icon(egui::CursorIcon::Move);
+    }
+
+    for (i, corner) in [
+        crop_rect.left_top(),
+        crop_rect.right_top(),
+        crop_rect.right_bottom(),
+        crop_rect.left_bottom(),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let handle_rect = Rect::from_center_size(corner, Vec2::splat(14.0));
+        painter.rect_filled(handle_rect.shrink(3.0), 1.0, Color32::WHITE);
+        let res = ui.interact(handle_rect, ui.id().with(("crop_corner", i)), Sense::drag());
+        if res.dragged() {
+            let d = to_normalized(res.drag_delta());
+            let crop = &mut app.crop;
+            if i == 0 || i == 3 {
+                crop.left += d.x;
+            } else {
+                crop.right += d.x;
+            }
+            if i < 2 {
+                crop.top += d.y;
+            } else {
+                crop.bottom += d.y;
+            }
+            *crop = crop.clamped();
+        }
+        if res.hovered() || res.dragged() {
+            ctx.set_cursor_icon(if i % 2 == 0 {
+                egui::CursorIcon::ResizeNwSe
+            } else {
+                egui::CursorIcon::ResizeNeSw
+            });
+        }
+    }
+
+    // Angle bar
+    egui::Area::new(ui.id().with("crop_bar"))
+        .anchor(egui::Align2::CENTER_BOTTOM, Vec2::new(0.0, -16.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Straighten");
+                    ui.add(
+                        egui::Slider::new(
+                            &mut app.crop.angle,
+                            -MAX_STRAIGHTEN_DEGREES..=MAX_STRAIGHTEN_DEGREES,
+                        )
+                        .suffix("°")
+                        .fixed_decimals(1),
+                    )
+                    .on_hover_text("[ / ] nudge by 0.1°, Shift for 1°");
+                    if ui.button("Reset").clicked() {
+                        app.crop = CropSettings::default();
+                    }
+                    if ui.button("Done").clicked() {
+                        app.set_crop_mode(ctx, false);
+                    }
+                });
+            });
+        });
+}
+
 fn render_processing_overlay(_app: &mut FilmrApp, ui: &mut egui::Ui, ctx: &Context) {
     let rect = ui.available_rect_before_wrap();
     ui.painter()
@@ -7,12 +7,14 @@ use egui::{Context, RichText};
 use egui_taffy::{taffy, tui, TuiBuilderLogic};
 
 use crate::config::UxMode;
-use crate::ui::app::{FilmrApp, RightTab};
+use crate::ui::app::{FilmrApp, RightTab, CONTRAST_RANGE, EXPOSURE_RANGE};
 use crate::ui::components::{
-    labeled_slider, section_divider, section_header, ACCENT, BG_MEDIUM, TEXT_DARK, TEXT_SECONDARY,
+    labeled_slider, pill_selector_rows, section_divider, section_header, ACCENT, BG_MEDIUM,
+    TEXT_DARK, TEXT_SECONDARY,
 };
+use filmr::{BwFilter, ShutterSpeed};
 
-pub use shutter_speed::ShutterSpeed;
+pub use shutter_speed::shutter_speed_slider;
 
 /// Center arbitrary widgets horizontally using taffy flexbox.
 fn centered_horizontal(ui: &mut egui::Ui, id_salt: &str, add_contents: impl FnOnce(&mut egui::Ui)) {
@@ -202,8 +204,32 @@ pub fn render_controls(app: &mut FilmrApp, ctx: &Context) {
     }
 }
 
+/// Smallest reciprocity compensation, in stops, worth a warning.
+const RECIPROCITY_WARNING_STOPS: f32 = 0.1;
+
+/// Warn when the shutter speed is long enough for reciprocity failure.
+fn render_reciprocity_warning(app: &FilmrApp, ui: &mut egui::Ui) {
+    let Some(shutter) = app.shutter_speed else {
+        return;
+    };
+    let stock = app.get_current_stock();
+    let stops = stock.reciprocity_compensation(shutter.seconds());
+    if stops < RECIPROCITY_WARNING_STOPS {
+        return;
+    }
+    let mut text = format!("⚠ +{:.1} stops reciprocity correction needed", stops);
+    if !stock.is_monochrome() {
+        text.push_str(", expect a color shift");
+    }
+    ui.label(RichText::new(text).size(11.0).color(ACCENT))
+        .on_hover_text(
+            "Long exposures lose sensitivity; the preview shows the underexposure \
+             at this shutter speed. Raise the exposure by this much to compensate.",
+        );
+}
+
 /// Adjust tab — shown in both Simple and Professional modes.
-fn render_adjust_tab(app: &mut FilmrApp, ui: &mut egui::Ui, _ctx: &Context, changed: &mut bool) {
+fn render_adjust_tab(app: &mut FilmrApp, ui: &mut egui::Ui, ctx: &Context, changed: &mut bool) {
     // Exposure
     section_header(ui, "EXPOSURE");
     if app.ux_mode == UxMode::Professional {
@@ -212,18 +238,29 @@ fn render_adjust_tab(app: &mut FilmrApp, ui: &mut egui::Ui, _ctx: &Context, chan
         let primary = egui::Color32::from_rgb(220, 220, 225);
         ui.horizontal(|ui| {
             ui.label(RichText::new("Exposure Time").size(12.0).color(secondary));
+            // Click the value to type an exact time
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                ui.label(
-                    RichText::new(format!("{:.1}\"", app.exposure_time))
-                        .size(12.0)
-                        .color(primary),
-                );
+                ui.visuals_mut().override_text_color = Some(primary);
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut app.exposure_time)
+                            .range(EXPOSURE_RANGE)
+                            .speed(0.01)
+                            .min_decimals(1)
+                            .max_decimals(3)
+                            .suffix("\""),
+                    )
+                    .on_hover_text("[ / ] step a third of a stop")
+                    .changed()
+                {
+                    *changed = true;
+                }
             });
         });
         ui.spacing_mut().slider_width = ui.available_width();
         if ui
             .add(
-                egui::Slider::new(&mut app.exposure_time, 0.001..=30.0)
+                egui::Slider::new(&mut app.exposure_time, EXPOSURE_RANGE)
                     .show_value(false)
                     .logarithmic(true),
             )
@@ -231,17 +268,78 @@ fn render_adjust_tab(app: &mut FilmrApp, ui: &mut egui::Ui, _ctx: &Context, chan
         {
             *changed = true;
         }
+        // Exposure above sets the density; the shutter only adds reciprocity
+        let mut use_shutter = app.shutter_speed.is_some();
+        if ui
+            .checkbox(&mut use_shutter, "Shutter Speed")
+            .on_hover_text("Simulate reciprocity failure at this shutter speed")
+            .changed()
+        {
+            app.shutter_speed = use_shutter.then(ShutterSpeed::default);
+            *changed = true;
+        }
+        if let Some(speed) = app.shutter_speed.as_mut() {
+            if shutter_speed_slider(ui, speed).changed() {
+                *changed = true;
+            }
+        }
         ui.add_space(4.0);
     } else if labeled_slider(
         ui,
         "☀ Brightness",
         &mut app.exposure_time,
-        0.001..=30.0,
+        EXPOSURE_RANGE,
         true,
     ) {
         *changed = true;
     }
-    if labeled_slider(ui, "◑ Contrast", &mut app.gamma_boost, 0.5..=2.0, false) {
+    render_reciprocity_warning(app, ui);
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(
+                app.auto_exposure.is_none(),
+                egui::Button::new("✨ Auto Exposure"),
+            )
+            .on_hover_text("Keep highlight clipping under 0.5% and lift crushed shadows")
+            .clicked()
+        {
+            app.start_auto_exposure(ctx);
+        }
+        // Rebuilding the overlay needs a fresh preview
+        if ui
+            .checkbox(&mut app.show_clip_warnings, "Clip Warnings")
+            .on_hover_text("Show blown highlights in red and crushed shadows in blue")
+            .changed()
+        {
+            *changed = true;
+        }
+        if ui
+            .checkbox(&mut app.show_shoulder_clip, "Shoulder Clip")
+            .on_hover_text(
+                "Show in yellow where the density runs past the stock's shoulder, \
+                 where slide film clips",
+            )
+            .changed()
+        {
+            *changed = true;
+        }
+    });
+    if labeled_slider(
+        ui,
+        "◑ Contrast",
+        &mut app.gamma_boost,
+        CONTRAST_RANGE,
+        false,
+    ) {
+        *changed = true;
+    }
+    if labeled_slider(
+        ui,
+        "🎞 Film Amount",
+        &mut app.effect_amount,
+        0.0..=1.0,
+        false,
+    ) {
         *changed = true;
     }
     section_divider(ui);
@@ -254,6 +352,20 @@ fn render_adjust_tab(app: &mut FilmrApp, ui: &mut egui::Ui, _ctx: &Context, chan
     if labeled_slider(ui, "🌈 Intensity", &mut app.saturation, 0.0..=2.0, false) {
         *changed = true;
     }
+    if app.get_current_stock().is_monochrome() {
+        ui.add_space(4.0);
+        ui.label(RichText::new("B&W Filter").size(12.0).color(TEXT_SECONDARY));
+        let options: Vec<(BwFilter, &str)> = BwFilter::ALL.iter().map(|f| (*f, f.name())).collect();
+        let rows: &[&[(BwFilter, &str)]] = &[&options[..3], &options[3..]];
+        if pill_selector_rows(ui, "bw_filter", &mut app.bw_filter, rows) {
+            *changed = true;
+        }
+        ui.add_space(4.0);
+        ui.label(RichText::new("Toning").size(12.0).color(TEXT_SECONDARY));
+        if crate::ui::panels::studio::render_toning(ui, &mut app.toning) {
+            *changed = true;
+        }
+    }
     section_divider(ui);
 
     // Auto corrections
@@ -279,10 +391,12 @@ fn render_adjust_tab(app: &mut FilmrApp, ui: &mut egui::Ui, _ctx: &Context, chan
     }
     section_divider(ui);
 
-    // Professional-only: WB + Output
+    // Professional-only: WB + Development + Output
     if app.ux_mode == UxMode::Professional {
         professional::render_white_balance(app, ui, changed);
         section_divider(ui);
+        professional::render_development(app, ui, changed);
+        section_divider(ui);
         professional::render_output_mode(app, ui, changed);
     }
 }
@@ -1,4 +1,6 @@
 #[cfg(not(target_arch = "wasm32"))]
+use crate::ui::app::parse_stock_json;
+#[cfg(not(target_arch = "wasm32"))]
 use filmr::film::FilmStockCollection;
 #[cfg(not(target_arch = "wasm32"))]
 use rfd::FileDialog;
@@ -7,29 +9,35 @@ use crate::ui::app::FilmrApp;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub fn import_preset(app: &mut FilmrApp, changed: &mut bool) {
-    if let Some(path) = FileDialog::new().add_filter("JSON", &["json"]).pick_file() {
-        if let Ok(file) = std::fs::File::open(&path) {
-            let reader = std::io::BufReader::new(file);
-            if let Ok(collection) = serde_json::from_reader::<_, FilmStockCollection>(reader) {
-                for (name, mut stock) in collection.stocks {
-                    if stock.name.is_empty() {
-                        stock.name = name;
-                    }
-                    app.stocks.push(std::rc::Rc::from(stock));
-                }
-                app.status_msg = "Loaded preset collection".to_string();
-                *changed = true;
-            } else if let Ok(mut stock) = filmr::FilmStock::load_from_file(&path) {
-                let name = path.file_stem().unwrap().to_string_lossy().to_string();
-                if stock.name.is_empty() {
-                    stock.name = name.clone();
-                }
-                app.stocks.push(std::rc::Rc::from(stock));
-                app.selected_stock_idx = app.stocks.len() - 1;
-                app.load_preset_values();
+    if let Some(path) = FileDialog::new()
+        .add_filter("Presets", &["json", "xmp"])
+        .pick_file()
+    {
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("xmp"))
+        {
+            let (loaded, errors) = match filmr::FilmStock::from_xmp(&path) {
+                Ok(stock) => (vec![stock], Vec::new()),
+                Err(e) => (Vec::new(), vec![e.to_string()]),
+            };
+            if app.add_imported_stocks(&file_name, loaded, &errors) {
                 *changed = true;
-                app.status_msg = format!("Loaded preset: {}", name);
             }
+            return;
+        }
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                app.status_msg = format!("Failed to read {}: {}", file_name, e);
+                return;
+            }
+        };
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        let (loaded, errors) = parse_stock_json(&bytes, &name);
+        if app.add_imported_stocks(&file_name, loaded, &errors) {
+            *changed = true;
         }
     }
 }
@@ -55,6 +63,74 @@ pub fn export_preset(app: &mut FilmrApp) {
     }
 }
 
+/// Export every custom (non built-in) stock into a single collection file.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export_library(app: &mut FilmrApp) {
+    let custom = &app.stocks[app.builtin_stock_count..];
+    if custom.is_empty() {
+        app.status_msg = "No custom stocks to export".to_string();
+        return;
+    }
+    if let Some(path) = FileDialog::new()
+        .add_filter("JSON", &["json"])
+        .set_file_name("filmr_library.json")
+        .save_file()
+    {
+        let collection = FilmStockCollection::from_stocks(custom);
+        match collection.save_to_file(&path) {
+            Ok(()) => {
+                app.status_msg = format!(
+                    "Exported {} custom stocks to {:?}",
+                    collection.stocks.len(),
+                    path
+                );
+                app.has_unsaved_changes = false;
+            }
+            Err(e) => app.status_msg = format!("Failed to export library: {}", e),
+        }
+    }
+}
+
+/// Save the selected stock and all develop parameters as a recipe.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export_recipe(app: &mut FilmrApp) {
+    if let Some(path) = FileDialog::new()
+        .add_filter("Recipe", &["json"])
+        .set_file_name("recipe.json")
+        .save_file()
+    {
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        match app.recipe(&name).save_to_file(&path) {
+            Ok(()) => app.status_msg = format!("Saved recipe to {:?}", path),
+            Err(e) => app.status_msg = format!("Failed to save recipe: {}", e),
+        }
+    }
+}
+
+/// Load a recipe into the develop controls.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn import_recipe(app: &mut FilmrApp, changed: &mut bool) {
+    if let Some(path) = FileDialog::new()
+        .add_filter("Recipe", &["json"])
+        .pick_file()
+    {
+        match filmr::Recipe::load_from_file(&path) {
+            Ok(recipe) => {
+                app.status_msg = if app.apply_recipe(&recipe) {
+                    format!("Applied recipe {}", recipe.name)
+                } else {
+                    format!(
+                        "Applied recipe {}; stock {} is not installed",
+                        recipe.name, recipe.stock_name
+                    )
+                };
+                *changed = true;
+            }
+            Err(e) => app.status_msg = format!("Failed to load recipe: {}", e),
+        }
+    }
+}
+
 pub fn create_custom_stock(app: &mut FilmrApp, ctx: &egui::Context) {
     use crate::ui::app::AppMode;
 
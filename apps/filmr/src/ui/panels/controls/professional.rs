@@ -1,11 +1,16 @@
-use filmr::light_leak::{LightLeak, LightLeakShape};
-use filmr::{OutputMode, WhiteBalanceMode};
+use filmr::light_leak::{LightLeak, LightLeakBlendMode, LightLeakShape};
+use filmr::{
+    DevelopmentParams, DitherMode, GrainSpace, OutputMode, OutputTransfer, ScannerProfile,
+    WhiteBalanceMode, WorkingSpace,
+};
 
 use crate::ui::app::{AppMode, FilmrApp};
 
 use super::preset_io::create_custom_stock;
 #[cfg(not(target_arch = "wasm32"))]
-use super::preset_io::{export_preset, import_preset};
+use super::preset_io::{
+    export_library, export_preset, export_recipe, import_preset, import_recipe,
+};
 use crate::ui::components::{
     action_button, collapsing_section, labeled_slider, pill_selector, section_divider,
     section_header,
@@ -25,11 +30,33 @@ pub fn render_effects_tab(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut b
                     #[cfg(not(target_arch = "wasm32"))]
                     export_preset(app);
                 }
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.add(action_button("Export All")).clicked() {
+                    export_library(app);
+                }
                 if ui.add(action_button("✨ Create Custom")).clicked() {
                     create_custom_stock(app, &ui.ctx().clone());
                     app.process_and_update_texture(&ui.ctx().clone());
                 }
             });
+            #[cfg(not(target_arch = "wasm32"))]
+            ui.horizontal(|ui| {
+                if ui.add(action_button("Load Recipe")).clicked() {
+                    import_recipe(app, changed);
+                }
+                if ui.add(action_button("Save Recipe")).clicked() {
+                    export_recipe(app);
+                }
+            });
+            if app.embedded_recipe.is_some()
+                && ui
+                    .add(action_button("↺ Restore Image Settings"))
+                    .on_hover_text("Apply the develop settings saved in this image's EXIF")
+                    .clicked()
+            {
+                app.restore_embedded_recipe();
+                *changed = true;
+            }
             if app.selected_stock_idx >= app.builtin_stock_count
                 && ui.add(action_button("📝 Edit in Studio")).clicked()
             {
@@ -63,6 +90,18 @@ pub fn render_effects_tab(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut b
     ) {
         *changed = true;
     }
+    if labeled_slider(
+        ui,
+        "Chromatic Aberration ‰",
+        &mut app.lens.chromatic_aberration,
+        0.0..=5.0,
+        false,
+    ) {
+        *changed = true;
+    }
+    if labeled_slider(ui, "Diffusion", &mut app.lens.diffusion, 0.0..=1.0, false) {
+        *changed = true;
+    }
     if labeled_slider(ui, "DOF Amount", &mut app.dof_amount, 0.0..=2.0, false) {
         *changed = true;
     }
@@ -124,6 +163,27 @@ pub fn render_detail_tab(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut bo
     if labeled_slider(ui, "Blur", &mut app.grain_blur_radius, 0.0..=2.0, false) {
         *changed = true;
     }
+    ui.label("Grain Space").on_hover_text(
+        "Linear fades grain out in the shadows; sRGB and Density keep it visible there.",
+    );
+    let grain_spaces = GrainSpace::ALL.map(|space| (space, space.name()));
+    if pill_selector(ui, "grain_space", &mut app.grain_space, &grain_spaces) {
+        // Regrain the loupe in the new space
+        app.loupe_requested = None;
+        *changed = true;
+    }
+    if ui
+        .checkbox(&mut app.show_grain_loupe, "Grain Loupe")
+        .on_hover_text(
+            "At 100% zoom and above, show grain at full resolution on the visible area. \
+             The fitted preview is shown without grain meanwhile.",
+        )
+        .changed()
+    {
+        app.grain_loupe = None;
+        app.loupe_requested = None;
+        *changed = true;
+    }
     section_divider(ui);
 
     // Depth Map Preview
@@ -222,16 +282,103 @@ pub fn render_white_balance(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut
     }
 }
 
+/// Development chemistry section (Professional Adjust tab).
+pub fn render_development(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut bool) {
+    section_header(ui, "DEVELOPMENT");
+
+    let dev = &mut app.development;
+    if labeled_slider(
+        ui,
+        "Temperature °C",
+        &mut dev.temperature_c,
+        14.0..=32.0,
+        false,
+    ) {
+        *changed = true;
+    }
+    if labeled_slider(ui, "Time ×", &mut dev.time_rel, 0.5..=2.0, false) {
+        *changed = true;
+    }
+    if labeled_slider(ui, "Push/Pull", &mut dev.push_pull_stops, -2.0..=3.0, false) {
+        *changed = true;
+    }
+    if dev.push_pull_stops != 0.0
+        && ui
+            .checkbox(&mut dev.decouple_push_grain, "Manual Grain")
+            .on_hover_text("Push only the contrast; leave grain to the grain sliders")
+            .changed()
+    {
+        *changed = true;
+    }
+    if *dev != DevelopmentParams::default() && ui.add(action_button("Standard")).clicked() {
+        *dev = DevelopmentParams::default();
+        *changed = true;
+    }
+}
+
 /// Output mode section — pill style.
 pub fn render_output_mode(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut bool) {
     section_header(ui, "OUTPUT");
     let options = [
         (OutputMode::Positive, "Positive"),
         (OutputMode::Negative, "Negative"),
+        (OutputMode::Inverted, "Inverted"),
     ];
     if pill_selector(ui, "output_mode", &mut app.output_mode, &options) {
         *changed = true;
     }
+
+    // Lab inversion of the scanned negative
+    if app.output_mode == OutputMode::Inverted {
+        let inversion = &mut app.inversion;
+        if labeled_slider(
+            ui,
+            "Mask Removal",
+            &mut inversion.mask_removal,
+            0.0..=1.0,
+            false,
+        ) {
+            *changed = true;
+        }
+        if labeled_slider(ui, "Contrast", &mut inversion.contrast, 0.5..=2.0, false) {
+            *changed = true;
+        }
+    }
+
+    // Applies to the developed file; the preview stays sRGB
+    ui.label("Transfer");
+    let transfers = [
+        (OutputTransfer::Srgb, "sRGB"),
+        (OutputTransfer::Gamma(2.2), "Gamma 2.2"),
+        (OutputTransfer::Rec709, "Rec.709"),
+        (OutputTransfer::Linear, "Linear"),
+    ];
+    if pill_selector(ui, "output_transfer", &mut app.output_transfer, &transfers) {
+        *changed = true;
+    }
+
+    ui.label("Working Space");
+    let spaces = WorkingSpace::ALL.map(|space| (space, space.name()));
+    if pill_selector(ui, "working_space", &mut app.working_space, &spaces) {
+        *changed = true;
+    }
+
+    ui.label("Dither");
+    let dithers = DitherMode::ALL.map(|dither| (dither, dither.name()));
+    if pill_selector(ui, "dither", &mut app.dither, &dithers) {
+        *changed = true;
+    }
+
+    ui.label("Scanner");
+    let scanners = [
+        (None, "Off"),
+        (Some(ScannerProfile::noritsu()), "Noritsu"),
+        (Some(ScannerProfile::frontier()), "Frontier"),
+        (Some(ScannerProfile::flatbed()), "Flatbed"),
+    ];
+    if pill_selector(ui, "scanner", &mut app.scanner, &scanners) {
+        *changed = true;
+    }
 }
 
 fn render_light_leaks(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut bool) {
@@ -350,6 +497,80 @@ fn render_light_leaks(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut bool)
                 app.light_leak_config.leaks.remove(i);
             }
         }
+
+        render_leak_overlays(app, ui, changed);
+    }
+}
+
+/// Scanned light-leak images, screen/add blended over the developed frame.
+fn render_leak_overlays(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut bool) {
+    #[cfg(not(target_arch = "wasm32"))]
+    if ui.add(action_button("Add Image Leak")).clicked() {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Image", &["png", "jpg", "jpeg", "tif", "tiff"])
+            .pick_file()
+        {
+            match image::open(&path) {
+                Ok(img) => {
+                    app.light_leak_config
+                        .overlays
+                        .push(filmr::light_leak::LightLeakOverlay {
+                            source: path.to_string_lossy().to_string(),
+                            image: Some(std::sync::Arc::new(img.to_rgba8())),
+                            ..Default::default()
+                        });
+                    *changed = true;
+                }
+                Err(e) => app.status_msg = format!("Failed to load leak image: {}", e),
+            }
+        }
+    }
+
+    let mut overlays_to_remove = Vec::new();
+    for (i, overlay) in app.light_leak_config.overlays.iter_mut().enumerate() {
+        let name = std::path::Path::new(&overlay.source)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| format!("Image Leak #{}", i + 1));
+        collapsing_section(ui, &name, false, |ui| {
+            if overlay.image.is_none() {
+                ui.label("Image not loaded");
+            }
+            if ui
+                .add(egui::Slider::new(&mut overlay.opacity, 0.0..=1.0).text("Opacity"))
+                .changed()
+            {
+                *changed = true;
+            }
+            if ui
+                .add(
+                    egui::Slider::new(&mut overlay.rotation, 0.0..=std::f32::consts::TAU)
+                        .text("Rotation"),
+                )
+                .changed()
+            {
+                *changed = true;
+            }
+            let options = [
+                (LightLeakBlendMode::Screen, "Screen"),
+                (LightLeakBlendMode::Add, "Add"),
+            ];
+            if pill_selector(
+                ui,
+                &format!("leak_blend_{}", i),
+                &mut overlay.blend,
+                &options,
+            ) {
+                *changed = true;
+            }
+            if ui.add(action_button("Remove")).clicked() {
+                overlays_to_remove.push(i);
+                *changed = true;
+            }
+        });
+    }
+    for i in overlays_to_remove.into_iter().rev() {
+        app.light_leak_config.overlays.remove(i);
     }
 }
 
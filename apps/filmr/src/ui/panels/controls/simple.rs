@@ -23,22 +23,11 @@ pub fn render_film_list(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut boo
             ui.vertical(|ui| {
                 ui.set_min_size(ui.available_size());
 
-                let mut groups: std::collections::BTreeMap<String, Vec<usize>> = Default::default();
-                for (idx, stock) in app.stocks.iter().enumerate() {
-                    let name = stock.full_name();
-                    let brand = name
-                        .split_whitespace()
-                        .next()
-                        .unwrap_or("Other")
-                        .to_string();
-                    groups.entry(brand).or_default().push(idx);
-                }
-
-                for (brand, indices) in groups {
+                for (brand, indices) in app.stock_groups() {
                     collapsing_section(ui, &brand.to_uppercase(), true, |ui| {
                         for idx in indices {
                             let stock = &app.stocks[idx];
-                            let full_name = &stock.full_name();
+                            let thumb_key = stock.stable_id();
                             let name = &stock.name;
                             let is_selected = app.selected_stock_idx == idx;
 
@@ -70,7 +59,7 @@ pub fn render_film_list(app: &mut FilmrApp, ui: &mut egui::Ui, changed: &mut boo
                                 ui.painter().rect_filled(rect, corner_radius, bg_color);
                             }
 
-                            if let Some(thumb) = app.preset_thumbnails.get(full_name) {
+                            if let Some(thumb) = app.preset_thumbnails.get(&thumb_key) {
                                 let img_aspect = thumb.size()[0] as f32 / thumb.size()[1] as f32;
                                 let container_aspect = thumb_w / thumb_h;
 
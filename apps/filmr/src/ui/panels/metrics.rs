@@ -1,6 +1,9 @@
-use crate::ui::app::{FilmrApp, UxMode};
+use crate::ui::app::{FilmrApp, Scopes, UxMode};
 use egui::Context;
-use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints, Points};
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotImage, PlotPoint, PlotPoints, Points};
+use filmr::metrics::{
+    smooth_histogram, HistogramAxis, HistogramSmoothing, Vectorscope, SCOPE_BINS,
+};
 
 pub fn render_metrics(app: &mut FilmrApp, ctx: &Context) {
     if app.show_metrics {
@@ -11,8 +14,34 @@ pub fn render_metrics(app: &mut FilmrApp, ctx: &Context) {
                 ui.heading("Image Metrics");
                 ui.separator();
 
+                // A drawn region meters the develop, not the original
+                if app.metrics_region.is_some() && !app.show_original {
+                    ui.horizontal(|ui| {
+                        ui.label("Region (shift-drag to redraw)");
+                        if ui.button("Clear Region").clicked() {
+                            app.set_metering_region(None);
+                        }
+                    });
+                    ui.separator();
+                }
+
+                let mut show_scopes = app.show_scopes;
+                if ui
+                    .checkbox(&mut show_scopes, "Waveform & Vectorscope")
+                    .on_hover_text("Scopes of the develop, within the region if one is drawn")
+                    .changed()
+                {
+                    app.set_show_scopes(show_scopes);
+                }
+
+                if !app.show_original {
+                    render_target_match(app, ui, ctx);
+                }
+
                 let metrics_to_show = if app.show_original {
                     &app.metrics_original
+                } else if app.metrics_region.is_some() {
+                    &app.metrics_region
                 } else if app.developed_image.is_some() {
                     &app.metrics_developed
                 } else {
@@ -27,10 +56,16 @@ pub fn render_metrics(app: &mut FilmrApp, ctx: &Context) {
                             metrics,
                             &mut app.hist_log_scale,
                             &mut app.hist_clamp_zeros,
-                            app.hist_smooth,
+                            &mut app.hist_axis,
+                            &mut app.hist_smoothing,
+                            &mut app.hist_smooth_radius,
                             app.ux_mode,
                         );
 
+                        if let Some(scopes) = app.scopes.as_mut().filter(|_| !app.show_original) {
+                            render_scopes(ui, scopes);
+                        }
+
                         // 2. Advanced Metrics (Only in Pro Mode)
                         if app.ux_mode == UxMode::Professional {
                             render_advanced_metrics(ui, metrics);
@@ -43,19 +78,109 @@ pub fn render_metrics(app: &mut FilmrApp, ctx: &Context) {
     }
 }
 
+/// Reference slot and how far the develop is from its look.
+fn render_target_match(app: &mut FilmrApp, ui: &mut egui::Ui, ctx: &Context) {
+    ui.horizontal(|ui| {
+        #[cfg(not(target_arch = "wasm32"))]
+        if ui
+            .button("Load Reference")
+            .on_hover_text("Compare the develop with a photo whose look you want to match")
+            .clicked()
+        {
+            app.load_reference();
+        }
+        if let Some(name) = app.reference_name.clone() {
+            ui.label(name);
+            if ui.button("Clear").clicked() {
+                app.clear_reference();
+            }
+        }
+    });
+
+    if let Some(target) = app.target_match {
+        egui::Grid::new("target_match_grid")
+            .striped(true)
+            .spacing([20.0, 4.0])
+            .show(ui, |ui| {
+                let mut row = |name: &str, value: String| {
+                    ui.label(name);
+                    ui.label(value);
+                    ui.end_row();
+                };
+                let [l, a, b] = target.lab_delta;
+                row("ΔL* / Δa* / Δb*", format!("{l:+.1} / {a:+.1} / {b:+.1}"));
+                row("ΔE (means)", format!("{:.1}", target.mean_delta_e));
+                row(
+                    "Δ Contrast (L* σ)",
+                    format!("{:+.1}", target.contrast_delta),
+                );
+                row("Δ Saturation", format!("{:+.1}", target.saturation_delta));
+                row(
+                    "Δ CCT / Tint",
+                    format!("{:+.0} K / {:+.3}", target.cct_delta, target.tint_delta),
+                );
+                if let Some(diff) = target.frame_diff {
+                    row("ΔE (pixels)", format!("{:.1}", diff.mean_delta_e));
+                }
+            });
+
+        let nudges = target.nudges;
+        ui.label(format!(
+            "Suggested: exposure {:+.2} EV, warmth {:+.2}, saturation ×{:.2}",
+            nudges.exposure_ev, nudges.warmth, nudges.saturation_scale
+        ));
+        if ui
+            .button("Apply Suggestions")
+            .on_hover_text("Estimates; apply again after the new develop to converge")
+            .clicked()
+        {
+            app.apply_match_nudges(ctx);
+        }
+    }
+    ui.separator();
+}
+
 fn render_rgb_histogram(
     ui: &mut egui::Ui,
     metrics: &filmr::FilmMetrics,
     hist_log_scale: &mut bool,
     hist_clamp_zeros: &mut bool,
-    hist_smooth: bool,
+    hist_axis: &mut HistogramAxis,
+    hist_smoothing: &mut HistogramSmoothing,
+    hist_smooth_radius: &mut u32,
     ux_mode: UxMode,
 ) {
     let mut plot_hist = |ui: &mut egui::Ui| {
         ui.horizontal(|ui| {
             ui.checkbox(hist_log_scale, "Log Scale");
             ui.checkbox(hist_clamp_zeros, "Ignore Blacks");
+            egui::ComboBox::from_id_salt("hist_axis")
+                .selected_text(hist_axis.label())
+                .show_ui(ui, |ui| {
+                    for axis in HistogramAxis::ALL {
+                        ui.selectable_value(hist_axis, axis, axis.label());
+                    }
+                });
+            egui::ComboBox::from_id_salt("hist_smoothing")
+                .selected_text(hist_smoothing.label())
+                .show_ui(ui, |ui| {
+                    for kernel in HistogramSmoothing::ALL {
+                        ui.selectable_value(hist_smoothing, kernel, kernel.label());
+                    }
+                });
+            if *hist_smoothing != HistogramSmoothing::None {
+                ui.add(
+                    egui::DragValue::new(hist_smooth_radius)
+                        .range(1..=16)
+                        .suffix(" bins"),
+                )
+                .on_hover_text(
+                    "Smoothing radius. Clipped bins at either end are left as they are.",
+                );
+            }
         });
+        let axis = *hist_axis;
+        let hist_rgb = metrics.histogram(axis);
         Plot::new("rgb_hist")
             .view_aspect(1.5)
             .legend(Legend::default())
@@ -65,47 +190,9 @@ fn render_rgb_histogram(
             .allow_drag(false)
             .allow_scroll(false)
             .show(ui, |plot_ui| {
-                // Apply 3-tap [1,2,1]/4 smoothing when enabled
-                let smooth3 = |hist: &[u32; 256]| -> [f64; 256] {
-                    let mut out = [0.0f64; 256];
-                    for i in 0..256 {
-                        let prev = if i > 0 {
-                            hist[i - 1] as f64
-                        } else {
-                            hist[0] as f64
-                        };
-                        let curr = hist[i] as f64;
-                        let next = if i < 255 {
-                            hist[i + 1] as f64
-                        } else {
-                            hist[255] as f64
-                        };
-                        out[i] = (prev + 2.0 * curr + next) / 4.0;
-                    }
-                    out
-                };
-
-                let raw_to_f64 = |hist: &[u32; 256]| -> [f64; 256] {
-                    let mut out = [0.0f64; 256];
-                    for i in 0..256 {
-                        out[i] = hist[i] as f64;
-                    }
-                    out
-                };
-
-                let channels: [[f64; 256]; 3] = if hist_smooth {
-                    [
-                        smooth3(&metrics.hist_rgb[0]),
-                        smooth3(&metrics.hist_rgb[1]),
-                        smooth3(&metrics.hist_rgb[2]),
-                    ]
-                } else {
-                    [
-                        raw_to_f64(&metrics.hist_rgb[0]),
-                        raw_to_f64(&metrics.hist_rgb[1]),
-                        raw_to_f64(&metrics.hist_rgb[2]),
-                    ]
-                };
+                let channels = hist_rgb
+                    .each_ref()
+                    .map(|hist| smooth_histogram(hist, *hist_smoothing, *hist_smooth_radius));
 
                 let mut all_counts = Vec::with_capacity(256 * 3);
                 for ch in &channels {
@@ -145,7 +232,7 @@ fn render_rgb_histogram(
                             v
                         };
                         let val_norm = (val_raw / norm_denom).min(1.0);
-                        line_points.push([i as f64, val_norm]);
+                        line_points.push([axis.bin_value(i) as f64, val_norm]);
                     }
                     if !line_points.is_empty() {
                         plot_ui.line(
@@ -171,6 +258,109 @@ fn render_rgb_histogram(
     }
 }
 
+/// Log-scaled counts as a grayscale-green image, `width` bins per row.
+fn scope_image(bins: &[u32], width: usize, flip_rows: bool) -> egui::ColorImage {
+    let height = bins.len() / width;
+    let peak = (bins.iter().copied().max().unwrap_or(0) as f32)
+        .ln_1p()
+        .max(1.0);
+    let mut rgb = Vec::with_capacity(bins.len() * 3);
+    for row in 0..height {
+        let row = if flip_rows { height - 1 - row } else { row };
+        for &count in &bins[row * width..(row + 1) * width] {
+            let v = (count as f32).ln_1p() / peak;
+            rgb.extend([v * 0.7, v, v * 0.7].map(|c| (c * 255.0) as u8));
+        }
+    }
+    egui::ColorImage::from_rgb([width, height], &rgb)
+}
+
+fn render_scopes(ui: &mut egui::Ui, scopes: &mut Scopes) {
+    let [waveform_tex, vectorscope_tex] = scopes.textures.get_or_insert_with(|| {
+        // Waveform bins are band-major; transpose so rows are luma levels
+        let waveform = &scopes.waveform;
+        let by_luma: Vec<u32> = (0..SCOPE_BINS)
+            .flat_map(|luma| (0..waveform.columns).map(move |c| waveform.column(c)[luma]))
+            .collect();
+        let ctx = ui.ctx();
+        [
+            ctx.load_texture(
+                "waveform",
+                scope_image(&by_luma, waveform.columns, true),
+                egui::TextureOptions::LINEAR,
+            ),
+            ctx.load_texture(
+                "vectorscope",
+                scope_image(&scopes.vectorscope.bins, SCOPE_BINS, true),
+                egui::TextureOptions::LINEAR,
+            ),
+        ]
+    });
+
+    ui.collapsing("Waveform", |ui| {
+        Plot::new("waveform_plot")
+            .view_aspect(1.5)
+            .include_x(0.0)
+            .include_x(1.0)
+            .include_y(0.0)
+            .include_y(255.0)
+            .show_axes([false, true])
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show(ui, |plot_ui| {
+                plot_ui.image(PlotImage::new(
+                    "waveform",
+                    waveform_tex.id(),
+                    PlotPoint::new(0.5, 127.5),
+                    egui::vec2(1.0, 256.0),
+                ));
+            });
+    });
+
+    ui.collapsing("Vectorscope (Cb vs Cr)", |ui| {
+        Plot::new("vectorscope_plot")
+            .view_aspect(1.0)
+            .data_aspect(1.0)
+            .include_x(-0.5)
+            .include_x(0.5)
+            .include_y(-0.5)
+            .include_y(0.5)
+            .show_axes([false, false])
+            .allow_zoom(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .show(ui, |plot_ui| {
+                plot_ui.image(PlotImage::new(
+                    "vectorscope",
+                    vectorscope_tex.id(),
+                    PlotPoint::new(0.0, 0.0),
+                    egui::vec2(1.0, 1.0),
+                ));
+
+                // 75% primaries and secondaries, as on a hardware scope
+                let targets = [
+                    [191, 0, 0],
+                    [191, 191, 0],
+                    [0, 191, 0],
+                    [0, 191, 191],
+                    [0, 0, 191],
+                    [191, 0, 191],
+                ];
+                for rgb in targets {
+                    let (cb, cr) = Vectorscope::chroma(rgb);
+                    plot_ui.points(
+                        Points::new("targets", PlotPoints::from(vec![[cb as f64, cr as f64]]))
+                            .radius(4.0)
+                            .shape(egui_plot::MarkerShape::Square)
+                            .filled(false)
+                            .color(egui::Color32::from_rgb(rgb[0], rgb[1], rgb[2])),
+                    );
+                }
+            });
+    });
+}
+
 fn render_advanced_metrics(ui: &mut egui::Ui, metrics: &filmr::FilmMetrics) {
     ui.separator();
 
@@ -218,6 +408,35 @@ fn render_advanced_metrics(ui: &mut egui::Ui, metrics: &filmr::FilmMetrics) {
             "bits",
             egui::Color32::LIGHT_BLUE,
         );
+        if let Some(usage) = metrics.density_usage {
+            gauge(
+                ui,
+                "Density Used",
+                usage.utilization * 100.0,
+                0.0,
+                100.0,
+                "%",
+                egui::Color32::from_rgb(180, 140, 255),
+            );
+            gauge(
+                ui,
+                "Highlight Headroom",
+                usage.highlight_headroom * 100.0,
+                0.0,
+                100.0,
+                "%",
+                egui::Color32::from_rgb(255, 200, 120),
+            );
+            gauge(
+                ui,
+                "Shadow Headroom",
+                usage.shadow_headroom * 100.0,
+                0.0,
+                100.0,
+                "%",
+                egui::Color32::from_rgb(120, 170, 255),
+            );
+        }
         ui.add_space(5.0);
         ui.label("Clipping Ratio (Blacks vs Whites):");
         let zeros = metrics.clipping_ratio[0];
@@ -1,5 +1,8 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub mod batch;
 pub mod central;
 pub mod controls;
 pub mod metrics;
 pub mod settings;
+pub mod stock_strip;
 pub mod studio;
@@ -15,14 +15,26 @@ pub fn render_settings_window(app: &mut FilmrApp, ctx: &Context) {
             ui.set_min_width(400.0);
             if let Some(config_manager) = &mut app.config_manager {
                 ui.heading("General");
-                ui.separator();
-                ui.add_space(5.0);
-
-                ui.heading("Display");
                 ui.group(|ui| {
                     ui.horizontal(|ui| {
-                        ui.label("Smooth Histogram");
-                        ui.add(Toggle::new(&mut app.hist_smooth, ""));
+                        ui.label("Save develop sessions next to images");
+                        let before = config_manager.config.write_sidecars;
+                        ui.add(Toggle::new(&mut config_manager.config.write_sidecars, ""))
+                            .on_hover_text("Writes <image>.filmr.json and restores it on load");
+                        if config_manager.config.write_sidecars != before {
+                            config_manager.save();
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Reopen last image on startup");
+                        let before = config_manager.config.reopen_last_image;
+                        ui.add(Toggle::new(
+                            &mut config_manager.config.reopen_last_image,
+                            "",
+                        ));
+                        if config_manager.config.reopen_last_image != before {
+                            config_manager.save();
+                        }
                     });
                 });
                 ui.add_space(5.0);
@@ -0,0 +1,93 @@
+use crate::ui::app::FilmrApp;
+use crate::ui::components::{ACCENT, BG_DARK, BG_HOVER, TEXT_PRIMARY, TEXT_SECONDARY};
+use egui::{Color32, Context, Rect, Sense, Vec2};
+
+const THUMB_SIZE: Vec2 = Vec2::new(96.0, 72.0);
+const LABEL_HEIGHT: f32 = 18.0;
+
+/// Horizontal strip of every stock's thumbnail applied to the current image.
+/// Clicking a thumbnail selects that stock.
+pub fn render_stock_strip(app: &mut FilmrApp, ctx: &Context) {
+    let mut clicked = None;
+    egui::TopBottomPanel::bottom("stock_strip")
+        .resizable(false)
+        .frame(egui::Frame::side_top_panel(&ctx.style()).fill(BG_DARK))
+        .show(ctx, |ui| {
+            // Follow the selection when it changes elsewhere, e.g. from the keyboard
+            let last_id = ui.id().with("last_selected");
+            let last = ui.data(|d| d.get_temp::<usize>(last_id));
+            let follow = last != Some(app.selected_stock_idx);
+            ui.data_mut(|d| d.insert_temp(last_id, app.selected_stock_idx));
+
+            egui::ScrollArea::horizontal().show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for idx in app.stock_groups().into_iter().flat_map(|(_, i)| i) {
+                        let stock = &app.stocks[idx];
+                        let is_selected = idx == app.selected_stock_idx;
+                        let (rect, response) = ui.allocate_exact_size(
+                            THUMB_SIZE + Vec2::new(0.0, LABEL_HEIGHT),
+                            Sense::click(),
+                        );
+                        let thumb_rect = Rect::from_min_size(rect.min, THUMB_SIZE);
+                        let painter = ui.painter();
+
+                        painter.rect_filled(thumb_rect, 4.0, Color32::from_gray(50));
+                        if let Some(thumb) = app.preset_thumbnails.get(&stock.stable_id()) {
+                            // Fit inside the cell, keeping the aspect ratio
+                            let size = thumb.size_vec2();
+                            let scale = (THUMB_SIZE.x / size.x).min(THUMB_SIZE.y / size.y);
+                            egui::Image::new(thumb).corner_radius(4.0).paint_at(
+                                ui,
+                                Rect::from_center_size(thumb_rect.center(), size * scale),
+                            );
+                        }
+
+                        if is_selected {
+                            painter.rect_stroke(
+                                thumb_rect,
+                                4.0,
+                                egui::Stroke::new(2.0, ACCENT),
+                                egui::StrokeKind::Outside,
+                            );
+                        } else if response.hovered() {
+                            painter.rect_stroke(
+                                thumb_rect,
+                                4.0,
+                                egui::Stroke::new(1.0, BG_HOVER),
+                                egui::StrokeKind::Outside,
+                            );
+                        }
+
+                        let text_color = if is_selected {
+                            ACCENT
+                        } else if response.hovered() {
+                            TEXT_PRIMARY
+                        } else {
+                            TEXT_SECONDARY
+                        };
+                        let label_rect =
+                            Rect::from_min_max(egui::pos2(rect.min.x, thumb_rect.max.y), rect.max);
+                        ui.painter().with_clip_rect(label_rect).text(
+                            label_rect.center(),
+                            egui::Align2::CENTER_CENTER,
+                            &stock.name,
+                            egui::FontId::proportional(11.0),
+                            text_color,
+                        );
+
+                        let response = response.on_hover_text(stock.full_name());
+                        if is_selected && follow {
+                            response.scroll_to_me(Some(egui::Align::Center));
+                        }
+                        if response.clicked() && !is_selected {
+                            clicked = Some(idx);
+                        }
+                    }
+                });
+            });
+        });
+
+    if let Some(idx) = clicked {
+        app.select_stock(ctx, idx);
+    }
+}
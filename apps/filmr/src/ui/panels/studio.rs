@@ -1,7 +1,10 @@
 use crate::ui::app::FilmrApp;
-use crate::ui::components::{labeled_slider, ACCENT, TEXT_DARK, TEXT_DISABLED};
+use crate::ui::components::{
+    labeled_slider, pill_selector, pill_selector_rows, ACCENT, TEXT_DARK, TEXT_DISABLED,
+};
 use egui::{Color32, Ui};
-use filmr::film::{FilmType, SegmentedCurve};
+use filmr::film::{FilmFormat, FilmStock, FilmType, SegmentedCurve};
+use filmr::{GrainStructure, Toning};
 
 pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
     egui::SidePanel::right("studio_panel")
@@ -132,23 +135,15 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
                     ui.horizontal(|ui| {
                         ui.label("Type:");
                         egui::ComboBox::from_id_salt("film_type")
-                            .selected_text(format!("{:?}", app.studio_stock.film_type))
+                            .selected_text(app.studio_stock.film_type.name())
                             .show_ui(ui, |ui| {
-                                ui.selectable_value(
-                                    &mut app.studio_stock.film_type,
-                                    FilmType::ColorNegative,
-                                    "Color Negative",
-                                );
-                                ui.selectable_value(
-                                    &mut app.studio_stock.film_type,
-                                    FilmType::ColorSlide,
-                                    "Color Slide",
-                                );
-                                ui.selectable_value(
-                                    &mut app.studio_stock.film_type,
-                                    FilmType::BwNegative,
-                                    "B&W Negative",
-                                );
+                                for film_type in FilmType::all() {
+                                    ui.selectable_value(
+                                        &mut app.studio_stock.film_type,
+                                        film_type,
+                                        film_type.name(),
+                                    );
+                                }
                             });
                     });
 
@@ -186,6 +181,12 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
                         .color(TEXT_DISABLED),
                 )
                 .show(ui, |ui| {
+                    ui.label("Crossover");
+                    render_crossover_plot(ui, &app.studio_stock);
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.add_space(8.0);
+
                     ui.label("Red Channel");
                     if render_curve_editor(ui, &mut app.studio_stock.r_curve, "r_curve") {
                         changed = true;
@@ -206,6 +207,13 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
                     if render_curve_editor(ui, &mut app.studio_stock.b_curve, "b_curve") {
                         changed = true;
                     }
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.add_space(8.0);
+
+                    if render_wedge_fit(app, ui) {
+                        changed = true;
+                    }
                 });
 
                 ui.add_space(8.0);
@@ -321,7 +329,53 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
                         .color(TEXT_DISABLED),
                 )
                 .show(ui, |ui| {
+                    if app.studio_stock.is_monochrome() {
+                        ui.label("B&W Channel Mix");
+                        ui.label(
+                            egui::RichText::new(
+                                "Scene R, G, B weights of the luminance. B&W stocks ignore the matrix below.",
+                            )
+                            .size(10.0)
+                            .color(TEXT_DISABLED),
+                        );
+                        ui.horizontal(|ui| {
+                            for (c, name) in ["R", "G", "B"].into_iter().enumerate() {
+                                ui.label(name);
+                                if ui
+                                    .add(
+                                        egui::DragValue::new(
+                                            &mut app.studio_stock.bw_channel_mix[c],
+                                        )
+                                        .speed(0.01)
+                                        .range(0.0..=1.0),
+                                    )
+                                    .changed()
+                                {
+                                    changed = true;
+                                }
+                            }
+                        });
+                        ui.add_space(8.0);
+                    }
                     ui.label("Color Correction Matrix");
+                    ui.label(
+                        egui::RichText::new("Rows are output R, G, B; columns the input mix.")
+                            .size(10.0)
+                            .color(TEXT_DISABLED),
+                    );
+                    ui.horizontal_wrapped(|ui| {
+                        for preset in MatrixPreset::ALL {
+                            if ui
+                                .button(preset.label())
+                                .on_hover_text(preset.description())
+                                .clicked()
+                            {
+                                app.studio_stock.color_matrix =
+                                    preset.apply(app.studio_stock.color_matrix);
+                                changed = true;
+                            }
+                        }
+                    });
                     egui::Grid::new("color_matrix_grid").show(ui, |ui| {
                         for r in 0..3 {
                             for c in 0..3 {
@@ -353,6 +407,21 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
                         .color(TEXT_DISABLED),
                 )
                 .show(ui, |ui| {
+                    let formats = [
+                        (FilmFormat::Format35mm, "35mm"),
+                        (FilmFormat::Medium6x6, "6x6"),
+                        (FilmFormat::Large4x5, "4x5"),
+                    ];
+                    if pill_selector(
+                        ui,
+                        "studio_film_format",
+                        &mut app.studio_stock.film_format,
+                        &formats,
+                    ) {
+                        changed = true;
+                    }
+                    ui.add_space(4.0);
+
                     let grain = &mut app.studio_stock.grain_model;
                     if labeled_slider(ui, "Alpha (Strength)", &mut grain.alpha, 0.0..=1.0, false) {
                         changed = true;
@@ -372,6 +441,24 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
                     if labeled_slider(ui, "Blur Radius", &mut grain.blur_radius, 0.0..=5.0, false) {
                         changed = true;
                     }
+                    let mut clumped = matches!(grain.structure, GrainStructure::Clumped { .. });
+                    if ui
+                        .checkbox(&mut clumped, "Clumped Grain")
+                        .on_hover_text("Grain gathers into clumps instead of per-pixel noise")
+                        .changed()
+                    {
+                        grain.structure = if clumped {
+                            GrainStructure::Clumped { size: 2.5 }
+                        } else {
+                            GrainStructure::PerPixel
+                        };
+                        changed = true;
+                    }
+                    if let GrainStructure::Clumped { size } = &mut grain.structure {
+                        if labeled_slider(ui, "Clump Size (px)", size, 1.0..=8.0, false) {
+                            changed = true;
+                        }
+                    }
                 });
 
                 ui.add_space(8.0);
@@ -385,6 +472,26 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
                         .color(TEXT_DISABLED),
                 )
                 .show(ui, |ui| {
+                    let mut backed = app.studio_stock.anti_halation_backing >= 1.0;
+                    if ui
+                        .checkbox(&mut backed, "Anti-Halation Backing")
+                        .on_hover_text(
+                            "On: modern suppressed halation. Off: no backing, glowing highlights",
+                        )
+                        .changed()
+                    {
+                        app.studio_stock.anti_halation_backing = if backed { 1.0 } else { 0.0 };
+                        changed = true;
+                    }
+                    if labeled_slider(
+                        ui,
+                        "Backing Effect",
+                        &mut app.studio_stock.anti_halation_backing,
+                        0.0..=1.0,
+                        false,
+                    ) {
+                        changed = true;
+                    }
                     if labeled_slider(
                         ui,
                         "Strength",
@@ -407,7 +514,34 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
                         ui,
                         "Sigma (Spread)",
                         &mut app.studio_stock.halation_sigma,
-                        0.0..=10.0,
+                        0.0..=0.1,
+                        false,
+                    ) {
+                        changed = true;
+                    }
+                    if labeled_slider(
+                        ui,
+                        "Skirt Strength",
+                        &mut app.studio_stock.halation_strength2,
+                        0.0..=1.0,
+                        false,
+                    ) {
+                        changed = true;
+                    }
+                    if labeled_slider(
+                        ui,
+                        "Skirt Sigma",
+                        &mut app.studio_stock.halation_sigma2,
+                        0.0..=0.1,
+                        false,
+                    ) {
+                        changed = true;
+                    }
+                    if labeled_slider(
+                        ui,
+                        "Color Rejection",
+                        &mut app.studio_stock.halation_saturation_weight,
+                        0.0..=1.0,
                         false,
                     ) {
                         changed = true;
@@ -423,7 +557,38 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
                         app.studio_stock.halation_tint = color;
                         changed = true;
                     }
+
+                    ui.label("Layer Gain");
+                    for (label, idx) in [("Red Layer", 0), ("Green Layer", 1), ("Blue Layer", 2)] {
+                        if labeled_slider(
+                            ui,
+                            label,
+                            &mut app.studio_stock.halation_channel_gain[idx],
+                            0.0..=2.0,
+                            false,
+                        ) {
+                            changed = true;
+                        }
+                    }
                 });
+
+                if app.studio_stock.is_monochrome() {
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.add_space(8.0);
+
+                    egui::CollapsingHeader::new(
+                        egui::RichText::new("Toning")
+                            .strong()
+                            .size(12.0)
+                            .color(TEXT_DISABLED),
+                    )
+                    .show(ui, |ui| {
+                        if render_toning(ui, &mut app.toning) {
+                            changed = true;
+                        }
+                    });
+                }
             });
 
             ui.add_space(20.0);
@@ -442,27 +607,393 @@ pub fn render_studio_panel(app: &mut FilmrApp, ctx: &egui::Context) {
         });
 }
 
+/// Toning presets plus a split tone with editable shadow and highlight tints.
+pub fn render_toning(ui: &mut Ui, toning: &mut Toning) -> bool {
+    // Keep the current split tints selected rather than resetting them
+    let split = match *toning {
+        Toning::Split { .. } => *toning,
+        _ => Toning::CLASSIC_SPLIT,
+    };
+    let options: Vec<(Toning, &str)> = Toning::PRESETS
+        .iter()
+        .chain([&split])
+        .map(|t| (*t, t.name()))
+        .collect();
+    let rows: &[&[(Toning, &str)]] = &[&options[..3], &options[3..]];
+    let mut changed = pill_selector_rows(ui, "toning", toning, rows);
+
+    if let Toning::Split { shadow, highlight } = toning {
+        ui.horizontal(|ui| {
+            ui.label("Shadows");
+            changed |= ui.color_edit_button_rgb(shadow).changed();
+            ui.label("Highlights");
+            changed |= ui.color_edit_button_rgb(highlight).changed();
+        });
+    }
+    changed
+}
+
+/// Rec. 601 luma weights, the same mix the mono presets use.
+const LUMA_WEIGHTS: [f32; 3] = [0.3, 0.59, 0.11];
+
+/// Saturation factor applied per click of [`MatrixPreset::Saturate`].
+const SATURATE_STEP: f32 = 1.1;
+
+/// Quick edits of the color matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatrixPreset {
+    Neutral,
+    Saturate,
+    Luminance,
+    Normalize,
+}
+
+impl MatrixPreset {
+    const ALL: [Self; 4] = [
+        Self::Neutral,
+        Self::Saturate,
+        Self::Luminance,
+        Self::Normalize,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Neutral => "Neutral",
+            Self::Saturate => "Saturation +",
+            Self::Luminance => "B&W Luminance",
+            Self::Normalize => "Normalize Rows",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Neutral => "Identity matrix: no cross-talk between channels",
+            Self::Saturate => "Push colors away from gray, keeping luminance",
+            Self::Luminance => "Every channel sees 0.3 R + 0.59 G + 0.11 B",
+            Self::Normalize => "Scale each row to sum to 1 so gray stays gray",
+        }
+    }
+
+    fn apply(self, m: [[f32; 3]; 3]) -> [[f32; 3]; 3] {
+        match self {
+            Self::Neutral => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            Self::Saturate => {
+                // Saturation matrix about the luma axis, applied after `m`
+                let s = SATURATE_STEP;
+                let sat: [[f32; 3]; 3] = std::array::from_fn(|r| {
+                    std::array::from_fn(|c| {
+                        (1.0 - s) * LUMA_WEIGHTS[c] + if r == c { s } else { 0.0 }
+                    })
+                });
+                std::array::from_fn(|r| {
+                    std::array::from_fn(|c| (0..3).map(|k| sat[r][k] * m[k][c]).sum())
+                })
+            }
+            Self::Luminance => [LUMA_WEIGHTS; 3],
+            Self::Normalize => m.map(|row| {
+                let sum: f32 = row.iter().sum();
+                if sum.abs() < 1e-6 {
+                    row
+                } else {
+                    row.map(|v| v / sum)
+                }
+            }),
+        }
+    }
+}
+
+/// `log E, density` pairs from pasted or imported step wedge readings. Any
+/// separator works; lines without two numbers (headers, notes) are skipped.
+fn parse_wedge_points(text: &str) -> Vec<(f32, f32)> {
+    text.lines()
+        .filter_map(|line| {
+            let mut numbers = line
+                .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+                .filter(|field| !field.is_empty())
+                .map(|field| field.parse::<f32>());
+            match (numbers.next(), numbers.next()) {
+                (Some(Ok(log_e)), Some(Ok(density))) => Some((log_e, density)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Fit the characteristic curves to measured step wedge densities.
+/// Returns true if a curve changed.
+fn render_wedge_fit(app: &mut FilmrApp, ui: &mut Ui) -> bool {
+    let mut changed = false;
+    ui.label("Fit From Step Wedge");
+    ui.label(
+        egui::RichText::new("One \"log E, density\" reading per line")
+            .size(11.0)
+            .color(TEXT_DISABLED),
+    );
+    ui.add(
+        egui::TextEdit::multiline(&mut app.studio_wedge_text)
+            .hint_text("-2.85, 0.27\n-2.70, 0.29\n...")
+            .desired_rows(5)
+            .desired_width(f32::INFINITY)
+            .code_editor(),
+    );
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if ui.button("Import CSV...").clicked() {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Readings", &["csv", "txt", "tsv"])
+            .pick_file()
+        {
+            match std::fs::read_to_string(&path) {
+                Ok(text) => app.studio_wedge_text = text,
+                Err(e) => app.status_msg = format!("Failed to read readings: {}", e),
+            }
+        }
+    }
+
+    let points = parse_wedge_points(&app.studio_wedge_text);
+    let fit = SegmentedCurve::fit(&points);
+    let channels = [
+        ("Fit R", [true, false, false]),
+        ("Fit G", [false, true, false]),
+        ("Fit B", [false, false, true]),
+        ("Fit All", [true; 3]),
+    ];
+    ui.horizontal(|ui| {
+        for (label, selected) in channels {
+            let clicked = ui
+                .add_enabled(fit.is_some(), egui::Button::new(label))
+                .clicked();
+            let Some(fit) = fit.filter(|_| clicked) else {
+                continue;
+            };
+            let stock = &mut app.studio_stock;
+            for (curve, selected) in [&mut stock.r_curve, &mut stock.g_curve, &mut stock.b_curve]
+                .into_iter()
+                .zip(selected)
+            {
+                if selected {
+                    *curve = fit;
+                }
+            }
+            app.studio_wedge_fit = Some((fit.fit_error(&points), points.len()));
+            changed = true;
+        }
+    });
+
+    if fit.is_none() && !app.studio_wedge_text.trim().is_empty() {
+        ui.label(
+            egui::RichText::new("Needs at least 4 readings with density rising with exposure")
+                .size(11.0)
+                .color(TEXT_DISABLED),
+        );
+    } else if let Some((error, count)) = app.studio_wedge_fit {
+        ui.label(
+            egui::RichText::new(format!("RMS error {:.3} D over {} readings", error, count))
+                .size(11.0)
+                .color(TEXT_DISABLED),
+        );
+    }
+    changed
+}
+
+/// Control points of the curve plot. Each handle edits one parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CurveHandle {
+    /// Toe floor, dragged vertically.
+    DMin,
+    /// Shoulder ceiling, dragged vertically.
+    DMax,
+    /// Inflection point, dragged horizontally to change speed.
+    Speed,
+    /// Point on the inflection tangent, dragged vertically to change gamma.
+    Gamma,
+    /// Shoulder softening density, dragged vertically.
+    Shoulder,
+}
+
+/// log E distance from the inflection point to the gamma handle.
+const GAMMA_HANDLE_DX: f32 = 0.3;
+
+/// Handle positions in plot coordinates (log E, density).
+fn curve_handles(curve: &SegmentedCurve, x_max: f32) -> [(CurveHandle, [f32; 2]); 5] {
+    let log_e0 = curve.exposure_offset.log10();
+    let range = (curve.d_max - curve.d_min).max(0.0);
+    // Width of the erf transition, see SegmentedCurve::map_erf
+    let sigma = range / (curve.gamma * std::f32::consts::PI.sqrt());
+    let mid = curve.d_min + 0.5 * range;
+    [
+        (CurveHandle::DMin, [log_e0 - 2.0 * sigma, curve.d_min]),
+        (CurveHandle::DMax, [log_e0 + 2.0 * sigma, curve.d_max]),
+        (CurveHandle::Speed, [log_e0, mid]),
+        (
+            CurveHandle::Gamma,
+            [
+                log_e0 + GAMMA_HANDLE_DX,
+                mid + curve.gamma * GAMMA_HANDLE_DX,
+            ],
+        ),
+        (
+            CurveHandle::Shoulder,
+            [x_max - 0.25, curve.shoulder_density()],
+        ),
+    ]
+}
+
+/// Move `handle` to the plot position `(x, y)`.
+fn drag_curve_handle(curve: &mut SegmentedCurve, handle: CurveHandle, x: f32, y: f32) {
+    const MIN_RANGE: f32 = 0.05;
+    match handle {
+        CurveHandle::DMin => curve.d_min = y.clamp(0.0, curve.d_max - MIN_RANGE),
+        CurveHandle::DMax => curve.d_max = y.clamp(curve.d_min + MIN_RANGE, 4.0),
+        CurveHandle::Speed => curve.exposure_offset = 10f32.powf(x.clamp(-3.0, 3.0)),
+        CurveHandle::Gamma => {
+            let mid = 0.5 * (curve.d_min + curve.d_max);
+            curve.gamma = ((y - mid) / GAMMA_HANDLE_DX).clamp(0.1, 5.0);
+        }
+        // Kept as a fraction of D_max, like the presets
+        CurveHandle::Shoulder => {
+            curve.shoulder_point = y.clamp(curve.d_min, curve.d_max) / curve.d_max
+        }
+    }
+}
+
+/// The three characteristic curves overlaid as density above base, so the
+/// channels line up at the toe. Where they part, the print picks up a color
+/// cast that differs between shadows and highlights.
+fn render_crossover_plot(ui: &mut Ui, stock: &FilmStock) {
+    use egui_plot::{Line, Plot, PlotPoints};
+
+    let center = stock.g_curve.exposure_offset.log10().round();
+    let (x_min, x_max) = (center - 2.5, center + 2.5);
+    let channels = [
+        ("Red", &stock.r_curve, Color32::from_rgb(230, 90, 90)),
+        ("Green", &stock.g_curve, Color32::from_rgb(90, 200, 110)),
+        ("Blue", &stock.b_curve, Color32::from_rgb(100, 140, 240)),
+    ];
+
+    Plot::new("crossover")
+        .view_aspect(2.0)
+        .allow_drag(false)
+        .allow_zoom(false)
+        .allow_scroll(false)
+        .include_x(x_min)
+        .include_x(x_max)
+        .include_y(0.0)
+        .x_axis_label("log E")
+        .y_axis_label("D - Dmin")
+        .show(ui, |plot_ui| {
+            for (name, curve, color) in channels {
+                let points: Vec<[f64; 2]> = (0..=200)
+                    .map(|i| {
+                        let x = x_min + (x_max - x_min) * i as f32 / 200.0;
+                        [x as f64, (curve.map(x) - curve.d_min) as f64]
+                    })
+                    .collect();
+                plot_ui.line(Line::new(name, PlotPoints::new(points)).color(color));
+            }
+        });
+}
+
+/// Characteristic curve (log E -> density) with draggable control points,
+/// followed by the numeric fields. Returns true if the curve changed.
 fn render_curve_editor(ui: &mut Ui, curve: &mut SegmentedCurve, id_salt: &str) -> bool {
+    use egui_plot::{Line, LineStyle, Plot, PlotPoint, PlotPoints, Points};
+
     let mut changed = false;
 
-    // Visualize curve
-    let points: Vec<[f64; 2]> = (0..=100)
+    // Window of 5 stops-of-ten around the speed point, snapped so it only
+    // moves when the speed crosses a whole decade
+    let center = curve.exposure_offset.log10().round();
+    let (x_min, x_max) = (center - 2.5, center + 2.5);
+
+    let points: Vec<[f64; 2]> = (0..=200)
         .map(|i| {
-            let x = i as f32 / 100.0;
-            let y = curve.map(x);
-            [x as f64, y as f64]
+            let x = x_min + (x_max - x_min) * i as f32 / 200.0;
+            [x as f64, curve.map(x) as f64]
         })
         .collect();
-
-    use egui_plot::{Line, Plot, PlotPoints};
-    let line = Line::new("Curve", PlotPoints::new(points));
+    let handles = curve_handles(curve, x_max);
+    let log_e0 = curve.exposure_offset.log10();
+    let mid = 0.5 * (curve.d_min + curve.d_max);
+    let tangent = [
+        [(log_e0 - 0.5) as f64, (mid - 0.5 * curve.gamma) as f64],
+        [(log_e0 + 0.5) as f64, (mid + 0.5 * curve.gamma) as f64],
+    ];
+    let drag_id = ui.id().with((id_salt, "curve_drag"));
 
     Plot::new(id_salt)
         .view_aspect(2.0)
         .allow_drag(false)
         .allow_zoom(false)
         .allow_scroll(false)
-        .show(ui, |plot_ui| plot_ui.line(line));
+        .allow_double_click_reset(false)
+        .include_x(x_min)
+        .include_x(x_max)
+        .include_y(0.0)
+        .include_y(4.2)
+        .x_axis_label("log E")
+        .y_axis_label("D")
+        .show(ui, |plot_ui| {
+            plot_ui.line(Line::new("Curve", PlotPoints::new(points)));
+            plot_ui.line(
+                Line::new("Gamma", PlotPoints::from(tangent.to_vec()))
+                    .style(LineStyle::dashed_loose())
+                    .color(Color32::GRAY),
+            );
+            plot_ui.line(
+                Line::new(
+                    "Shoulder",
+                    PlotPoints::from(vec![
+                        [x_min as f64, curve.shoulder_density() as f64],
+                        [x_max as f64, curve.shoulder_density() as f64],
+                    ]),
+                )
+                .style(LineStyle::dotted_dense())
+                .color(Color32::DARK_GRAY),
+            );
+            plot_ui.points(
+                Points::new(
+                    "Handles",
+                    PlotPoints::from(
+                        handles
+                            .iter()
+                            .map(|(_, [x, y])| [*x as f64, *y as f64])
+                            .collect::<Vec<_>>(),
+                    ),
+                )
+                .radius(5.0)
+                .shape(egui_plot::MarkerShape::Circle)
+                .color(Color32::WHITE),
+            );
+
+            // Grab the nearest handle on press, follow the pointer while held
+            let response = plot_ui.response().clone();
+            if response.drag_started() {
+                let grabbed = response.interact_pointer_pos().and_then(|pos| {
+                    handles
+                        .iter()
+                        .map(|&(handle, [x, y])| {
+                            let screen =
+                                plot_ui.screen_from_plot(PlotPoint::new(x as f64, y as f64));
+                            (handle, screen.distance(pos))
+                        })
+                        .filter(|&(_, dist)| dist < 12.0)
+                        .min_by(|a, b| a.1.total_cmp(&b.1))
+                        .map(|(handle, _)| handle)
+                });
+                plot_ui.ctx().data_mut(|d| d.insert_temp(drag_id, grabbed));
+            }
+            if response.dragged() {
+                let grabbed = plot_ui
+                    .ctx()
+                    .data(|d| d.get_temp::<Option<CurveHandle>>(drag_id))
+                    .flatten();
+                if let (Some(handle), Some(pointer)) = (grabbed, plot_ui.pointer_coordinate()) {
+                    drag_curve_handle(curve, handle, pointer.x as f32, pointer.y as f32);
+                    changed = true;
+                }
+            }
+        });
 
     if labeled_slider(ui, "D Min", &mut curve.d_min, 0.0..=1.0, false) {
         changed = true;
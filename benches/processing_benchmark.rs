@@ -16,6 +16,7 @@ fn benchmark_processing(c: &mut Criterion) {
         light_leak: filmr::light_leak::LightLeakConfig {
             enabled: true,
             leaks: vec![filmr::light_leak::LightLeak::default()],
+            ..Default::default()
         },
         ..Default::default()
     };
@@ -42,5 +43,30 @@ fn benchmark_processing(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, benchmark_processing);
+/// GPU develop at a preview and a full-HD size, for tuning
+/// `gpu_pipelines::WORKGROUP_SIZE`.
+#[cfg(feature = "compute-gpu")]
+fn benchmark_gpu_sizes(c: &mut Criterion) {
+    let film = KODAK_PORTRA_400();
+    let config = SimulationConfig {
+        use_gpu: true,
+        ..Default::default()
+    };
+    let (wx, wy) = filmr::gpu_pipelines::WORKGROUP_SIZE;
+
+    let mut group = c.benchmark_group(format!("gpu_workgroup_{wx}x{wy}"));
+    group.sample_size(10);
+    for (name, width, height) in [("preview", 320, 213), ("1080p", 1920, 1080)] {
+        let input = RgbImage::from_fn(width, height, |x, y| {
+            Rgb([(x % 255) as u8, (y % 255) as u8, ((x + y) % 255) as u8])
+        });
+        group.bench_function(name, |b| b.iter(|| process_image(&input, &film, &config)));
+    }
+    group.finish();
+}
+
+#[cfg(not(feature = "compute-gpu"))]
+fn benchmark_gpu_sizes(_c: &mut Criterion) {}
+
+criterion_group!(benches, benchmark_processing, benchmark_gpu_sizes);
 criterion_main!(benches);
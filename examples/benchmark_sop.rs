@@ -57,6 +57,7 @@ fn main() {
             light_leak: filmr::light_leak::LightLeakConfig {
                 enabled: true, // Enable light leak to test full pipeline
                 leaks: vec![filmr::light_leak::LightLeak::default()],
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -19,14 +19,13 @@ fn main() {
     // 2. Setup Film Simulation
     // Use preset
     let film = other::STANDARD_DAYLIGHT();
-    let config = SimulationConfig {
-        exposure_time: 1.0,
-        enable_grain: true,
-        output_mode: OutputMode::Positive, // Generate a positive image
-        white_balance_mode: WhiteBalanceMode::Auto,
-        white_balance_strength: 1.0,
-        ..Default::default()
-    };
+    let config = SimulationConfig::builder()
+        .exposure_time(1.0)
+        .enable_grain(true)
+        .output_mode(OutputMode::Positive) // Generate a positive image
+        .white_balance_mode(WhiteBalanceMode::Auto)
+        .white_balance_strength(1.0)
+        .build();
 
     println!("Starting simulation (Positive Mode)...");
     let output = process_image(&img, &film, &config);
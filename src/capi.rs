@@ -0,0 +1,307 @@
+//! C ABI for hosts outside Rust, behind the `capi` feature.
+//!
+//! Build it as a shared library for C hosts and Python (via `ctypes` or
+//! `cffi`) with
+//!
+//! ```sh
+//! cargo rustc --lib --release --features capi --crate-type cdylib
+//! ```
+//!
+//! which leaves `libfilmr.so` (`filmr.dll`, `libfilmr.dylib`) in
+//! `target/release`. Rust users of the crate do not pay for a `cdylib`. The
+//! surface is kept small: develop an RGB8 buffer with a stock and a flat
+//! config, list the stocks, and free what the library returned.
+//!
+//! ```c
+//! typedef struct {
+//!     float exposure_time;
+//!     bool enable_grain;
+//!     uint64_t grain_seed;            /* 0 draws fresh grain on every call */
+//!     uint32_t output_mode;           /* 0 positive, 1 negative, 2 inverted */
+//!     uint32_t white_balance_mode;    /* 0 auto, 1 gray, 2 white, 3 off */
+//!     float white_balance_strength;
+//!     float warmth;
+//!     float saturation;
+//!     bool auto_levels;
+//! } FilmrConfig;
+//!
+//! FilmrConfig filmr_config_default(void);
+//! uint8_t *filmr_process(const char *stock, const FilmrConfig *config,
+//!                        const uint8_t *rgb, uint32_t width, uint32_t height,
+//!                        size_t *out_len);
+//! uint8_t *filmr_list_stocks(size_t *out_len);
+//! void filmr_free(uint8_t *buffer, size_t len);
+//! ```
+//!
+//! Functions return null on any failure and never unwind into the caller.
+
+use crate::presets;
+use crate::processor::{process_image, OutputMode, SimulationConfig, WhiteBalanceMode};
+use image::RgbImage;
+use std::ffi::{c_char, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Flat, C-compatible subset of [`SimulationConfig`]. Everything not listed
+/// keeps its default.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilmrConfig {
+    /// See [`SimulationConfig::exposure_time`].
+    pub exposure_time: f32,
+    /// See [`SimulationConfig::enable_grain`].
+    pub enable_grain: bool,
+    /// Grain seed; 0 draws fresh grain on every call.
+    pub grain_seed: u64,
+    /// 0 positive, 1 negative, 2 inverted.
+    pub output_mode: u32,
+    /// 0 auto, 1 gray, 2 white, 3 off.
+    pub white_balance_mode: u32,
+    /// See [`SimulationConfig::white_balance_strength`].
+    pub white_balance_strength: f32,
+    /// See [`SimulationConfig::warmth`].
+    pub warmth: f32,
+    /// See [`SimulationConfig::saturation`].
+    pub saturation: f32,
+    /// See [`SimulationConfig::auto_levels`].
+    pub auto_levels: bool,
+}
+
+impl Default for FilmrConfig {
+    fn default() -> Self {
+        let config = SimulationConfig::default();
+        Self {
+            exposure_time: config.exposure_time,
+            enable_grain: config.enable_grain,
+            grain_seed: config.grain_seed.unwrap_or(0),
+            output_mode: 0,
+            white_balance_mode: 0,
+            white_balance_strength: config.white_balance_strength,
+            warmth: config.warmth,
+            saturation: config.saturation,
+            auto_levels: config.auto_levels,
+        }
+    }
+}
+
+impl FilmrConfig {
+    /// The full config, or `None` if an enum field is out of range.
+    pub fn to_config(&self) -> Option<SimulationConfig> {
+        let output_mode = match self.output_mode {
+            0 => OutputMode::Positive,
+            1 => OutputMode::Negative,
+            2 => OutputMode::Inverted,
+            _ => return None,
+        };
+        let white_balance_mode = match self.white_balance_mode {
+            0 => WhiteBalanceMode::Auto,
+            1 => WhiteBalanceMode::Gray,
+            2 => WhiteBalanceMode::White,
+            3 => WhiteBalanceMode::Off,
+            _ => return None,
+        };
+        Some(SimulationConfig {
+            exposure_time: self.exposure_time,
+            enable_grain: self.enable_grain,
+            grain_seed: (self.grain_seed != 0).then_some(self.grain_seed),
+            output_mode,
+            white_balance_mode,
+            white_balance_strength: self.white_balance_strength,
+            warmth: self.warmth,
+            saturation: self.saturation,
+            auto_levels: self.auto_levels,
+            ..Default::default()
+        })
+    }
+}
+
+/// Hand `bytes` to the caller, who returns them through [`filmr_free`].
+///
+/// # Safety
+///
+/// `out_len` must be null or valid for a write.
+unsafe fn into_raw(bytes: Vec<u8>, out_len: *mut usize) -> *mut u8 {
+    if !out_len.is_null() {
+        *out_len = bytes.len();
+    }
+    Box::into_raw(bytes.into_boxed_slice()) as *mut u8
+}
+
+/// The default config, as [`SimulationConfig::default`].
+#[no_mangle]
+pub extern "C" fn filmr_config_default() -> FilmrConfig {
+    FilmrConfig::default()
+}
+
+/// Develop a `width` x `height` RGB8 buffer with the stock named `stock`
+/// (anything [`presets::by_name`] finds) and `config` (null for the
+/// default). Returns a new RGB8 buffer of the same size and writes its
+/// length to `out_len`, or returns null if the stock is unknown, the config
+/// is out of range or the buffer is empty or too large to address.
+///
+/// # Safety
+///
+/// `stock` must be a NUL-terminated string, `config` null or valid for a
+/// read, `rgb` valid for `width * height * 3` bytes and `out_len` null or
+/// valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn filmr_process(
+    stock: *const c_char,
+    config: *const FilmrConfig,
+    rgb: *const u8,
+    width: u32,
+    height: u32,
+    out_len: *mut usize,
+) -> *mut u8 {
+    if stock.is_null() || rgb.is_null() || width == 0 || height == 0 {
+        return std::ptr::null_mut();
+    }
+    let Ok(name) = CStr::from_ptr(stock).to_str() else {
+        return std::ptr::null_mut();
+    };
+    let flat = if config.is_null() {
+        FilmrConfig::default()
+    } else {
+        *config
+    };
+    let Some(len) = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|n| n.checked_mul(3))
+        .filter(|&len| len <= isize::MAX as usize)
+    else {
+        return std::ptr::null_mut();
+    };
+    let pixels = std::slice::from_raw_parts(rgb, len).to_vec();
+
+    let output = catch_unwind(AssertUnwindSafe(|| {
+        let film = presets::by_name(name)?;
+        let config = flat.to_config()?;
+        let input = RgbImage::from_raw(width, height, pixels)?;
+        Some(process_image(&input, &film, &config).into_raw())
+    }));
+    match output {
+        Ok(Some(bytes)) => into_raw(bytes, out_len),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// Full names of the built-in stocks, one per line, as a NUL-terminated
+/// UTF-8 string. `out_len` receives the buffer length including the NUL.
+///
+/// # Safety
+///
+/// `out_len` must be null or valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn filmr_list_stocks(out_len: *mut usize) -> *mut u8 {
+    let names = catch_unwind(|| {
+        presets::all_unique()
+            .iter()
+            .map(|stock| stock.full_name())
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+    match names {
+        Ok(names) => {
+            let mut bytes = names.into_bytes();
+            bytes.push(0);
+            into_raw(bytes, out_len)
+        }
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Free a buffer returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `buffer` must come from [`filmr_process`] or [`filmr_list_stocks`] with
+/// the `len` it reported, and not be freed twice.
+#[no_mangle]
+pub unsafe extern "C" fn filmr_free(buffer: *mut u8, len: usize) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            buffer, len,
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_round_trips_through_the_c_abi() {
+        let (width, height) = (16u32, 8u32);
+        let rgb = vec![128u8; (width * height * 3) as usize];
+        let config = FilmrConfig {
+            grain_seed: 7,
+            ..filmr_config_default()
+        };
+
+        let mut len = 0;
+        let out = unsafe {
+            filmr_process(
+                c"Kodak Portra 400".as_ptr(),
+                &config,
+                rgb.as_ptr(),
+                width,
+                height,
+                &mut len,
+            )
+        };
+        assert!(!out.is_null());
+        assert_eq!(len, rgb.len());
+
+        // Same as calling the library directly
+        let film = presets::by_name("Kodak Portra 400").unwrap();
+        let input = RgbImage::from_raw(width, height, rgb.clone()).unwrap();
+        let expected = process_image(&input, &film, &config.to_config().unwrap());
+        assert_eq!(
+            unsafe { std::slice::from_raw_parts(out, len) },
+            expected.as_raw()
+        );
+        unsafe { filmr_free(out, len) };
+
+        // Unknown stocks, overflowing sizes and out-of-range enums fail
+        // instead of unwinding
+        let unknown = unsafe {
+            filmr_process(
+                c"No Such Film".as_ptr(),
+                std::ptr::null(),
+                rgb.as_ptr(),
+                width,
+                height,
+                &mut len,
+            )
+        };
+        assert!(unknown.is_null());
+        let huge = unsafe {
+            filmr_process(
+                c"Kodak Portra 400".as_ptr(),
+                std::ptr::null(),
+                rgb.as_ptr(),
+                u32::MAX,
+                u32::MAX,
+                &mut len,
+            )
+        };
+        assert!(huge.is_null());
+        let bad = FilmrConfig {
+            output_mode: 9,
+            ..config
+        };
+        assert!(bad.to_config().is_none());
+    }
+
+    #[test]
+    fn test_list_stocks_is_a_c_string() {
+        let mut len = 0;
+        let list = unsafe { filmr_list_stocks(&mut len) };
+        let names = unsafe { CStr::from_ptr(list as *const c_char) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(names.len() + 1, len);
+        assert!(names.lines().any(|name| name == "Kodak Portra 400"));
+        unsafe { filmr_free(list, len) };
+    }
+}
@@ -0,0 +1,262 @@
+//! Synthetic test chart and a reproducible render of it through a stock.
+//!
+//! Used as golden images: the chart is generated in code and the render is
+//! fully seeded, so any change in the output comes from the develop math.
+//!
+//! [`color_transform`] follows single swatches instead: the primaries and a
+//! ColorChecker through linearization, the color matrix and the curves, as
+//! a table or a labeled chart for triaging color reports.
+
+use crate::contact_sheet::burn_text;
+use crate::film::FilmStock;
+use crate::processor::{develop_pixel, process_image, SimulationConfig};
+use image::{Rgb, RgbImage};
+use std::fmt::Write;
+
+/// Size of the chart rendered by [`render_lut_preview`].
+pub const CHART_WIDTH: u32 = 384;
+pub const CHART_HEIGHT: u32 = 256;
+
+/// Grain seed used by [`render_lut_preview`].
+pub const LUT_PREVIEW_GRAIN_SEED: u64 = 0x5EED;
+
+/// Patches in the lower half of the chart, two rows of six, in sRGB.
+const PATCHES: [[u8; 3]; 12] = [
+    [255, 0, 0],
+    [0, 255, 0],
+    [0, 0, 255],
+    [0, 255, 255],
+    [255, 0, 255],
+    [255, 255, 0],
+    [115, 82, 68],   // Dark skin
+    [194, 150, 130], // Light skin
+    [98, 122, 157],  // Blue sky
+    [87, 108, 67],   // Foliage
+    [118, 118, 118], // 18% gray
+    [243, 243, 242], // White
+];
+
+/// Test chart: a neutral ramp from black to white across the upper half,
+/// color patches in the lower half.
+pub fn test_chart(width: u32, height: u32) -> RgbImage {
+    RgbImage::from_fn(width, height, |x, y| {
+        if y < height / 2 {
+            let v = (x * 255 / width.saturating_sub(1).max(1)) as u8;
+            Rgb([v, v, v])
+        } else {
+            let col = (x * 6 / width).min(5);
+            let row = ((y - height / 2) * 2 / (height - height / 2)).min(1);
+            Rgb(PATCHES[(row * 6 + col) as usize])
+        }
+    })
+}
+
+/// Render the test chart through `film` with the default settings and a
+/// fixed grain seed. Always runs on the CPU, so the result only depends on
+/// the stock and the develop code.
+pub fn render_lut_preview(film: &FilmStock) -> RgbImage {
+    let chart = test_chart(CHART_WIDTH, CHART_HEIGHT);
+    let config = SimulationConfig {
+        use_gpu: false,
+        grain_seed: Some(LUT_PREVIEW_GRAIN_SEED),
+        ..Default::default()
+    };
+    process_image(&chart, film, &config)
+}
+
+/// Pure sRGB primaries, listed before the ColorChecker by [`color_transform`].
+pub const PRIMARIES: [(&str, [u8; 3]); 3] = [
+    ("Red", [255, 0, 0]),
+    ("Green", [0, 255, 0]),
+    ("Blue", [0, 0, 255]),
+];
+
+/// The 24 ColorChecker Classic patches in sRGB, row by row.
+pub const COLOR_CHECKER: [(&str, [u8; 3]); 24] = [
+    ("Dark skin", [115, 82, 68]),
+    ("Light skin", [194, 150, 130]),
+    ("Blue sky", [98, 122, 157]),
+    ("Foliage", [87, 108, 67]),
+    ("Blue flower", [133, 128, 177]),
+    ("Bluish green", [103, 189, 170]),
+    ("Orange", [214, 126, 44]),
+    ("Purplish blue", [80, 91, 166]),
+    ("Moderate red", [193, 90, 99]),
+    ("Purple", [94, 60, 108]),
+    ("Yellow green", [157, 188, 64]),
+    ("Orange yellow", [224, 163, 46]),
+    ("Blue", [56, 61, 150]),
+    ("Green", [70, 148, 73]),
+    ("Red", [175, 54, 60]),
+    ("Yellow", [231, 199, 31]),
+    ("Magenta", [187, 86, 149]),
+    ("Cyan", [8, 133, 161]),
+    ("White", [243, 243, 242]),
+    ("Neutral 8", [200, 200, 200]),
+    ("Neutral 6.5", [160, 160, 160]),
+    ("Neutral 5", [122, 122, 121]),
+    ("Neutral 3.5", [85, 85, 85]),
+    ("Black", [52, 52, 52]),
+];
+
+/// One swatch through the per-pixel develop.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwatchTransform {
+    pub name: &'static str,
+    /// sRGB input.
+    pub input: [u8; 3],
+    /// Linearized input.
+    pub linear: [f32; 3],
+    /// Film density after the color matrix and the curves.
+    pub density: [f32; 3],
+    /// Encoded output.
+    pub output: [u8; 3],
+}
+
+/// [`PRIMARIES`] and [`COLOR_CHECKER`] developed on `film` with
+/// [`develop_pixel`], so spatial and whole-frame steps are left out.
+pub fn color_transform(film: &FilmStock, config: &SimulationConfig) -> Vec<SwatchTransform> {
+    PRIMARIES
+        .iter()
+        .chain(&COLOR_CHECKER)
+        .map(|&(name, input)| {
+            let (linear, density, output) = develop_pixel(film, config, input);
+            SwatchTransform {
+                name,
+                input,
+                linear,
+                density,
+                output,
+            }
+        })
+        .collect()
+}
+
+/// [`color_transform`] as plain text: the stock's spectral and color
+/// matrices, then one swatch per line.
+pub fn color_transform_table(film: &FilmStock, config: &SimulationConfig) -> String {
+    let mut table = format!("{}\n", film.full_name());
+    let matrix_rows = |table: &mut String, label: &str, matrix: [[f32; 3]; 3]| {
+        for (i, row) in matrix.iter().enumerate() {
+            let label = if i == 0 { label } else { "" };
+            let _ = writeln!(
+                table,
+                "{label:<16}{:>8.4}{:>8.4}{:>8.4}",
+                row[0], row[1], row[2]
+            );
+        }
+    };
+    matrix_rows(
+        &mut table,
+        "Spectral matrix",
+        film.compute_spectral_matrix(),
+    );
+    matrix_rows(&mut table, "Color matrix", film.color_matrix);
+
+    let _ = writeln!(
+        table,
+        "\n{:<14}{:>13}  {:>20}  {:>20}  {:>13}",
+        "Swatch", "Input", "Linear", "Density", "Output"
+    );
+    for swatch in color_transform(film, config) {
+        let [ir, ig, ib] = swatch.input;
+        let [lr, lg, lb] = swatch.linear;
+        let [dr, dg, db] = swatch.density;
+        let [or, og, ob] = swatch.output;
+        let _ = writeln!(
+            table,
+            "{:<14}{ir:>5}{ig:>4}{ib:>4}  {lr:>6.3} {lg:>6.3} {lb:>6.3}  \
+             {dr:>6.3} {dg:>6.3} {db:>6.3}  {or:>5}{og:>4}{ob:>4}",
+            swatch.name
+        );
+    }
+    table
+}
+
+/// Size of one swatch in [`color_transform_chart`]; the input fills the
+/// upper half, the output the lower.
+const SWATCH_WIDTH: u32 = 104;
+const SWATCH_HEIGHT: u32 = 80;
+
+/// [`color_transform`] as a labeled chart: the primaries on the first row,
+/// then the ColorChecker, six swatches to a row, each named below.
+pub fn color_transform_chart(film: &FilmStock, config: &SimulationConfig) -> RgbImage {
+    const COLS: u32 = 6;
+    const BORDER: u32 = 6;
+    const LABEL_HEIGHT: u32 = 16;
+    const SCALE: u32 = 2;
+    let background = Rgb([24, 24, 24]);
+    let ink = Rgb([220, 220, 220]);
+
+    let swatches = color_transform(film, config);
+    let (cell_w, cell_h) = (
+        SWATCH_WIDTH + 2 * BORDER,
+        SWATCH_HEIGHT + BORDER + LABEL_HEIGHT,
+    );
+    // The primaries get a row to themselves
+    let rows = 1 + (swatches.len() - PRIMARIES.len()) as u32 / COLS;
+    let mut chart = RgbImage::from_pixel(COLS * cell_w, rows * cell_h + BORDER, background);
+
+    for (i, swatch) in swatches.iter().enumerate() {
+        let (col, row) = match i.checked_sub(PRIMARIES.len()) {
+            None => (i as u32, 0),
+            Some(j) => (j as u32 % COLS, 1 + j as u32 / COLS),
+        };
+        let (x0, y0) = (col * cell_w + BORDER, row * cell_h + BORDER);
+        for y in 0..SWATCH_HEIGHT {
+            let color = if y < SWATCH_HEIGHT / 2 {
+                swatch.input
+            } else {
+                swatch.output
+            };
+            for x in 0..SWATCH_WIDTH {
+                chart.put_pixel(x0 + x, y0 + y, Rgb(color));
+            }
+        }
+        let text_y = y0 + SWATCH_HEIGHT + (LABEL_HEIGHT - 5 * SCALE) / 2;
+        burn_text(
+            &mut chart,
+            swatch.name,
+            x0,
+            text_y,
+            x0 + SWATCH_WIDTH,
+            SCALE,
+            ink,
+        );
+    }
+    chart
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::kodak::KODAK_PORTRA_400;
+
+    #[test]
+    fn test_color_transform_lists_primaries_and_checker() {
+        let film = KODAK_PORTRA_400();
+        let config = SimulationConfig::default();
+        let swatches = color_transform(&film, &config);
+        assert_eq!(swatches.len(), 27);
+
+        // Each swatch is what develop_pixel gives for it
+        let red = swatches[0];
+        assert_eq!(red.name, "Red");
+        assert_eq!(red.output, develop_pixel(&film, &config, [255, 0, 0]).2);
+        assert!(red.output[0] > red.output[1] && red.output[0] > red.output[2]);
+
+        let table = color_transform_table(&film, &config);
+        assert!(table.starts_with("Kodak Portra 400\n"));
+        assert!(table.contains("Color matrix"));
+        assert_eq!(
+            table.lines().filter(|l| l.starts_with("Neutral")).count(),
+            4
+        );
+
+        // The chart shows the input over the output of each swatch
+        let chart = color_transform_chart(&film, &config);
+        assert_eq!(chart.dimensions(), (6 * 116, 5 * 102 + 6));
+        assert_eq!(chart.get_pixel(10, 10).0, [255, 0, 0]);
+        assert_eq!(chart.get_pixel(10, 80).0, red.output);
+    }
+}
@@ -0,0 +1,249 @@
+//! Contact sheets: several frames developed on one stock and laid out in a
+//! grid, as if printed from a ganged strip of film.
+//!
+//! Every frame goes through the same develop, so grain and color match
+//! across the sheet. Each frame sits in a rebate border with its frame
+//! number burned into the lower edge, like the edge print on a real strip.
+//!
+//! A stock comparison is the opposite: one frame developed on many stocks,
+//! each tile labeled with its stock, for a side-by-side film test.
+
+use crate::film::FilmStock;
+use crate::processor::{process_images_batch, SimulationConfig};
+use crate::utils::resize_to_fit_linear;
+use image::{Rgb, RgbImage};
+use std::borrow::Borrow;
+
+/// Unexposed rebate as it prints on a contact sheet.
+const REBATE_COLOR: Rgb<u8> = Rgb([18, 16, 14]);
+
+/// Edge print color of color stocks: the amber of latent-image edge codes.
+const EDGE_PRINT_COLOR: Rgb<u8> = Rgb([232, 164, 64]);
+
+/// Edge print color of B&W stocks.
+const EDGE_PRINT_MONO: Rgb<u8> = Rgb([220, 220, 220]);
+
+/// 3×5 bitmaps of the digits 0-9, one row per entry, high bit on the left.
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// 3×5 bitmaps of the letters A-Z, laid out like [`DIGITS`].
+const LETTERS: [[u8; 5]; 26] = [
+    [0b010, 0b101, 0b111, 0b101, 0b101],
+    [0b110, 0b101, 0b110, 0b101, 0b110],
+    [0b011, 0b100, 0b100, 0b100, 0b011],
+    [0b110, 0b101, 0b101, 0b101, 0b110],
+    [0b111, 0b100, 0b110, 0b100, 0b111],
+    [0b111, 0b100, 0b110, 0b100, 0b100],
+    [0b011, 0b100, 0b101, 0b101, 0b011],
+    [0b101, 0b101, 0b111, 0b101, 0b101],
+    [0b111, 0b010, 0b010, 0b010, 0b111],
+    [0b001, 0b001, 0b001, 0b101, 0b010],
+    [0b101, 0b101, 0b110, 0b101, 0b101],
+    [0b100, 0b100, 0b100, 0b100, 0b111],
+    [0b101, 0b111, 0b111, 0b101, 0b101],
+    [0b110, 0b101, 0b101, 0b101, 0b101],
+    [0b010, 0b101, 0b101, 0b101, 0b010],
+    [0b110, 0b101, 0b110, 0b100, 0b100],
+    [0b010, 0b101, 0b101, 0b110, 0b011],
+    [0b110, 0b101, 0b110, 0b101, 0b101],
+    [0b011, 0b100, 0b010, 0b001, 0b110],
+    [0b111, 0b010, 0b010, 0b010, 0b010],
+    [0b101, 0b101, 0b101, 0b101, 0b111],
+    [0b101, 0b101, 0b101, 0b101, 0b010],
+    [0b101, 0b101, 0b111, 0b111, 0b101],
+    [0b101, 0b101, 0b010, 0b101, 0b101],
+    [0b101, 0b101, 0b010, 0b010, 0b010],
+    [0b111, 0b001, 0b010, 0b100, 0b111],
+];
+
+/// Glyph of `c`, case-insensitive. Characters without one print as a space.
+fn glyph(c: char) -> Option<[u8; 5]> {
+    Some(match c.to_ascii_uppercase() {
+        '0'..='9' => DIGITS[c as usize - '0' as usize],
+        'A'..='Z' => LETTERS[c.to_ascii_uppercase() as usize - 'A' as usize],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '&' => [0b010, 0b101, 0b010, 0b101, 0b011],
+        '(' => [0b010, 0b100, 0b100, 0b100, 0b010],
+        ')' => [0b010, 0b001, 0b001, 0b001, 0b010],
+        '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        _ => return None,
+    })
+}
+
+/// Develop `image` on each of `stocks` and lay the results out in a grid of
+/// `cols` columns, each tile labeled with the stock's full name.
+///
+/// The image is first scaled to fit `tile_size` on its longer side, so
+/// every stock develops the same pixels. Tiles sit in a rebate border like
+/// [`make_contact_sheet`] frames, with the name in the lower border; names
+/// too long for the tile are cut off. Returns an empty image for no stocks;
+/// `cols` is clamped to at least 1.
+pub fn export_stock_comparison<S: Borrow<FilmStock>>(
+    image: &RgbImage,
+    stocks: &[S],
+    config: &SimulationConfig,
+    tile_size: u32,
+    cols: u32,
+) -> RgbImage {
+    if stocks.is_empty() || image.width() == 0 || image.height() == 0 {
+        return RgbImage::new(0, 0);
+    }
+    let tile = resize_to_fit_linear(
+        image,
+        tile_size.max(1),
+        image::imageops::FilterType::Lanczos3,
+    );
+    let items: Vec<_> = stocks
+        .iter()
+        .map(|stock| (&tile, stock.borrow(), config))
+        .collect();
+    let frames = process_images_batch(&items);
+
+    let (tile_w, tile_h) = tile.dimensions();
+    let border = (tile_w.min(tile_h) / 24).max(6);
+    // Labels fill about 60% of a border twice as deep as the others
+    let label_h = 2 * border;
+    let scale = (label_h * 3 / 5 / 5).max(1);
+    let (cell_w, cell_h) = (tile_w + 2 * border, tile_h + border + label_h);
+    let cols = cols.clamp(1, frames.len() as u32);
+    let rows = (frames.len() as u32).div_ceil(cols);
+
+    let mut sheet = RgbImage::from_pixel(cols * cell_w, rows * cell_h, REBATE_COLOR);
+    for (i, (frame, stock)) in frames.iter().zip(stocks).enumerate() {
+        let (col, row) = (i as u32 % cols, i as u32 / cols);
+        let (cell_x, cell_y) = (col * cell_w, row * cell_h);
+        image::imageops::replace(
+            &mut sheet,
+            frame,
+            (cell_x + border) as i64,
+            (cell_y + border) as i64,
+        );
+
+        let stock = stock.borrow();
+        let ink = if stock.is_monochrome() {
+            EDGE_PRINT_MONO
+        } else {
+            EDGE_PRINT_COLOR
+        };
+        let max_chars = (tile_w / (4 * scale)) as usize;
+        let label: String = stock.full_name().chars().take(max_chars).collect();
+        let text_y = cell_y + border + tile_h + (label_h - 5 * scale) / 2;
+        let clip = cell_x + border + tile_w;
+        burn_text(
+            &mut sheet,
+            &label,
+            cell_x + border,
+            text_y,
+            clip,
+            scale,
+            ink,
+        );
+    }
+    sheet
+}
+
+/// Develop `images` on `film` and lay them out in a grid of `cols` columns.
+///
+/// Frames keep their size and are centered in cells as large as the
+/// largest frame. Each cell has a rebate border of 1/12 of its shorter side
+/// (at least 6 px), with the 1-based frame number in the lower border.
+/// Frames are developed with [`process_images_batch`], so they share one
+/// GPU submission when `config.use_gpu` is set. Returns an empty image for
+/// no frames; `cols` is clamped to at least 1.
+pub fn make_contact_sheet(
+    images: &[RgbImage],
+    film: &FilmStock,
+    config: &SimulationConfig,
+    cols: u32,
+) -> RgbImage {
+    if images.is_empty() {
+        return RgbImage::new(0, 0);
+    }
+    let items: Vec<_> = images.iter().map(|image| (image, film, config)).collect();
+    let frames = process_images_batch(&items);
+
+    let frame_w = frames.iter().map(RgbImage::width).max().unwrap_or(0);
+    let frame_h = frames.iter().map(RgbImage::height).max().unwrap_or(0);
+    let border = (frame_w.min(frame_h) / 12).max(6);
+    let (cell_w, cell_h) = (frame_w + 2 * border, frame_h + 2 * border);
+    let cols = cols.clamp(1, frames.len() as u32);
+    let rows = (frames.len() as u32).div_ceil(cols);
+
+    let mut sheet = RgbImage::from_pixel(cols * cell_w, rows * cell_h, REBATE_COLOR);
+    let ink = if film.is_monochrome() {
+        EDGE_PRINT_MONO
+    } else {
+        EDGE_PRINT_COLOR
+    };
+    // Digits fill about 60% of the border height
+    let scale = (border * 3 / 5 / 5).max(1);
+
+    for (i, frame) in frames.iter().enumerate() {
+        let (col, row) = (i as u32 % cols, i as u32 / cols);
+        let (cell_x, cell_y) = (col * cell_w, row * cell_h);
+        let x0 = cell_x + border + (frame_w - frame.width()) / 2;
+        let y0 = cell_y + border + (frame_h - frame.height()) / 2;
+        image::imageops::replace(&mut sheet, frame, x0 as i64, y0 as i64);
+
+        let text_y = cell_y + border + frame_h + (border - 5 * scale) / 2;
+        burn_number(&mut sheet, i + 1, cell_x + border, text_y, scale, ink);
+    }
+    sheet
+}
+
+/// Draw `number` with its top-left corner at `(x, y)`, each font pixel
+/// `scale` × `scale` image pixels.
+fn burn_number(sheet: &mut RgbImage, number: usize, x: u32, y: u32, scale: u32, ink: Rgb<u8>) {
+    let clip = sheet.width();
+    burn_text(sheet, &number.to_string(), x, y, clip, scale, ink);
+}
+
+/// Draw `text` like [`burn_number`], leaving out pixels right of `clip_x`.
+pub(crate) fn burn_text(
+    sheet: &mut RgbImage,
+    text: &str,
+    x: u32,
+    y: u32,
+    clip_x: u32,
+    scale: u32,
+    ink: Rgb<u8>,
+) {
+    let advance = 4 * scale;
+    let clip_x = clip_x.min(sheet.width());
+    for (n, c) in text.chars().enumerate() {
+        let Some(glyph) = glyph(c) else {
+            continue;
+        };
+        let left = x + n as u32 * advance;
+        for (gy, bits) in glyph.iter().enumerate() {
+            for gx in 0..3 {
+                if bits & (0b100 >> gx) == 0 {
+                    continue;
+                }
+                for dy in 0..scale {
+                    for dx in 0..scale {
+                        let (px, py) = (left + gx * scale + dx, y + gy as u32 * scale + dy);
+                        if px < clip_x && py < sheet.height() {
+                            sheet.put_pixel(px, py, ink);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
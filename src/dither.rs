@@ -0,0 +1,194 @@
+//! Dithering for the final 8-bit quantization.
+//!
+//! Steep film curves stretch smooth gradients such as skies across few
+//! output levels, and plain rounding turns them into visible bands. Adding
+//! a threshold offset of up to half a level before rounding trades the
+//! bands for fine, even texture.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Threshold pattern added before rounding to 8 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DitherMode {
+    /// Plain rounding.
+    #[default]
+    None,
+    /// 8×8 Bayer matrix. Cheap, but its cross-hatch is visible up close.
+    Ordered,
+    /// 64×64 blue-noise mask. Only high-frequency noise, which the eye
+    /// barely notices; the best looking choice.
+    BlueNoise,
+}
+
+/// Side of the tiled blue-noise mask.
+const BLUE_NOISE_SIZE: usize = 64;
+
+/// Standard deviation, in pixels, of the void-and-cluster energy filter.
+const BLUE_NOISE_SIGMA: f32 = 1.5;
+
+impl DitherMode {
+    /// Every mode, in UI order.
+    pub const ALL: [DitherMode; 3] = [DitherMode::None, DitherMode::Ordered, DitherMode::BlueNoise];
+
+    /// Display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            DitherMode::None => "None",
+            DitherMode::Ordered => "Ordered",
+            DitherMode::BlueNoise => "Blue Noise",
+        }
+    }
+
+    /// Quantize an encoded 0-1 value at pixel `(x, y)` to 8 bits.
+    pub fn quantize(self, v: f32, x: u32, y: u32) -> u8 {
+        (v * 255.0 + self.offset(x, y)).round().clamp(0.0, 255.0) as u8
+    }
+
+    /// Offset in output levels, within -0.5..0.5, to add at pixel `(x, y)`
+    /// before rounding.
+    pub fn offset(self, x: u32, y: u32) -> f32 {
+        match self {
+            DitherMode::None => 0.0,
+            DitherMode::Ordered => {
+                let rank = bayer_rank(x as usize % 8, y as usize % 8, 8);
+                (rank as f32 + 0.5) / 64.0 - 0.5
+            }
+            DitherMode::BlueNoise => {
+                let (x, y) = (x as usize % BLUE_NOISE_SIZE, y as usize % BLUE_NOISE_SIZE);
+                blue_noise()[y * BLUE_NOISE_SIZE + x]
+            }
+        }
+    }
+}
+
+/// Rank of `(x, y)` in the recursive Bayer matrix of side `n` (a power of two).
+fn bayer_rank(x: usize, y: usize, n: usize) -> usize {
+    if n == 1 {
+        return 0;
+    }
+    let half = n / 2;
+    let quadrant = match (x >= half, y >= half) {
+        (false, false) => 0,
+        (true, true) => 1,
+        (true, false) => 2,
+        (false, true) => 3,
+    };
+    4 * bayer_rank(x % half, y % half, half) + quadrant
+}
+
+/// The blue-noise mask as offsets in -0.5..0.5, generated on first use.
+fn blue_noise() -> &'static [f32] {
+    static MASK: OnceLock<Vec<f32>> = OnceLock::new();
+    MASK.get_or_init(|| {
+        let n = BLUE_NOISE_SIZE * BLUE_NOISE_SIZE;
+        void_and_cluster()
+            .into_iter()
+            .map(|rank| (rank as f32 + 0.5) / n as f32 - 0.5)
+            .collect()
+    })
+}
+
+/// Ulichney's void-and-cluster method: ranks for every pixel of a tileable
+/// `BLUE_NOISE_SIZE` square such that each rank threshold is evenly spread.
+fn void_and_cluster() -> Vec<usize> {
+    let size = BLUE_NOISE_SIZE;
+    let n = size * size;
+
+    // Toroidal Gaussian, indexed by the wrapped offset between two pixels
+    let kernel: Vec<f32> = (0..n)
+        .map(|i| {
+            let wrap = |d: usize| d.min(size - d) as f32;
+            let (dx, dy) = (wrap(i % size), wrap(i / size));
+            (-(dx * dx + dy * dy) / (2.0 * BLUE_NOISE_SIGMA * BLUE_NOISE_SIGMA)).exp()
+        })
+        .collect();
+    let offset = |a: usize, b: usize| {
+        let dx = (a % size + size - b % size) % size;
+        let dy = (a / size + size - b / size) % size;
+        dy * size + dx
+    };
+    let update = |energy: &mut [f32], at: usize, sign: f32| {
+        for (i, e) in energy.iter_mut().enumerate() {
+            *e += sign * kernel[offset(i, at)];
+        }
+    };
+    let tightest_cluster = |pattern: &[bool], energy: &[f32]| {
+        (0..n)
+            .filter(|&i| pattern[i])
+            .max_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+            .unwrap()
+    };
+    let largest_void = |pattern: &[bool], energy: &[f32]| {
+        (0..n)
+            .filter(|&i| !pattern[i])
+            .min_by(|&a, &b| energy[a].total_cmp(&energy[b]))
+            .unwrap()
+    };
+
+    // Initial pattern: a tenth of the pixels at random, then relaxed by
+    // moving the tightest cluster into the largest void until stable
+    let mut rng = StdRng::seed_from_u64(0xB10E);
+    let mut pattern = vec![false; n];
+    let mut energy = vec![0.0f32; n];
+    let initial = n / 10;
+    while pattern.iter().filter(|&&p| p).count() < initial {
+        let i = rng.gen_range(0..n);
+        if !pattern[i] {
+            pattern[i] = true;
+            update(&mut energy, i, 1.0);
+        }
+    }
+    loop {
+        let cluster = tightest_cluster(&pattern, &energy);
+        pattern[cluster] = false;
+        update(&mut energy, cluster, -1.0);
+        let void = largest_void(&pattern, &energy);
+        pattern[void] = true;
+        update(&mut energy, void, 1.0);
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut ranks = vec![0usize; n];
+
+    // Phase 1: rank the initial points by removing the tightest cluster
+    let (mut phase1, mut phase1_energy) = (pattern.clone(), energy.clone());
+    for rank in (0..initial).rev() {
+        let cluster = tightest_cluster(&phase1, &phase1_energy);
+        phase1[cluster] = false;
+        update(&mut phase1_energy, cluster, -1.0);
+        ranks[cluster] = rank;
+    }
+
+    // Phase 2: fill the remaining pixels, largest void first
+    for rank in initial..n {
+        let void = largest_void(&pattern, &energy);
+        pattern[void] = true;
+        update(&mut energy, void, 1.0);
+        ranks[void] = rank;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masks_are_permutations_of_all_levels() {
+        let mut bayer: Vec<usize> = (0..64).map(|i| bayer_rank(i % 8, i / 8, 8)).collect();
+        bayer.sort_unstable();
+        assert!(bayer.iter().copied().eq(0..64));
+
+        let mut ranks = void_and_cluster();
+        ranks.sort_unstable();
+        assert!(ranks
+            .iter()
+            .copied()
+            .eq(0..BLUE_NOISE_SIZE * BLUE_NOISE_SIZE));
+    }
+}
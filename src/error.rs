@@ -0,0 +1,69 @@
+//! Library error type.
+
+use crate::film::FilmStockError;
+
+/// Errors returned by the fallible public functions of the library.
+#[derive(Debug)]
+pub enum FilmrError {
+    /// Reading or writing a file failed.
+    Io(std::io::Error),
+    /// A preset or document could not be parsed.
+    Parse(String),
+    /// A film stock parsed but has invalid parameters.
+    Validation(Vec<FilmStockError>),
+    /// No usable GPU, or the GPU pipeline failed. Callers can retry on the CPU.
+    Gpu(String),
+    /// The input is not in a format the library understands.
+    UnsupportedFormat(String),
+}
+
+impl std::fmt::Display for FilmrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilmrError::Io(e) => write!(f, "I/O error: {}", e),
+            FilmrError::Parse(msg) => write!(f, "parse error: {}", msg),
+            FilmrError::Validation(errors) => {
+                let msg = errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                write!(f, "invalid film stock: {}", msg)
+            }
+            FilmrError::Gpu(msg) => write!(f, "GPU error: {}", msg),
+            FilmrError::UnsupportedFormat(msg) => write!(f, "unsupported format: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for FilmrError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FilmrError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for FilmrError {
+    fn from(e: std::io::Error) -> Self {
+        FilmrError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for FilmrError {
+    fn from(e: serde_json::Error) -> Self {
+        // Serde reports a failed read as an I/O error; keep it as one
+        if e.is_io() {
+            FilmrError::Io(e.into())
+        } else {
+            FilmrError::Parse(e.to_string())
+        }
+    }
+}
+
+impl From<Vec<FilmStockError>> for FilmrError {
+    fn from(errors: Vec<FilmStockError>) -> Self {
+        FilmrError::Validation(errors)
+    }
+}
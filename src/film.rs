@@ -1,3 +1,4 @@
+use crate::error::FilmrError;
 use crate::grain::GrainModel;
 use crate::physics;
 use crate::spectral::{FilmSensitivities, FilmSpectralParams};
@@ -71,18 +72,79 @@ impl FilmStyle {
     }
 }
 
+/// Schema version written by [`FilmStockCollection::save_to_file`].
+pub const FILM_STOCK_COLLECTION_VERSION: u32 = 1;
+
+/// Schema version of a [`FilmStock`], see [`FilmStock::version`].
+///
+/// - 1: files written without a version.
+/// - 2: `shoulder_point` is a fraction of `d_max`. Older files could also
+///   hold a density there, which loads converted when it is above 1.
+pub const FILM_STOCK_VERSION: u32 = 2;
+
+fn legacy_stock_version() -> u32 {
+    1
+}
+
+fn default_collection_version() -> u32 {
+    FILM_STOCK_COLLECTION_VERSION
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilmStockCollection {
+    /// Schema version of the file, used to migrate older exports.
+    /// Files written before versioning are treated as version 1.
+    #[serde(default = "default_collection_version")]
+    pub version: u32,
     pub stocks: std::collections::HashMap<String, FilmStock>,
 }
 
+impl FilmStockCollection {
+    /// Build a collection keyed by each stock's full name.
+    /// Duplicate names get a numeric suffix so no stock is dropped.
+    pub fn from_stocks(stocks: &[std::rc::Rc<FilmStock>]) -> Self {
+        let mut map = std::collections::HashMap::with_capacity(stocks.len());
+        for stock in stocks {
+            let base = stock.full_name();
+            let mut key = base.clone();
+            let mut n = 2;
+            while map.contains_key(&key) {
+                key = format!("{} ({})", base, n);
+                n += 1;
+            }
+            map.insert(key, stock.as_ref().clone());
+        }
+        Self {
+            version: FILM_STOCK_COLLECTION_VERSION,
+            stocks: map,
+        }
+    }
+
+    /// Save the collection to a JSON file
+    pub fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), FilmrError> {
+        let file = std::fs::File::create(path)?;
+        let writer = std::io::BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+
+    /// Load a collection from a JSON file
+    pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, FilmrError> {
+        let file = std::fs::File::open(path)?;
+        let reader = std::io::BufReader::new(file);
+        let mut collection: Self = serde_json::from_reader(reader)?;
+        collection.stocks.values_mut().for_each(FilmStock::migrate);
+        Ok(collection)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct SegmentedCurve {
     pub d_min: f32,
     pub d_max: f32,
     pub gamma: f32,
     pub exposure_offset: f32, // E0 in the doc, controls speed
-    pub shoulder_point: f32,  // Density where shoulder softening begins
+    pub shoulder_point: f32,  // Shoulder start as a fraction of d_max (0-1)
 }
 
 impl SegmentedCurve {
@@ -92,10 +154,15 @@ impl SegmentedCurve {
             d_max,
             gamma,
             exposure_offset,
-            shoulder_point: 0.8 * d_max, // Default to 80% of D_max
+            shoulder_point: 0.8, // Default to 80% of D_max
         }
     }
 
+    /// Density where the shoulder begins.
+    pub fn shoulder_density(&self) -> f32 {
+        self.shoulder_point * self.d_max
+    }
+
     /// Maps log10(Exposure) to Density.
     /// Implements a simplified sigmoid-like S-curve based on the segmented model logic
     /// but smoothed for better visual results if exact break points aren't provided.
@@ -153,15 +220,249 @@ impl SegmentedCurve {
         let sigmoid = 1.0 / (1.0 + (-k * x).exp());
         self.d_min + range * sigmoid
     }
+
+    /// Fit a curve to measured `(log10 exposure, density)` points, such as a
+    /// scanned step wedge, in the [`SegmentedCurve::map_smooth`] model the
+    /// develop uses.
+    ///
+    /// D_min and D_max start just outside the measured extremes, where the
+    /// asymptotes lie, and gamma and the speed from a straight-line fit of
+    /// the linearized points. All four are then refined together by
+    /// least squares. The shoulder is put where the slope has fallen to
+    /// half of gamma. Returns None with fewer than four usable points, or
+    /// when the density does not rise with exposure.
+    ///
+    /// See [`SegmentedCurve::fit_error`] for how well the result matches.
+    pub fn fit(points: &[(f32, f32)]) -> Option<Self> {
+        let points: Vec<(f64, f64)> = points
+            .iter()
+            .filter(|(x, d)| x.is_finite() && d.is_finite())
+            .map(|&(x, d)| (x as f64, d as f64))
+            .collect();
+        if points.len() < 4 {
+            return None;
+        }
+        let (d_lo, d_hi) = points
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(lo, hi), &(_, d)| {
+                (lo.min(d), hi.max(d))
+            });
+        if d_hi - d_lo < 1e-3 {
+            return None;
+        }
+
+        // Asymptotes just past the measured densities, so every point lies
+        // strictly between them and linearizes: ln(s / (1 - s)) = k (x - x0)
+        let pad = FIT_ASYMPTOTE_PAD * (d_hi - d_lo);
+        let (d_min, d_max) = ((d_lo - pad).max(0.0), d_hi + pad);
+        let logit: Vec<(f64, f64)> = points
+            .iter()
+            .map(|&(x, d)| {
+                let s = (d - d_min) / (d_max - d_min);
+                (x, (s / (1.0 - s)).ln())
+            })
+            .collect();
+        let (slope, intercept) = fit_line(&logit)?;
+        if slope <= 0.0 {
+            return None;
+        }
+
+        let params = refine_logistic(&points, [d_min, d_max, slope, -intercept / slope]);
+        let [d_min, d_max, k, x0] = params;
+        let range = d_max - d_min;
+        if !params.iter().all(|p| p.is_finite()) || k <= 0.0 || range <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            d_min: d_min as f32,
+            d_max: d_max as f32,
+            gamma: (k * range / 4.0) as f32,
+            exposure_offset: 10f64.powf(x0) as f32,
+            shoulder_point: ((d_min + SHOULDER_SLOPE_FRACTION * range) / d_max) as f32,
+        })
+    }
+
+    /// Root mean square density error of [`SegmentedCurve::map_smooth`]
+    /// over measured `(log10 exposure, density)` points, 0 without points.
+    pub fn fit_error(&self, points: &[(f32, f32)]) -> f32 {
+        if points.is_empty() {
+            return 0.0;
+        }
+        let sum: f32 = points
+            .iter()
+            .map(|&(x, d)| (self.map_smooth(x) - d).powi(2))
+            .sum();
+        (sum / points.len() as f32).sqrt()
+    }
+}
+
+/// Fraction of the density range the starting asymptotes of
+/// [`SegmentedCurve::fit`] lie beyond the measured densities.
+const FIT_ASYMPTOTE_PAD: f64 = 0.02;
+
+/// Fraction of the density range above D_min where the logistic slope has
+/// fallen to half of gamma: s (1 - s) = 1/8.
+const SHOULDER_SLOPE_FRACTION: f64 = 0.853_553_4;
+
+/// Least-squares line through `(x, y)` points as (slope, intercept), or
+/// None if the x values do not spread.
+fn fit_line(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    let sxy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    if sxx <= f64::EPSILON {
+        return None;
+    }
+    let slope = sxy / sxx;
+    Some((slope, mean_y - slope * mean_x))
+}
+
+/// Levenberg-Marquardt refinement of the logistic
+/// `D = d_min + (d_max - d_min) / (1 + exp(-k (x - x0)))` over `points`,
+/// from `[d_min, d_max, k, x0]`.
+fn refine_logistic(points: &[(f64, f64)], mut params: [f64; 4]) -> [f64; 4] {
+    let model = |p: &[f64; 4], x: f64| p[0] + (p[1] - p[0]) / (1.0 + (-p[2] * (x - p[3])).exp());
+    let sse =
+        |p: &[f64; 4]| -> f64 { points.iter().map(|&(x, d)| (d - model(p, x)).powi(2)).sum() };
+
+    let mut error = sse(&params);
+    let mut lambda = 1e-3;
+    for _ in 0..200 {
+        // Normal equations J^T J and J^T r
+        let mut jtj = [[0.0; 4]; 4];
+        let mut jtr = [0.0; 4];
+        for &(x, d) in points {
+            let s = 1.0 / (1.0 + (-params[2] * (x - params[3])).exp());
+            let slope = (params[1] - params[0]) * s * (1.0 - s);
+            let j = [1.0 - s, s, slope * (x - params[3]), -slope * params[2]];
+            let r = d - model(&params, x);
+            for a in 0..4 {
+                jtr[a] += j[a] * r;
+                for b in 0..4 {
+                    jtj[a][b] += j[a] * j[b];
+                }
+            }
+        }
+
+        // Raise the damping until a step lowers the error
+        let improved = loop {
+            let mut damped = jtj;
+            for (a, row) in damped.iter_mut().enumerate() {
+                row[a] += lambda * jtj[a][a].max(1e-12);
+            }
+            let Some(step) = solve_4x4(damped, jtr) else {
+                break None;
+            };
+            let mut next: [f64; 4] = std::array::from_fn(|a| params[a] + step[a]);
+            next[0] = next[0].max(0.0);
+            let next_error = sse(&next);
+            if next_error < error {
+                lambda = (lambda * 0.1).max(1e-12);
+                break Some((next, next_error));
+            }
+            lambda *= 10.0;
+            if lambda > 1e12 {
+                break None;
+            }
+        };
+        let Some((next, next_error)) = improved else {
+            break;
+        };
+        let converged = error - next_error <= 1e-14 * error.max(1e-30);
+        params = next;
+        error = next_error;
+        if converged {
+            break;
+        }
+    }
+    params
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Solve the 4x4 system `a x = b` by Gaussian elimination with partial
+/// pivoting, or None if it is singular.
+fn solve_4x4(mut a: [[f64; 4]; 4], mut b: [f64; 4]) -> Option<[f64; 4]> {
+    for col in 0..4 {
+        let pivot = (col..4).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-300 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..4 {
+            let factor = a[row][col] / a[col][col];
+            let pivot_row = a[col];
+            for (v, p) in a[row].iter_mut().zip(pivot_row).skip(col) {
+                *v -= factor * p;
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    let mut x = [0.0; 4];
+    for row in (0..4).rev() {
+        let tail: f64 = (row + 1..4).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
+
+/// Frame size the stock is shot on.
+/// Sets how large grain is relative to the picture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum FilmFormat {
+    /// 36 × 24 mm
+    #[default]
+    Format35mm,
+    /// 56 × 56 mm medium format
+    Medium6x6,
+    /// 4 × 5 inch sheet film (about 121 × 97 mm image area)
+    Large4x5,
+}
+
+impl FilmFormat {
+    /// Image area in mm as (long side, short side).
+    pub fn frame_size_mm(self) -> (f32, f32) {
+        match self {
+            FilmFormat::Format35mm => (36.0, 24.0),
+            FilmFormat::Medium6x6 => (56.0, 56.0),
+            FilmFormat::Large4x5 => (121.0, 97.0),
+        }
+    }
+
+    /// Pixels per mm of film when the frame's long side spans the image's long side.
+    pub fn pixels_per_mm(self, width: u32, height: u32) -> f32 {
+        width.max(height) as f32 / self.frame_size_mm().0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum FilmType {
     ColorNegative,
     ColorSlide,
     BwNegative,
 }
 
+impl FilmType {
+    /// Returns all film types
+    pub const fn all() -> [FilmType; 3] {
+        [
+            FilmType::ColorNegative,
+            FilmType::ColorSlide,
+            FilmType::BwNegative,
+        ]
+    }
+
+    /// Returns a short display name
+    pub const fn name(&self) -> &'static str {
+        match self {
+            FilmType::ColorNegative => "Color Negative",
+            FilmType::ColorSlide => "Color Slide",
+            FilmType::BwNegative => "B&W Negative",
+        }
+    }
+}
+
 /// Reciprocity Failure Parameters.
 ///
 /// Describes how the film responds to long exposures (Schwarzschild effect).
@@ -169,10 +470,135 @@ pub enum FilmType {
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct ReciprocityFailure {
     /// The beta coefficient (sensitivity to time).
-    /// Typical values range from 0.05 to 0.3.
+    /// Typical values range from 0.05 to 0.3; stocks with notoriously poor
+    /// long-exposure behavior, such as Pan F Plus, go above 1.
     pub beta: f32,
 }
 
+impl ReciprocityFailure {
+    /// Time the film effectively integrates over an exposure of `seconds`.
+    /// Exposures of a second or less are unaffected.
+    pub fn effective_time(&self, seconds: f32) -> f32 {
+        if seconds > 1.0 {
+            seconds / (1.0 + self.beta * seconds.log10().powi(2))
+        } else {
+            seconds
+        }
+    }
+}
+
+/// A single invalid field found by [`FilmStock::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilmStockError {
+    /// ISO must be a positive, finite number.
+    InvalidIso(f32),
+    /// Curve gamma must be positive and finite.
+    InvalidGamma { curve: &'static str, gamma: f32 },
+    /// Curve d_max must be greater than d_min.
+    InvalidDensityRange {
+        curve: &'static str,
+        d_min: f32,
+        d_max: f32,
+    },
+    /// Curve exposure offset must be positive (it is taken as log10).
+    InvalidExposureOffset { curve: &'static str, offset: f32 },
+    /// A color matrix row contains NaN or infinity.
+    NonFiniteColorMatrix { row: usize },
+    /// The B&W channel mix must be finite, non-negative and not all zero.
+    InvalidBwChannelMix([f32; 3]),
+    /// The native white balance must be a color temperature in 1000-20000 K.
+    InvalidNativeWhiteBalance(f32),
+    /// A halation parameter is outside its valid range.
+    HalationOutOfRange { field: &'static str, value: f32 },
+}
+
+impl FilmStockError {
+    /// Name of the offending field, as it appears in the JSON preset.
+    pub fn field(&self) -> String {
+        match self {
+            FilmStockError::InvalidIso(_) => "iso".to_string(),
+            FilmStockError::InvalidGamma { curve, .. } => format!("{}.gamma", curve),
+            FilmStockError::InvalidDensityRange { curve, .. } => format!("{}.d_max", curve),
+            FilmStockError::InvalidExposureOffset { curve, .. } => {
+                format!("{}.exposure_offset", curve)
+            }
+            FilmStockError::NonFiniteColorMatrix { row } => format!("color_matrix[{}]", row),
+            FilmStockError::InvalidBwChannelMix(_) => "bw_channel_mix".to_string(),
+            FilmStockError::InvalidNativeWhiteBalance(_) => "native_white_balance_k".to_string(),
+            FilmStockError::HalationOutOfRange { field, .. } => field.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for FilmStockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilmStockError::InvalidIso(iso) => write!(f, "iso must be > 0 (got {})", iso),
+            FilmStockError::InvalidGamma { gamma, .. } => {
+                write!(f, "{} must be > 0 (got {})", self.field(), gamma)
+            }
+            FilmStockError::InvalidDensityRange { d_min, d_max, .. } => write!(
+                f,
+                "{} must be greater than d_min (got d_min={}, d_max={})",
+                self.field(),
+                d_min,
+                d_max
+            ),
+            FilmStockError::InvalidExposureOffset { offset, .. } => {
+                write!(f, "{} must be > 0 (got {})", self.field(), offset)
+            }
+            FilmStockError::NonFiniteColorMatrix { .. } => {
+                write!(f, "{} contains a non-finite value", self.field())
+            }
+            FilmStockError::InvalidBwChannelMix(mix) => write!(
+                f,
+                "{} must be non-negative with a positive sum (got {:?})",
+                self.field(),
+                mix
+            ),
+            FilmStockError::InvalidNativeWhiteBalance(k) => {
+                write!(f, "{} is out of range (got {})", self.field(), k)
+            }
+            FilmStockError::HalationOutOfRange { value, .. } => {
+                write!(f, "{} is out of range (got {})", self.field(), value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FilmStockError {}
+
+/// Default halation layer gains: red layer dominant, a little green, almost no blue.
+pub fn default_halation_channel_gain() -> [f32; 3] {
+    [1.0, 0.25, 0.05]
+}
+
+/// Default anti-halation backing: fully effective, as on modern stocks.
+pub fn default_anti_halation_backing() -> f32 {
+    1.0
+}
+
+/// Extra halation without any anti-halation backing, on top of the tuned
+/// strengths: a stock with no backing glows this many times more.
+const NO_BACKING_HALATION_BOOST: f32 = 2.0;
+
+/// Rec.709 luminance coefficients, the default [`FilmStock::bw_channel_mix`].
+pub const REC709_LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+fn default_bw_channel_mix() -> [f32; 3] {
+    REC709_LUMA
+}
+
+/// Color temperature daylight-balanced film is made for, in Kelvin.
+pub const DAYLIGHT_BALANCE_K: f32 = 5500.0;
+
+/// Color temperature tungsten-balanced film is made for, in Kelvin.
+pub const TUNGSTEN_BALANCE_K: f32 = 3200.0;
+
+fn default_native_white_balance_k() -> f32 {
+    DAYLIGHT_BALANCE_K
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilmStock {
     /// Film Type (affects processing pipeline)
@@ -193,6 +619,19 @@ pub struct FilmStock {
     // D_out = Matrix * D_in
     pub color_matrix: [[f32; 3]; 3],
 
+    /// Weights of the scene's R, G and B in the luminance that exposes a
+    /// monochrome stock (see [`FilmStock::is_monochrome`]), which ignores
+    /// `color_matrix`. Normalized before use. Defaults to [`REC709_LUMA`];
+    /// more red and less blue darkens skies like a red filter.
+    #[serde(default = "default_bw_channel_mix")]
+    pub bw_channel_mix: [f32; 3],
+
+    /// Color temperature of the light the stock renders neutral, in Kelvin
+    /// (e.g. [`DAYLIGHT_BALANCE_K`] or [`TUNGSTEN_BALANCE_K`]). With white
+    /// balance off, other stocks show a cast, see [`FilmStock::native_balance_cast`].
+    #[serde(default = "default_native_white_balance_k")]
+    pub native_white_balance_k: f32,
+
     /// Spectral Sensitivity Parameters.
     /// Used to generate the spectral response curves at runtime.
     pub spectral_params: FilmSpectralParams,
@@ -200,8 +639,12 @@ pub struct FilmStock {
     /// Grain parameters derived from RMS Granularity.
     pub grain_model: GrainModel,
 
+    /// Frame size, used to scale grain to the picture.
+    #[serde(default)]
+    pub film_format: FilmFormat,
+
     /// Resolution limit in line pairs per mm (lp/mm).
-    /// Used to simulate optical softness before grain.
+    /// Sets the softness of the MTF stage, scaled by the film format.
     pub resolution_lp_mm: f32,
 
     /// Vignetting strength (0.0 = none, 1.0 = full cos⁴ falloff).
@@ -216,8 +659,11 @@ pub struct FilmStock {
     /// Primarily affects the Red layer (bottom layer) and spreads out (blur).
     pub halation_strength: f32,
 
-    /// Linear light threshold for halation (0.0 to 1.0).
-    /// Only highlights above this threshold trigger halation.
+    /// Halation threshold in scene-linear luminance (0.0 to 1.0), before
+    /// any tone curve: only light whose Rec.709 luminance exceeds it
+    /// triggers halation, and the glow grows with the excess. 0.18 is a mid
+    /// gray and 1.0 a diffuse white, so 0.5 starts the glow about 1.5 stops
+    /// above middle gray.
     pub halation_threshold: f32,
 
     /// Blur radius for halation as a fraction of image width (e.g. 0.02).
@@ -228,6 +674,39 @@ pub struct FilmStock {
     /// Usually reddish-orange [1.0, 0.4, 0.2] due to base reflection.
     pub halation_tint: [f32; 3],
 
+    /// Per-layer gain for the reflected halation light (R, G, B layers).
+    /// Light reflected off the base re-exposes mostly the bottom (red-sensitive)
+    /// layer, so the green and blue gains are small.
+    #[serde(default = "default_halation_channel_gain")]
+    pub halation_channel_gain: [f32; 3],
+
+    /// Strength of the optional wide halation skirt (0.0 = single Gaussian).
+    /// Summed with the core bloom from `halation_strength`.
+    #[serde(default)]
+    pub halation_strength2: f32,
+
+    /// Blur radius of the wide halation skirt as a fraction of image width.
+    /// Typically several times `halation_sigma`.
+    #[serde(default)]
+    pub halation_sigma2: f32,
+
+    /// Effectiveness of the anti-halation backing, 0.0 (none, e.g. rem-jet
+    /// removed or early emulsions) to 1.0 (modern, fully suppressed).
+    /// Scales both halation strengths, see [`FilmStock::halation_backing_gain`].
+    #[serde(default = "default_anti_halation_backing")]
+    pub anti_halation_backing: f32,
+
+    /// How much less saturated highlights halate than neutral ones of the
+    /// same luminance, 0.0 (no difference) to 1.0 (fully saturated light
+    /// does not halate). Keeps neon colors from blooming like specular whites.
+    #[serde(default)]
+    pub halation_saturation_weight: f32,
+
+    /// Schema version, [`FILM_STOCK_VERSION`] for stocks created now. Files
+    /// written before versioning read as 1.
+    #[serde(default = "legacy_stock_version")]
+    pub version: u32,
+
     /// Manufacturer name (e.g., "Kodak", "Fujifilm", "Ilford").
     #[serde(default)]
     pub manufacturer: String,
@@ -271,7 +750,10 @@ impl FilmStock {
             b_curve,
             spectral_params,
             color_matrix,
+            bw_channel_mix: REC709_LUMA,
+            native_white_balance_k: DAYLIGHT_BALANCE_K,
             grain_model,
+            film_format: FilmFormat::default(),
             resolution_lp_mm,
             vignette_strength: 0.5,
             reciprocity,
@@ -279,13 +761,22 @@ impl FilmStock {
             halation_threshold,
             halation_sigma,
             halation_tint,
+            halation_channel_gain: default_halation_channel_gain(),
+            halation_strength2: 0.0,
+            halation_sigma2: 0.0,
+            anti_halation_backing: default_anti_halation_backing(),
+            halation_saturation_weight: 0.0,
+            version: FILM_STOCK_VERSION,
             manufacturer,
             name,
             layer_stack: None,
         }
     }
 
-    /// Get the full display name of the film stock (e.g., "Kodak Portra 400")
+    /// Get the full display name of the film stock (e.g., "Kodak Portra 400"):
+    /// the manufacturer and name joined by a space, or just the name for
+    /// stocks without a manufacturer. Recipes and preset collections refer to
+    /// stocks by this name.
     pub fn full_name(&self) -> String {
         if self.manufacturer.is_empty() {
             self.name.clone()
@@ -294,6 +785,25 @@ impl FilmStock {
         }
     }
 
+    /// [`FilmStock::full_name`] as a lowercase ASCII slug, e.g.
+    /// "kodak-portra-400". Each run of other characters becomes a single '-',
+    /// so the id is safe as a file name or map key and only changes when the
+    /// name does. [`crate::presets::by_name`] accepts it as well.
+    pub fn stable_id(&self) -> String {
+        let mut id = String::new();
+        for c in self.full_name().chars() {
+            if c.is_ascii_alphanumeric() {
+                id.push(c.to_ascii_lowercase());
+            } else if !id.is_empty() && !id.ends_with('-') {
+                id.push('-');
+            }
+        }
+        if id.ends_with('-') {
+            id.pop();
+        }
+        id
+    }
+
     /// Generate spectral sensitivities from parameters
     pub fn get_spectral_sensitivities(&self) -> FilmSensitivities {
         FilmSensitivities::from_params(self.spectral_params)
@@ -398,21 +908,236 @@ impl FilmStock {
     }
 
     /// Save the film stock to a JSON file
-    pub fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), std::io::Error> {
+    pub fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), FilmrError> {
         let file = std::fs::File::create(path)?;
         let writer = std::io::BufWriter::new(file);
         serde_json::to_writer_pretty(writer, self)?;
         Ok(())
     }
 
-    /// Load a film stock from a JSON file
-    pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, std::io::Error> {
+    /// Load a film stock from a JSON file.
+    ///
+    /// The stock is validated after parsing; invalid fields are reported as
+    /// [`FilmrError::Validation`] listing every failing field.
+    pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, FilmrError> {
         let file = std::fs::File::open(path)?;
         let reader = std::io::BufReader::new(file);
-        let stock = serde_json::from_reader(reader)?;
+        let mut stock: FilmStock = serde_json::from_reader(reader)?;
+        stock.migrate();
+        stock.validate()?;
         Ok(stock)
     }
 
+    /// Bring a stock read from an older file up to [`FILM_STOCK_VERSION`].
+    /// Current stocks are left as they are, so it is safe to call on every
+    /// load.
+    pub fn migrate(&mut self) {
+        if self.version < 2 {
+            for curve in [&mut self.r_curve, &mut self.g_curve, &mut self.b_curve] {
+                // Version 1 read a shoulder above 1 as a density
+                if curve.shoulder_point > 1.0 {
+                    curve.shoulder_point /= curve.d_max;
+                }
+            }
+        }
+        self.version = self.version.max(FILM_STOCK_VERSION);
+    }
+
+    /// Check that the stock's parameters are physically meaningful.
+    ///
+    /// Returns every invalid field rather than stopping at the first one, so
+    /// a hand-edited preset can be fixed in a single pass.
+    pub fn validate(&self) -> Result<(), Vec<FilmStockError>> {
+        let mut errors = Vec::new();
+
+        if !(self.iso.is_finite() && self.iso > 0.0) {
+            errors.push(FilmStockError::InvalidIso(self.iso));
+        }
+
+        for (curve_name, curve) in [
+            ("r_curve", &self.r_curve),
+            ("g_curve", &self.g_curve),
+            ("b_curve", &self.b_curve),
+        ] {
+            if !(curve.gamma.is_finite() && curve.gamma > 0.0) {
+                errors.push(FilmStockError::InvalidGamma {
+                    curve: curve_name,
+                    gamma: curve.gamma,
+                });
+            }
+            if !(curve.d_min.is_finite() && curve.d_max.is_finite() && curve.d_max > curve.d_min) {
+                errors.push(FilmStockError::InvalidDensityRange {
+                    curve: curve_name,
+                    d_min: curve.d_min,
+                    d_max: curve.d_max,
+                });
+            }
+            if !(curve.exposure_offset.is_finite() && curve.exposure_offset > 0.0) {
+                errors.push(FilmStockError::InvalidExposureOffset {
+                    curve: curve_name,
+                    offset: curve.exposure_offset,
+                });
+            }
+        }
+
+        for (row_idx, row) in self.color_matrix.iter().enumerate() {
+            if row.iter().any(|v| !v.is_finite()) {
+                errors.push(FilmStockError::NonFiniteColorMatrix { row: row_idx });
+            }
+        }
+
+        let mix = self.bw_channel_mix;
+        if !(mix.iter().all(|v| v.is_finite() && *v >= 0.0) && mix.iter().sum::<f32>() > 0.0) {
+            errors.push(FilmStockError::InvalidBwChannelMix(mix));
+        }
+
+        let kelvin = self.native_white_balance_k;
+        if !(1000.0..=20000.0).contains(&kelvin) {
+            errors.push(FilmStockError::InvalidNativeWhiteBalance(kelvin));
+        }
+
+        let halation_checks = [
+            ("halation_strength", self.halation_strength, 0.0, f32::MAX),
+            ("halation_threshold", self.halation_threshold, 0.0, 1.0),
+            ("halation_sigma", self.halation_sigma, 0.0, 1.0),
+            ("halation_strength2", self.halation_strength2, 0.0, f32::MAX),
+            ("halation_sigma2", self.halation_sigma2, 0.0, 1.0),
+            (
+                "anti_halation_backing",
+                self.anti_halation_backing,
+                0.0,
+                1.0,
+            ),
+            (
+                "halation_saturation_weight",
+                self.halation_saturation_weight,
+                0.0,
+                1.0,
+            ),
+            ("halation_tint[0]", self.halation_tint[0], 0.0, f32::MAX),
+            ("halation_tint[1]", self.halation_tint[1], 0.0, f32::MAX),
+            ("halation_tint[2]", self.halation_tint[2], 0.0, f32::MAX),
+            (
+                "halation_channel_gain[0]",
+                self.halation_channel_gain[0],
+                0.0,
+                f32::MAX,
+            ),
+            (
+                "halation_channel_gain[1]",
+                self.halation_channel_gain[1],
+                0.0,
+                f32::MAX,
+            ),
+            (
+                "halation_channel_gain[2]",
+                self.halation_channel_gain[2],
+                0.0,
+                f32::MAX,
+            ),
+        ];
+        for (field, value, min, max) in halation_checks {
+            if !(value.is_finite() && value >= min && value <= max) {
+                errors.push(FilmStockError::HalationOutOfRange { field, value });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// True for B&W stocks: B&W negatives and other stocks with monochrome
+    /// grain, such as B&W slide film. These take their tones from
+    /// [`FilmStock::bw_luminance_weights`] instead of the color matrix.
+    pub fn is_monochrome(&self) -> bool {
+        self.film_type == FilmType::BwNegative || self.grain_model.monochrome
+    }
+
+    /// [`FilmStock::bw_channel_mix`] scaled to sum to 1, so a neutral gray
+    /// keeps its value.
+    pub fn bw_luminance_weights(&self) -> [f32; 3] {
+        let sum: f32 = self.bw_channel_mix.iter().sum();
+        if sum > 0.0 {
+            self.bw_channel_mix.map(|w| w / sum)
+        } else {
+            REC709_LUMA
+        }
+    }
+
+    /// Per-layer exposure gains that neutralize a blackbody at `kelvin`
+    /// relative to daylight, evaluated through the stock's own sensitivities.
+    /// Green is fixed, so only the color balance changes.
+    pub fn kelvin_gains(&self, kelvin: f32) -> [f32; 3] {
+        use crate::spectral::Spectrum;
+
+        let sensitivities = self.get_spectral_sensitivities();
+        let reference = sensitivities.expose(&Spectrum::new_blackbody(DAYLIGHT_BALANCE_K));
+        let target = sensitivities.expose(&Spectrum::new_blackbody(kelvin));
+
+        let gains: [f32; 3] = std::array::from_fn(|c| {
+            if target[c] > 0.0 && reference[c] > 0.0 {
+                reference[c] / target[c]
+            } else {
+                1.0
+            }
+        });
+        let g = gains[1];
+        gains.map(|v| v / g)
+    }
+
+    /// Multiplier on the halation strengths from the anti-halation backing:
+    /// 1 with a fully effective backing, up to `1 + NO_BACKING_HALATION_BOOST`
+    /// without one.
+    pub fn halation_backing_gain(&self) -> f32 {
+        1.0 + (1.0 - self.anti_halation_backing.clamp(0.0, 1.0)) * NO_BACKING_HALATION_BOOST
+    }
+
+    /// Cast this stock puts on the input when white balance is off.
+    ///
+    /// The stock records light at its native balance as neutral: a tungsten
+    /// stock is more sensitive to blue by [`FilmStock::kelvin_gains`] of its
+    /// balance. The input already shows the scene's light relative to
+    /// daylight, so what is left of the offset between the scene and the
+    /// stock's balance is daylight against the native balance. Tungsten stocks
+    /// therefore turn daylight scenes blue (no 85 filter) and render tungsten
+    /// light neutral; daylight stocks get no cast.
+    pub fn native_balance_cast(&self) -> [f32; 3] {
+        if (self.native_white_balance_k - DAYLIGHT_BALANCE_K).abs() < 1.0 {
+            return [1.0; 3];
+        }
+        self.kelvin_gains(self.native_white_balance_k)
+    }
+
+    /// Extra exposure, in stops, that makes up for reciprocity failure at an
+    /// exposure of `seconds`. Zero for a second or less.
+    ///
+    /// The model scales all channels alike, so for color stocks this is only
+    /// the density loss; real color film also shifts color at these times.
+    pub fn reciprocity_compensation(&self, seconds: f32) -> f32 {
+        let effective = self.reciprocity.effective_time(seconds);
+        if seconds > 0.0 && effective > 0.0 {
+            (seconds / effective).log2()
+        } else {
+            0.0
+        }
+    }
+
+    /// Transmission of the orange mask of a color negative, from the base
+    /// density of each channel above the clearest one. The colored couplers
+    /// leave the most density in blue and the least in red. Neutral for every
+    /// other film type.
+    pub fn mask_color(&self) -> [f32; 3] {
+        if self.film_type != FilmType::ColorNegative {
+            return [1.0; 3];
+        }
+        let d_min = [self.r_curve.d_min, self.g_curve.d_min, self.b_curve.d_min];
+        let base = d_min.iter().copied().fold(f32::INFINITY, f32::min);
+        d_min.map(|d| physics::density_to_transmission(d - base))
+    }
+
     /// Precompute the 3x3 spectral matrix that maps Linear RGB -> Film Layer Exposure.
     /// This avoids per-pixel full spectrum integration (~600 FLOPS -> 15 FLOPS).
     /// The matrix incorporates camera sensitivities, D65 illuminant, and film sensitivities.
@@ -474,20 +1199,36 @@ impl FilmStock {
         let net_g = (d_g - self.g_curve.d_min).max(0.0);
         let net_b = (d_b - self.b_curve.d_min).max(0.0);
 
+        // B&W: one silver image, no dye coupling. The layers saw the same
+        // luminance, so averaging only evens out curve differences.
+        if self.is_monochrome() {
+            let net = (net_r + net_g + net_b) / 3.0;
+            return [
+                net + self.r_curve.d_min,
+                net + self.g_curve.d_min,
+                net + self.b_curve.d_min,
+            ];
+        }
+
         // 2. Apply Color Matrix (Simulates Section 5 - Layer Coupling)
         // [Dr']   [ M00 M01 M02 ] [ Dr ]
         // [Dg'] = [ M10 M11 M12 ] [ Dg ]
         // [Db']   [ M20 M21 M22 ] [ Db ]
+        // Negative off-diagonals take density away from the other layers; a
+        // layer cannot go below its base, so saturated colors stop at d_min
 
-        let d_r_out = self.color_matrix[0][0] * net_r
+        let d_r_out = (self.color_matrix[0][0] * net_r
             + self.color_matrix[0][1] * net_g
-            + self.color_matrix[0][2] * net_b;
-        let d_g_out = self.color_matrix[1][0] * net_r
+            + self.color_matrix[0][2] * net_b)
+            .max(0.0);
+        let d_g_out = (self.color_matrix[1][0] * net_r
             + self.color_matrix[1][1] * net_g
-            + self.color_matrix[1][2] * net_b;
-        let d_b_out = self.color_matrix[2][0] * net_r
+            + self.color_matrix[1][2] * net_b)
+            .max(0.0);
+        let d_b_out = (self.color_matrix[2][0] * net_r
             + self.color_matrix[2][1] * net_g
-            + self.color_matrix[2][2] * net_b;
+            + self.color_matrix[2][2] * net_b)
+            .max(0.0);
 
         [
             d_r_out + self.r_curve.d_min,
@@ -515,7 +1256,7 @@ fn boost_color_matrix(matrix: [[f32; 3]; 3], factor: f32) -> [[f32; 3]; 3] {
 }
 
 /// Reduce color saturation by moving matrix towards grayscale
-fn reduce_saturation(matrix: [[f32; 3]; 3], amount: f32) -> [[f32; 3]; 3] {
+pub(crate) fn reduce_saturation(matrix: [[f32; 3]; 3], amount: f32) -> [[f32; 3]; 3] {
     let gray = [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]];
     let mut result = matrix;
     for i in 0..3 {
@@ -530,6 +1271,20 @@ fn reduce_saturation(matrix: [[f32; 3]; 3], amount: f32) -> [[f32; 3]; 3] {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_reciprocity_compensation_follows_datasheets() {
+        use crate::presets::fujifilm::VELVIA_50;
+        use crate::presets::ilford::PAN_F_PLUS_50;
+        let (pan_f, velvia) = (PAN_F_PLUS_50(), VELVIA_50());
+
+        assert_eq!(pan_f.reciprocity_compensation(0.5), 0.0);
+        assert_eq!(velvia.reciprocity_compensation(1.0), 0.0);
+        // Ilford: about +1 stop at 10 s; Fuji: about +1/2 stop at 8 s
+        assert!((pan_f.reciprocity_compensation(10.0) - 1.1).abs() < 0.15);
+        assert!((velvia.reciprocity_compensation(8.0) - 0.5).abs() < 0.1);
+        assert!(pan_f.reciprocity_compensation(10.0) > velvia.reciprocity_compensation(10.0));
+    }
+
     #[test]
     fn test_segmented_curve_monotonicity() {
         let curve = SegmentedCurve::new(0.1, 2.5, 0.8, 1.0);
@@ -592,6 +1347,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fit_recovers_a_step_wedge() {
+        let truth = SegmentedCurve::new(0.25, 2.6, 0.65, 0.18);
+        // A 21-step wedge in half-stop-ish steps of 0.15 log E
+        let wedge: Vec<(f32, f32)> = (0..21)
+            .map(|i| {
+                let log_e = -3.0 + 0.15 * i as f32;
+                (log_e, truth.map_smooth(log_e))
+            })
+            .collect();
+
+        let fit = SegmentedCurve::fit(&wedge).unwrap();
+        assert!(fit.fit_error(&wedge) < 1e-3, "{fit:?}");
+        assert!((fit.d_min - truth.d_min).abs() < 0.01, "{fit:?}");
+        assert!((fit.d_max - truth.d_max).abs() < 0.01, "{fit:?}");
+        assert!((fit.gamma - truth.gamma).abs() < 0.01, "{fit:?}");
+        assert!((fit.exposure_offset.log10() - truth.exposure_offset.log10()).abs() < 0.01);
+        assert!(fit.shoulder_point <= 1.0, "{fit:?}");
+        let shoulder = truth.d_min + 0.8536 * (truth.d_max - truth.d_min);
+        assert!((fit.shoulder_density() - shoulder).abs() < 0.02, "{fit:?}");
+
+        // Measurement noise shows up in the error, not as a failed fit
+        let noisy: Vec<(f32, f32)> = wedge
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, d))| (x, d + if i % 2 == 0 { 0.02 } else { -0.02 }))
+            .collect();
+        let fit = SegmentedCurve::fit(&noisy).unwrap();
+        let error = fit.fit_error(&noisy);
+        assert!(error > 0.01 && error < 0.03, "{error}");
+        assert!((fit.gamma - truth.gamma).abs() < 0.05, "{fit:?}");
+
+        // Too few points, or density falling with exposure, cannot be fit
+        assert!(SegmentedCurve::fit(&wedge[..3]).is_none());
+        let falling: Vec<(f32, f32)> = wedge.iter().map(|&(x, d)| (-x, d)).collect();
+        assert!(SegmentedCurve::fit(&falling).is_none());
+    }
+
     #[test]
     fn test_segmented_curve_limits() {
         let curve = SegmentedCurve::new(0.2, 2.8, 1.0, 1.0);
@@ -675,4 +1468,177 @@ mod tests {
         // Check halation increased
         assert!(artistic.halation_strength > original_halation);
     }
+
+    #[test]
+    fn test_builtin_stocks_validate() {
+        for stock in crate::presets::get_all_stocks() {
+            assert!(
+                stock.validate().is_ok(),
+                "{} failed validation: {:?}",
+                stock.full_name(),
+                stock.validate()
+            );
+        }
+    }
+
+    #[test]
+    fn test_stable_id_is_a_slug() {
+        let portra = crate::presets::kodak::KODAK_PORTRA_400();
+        assert_eq!(portra.stable_id(), "kodak-portra-400");
+
+        let mut stock = portra;
+        stock.manufacturer = "Polaroid".to_string();
+        stock.name = " B&W  667 (Custom)".to_string();
+        assert_eq!(stock.stable_id(), "polaroid-b-w-667-custom");
+    }
+
+    #[test]
+    fn test_validate_reports_each_field() {
+        let mut stock = crate::presets::kodak::KODAK_PORTRA_400();
+        stock.iso = 0.0;
+        stock.g_curve.gamma = -0.5;
+        stock.b_curve.d_max = stock.b_curve.d_min;
+        stock.color_matrix[1][2] = f32::NAN;
+        stock.bw_channel_mix = [0.0; 3];
+        stock.halation_threshold = 1.5;
+
+        let errors = stock.validate().unwrap_err();
+        let fields: Vec<String> = errors.iter().map(|e| e.field()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "iso",
+                "g_curve.gamma",
+                "b_curve.d_max",
+                "color_matrix[1]",
+                "bw_channel_mix",
+                "halation_threshold"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bw_density_ignores_color_matrix() {
+        let mut stock = crate::presets::kodak::KODAK_TRI_X_400();
+        let log_e = [-0.5, 0.0, 0.3];
+        let d = stock.map_log_exposure(log_e);
+        assert!(d[0] == d[1] && d[1] == d[2], "BW density must be neutral");
+
+        stock.color_matrix = [[0.30, 0.59, 0.11]; 3];
+        assert_eq!(stock.map_log_exposure(log_e), d);
+    }
+
+    #[test]
+    fn test_color_matrix_keeps_layers_above_base() {
+        // Only the blue layer exposed: the negative off-diagonals would
+        // take red and green below their base
+        let stock = crate::presets::kodak::KODAK_EKTAR_100();
+        let d = stock.map_log_exposure([-6.0, -6.0, 1.0]);
+        let d_min = [
+            stock.r_curve.d_min,
+            stock.g_curve.d_min,
+            stock.b_curve.d_min,
+        ];
+        for c in 0..3 {
+            assert!(d[c].is_finite() && d[c] >= d_min[c], "{:?}", d);
+        }
+        assert!(d[2] > d_min[2] + 1.0);
+    }
+
+    #[test]
+    fn test_mask_color() {
+        let mask = crate::presets::kodak::KODAK_PORTRA_400().mask_color();
+        assert_eq!(mask[0], 1.0);
+        assert!(
+            mask[0] > mask[1] && mask[1] > mask[2],
+            "mask should be orange: {mask:?}"
+        );
+
+        let bw = crate::presets::kodak::KODAK_TRI_X_400().mask_color();
+        assert_eq!(bw, [1.0; 3]);
+    }
+
+    #[test]
+    fn test_collection_round_trip() {
+        let mut first = crate::presets::kodak::KODAK_PORTRA_400();
+        first.name = "Custom - Portra".to_string();
+        first.manufacturer = String::new();
+        let mut second = first.clone();
+        second.r_curve.gamma = 0.9;
+        let stocks = [std::rc::Rc::new(first), std::rc::Rc::new(second)];
+
+        let collection = FilmStockCollection::from_stocks(&stocks);
+        assert_eq!(
+            collection.stocks.len(),
+            2,
+            "duplicate names must not collide"
+        );
+
+        let path = std::env::temp_dir().join("filmr_collection_round_trip.json");
+        collection.save_to_file(&path).unwrap();
+        let loaded = FilmStockCollection::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.version, FILM_STOCK_COLLECTION_VERSION);
+        assert_eq!(loaded.stocks.len(), 2);
+        assert!(loaded.stocks.values().all(|s| s.name == "Custom - Portra"));
+        assert!(loaded.stocks.values().any(|s| s.r_curve.gamma == 0.9));
+    }
+
+    #[test]
+    fn test_unversioned_stock_gets_a_shoulder_fraction() {
+        let mut stock = crate::presets::kodak::KODAK_PORTRA_400();
+        let fraction = stock.g_curve.shoulder_point;
+        let density = stock.g_curve.shoulder_density();
+        stock.g_curve.shoulder_point = density;
+        let mut json = serde_json::to_value(&stock).unwrap();
+        json.as_object_mut().unwrap().remove("version");
+
+        let path = std::env::temp_dir().join("filmr_unversioned_stock.json");
+        std::fs::write(&path, json.to_string()).unwrap();
+        let loaded = FilmStock::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded.version, FILM_STOCK_VERSION);
+        assert!((loaded.g_curve.shoulder_point - fraction).abs() < 1e-6);
+        assert!((loaded.g_curve.shoulder_density() - density).abs() < 1e-6);
+        // Fractions load unchanged
+        assert_eq!(loaded.r_curve.shoulder_point, stock.r_curve.shoulder_point);
+
+        // Current stocks load unchanged
+        let mut current = loaded.clone();
+        current.migrate();
+        assert_eq!(
+            current.g_curve.shoulder_point,
+            loaded.g_curve.shoulder_point
+        );
+    }
+
+    #[test]
+    fn test_load_from_file_error_kinds() {
+        let dir = std::env::temp_dir();
+        let missing = dir.join("filmr_load_missing.json");
+        assert!(matches!(
+            FilmStock::load_from_file(&missing),
+            Err(FilmrError::Io(_))
+        ));
+
+        let garbled = dir.join("filmr_load_garbled.json");
+        std::fs::write(&garbled, "{ not json").unwrap();
+        let parsed = FilmStock::load_from_file(&garbled);
+        std::fs::remove_file(&garbled).ok();
+        assert!(matches!(parsed, Err(FilmrError::Parse(_))));
+
+        let mut stock = crate::presets::kodak::KODAK_PORTRA_400();
+        stock.iso = 0.0;
+        let invalid = dir.join("filmr_load_invalid.json");
+        stock.save_to_file(&invalid).unwrap();
+        let loaded = FilmStock::load_from_file(&invalid);
+        std::fs::remove_file(&invalid).ok();
+        match loaded {
+            Err(FilmrError::Validation(errors)) => {
+                assert_eq!(errors, vec![FilmStockError::InvalidIso(0.0)])
+            }
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
 }
@@ -1,13 +1,127 @@
 #[cfg(feature = "compute-gpu")]
-use tracing::info;
+use crate::processor::GpuPowerPreference;
+#[cfg(feature = "compute-gpu")]
+use tracing::{info, warn};
 
 #[cfg(feature = "compute-gpu")]
-use std::sync::OnceLock;
+use std::collections::VecDeque;
+#[cfg(feature = "compute-gpu")]
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+#[cfg(feature = "compute-gpu")]
+use std::sync::{Mutex, OnceLock};
 
 #[cfg(feature = "compute-gpu")]
 pub struct GpuContext {
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
+    pub buffer_pool: BufferPool,
+    /// Preference the adapter was requested with.
+    pub power_preference: GpuPowerPreference,
+}
+
+/// Usage flags for the intermediate image buffers passed between stages.
+#[cfg(feature = "compute-gpu")]
+pub const IMAGE_BUFFER_USAGE: wgpu::BufferUsages = wgpu::BufferUsages::STORAGE
+    .union(wgpu::BufferUsages::COPY_SRC)
+    .union(wgpu::BufferUsages::COPY_DST);
+
+/// Usage flags for readback staging buffers.
+#[cfg(feature = "compute-gpu")]
+pub const STAGING_BUFFER_USAGE: wgpu::BufferUsages =
+    wgpu::BufferUsages::MAP_READ.union(wgpu::BufferUsages::COPY_DST);
+
+/// Recycles image-sized buffers between frames.
+///
+/// Pipelines acquire their temp/output buffers here and the processor hands them
+/// back once the next stage (or readback) has consumed them, so repeated previews
+/// at the same resolution stop allocating after the first frame. Buffers are matched
+/// by size and usage; wgpu orders queue submissions, so a buffer released after its
+/// last submit can be safely written by the next one. Free buffers are capped at
+/// [`BufferPool::MAX_FREE_BYTES`], dropping the least recently released first, so
+/// buffers of sizes no longer in use do not hold on to memory.
+#[cfg(feature = "compute-gpu")]
+#[derive(Default)]
+pub struct BufferPool {
+    free: Mutex<FreeBuffers>,
+    allocations: AtomicUsize,
+}
+
+/// Free buffers of a [`BufferPool`], least recently released first.
+#[cfg(feature = "compute-gpu")]
+#[derive(Default)]
+struct FreeBuffers {
+    buffers: VecDeque<wgpu::Buffer>,
+    bytes: u64,
+}
+
+#[cfg(feature = "compute-gpu")]
+impl BufferPool {
+    /// Total size of the free buffers kept; the oldest are dropped beyond it.
+    pub const MAX_FREE_BYTES: u64 = 256 << 20;
+
+    pub fn acquire(
+        &self,
+        device: &wgpu::Device,
+        label: &str,
+        size: u64,
+        usage: wgpu::BufferUsages,
+    ) -> wgpu::Buffer {
+        {
+            let mut free = self.free.lock().unwrap();
+            let found = free
+                .buffers
+                .iter()
+                .rposition(|buffer| buffer.size() == size && buffer.usage() == usage);
+            if let Some(buffer) = found.and_then(|i| free.buffers.remove(i)) {
+                free.bytes -= size;
+                return buffer;
+            }
+        }
+
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size,
+            usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    pub fn release(&self, buffer: wgpu::Buffer) {
+        let mut free = self.free.lock().unwrap();
+        free.bytes += buffer.size();
+        free.buffers.push_back(buffer);
+        while free.bytes > Self::MAX_FREE_BYTES {
+            let Some(oldest) = free.buffers.pop_front() else {
+                break;
+            };
+            free.bytes -= oldest.size();
+        }
+    }
+
+    /// Total size of the buffers waiting to be reused.
+    pub fn free_bytes(&self) -> u64 {
+        self.free.lock().unwrap().bytes
+    }
+
+    /// Total buffers created by the pool so far (for allocation profiling).
+    pub fn allocation_count(&self) -> usize {
+        self.allocations.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "compute-gpu")]
+impl GpuContext {
+    /// Borrow an intermediate image buffer from the pool.
+    pub fn acquire_image_buffer(&self, label: &str, size: u64) -> wgpu::Buffer {
+        self.buffer_pool
+            .acquire(&self.device, label, size, IMAGE_BUFFER_USAGE)
+    }
+
+    /// Return a stage output to the pool once nothing will read it again.
+    pub fn recycle(&self, buffer: GpuBuffer) {
+        self.buffer_pool.release(buffer.buffer);
+    }
 }
 
 #[cfg(feature = "compute-gpu")]
@@ -19,18 +133,46 @@ pub struct GpuBuffer {
 }
 
 #[cfg(all(feature = "compute-gpu", not(target_arch = "wasm32")))]
-static GPU_CONTEXT: OnceLock<GpuContext> = OnceLock::new();
+static GPU_CONTEXT: OnceLock<Option<GpuContext>> = OnceLock::new();
 
 #[cfg(all(feature = "compute-gpu", not(target_arch = "wasm32")))]
 pub fn get_gpu_context() -> Option<&'static GpuContext> {
-    if let Some(ctx) = GPU_CONTEXT.get() {
-        return Some(ctx);
-    }
+    get_gpu_context_with_preference(GpuPowerPreference::default())
+}
+
+/// Get the shared GPU context, creating it with `preference` on first use.
+///
+/// The first call decides the adapter for the lifetime of the process; a later
+/// call with another preference gets the same context and logs a warning once.
+/// If no suitable adapter exists the failure is remembered and logged once, and
+/// every caller gets `None` so processing continues on the CPU pipeline.
+#[cfg(all(feature = "compute-gpu", not(target_arch = "wasm32")))]
+pub fn get_gpu_context_with_preference(
+    preference: GpuPowerPreference,
+) -> Option<&'static GpuContext> {
+    let ctx = GPU_CONTEXT
+        .get_or_init(|| {
+            let ctx = block_on(GpuContext::with_power_preference(preference));
+            if ctx.is_none() {
+                warn!("No usable GPU adapter, falling back to the CPU pipeline");
+            }
+            ctx
+        })
+        .as_ref()?;
+    warn_preference_mismatch(ctx, preference);
+    Some(ctx)
+}
 
-    // Initialize if not present
-    let ctx = block_on(GpuContext::new())?;
-    let _ = GPU_CONTEXT.set(ctx);
-    GPU_CONTEXT.get()
+/// Log once when a caller asks for another adapter than the live context uses.
+#[cfg(feature = "compute-gpu")]
+fn warn_preference_mismatch(ctx: &GpuContext, preference: GpuPowerPreference) {
+    static WARNED: AtomicBool = AtomicBool::new(false);
+    if ctx.power_preference != preference && !WARNED.swap(true, Ordering::Relaxed) {
+        warn!(
+            "GPU context already uses {:?}, ignoring the requested {:?}",
+            ctx.power_preference, preference
+        );
+    }
 }
 
 // WASM Implementation using Unsafe Wrapper to bypass Send/Sync check
@@ -57,10 +199,26 @@ pub fn get_gpu_context() -> Option<&'static GpuContext> {
     WASM_GPU_CONTEXT.get().map(|h| &h.0)
 }
 
+/// On WASM the worker creates the context up front, so the preference is
+/// applied there via [`GpuContext::with_power_preference`]; another one here
+/// only logs a warning.
+#[cfg(all(feature = "compute-gpu", target_arch = "wasm32"))]
+pub fn get_gpu_context_with_preference(
+    preference: GpuPowerPreference,
+) -> Option<&'static GpuContext> {
+    let ctx = get_gpu_context()?;
+    warn_preference_mismatch(ctx, preference);
+    Some(ctx)
+}
+
 #[cfg(feature = "compute-gpu")]
 impl GpuContext {
     pub async fn new() -> Option<Self> {
-        info!("Initializing WGPU context");
+        Self::with_power_preference(GpuPowerPreference::default()).await
+    }
+
+    pub async fn with_power_preference(preference: GpuPowerPreference) -> Option<Self> {
+        info!("Initializing WGPU context ({:?})", preference);
         let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
             backends: wgpu::Backends::all(),
             ..Default::default()
@@ -68,10 +226,14 @@ impl GpuContext {
 
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::HighPerformance,
+                power_preference: match preference {
+                    GpuPowerPreference::HighPerformance => wgpu::PowerPreference::HighPerformance,
+                    GpuPowerPreference::LowPower => wgpu::PowerPreference::LowPower,
+                },
                 ..Default::default()
             })
             .await
+            .map_err(|e| warn!("GPU adapter request failed: {}", e))
             .ok()?;
 
         info!("Using GPU adapter: {:?}", adapter.get_info());
@@ -86,9 +248,15 @@ impl GpuContext {
                 trace: Default::default(),
             })
             .await
+            .map_err(|e| warn!("GPU device request failed: {}", e))
             .ok()?;
 
-        Some(Self { device, queue })
+        Some(Self {
+            device,
+            queue,
+            buffer_pool: BufferPool::default(),
+            power_preference: preference,
+        })
     }
 }
 
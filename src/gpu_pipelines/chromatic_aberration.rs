@@ -0,0 +1,187 @@
+#[cfg(feature = "compute-gpu")]
+use super::common::{compilation_options, workgroup_count};
+#[cfg(feature = "compute-gpu")]
+use crate::gpu::{GpuBuffer, GpuContext};
+#[cfg(feature = "compute-gpu")]
+use wgpu::util::DeviceExt;
+
+#[cfg(feature = "compute-gpu")]
+pub struct ChromaticAberrationPipeline {
+    pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+}
+
+#[cfg(feature = "compute-gpu")]
+impl ChromaticAberrationPipeline {
+    pub fn new(context: &GpuContext) -> Self {
+        let shader = context
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Chromatic Aberration Shader"),
+                source: wgpu::ShaderSource::Wgsl(
+                    include_str!("../shaders/chromatic_aberration.wgsl").into(),
+                ),
+            });
+
+        let bind_group_layout =
+            context
+                .device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some("Chromatic Aberration Bind Group Layout"),
+                    entries: &[
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: wgpu::ShaderStages::COMPUTE,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: wgpu::ShaderStages::COMPUTE,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: wgpu::ShaderStages::COMPUTE,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                    ],
+                });
+
+        let pipeline_layout =
+            context
+                .device
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: Some("Chromatic Aberration Pipeline Layout"),
+                    bind_group_layouts: &[&bind_group_layout],
+                    immediate_size: 0,
+                });
+
+        let pipeline = context
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some("Chromatic Aberration Pipeline"),
+                layout: Some(&pipeline_layout),
+                module: &shader,
+                entry_point: Some("main"),
+                compilation_options: compilation_options(),
+                cache: None,
+            });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+        }
+    }
+
+    pub fn process(
+        &self,
+        context: &GpuContext,
+        input: &GpuBuffer,
+        lens: &crate::processor::LensConfig,
+    ) -> Option<GpuBuffer> {
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Chromatic Aberration Encoder"),
+            });
+        let output = self.encode(context, &mut encoder, input, lens);
+        context.queue.submit(Some(encoder.finish()));
+        Some(output)
+    }
+
+    /// Record the chromatic aberration pass into `encoder`.
+    pub fn encode(
+        &self,
+        context: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &GpuBuffer,
+        lens: &crate::processor::LensConfig,
+    ) -> GpuBuffer {
+        let width = input.width;
+        let height = input.height;
+        let size = input.size;
+
+        let output_buffer = context.acquire_image_buffer("Chromatic Aberration Output", size);
+
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        struct Uniforms {
+            width: u32,
+            height: u32,
+            r_scale: f32,
+            b_scale: f32,
+        }
+        unsafe impl bytemuck::Zeroable for Uniforms {}
+        unsafe impl bytemuck::Pod for Uniforms {}
+
+        let (r_scale, b_scale) = lens.channel_scales();
+        let uniforms = Uniforms {
+            width,
+            height,
+            r_scale,
+            b_scale,
+        };
+
+        let uniform_buffer = context
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Chromatic Aberration Uniforms"),
+                contents: bytemuck::bytes_of(&uniforms),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+        let bind_group = context
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Chromatic Aberration Bind Group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: input.buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: output_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: uniform_buffer.as_entire_binding(),
+                    },
+                ],
+            });
+
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Chromatic Aberration Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.pipeline);
+            compute_pass.set_bind_group(0, &bind_group, &[]);
+            let (x_groups, y_groups) = workgroup_count(width, height);
+            compute_pass.dispatch_workgroups(x_groups, y_groups, 1);
+        }
+
+        GpuBuffer {
+            buffer: output_buffer,
+            width,
+            height,
+            size,
+        }
+    }
+}
@@ -3,6 +3,39 @@ use crate::gpu::{GpuBuffer, GpuContext};
 #[cfg(feature = "compute-gpu")]
 use futures::channel::oneshot;
 
+/// Workgroup size `(x, y)` of every image compute shader.
+///
+/// The shaders declare it through the `WORKGROUP_X` / `WORKGROUP_Y` override
+/// constants, set from here by [`compilation_options`], and the dispatch math
+/// uses [`workgroup_count`], so this is the one place to tune it. 8×8 or 32×8
+/// can be faster on some GPUs and wastes fewer threads on small previews.
+pub const WORKGROUP_SIZE: (u32, u32) = (16, 16);
+
+#[cfg(feature = "compute-gpu")]
+static WORKGROUP_CONSTANTS: [(&str, f64); 2] = [
+    ("WORKGROUP_X", WORKGROUP_SIZE.0 as f64),
+    ("WORKGROUP_Y", WORKGROUP_SIZE.1 as f64),
+];
+
+/// Compilation options for the image compute pipelines: sets the workgroup
+/// size overrides to [`WORKGROUP_SIZE`].
+#[cfg(feature = "compute-gpu")]
+pub fn compilation_options() -> wgpu::PipelineCompilationOptions<'static> {
+    wgpu::PipelineCompilationOptions {
+        constants: &WORKGROUP_CONSTANTS,
+        ..Default::default()
+    }
+}
+
+/// Workgroups to dispatch to cover a `width` × `height` image.
+#[cfg(feature = "compute-gpu")]
+pub fn workgroup_count(width: u32, height: u32) -> (u32, u32) {
+    (
+        width.div_ceil(WORKGROUP_SIZE.0),
+        height.div_ceil(WORKGROUP_SIZE.1),
+    )
+}
+
 // Wrapper to make WGPU types Send + Sync on WASM
 #[cfg(all(feature = "compute-gpu", target_arch = "wasm32"))]
 pub struct SendSyncWrapper<T>(pub T);
@@ -35,55 +68,87 @@ pub async fn read_gpu_buffer(
     context: &GpuContext,
     gpu_buffer: &GpuBuffer,
 ) -> Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>> {
-    let size = gpu_buffer.size;
-
-    // Create a staging buffer for reading back data
-    let staging_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-        label: Some("Staging Buffer"),
-        size,
-        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
-        mapped_at_creation: false,
-    });
+    read_gpu_buffers(context, &[gpu_buffer])
+        .await
+        .pop()
+        .flatten()
+}
 
-    // Copy from GPU buffer to staging buffer
+/// Read several buffers back with one copy submission and one wait.
+/// Results are in the same order as `gpu_buffers`.
+#[cfg(feature = "compute-gpu")]
+pub async fn read_gpu_buffers(
+    context: &GpuContext,
+    gpu_buffers: &[&GpuBuffer],
+) -> Vec<Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>>> {
+    // Create staging buffers for reading back data
+    let staging_buffers: Vec<wgpu::Buffer> = gpu_buffers
+        .iter()
+        .map(|gpu_buffer| {
+            context.buffer_pool.acquire(
+                &context.device,
+                "Staging Buffer",
+                gpu_buffer.size,
+                crate::gpu::STAGING_BUFFER_USAGE,
+            )
+        })
+        .collect();
+
+    // Copy every GPU buffer to its staging buffer in one submission
     let mut encoder = context
         .device
         .create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Readback Copy Encoder"),
         });
-    encoder.copy_buffer_to_buffer(&gpu_buffer.buffer, 0, &staging_buffer, 0, size);
-    let _submission_index = context.queue.submit(Some(encoder.finish()));
+    for (gpu_buffer, staging_buffer) in gpu_buffers.iter().zip(&staging_buffers) {
+        encoder.copy_buffer_to_buffer(&gpu_buffer.buffer, 0, staging_buffer, 0, gpu_buffer.size);
+    }
+    let submission_index = context.queue.submit(Some(encoder.finish()));
 
-    let buffer_slice = staging_buffer.slice(..);
+    let receivers: Vec<_> = staging_buffers
+        .iter()
+        .map(|staging_buffer| {
+            let (sender, receiver) = oneshot::channel();
+            staging_buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, move |v| {
+                    let _ = sender.send(v);
+                });
+            receiver
+        })
+        .collect();
 
-    let (sender, mut receiver) = oneshot::channel();
-    buffer_slice.map_async(wgpu::MapMode::Read, move |v| {
-        sender.send(v).unwrap();
+    // Native: block until the copy submission is done, which fires every map callback.
+    // WASM: polling is a no-op; the browser resolves the mappings on its event loop,
+    // so awaiting the receivers below yields instead of blocking the thread.
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = context.device.poll(wgpu::PollType::Wait {
+        submission_index: Some(submission_index),
+        timeout: None,
     });
+    #[cfg(target_arch = "wasm32")]
+    let _ = submission_index;
 
-    // Wait for the copy to finish and the map callback to fire
-    // We use PollType::Wait to block until the submission is done.
-    // Note: We need to loop because sometimes the callback fires on the *next* poll after completion?
-    // But let's try strict wait first.
-    loop {
-        let _ = context.device.poll(wgpu::PollType::Poll);
-        if let Ok(Some(result)) = receiver.try_recv() {
-            if result.is_ok() {
-                let data = buffer_slice.get_mapped_range();
-                let result: Vec<f32> = bytemuck::cast_slice(&data).to_vec();
-
-                drop(data);
-                staging_buffer.unmap();
-
-                return image::ImageBuffer::from_raw(gpu_buffer.width, gpu_buffer.height, result);
-            }
-            return None;
+    let mut results = Vec::with_capacity(gpu_buffers.len());
+    for ((gpu_buffer, staging_buffer), receiver) in
+        gpu_buffers.iter().zip(staging_buffers).zip(receivers)
+    {
+        if !matches!(receiver.await, Ok(Ok(()))) {
+            results.push(None);
+            continue;
         }
-        // If we have the submission index, we could use Wait, but Poll works in a loop too.
-        // Using Poll allows us to check the receiver.
-        // If we use Wait, we block.
-        // Let's stick to the loop with Poll for robustness as verified before,
-        // but now we are polling for the STAGING buffer which is correct.
-        std::thread::sleep(std::time::Duration::from_millis(1));
+        let data = staging_buffer.slice(..).get_mapped_range();
+        let result: Vec<f32> = bytemuck::cast_slice(&data).to_vec();
+
+        drop(data);
+        staging_buffer.unmap();
+        context.buffer_pool.release(staging_buffer);
+
+        results.push(image::ImageBuffer::from_raw(
+            gpu_buffer.width,
+            gpu_buffer.height,
+            result,
+        ));
     }
+    results
 }
@@ -1,4 +1,6 @@
 #[cfg(feature = "compute-gpu")]
+use super::common::{compilation_options, workgroup_count};
+#[cfg(feature = "compute-gpu")]
 use crate::gpu::{GpuBuffer, GpuContext};
 #[cfg(feature = "compute-gpu")]
 use wgpu::util::DeviceExt;
@@ -74,7 +76,7 @@ impl DevelopPipeline {
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("main"),
-                compilation_options: Default::default(),
+                compilation_options: compilation_options(),
                 cache: None,
             });
 
@@ -97,14 +99,7 @@ impl DevelopPipeline {
         let height = input.height;
         let size = input.size;
 
-        let output_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Develop Output Buffer"),
-            size,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let output_buffer = context.acquire_image_buffer("Develop Output Buffer", size);
 
         #[repr(C)]
         #[derive(Copy, Clone)]
@@ -159,8 +154,18 @@ impl DevelopPipeline {
         unsafe impl bytemuck::Zeroable for Uniforms {}
         unsafe impl bytemuck::Pod for Uniforms {}
 
-        let sm = spectral_matrix;
-        let cm = film.color_matrix;
+        // BW: every layer sees the scene's luminance and the nets are averaged,
+        // as in `FilmStock::map_log_exposure`. Row sums keep gray unchanged.
+        let (sm, cm) = if film.is_monochrome() {
+            let weights = film.bw_luminance_weights();
+            let sm: [[f32; 3]; 3] = std::array::from_fn(|c| {
+                let gray: f32 = spectral_matrix[c].iter().sum();
+                weights.map(|w| w * gray)
+            });
+            (sm, [[1.0 / 3.0; 3]; 3])
+        } else {
+            (*spectral_matrix, film.color_matrix)
+        };
         let uniforms = Uniforms {
             spectral_r: [sm[0][0], sm[0][1], sm[0][2], 0.0],
             spectral_g: [sm[1][0], sm[1][1], sm[1][2], 0.0],
@@ -222,8 +227,7 @@ impl DevelopPipeline {
             });
             compute_pass.set_pipeline(&self.pipeline);
             compute_pass.set_bind_group(0, &bind_group, &[]);
-            let x_groups = width.div_ceil(16);
-            let y_groups = height.div_ceil(16);
+            let (x_groups, y_groups) = workgroup_count(width, height);
             compute_pass.dispatch_workgroups(x_groups, y_groups, 1);
         }
 
@@ -0,0 +1,230 @@
+#[cfg(feature = "compute-gpu")]
+use super::common::{compilation_options, workgroup_count};
+#[cfg(feature = "compute-gpu")]
+use crate::gpu::{GpuBuffer, GpuContext};
+#[cfg(feature = "compute-gpu")]
+use crate::pipeline::{box_blur_sigma, DIFFUSION_KNEE, DIFFUSION_SIGMA};
+#[cfg(feature = "compute-gpu")]
+use wgpu::util::DeviceExt;
+
+#[cfg(feature = "compute-gpu")]
+pub struct DiffusionPipeline {
+    weight_pipeline: wgpu::ComputePipeline,
+    blend_pipeline: wgpu::ComputePipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+}
+
+#[cfg(feature = "compute-gpu")]
+impl DiffusionPipeline {
+    pub fn new(context: &GpuContext) -> Self {
+        let shader = context
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("Diffusion Shader"),
+                source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/diffusion.wgsl").into()),
+            });
+
+        let storage = |binding, read_only| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout =
+            context
+                .device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some("Diffusion Bind Group Layout"),
+                    entries: &[
+                        storage(0, true),
+                        storage(1, false),
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: wgpu::ShaderStages::COMPUTE,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        storage(3, true),
+                    ],
+                });
+
+        let pipeline_layout =
+            context
+                .device
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: Some("Diffusion Pipeline Layout"),
+                    bind_group_layouts: &[&bind_group_layout],
+                    immediate_size: 0,
+                });
+
+        let entry = |label, entry_point| {
+            context
+                .device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: Some(label),
+                    layout: Some(&pipeline_layout),
+                    module: &shader,
+                    entry_point: Some(entry_point),
+                    compilation_options: compilation_options(),
+                    cache: None,
+                })
+        };
+
+        Self {
+            weight_pipeline: entry("Diffusion Weight Pipeline", "weight"),
+            blend_pipeline: entry("Diffusion Blend Pipeline", "blend"),
+            bind_group_layout,
+        }
+    }
+
+    pub fn process(
+        &self,
+        context: &GpuContext,
+        input: &GpuBuffer,
+        lens: &crate::processor::LensConfig,
+    ) -> Option<GpuBuffer> {
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Diffusion Encoder"),
+            });
+        let mut retired = Vec::new();
+        let output = self.encode(context, &mut encoder, input, lens, &mut retired);
+        context.queue.submit(Some(encoder.finish()));
+        for buffer in retired {
+            context.buffer_pool.release(buffer);
+        }
+        Some(output)
+    }
+
+    /// Record the diffusion passes into `encoder`. Intermediate buffers are
+    /// pushed onto `retired` for release once the encoder has been submitted.
+    pub fn encode(
+        &self,
+        context: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &GpuBuffer,
+        lens: &crate::processor::LensConfig,
+        retired: &mut Vec<wgpu::Buffer>,
+    ) -> GpuBuffer {
+        let width = input.width;
+        let height = input.height;
+        let size = input.size;
+
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        struct Uniforms {
+            width: u32,
+            height: u32,
+            strength: f32,
+            knee: f32,
+        }
+        unsafe impl bytemuck::Zeroable for Uniforms {}
+        unsafe impl bytemuck::Pod for Uniforms {}
+
+        let uniforms = Uniforms {
+            width,
+            height,
+            strength: lens.diffusion.clamp(0.0, 1.0),
+            knee: DIFFUSION_KNEE,
+        };
+
+        let uniform_buffer = context
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Diffusion Uniforms"),
+                contents: bytemuck::bytes_of(&uniforms),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+        let bind_group = |label, output: &wgpu::Buffer, glow: &wgpu::Buffer| {
+            context
+                .device
+                .create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some(label),
+                    layout: &self.bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: input.buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: output.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: uniform_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: glow.as_entire_binding(),
+                        },
+                    ],
+                })
+        };
+        let (x_groups, y_groups) = workgroup_count(width, height);
+
+        // The weight pass does not read binding 3; the input stands in for it.
+        let scattered_buffer = context.acquire_image_buffer("Diffusion Scattered Buffer", size);
+        let weight_bind_group = bind_group(
+            "Diffusion Weight Bind Group",
+            &scattered_buffer,
+            &input.buffer,
+        );
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Diffusion Weight Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.weight_pipeline);
+            compute_pass.set_bind_group(0, &weight_bind_group, &[]);
+            compute_pass.dispatch_workgroups(x_groups, y_groups, 1);
+        }
+
+        let scattered = GpuBuffer {
+            buffer: scattered_buffer,
+            width,
+            height,
+            size,
+        };
+        // The CPU blurs with three box passes; match their effective sigma.
+        let glow = super::get_gaussian_pipeline(context).encode(
+            context,
+            encoder,
+            &scattered,
+            box_blur_sigma(width as f32 * DIFFUSION_SIGMA),
+            retired,
+        );
+        retired.push(scattered.buffer);
+
+        let output_buffer = context.acquire_image_buffer("Diffusion Output", size);
+        let blend_bind_group =
+            bind_group("Diffusion Blend Bind Group", &output_buffer, &glow.buffer);
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Diffusion Blend Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.blend_pipeline);
+            compute_pass.set_bind_group(0, &blend_bind_group, &[]);
+            compute_pass.dispatch_workgroups(x_groups, y_groups, 1);
+        }
+        retired.push(glow.buffer);
+
+        GpuBuffer {
+            buffer: output_buffer,
+            width,
+            height,
+            size,
+        }
+    }
+}
@@ -1,4 +1,6 @@
 #[cfg(feature = "compute-gpu")]
+use super::common::{compilation_options, workgroup_count};
+#[cfg(feature = "compute-gpu")]
 use crate::gpu::{GpuBuffer, GpuContext};
 #[cfg(feature = "compute-gpu")]
 use wgpu::util::DeviceExt;
@@ -74,7 +76,7 @@ impl GaussianPipeline {
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("main_x"),
-                compilation_options: Default::default(),
+                compilation_options: compilation_options(),
                 cache: None,
             });
 
@@ -85,7 +87,7 @@ impl GaussianPipeline {
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("main_y"),
-                compilation_options: Default::default(),
+                compilation_options: compilation_options(),
                 cache: None,
             });
 
@@ -102,25 +104,37 @@ impl GaussianPipeline {
         input: &GpuBuffer,
         sigma: f32,
     ) -> Option<GpuBuffer> {
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Blur Encoder"),
+            });
+        let mut retired = Vec::new();
+        let output = self.encode(context, &mut encoder, input, sigma, &mut retired);
+
+        context.queue.submit(Some(encoder.finish()));
+        for buffer in retired {
+            context.buffer_pool.release(buffer);
+        }
+        Some(output)
+    }
+
+    /// Record both blur passes into `encoder`.
+    /// The temp buffer is pushed to `retired`; return it to the pool after submitting.
+    pub fn encode(
+        &self,
+        context: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &GpuBuffer,
+        sigma: f32,
+        retired: &mut Vec<wgpu::Buffer>,
+    ) -> GpuBuffer {
         let width = input.width;
         let height = input.height;
         let size = input.size;
 
-        let temp_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Blur Temp Buffer"),
-            size,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
-            mapped_at_creation: false,
-        });
-
-        let output_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Blur Output Buffer"),
-            size,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let temp_buffer = context.acquire_image_buffer("Blur Temp Buffer", size);
+        let output_buffer = context.acquire_image_buffer("Blur Output Buffer", size);
 
         #[repr(C)]
         #[derive(Copy, Clone)]
@@ -190,14 +204,7 @@ impl GaussianPipeline {
                 ],
             });
 
-        let mut encoder = context
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Blur Encoder"),
-            });
-
-        let x_groups = width.div_ceil(16);
-        let y_groups = height.div_ceil(16);
+        let (x_groups, y_groups) = workgroup_count(width, height);
 
         {
             let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
@@ -219,13 +226,13 @@ impl GaussianPipeline {
             pass.dispatch_workgroups(x_groups, y_groups, 1);
         }
 
-        context.queue.submit(Some(encoder.finish()));
+        retired.push(temp_buffer);
 
-        Some(GpuBuffer {
+        GpuBuffer {
             buffer: output_buffer,
             width,
             height,
             size,
-        })
+        }
     }
 }
@@ -1,6 +1,10 @@
 #[cfg(feature = "compute-gpu")]
+use super::common::{compilation_options, workgroup_count};
+#[cfg(feature = "compute-gpu")]
 use crate::gpu::{GpuBuffer, GpuContext};
 #[cfg(feature = "compute-gpu")]
+use crate::pipeline::{box_blur_sigma, grain_sigma_and_scale, GRAIN_BOOST};
+#[cfg(feature = "compute-gpu")]
 use wgpu::util::DeviceExt;
 
 #[repr(C)]
@@ -9,9 +13,11 @@ pub struct GrainUniforms {
     pub width: u32,
     pub height: u32,
     pub seed: f32,
+    /// Grain strength per √density, scaled to the render size.
     pub alpha: f32,
-    pub sigma_read: f32,
-    pub roughness: f32,
+    pub color_correlation: f32,
+    /// Sigma in pixels the noise is blurred with before it is added.
+    pub blur_radius: f32,
     pub monochrome: u32,
     pub _pad: f32,
 }
@@ -20,6 +26,7 @@ unsafe impl bytemuck::Pod for GrainUniforms {}
 
 #[cfg(feature = "compute-gpu")]
 pub struct GrainPipeline {
+    noise_pipeline: wgpu::ComputePipeline,
     pipeline: wgpu::ComputePipeline,
     bind_group_layout: wgpu::BindGroupLayout,
 }
@@ -70,6 +77,16 @@ impl GrainPipeline {
                             },
                             count: None,
                         },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: wgpu::ShaderStages::COMPUTE,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
                     ],
                 });
 
@@ -82,6 +99,18 @@ impl GrainPipeline {
                     immediate_size: 0,
                 });
 
+        let noise_pipeline =
+            context
+                .device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: Some("Grain Noise Pipeline"),
+                    layout: Some(&pipeline_layout),
+                    module: &shader,
+                    entry_point: Some("noise"),
+                    compilation_options: compilation_options(),
+                    cache: None,
+                });
+
         let pipeline = context
             .device
             .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
@@ -89,11 +118,12 @@ impl GrainPipeline {
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("main"),
-                compilation_options: Default::default(),
+                compilation_options: compilation_options(),
                 cache: None,
             });
 
         Self {
+            noise_pipeline,
             pipeline,
             bind_group_layout,
         }
@@ -109,15 +139,8 @@ impl GrainPipeline {
         let height = input.height;
         let size = input.size;
 
-        if film.grain_model.alpha <= 0.0 && film.grain_model.sigma_read <= 0.0 {
-            let output_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Grain Output Buffer (Copy)"),
-                size,
-                usage: wgpu::BufferUsages::STORAGE
-                    | wgpu::BufferUsages::COPY_SRC
-                    | wgpu::BufferUsages::COPY_DST,
-                mapped_at_creation: false,
-            });
+        if film.grain_model.alpha <= 0.0 {
+            let output_buffer = context.acquire_image_buffer("Grain Output Buffer (Copy)", size);
             let mut encoder =
                 context
                     .device
@@ -134,31 +157,27 @@ impl GrainPipeline {
             });
         }
 
-        let output_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Grain Output Buffer"),
-            size,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let output_buffer = context.acquire_image_buffer("Grain Output Buffer", size);
+        let noise_buffer = context.acquire_image_buffer("Grain Noise Buffer", size);
 
         let seed = 1234.5678;
 
-        const REFERENCE_WIDTH: f32 = 2048.0;
-        let scale_factor = width as f32 / REFERENCE_WIDTH;
-
-        let alpha_scaled = film.grain_model.alpha * scale_factor * scale_factor;
-        let sigma_read_scaled = film.grain_model.sigma_read * scale_factor;
+        // Same grain as the CPU GrainStage: white noise blurred to the physical
+        // grain size, then added with a strength of alpha × √D. Clumped grain
+        // structure is only rendered on the CPU.
+        let gm = &film.grain_model;
+        let (grain_sigma, grain_scale) = grain_sigma_and_scale(film, width, height, None);
+        // The CPU blurs with three box passes; match their effective sigma.
+        let blur_radius = box_blur_sigma(grain_sigma);
 
         let uniforms = GrainUniforms {
             width,
             height,
             seed,
-            alpha: alpha_scaled,
-            sigma_read: sigma_read_scaled,
-            roughness: film.grain_model.roughness,
-            monochrome: if film.grain_model.monochrome { 1 } else { 0 },
+            alpha: gm.alpha * GRAIN_BOOST * grain_scale,
+            color_correlation: gm.color_correlation,
+            blur_radius,
+            monochrome: if gm.monochrome { 1 } else { 0 },
             _pad: 0.0,
         };
 
@@ -170,33 +189,74 @@ impl GrainPipeline {
                 usage: wgpu::BufferUsages::UNIFORM,
             });
 
-        let bind_group = context
-            .device
-            .create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("Grain Bind Group"),
-                layout: &self.bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: input.buffer.as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: output_buffer.as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 2,
-                        resource: uniform_buffer.as_entire_binding(),
-                    },
-                ],
-            });
+        let bind_group = |label, output: &wgpu::Buffer, noise: &wgpu::Buffer| {
+            context
+                .device
+                .create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some(label),
+                    layout: &self.bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: input.buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: output.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: uniform_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: noise.as_entire_binding(),
+                        },
+                    ],
+                })
+        };
 
         let mut encoder = context
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                 label: Some("Grain Encoder"),
             });
+        let (x_groups, y_groups) = workgroup_count(width, height);
 
+        // The noise pass does not read binding 3; the input stands in for it.
+        let noise_bind_group = bind_group("Grain Noise Bind Group", &noise_buffer, &input.buffer);
+        {
+            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Grain Noise Pass"),
+                timestamp_writes: None,
+            });
+            compute_pass.set_pipeline(&self.noise_pipeline);
+            compute_pass.set_bind_group(0, &noise_bind_group, &[]);
+            compute_pass.dispatch_workgroups(x_groups, y_groups, 1);
+        }
+
+        let mut retired = Vec::new();
+        let noise = GpuBuffer {
+            buffer: noise_buffer,
+            width,
+            height,
+            size,
+        };
+        let noise = if blur_radius > 0.0 {
+            let blurred = super::get_gaussian_pipeline(context).encode(
+                context,
+                &mut encoder,
+                &noise,
+                blur_radius,
+                &mut retired,
+            );
+            retired.push(noise.buffer);
+            blurred
+        } else {
+            noise
+        };
+
+        let bind_group = bind_group("Grain Bind Group", &output_buffer, &noise.buffer);
         {
             let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("Grain Pass"),
@@ -204,12 +264,14 @@ impl GrainPipeline {
             });
             compute_pass.set_pipeline(&self.pipeline);
             compute_pass.set_bind_group(0, &bind_group, &[]);
-            let x_groups = width.div_ceil(16);
-            let y_groups = height.div_ceil(16);
             compute_pass.dispatch_workgroups(x_groups, y_groups, 1);
         }
 
         context.queue.submit(Some(encoder.finish()));
+        retired.push(noise.buffer);
+        for buffer in retired {
+            context.buffer_pool.release(buffer);
+        }
 
         Some(GpuBuffer {
             buffer: output_buffer,
@@ -1,4 +1,6 @@
 #[cfg(feature = "compute-gpu")]
+use super::common::{compilation_options, workgroup_count};
+#[cfg(feature = "compute-gpu")]
 use crate::gpu::{GpuBuffer, GpuContext};
 #[cfg(feature = "compute-gpu")]
 use wgpu::util::DeviceExt;
@@ -132,7 +134,7 @@ impl HalationPipeline {
                 layout: Some(&pipeline_layout_x),
                 module: &shader,
                 entry_point: Some("main_x"),
-                compilation_options: Default::default(),
+                compilation_options: compilation_options(),
                 cache: None,
             });
 
@@ -143,7 +145,7 @@ impl HalationPipeline {
                 layout: Some(&pipeline_layout_y),
                 module: &shader,
                 entry_point: Some("main_y"),
-                compilation_options: Default::default(),
+                compilation_options: compilation_options(),
                 cache: None,
             });
 
@@ -161,25 +163,100 @@ impl HalationPipeline {
         input: &GpuBuffer,
         film: &crate::FilmStock,
     ) -> Option<GpuBuffer> {
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Halation Encoder"),
+            });
+        let mut retired = Vec::new();
+        let output = self.encode(context, &mut encoder, input, film, &mut retired);
+
+        context.queue.submit(Some(encoder.finish()));
+        // Intermediates can be reused as soon as their passes are submitted.
+        for buffer in retired {
+            context.buffer_pool.release(buffer);
+        }
+        Some(output)
+    }
+
+    /// Record the halation passes into `encoder`.
+    /// Intermediate buffers are pushed to `retired`; return them to the pool after submitting.
+    pub fn encode(
+        &self,
+        context: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &GpuBuffer,
+        film: &crate::FilmStock,
+        retired: &mut Vec<wgpu::Buffer>,
+    ) -> GpuBuffer {
         let width = input.width;
         let height = input.height;
         let size = input.size;
 
-        let temp_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Halation Temp Buffer"),
-            size,
-            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
-            mapped_at_creation: false,
-        });
+        let temp_buffer = context.acquire_image_buffer("Halation Temp Buffer", size);
+        let create_output = || context.acquire_image_buffer("Halation Output Buffer", size);
+
+        // Core bloom: threshold the input, blur, and blend over the input
+        let output_buffer = create_output();
+        self.encode_scale(
+            context,
+            encoder,
+            input,
+            &input.buffer,
+            &temp_buffer,
+            &output_buffer,
+            film,
+            film.halation_sigma,
+            film.halation_strength * film.halation_backing_gain(),
+        );
 
-        let output_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Halation Output Buffer"),
+        // Optional wide skirt: same bright mask from the input, blended over the core result.
+        // Passes in one encoder run in order, so the temp buffer can be reused.
+        let output_buffer = if film.halation_strength2 > 0.0 && film.halation_sigma2 > 0.0 {
+            let skirt_buffer = create_output();
+            self.encode_scale(
+                context,
+                encoder,
+                input,
+                &output_buffer,
+                &temp_buffer,
+                &skirt_buffer,
+                film,
+                film.halation_sigma2,
+                film.halation_strength2 * film.halation_backing_gain(),
+            );
+            retired.push(output_buffer);
+            skirt_buffer
+        } else {
+            output_buffer
+        };
+        retired.push(temp_buffer);
+
+        GpuBuffer {
+            buffer: output_buffer,
+            width,
+            height,
             size,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        }
+    }
+
+    /// Record one threshold + separable blur + blend scale.
+    /// The mask comes from `input`, the glow is added on top of `base`.
+    #[allow(clippy::too_many_arguments)]
+    fn encode_scale(
+        &self,
+        context: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &GpuBuffer,
+        base: &wgpu::Buffer,
+        temp_buffer: &wgpu::Buffer,
+        output_buffer: &wgpu::Buffer,
+        film: &crate::FilmStock,
+        sigma_fraction: f32,
+        strength: f32,
+    ) {
+        let width = input.width;
+        let height = input.height;
 
         #[repr(C)]
         #[derive(Copy, Clone)]
@@ -187,33 +264,33 @@ impl HalationPipeline {
             width: u32,
             height: u32,
             threshold: f32,
-            _pad1: f32,
+            saturation_weight: f32,
             sigma: f32,
             strength: f32,
             tint_r: f32,
             tint_g: f32,
             tint_b: f32,
-            _pad2: f32,
-            _pad3: f32,
-            _pad4: f32,
+            gain_r: f32,
+            gain_g: f32,
+            gain_b: f32,
         }
         unsafe impl bytemuck::Zeroable for Uniforms {}
         unsafe impl bytemuck::Pod for Uniforms {}
 
-        let sigma = width as f32 * film.halation_sigma;
+        let sigma = width as f32 * sigma_fraction;
         let uniforms = Uniforms {
             width,
             height,
             threshold: film.halation_threshold,
-            _pad1: 0.0,
+            saturation_weight: film.halation_saturation_weight,
             sigma,
-            strength: film.halation_strength,
+            strength,
             tint_r: film.halation_tint[0],
             tint_g: film.halation_tint[1],
             tint_b: film.halation_tint[2],
-            _pad2: 0.0,
-            _pad3: 0.0,
-            _pad4: 0.0,
+            gain_r: film.halation_channel_gain[0],
+            gain_g: film.halation_channel_gain[1],
+            gain_b: film.halation_channel_gain[2],
         };
 
         let uniform_buffer = context
@@ -265,19 +342,12 @@ impl HalationPipeline {
                     },
                     wgpu::BindGroupEntry {
                         binding: 3,
-                        resource: input.buffer.as_entire_binding(),
+                        resource: base.as_entire_binding(),
                     },
                 ],
             });
 
-        let mut encoder = context
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Halation Encoder"),
-            });
-
-        let x_groups = width.div_ceil(16);
-        let y_groups = height.div_ceil(16);
+        let (x_groups, y_groups) = workgroup_count(width, height);
 
         {
             let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
@@ -298,14 +368,5 @@ impl HalationPipeline {
             pass.set_bind_group(0, &bg_y, &[]);
             pass.dispatch_workgroups(x_groups, y_groups, 1);
         }
-
-        context.queue.submit(Some(encoder.finish()));
-
-        Some(GpuBuffer {
-            buffer: output_buffer,
-            width,
-            height,
-            size,
-        })
     }
 }
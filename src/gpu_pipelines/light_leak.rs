@@ -1,4 +1,6 @@
 #[cfg(feature = "compute-gpu")]
+use super::common::{compilation_options, workgroup_count};
+#[cfg(feature = "compute-gpu")]
 use crate::gpu::{GpuBuffer, GpuContext};
 #[cfg(feature = "compute-gpu")]
 use wgpu::util::DeviceExt;
@@ -73,7 +75,7 @@ impl LightLeakPipeline {
                 layout: Some(&layout),
                 module: &shader,
                 entry_point: Some("main"),
-                compilation_options: Default::default(),
+                compilation_options: compilation_options(),
                 cache: None,
             });
 
@@ -93,6 +95,27 @@ impl LightLeakPipeline {
             return;
         }
 
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("LightLeak Encoder"),
+            });
+        self.encode(context, &mut encoder, buffer, config);
+        context.queue.submit(Some(encoder.finish()));
+    }
+
+    /// Record the in-place light leak pass into `encoder`.
+    pub fn encode(
+        &self,
+        context: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        buffer: &mut GpuBuffer,
+        config: &crate::light_leak::LightLeakConfig,
+    ) {
+        if !config.enabled || config.leaks.is_empty() {
+            return;
+        }
+
         #[repr(C)]
         #[derive(Copy, Clone)]
         struct GpuLightLeak {
@@ -109,7 +132,7 @@ impl LightLeakPipeline {
         unsafe impl bytemuck::Pod for GpuLightLeak {}
 
         let gpu_leaks: Vec<GpuLightLeak> = config
-            .leaks
+            .leaks_at_time()
             .iter()
             .map(|l| GpuLightLeak {
                 position: [l.position.0, l.position.1],
@@ -178,12 +201,6 @@ impl LightLeakPipeline {
                 ],
             });
 
-        let mut encoder = context
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("LightLeak Encoder"),
-            });
-
         {
             let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("LightLeak Pass"),
@@ -191,11 +208,8 @@ impl LightLeakPipeline {
             });
             pass.set_pipeline(&self.pipeline);
             pass.set_bind_group(0, &bind_group, &[]);
-            let x_groups = buffer.width.div_ceil(16);
-            let y_groups = buffer.height.div_ceil(16);
+            let (x_groups, y_groups) = workgroup_count(buffer.width, buffer.height);
             pass.dispatch_workgroups(x_groups, y_groups, 1);
         }
-
-        context.queue.submit(Some(encoder.finish()));
     }
 }
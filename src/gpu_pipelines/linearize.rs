@@ -1,4 +1,6 @@
 #[cfg(feature = "compute-gpu")]
+use super::common::{compilation_options, workgroup_count};
+#[cfg(feature = "compute-gpu")]
 use crate::gpu::{GpuBuffer, GpuContext};
 #[cfg(feature = "compute-gpu")]
 use wgpu::util::DeviceExt;
@@ -74,7 +76,7 @@ impl LinearizePipeline {
                 layout: Some(&pipeline_layout),
                 module: &shader,
                 entry_point: Some("main"),
-                compilation_options: Default::default(),
+                compilation_options: compilation_options(),
                 cache: None,
             });
 
@@ -88,7 +90,31 @@ impl LinearizePipeline {
         &self,
         context: &GpuContext,
         input: &image::RgbImage,
+        input_is_linear: bool,
     ) -> Option<GpuBuffer> {
+        // wgpu rejects zero-sized bindings
+        if input.is_empty() {
+            return None;
+        }
+        let mut encoder = context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Linearize Encoder"),
+            });
+        let output = self.encode(context, &mut encoder, input, input_is_linear);
+        context.queue.submit(Some(encoder.finish()));
+        Some(output)
+    }
+
+    /// Upload `input` and record the linearize pass into `encoder`. With
+    /// `input_is_linear` the bytes are only scaled to 0..1, not decoded.
+    pub fn encode(
+        &self,
+        context: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &image::RgbImage,
+        input_is_linear: bool,
+    ) -> GpuBuffer {
         let width = input.width();
         let height = input.height();
         let pixel_count = (width * height) as u64;
@@ -108,25 +134,25 @@ impl LinearizePipeline {
                 usage: wgpu::BufferUsages::STORAGE,
             });
 
-        let output_buffer = context.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("Linearize Output Buffer"),
-            size: output_size,
-            usage: wgpu::BufferUsages::STORAGE
-                | wgpu::BufferUsages::COPY_SRC
-                | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
+        let output_buffer = context.acquire_image_buffer("Linearize Output Buffer", output_size);
 
         #[repr(C)]
         #[derive(Copy, Clone)]
         struct Uniforms {
             width: u32,
             height: u32,
+            input_is_linear: u32,
+            _pad: u32,
         }
         unsafe impl bytemuck::Zeroable for Uniforms {}
         unsafe impl bytemuck::Pod for Uniforms {}
 
-        let uniforms = Uniforms { width, height };
+        let uniforms = Uniforms {
+            width,
+            height,
+            input_is_linear: input_is_linear as u32,
+            _pad: 0,
+        };
         let uniform_buffer = context
             .device
             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -156,12 +182,6 @@ impl LinearizePipeline {
                 ],
             });
 
-        let mut encoder = context
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("Linearize Encoder"),
-            });
-
         {
             let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
                 label: Some("Linearize Pass"),
@@ -169,28 +189,27 @@ impl LinearizePipeline {
             });
             compute_pass.set_pipeline(&self.pipeline);
             compute_pass.set_bind_group(0, &bind_group, &[]);
-            let workgroup_size = 16;
-            let x_groups = width.div_ceil(workgroup_size);
-            let y_groups = height.div_ceil(workgroup_size);
+            let (x_groups, y_groups) = workgroup_count(width, height);
             compute_pass.dispatch_workgroups(x_groups, y_groups, 1);
         }
 
-        context.queue.submit(Some(encoder.finish()));
-
-        Some(GpuBuffer {
+        GpuBuffer {
             buffer: output_buffer,
             width,
             height,
             size: output_size,
-        })
+        }
     }
 
     pub async fn process_image_async(
         &self,
         context: &GpuContext,
         input: &image::RgbImage,
+        input_is_linear: bool,
     ) -> Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>> {
-        let gpu_buffer = self.process_to_gpu_buffer(context, input)?;
-        super::read_gpu_buffer(context, &gpu_buffer).await
+        let gpu_buffer = self.process_to_gpu_buffer(context, input, input_is_linear)?;
+        let result = super::read_gpu_buffer(context, &gpu_buffer).await;
+        context.recycle(gpu_buffer);
+        result
     }
 }
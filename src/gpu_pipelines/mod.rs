@@ -3,23 +3,28 @@ use crate::gpu::GpuContext;
 #[cfg(feature = "compute-gpu")]
 use std::sync::OnceLock;
 
+pub mod chromatic_aberration;
 mod common;
 pub mod develop;
+pub mod diffusion;
 pub mod gaussian;
 pub mod grain;
 pub mod halation;
 pub mod light_leak;
 pub mod linearize;
 
+pub use chromatic_aberration::ChromaticAberrationPipeline;
 pub use develop::DevelopPipeline;
+pub use diffusion::DiffusionPipeline;
 pub use gaussian::GaussianPipeline;
 pub use grain::{GrainPipeline, GrainUniforms};
 pub use halation::HalationPipeline;
 pub use light_leak::LightLeakPipeline;
 pub use linearize::LinearizePipeline;
 
+pub use common::WORKGROUP_SIZE;
 #[cfg(feature = "compute-gpu")]
-pub use common::read_gpu_buffer;
+pub use common::{read_gpu_buffer, read_gpu_buffers};
 
 #[cfg(all(feature = "compute-gpu", not(target_arch = "wasm32")))]
 type PipelineWrapper<T> = T;
@@ -38,6 +43,11 @@ static GAUSSIAN_PIPELINE: OnceLock<PipelineWrapper<GaussianPipeline>> = OnceLock
 #[cfg(feature = "compute-gpu")]
 static GRAIN_PIPELINE: OnceLock<PipelineWrapper<GrainPipeline>> = OnceLock::new();
 #[cfg(feature = "compute-gpu")]
+static CHROMATIC_ABERRATION_PIPELINE: OnceLock<PipelineWrapper<ChromaticAberrationPipeline>> =
+    OnceLock::new();
+#[cfg(feature = "compute-gpu")]
+static DIFFUSION_PIPELINE: OnceLock<PipelineWrapper<DiffusionPipeline>> = OnceLock::new();
+#[cfg(feature = "compute-gpu")]
 static DEVELOP_PIPELINE: OnceLock<PipelineWrapper<DevelopPipeline>> = OnceLock::new();
 
 #[cfg(feature = "compute-gpu")]
@@ -69,3 +79,16 @@ pub fn get_grain_pipeline(context: &GpuContext) -> &'static GrainPipeline {
 pub fn get_develop_pipeline(context: &GpuContext) -> &'static DevelopPipeline {
     DEVELOP_PIPELINE.get_or_init(|| common::wrap_pipeline(DevelopPipeline::new(context)))
 }
+
+#[cfg(feature = "compute-gpu")]
+pub fn get_chromatic_aberration_pipeline(
+    context: &GpuContext,
+) -> &'static ChromaticAberrationPipeline {
+    CHROMATIC_ABERRATION_PIPELINE
+        .get_or_init(|| common::wrap_pipeline(ChromaticAberrationPipeline::new(context)))
+}
+
+#[cfg(feature = "compute-gpu")]
+pub fn get_diffusion_pipeline(context: &GpuContext) -> &'static DiffusionPipeline {
+    DIFFUSION_PIPELINE.get_or_init(|| common::wrap_pipeline(DiffusionPipeline::new(context)))
+}
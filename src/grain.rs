@@ -6,7 +6,59 @@ use rand::Rng;
 use rand_distr::{Distribution, Normal};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+/// Scan density the grain amplitude is calibrated at (a 2048px-wide 35mm scan).
+/// Renders at other densities keep the same grain per mm² of film.
+pub const GRAIN_REFERENCE_PIXELS_PER_MM: f32 = 2048.0 / 36.0;
+
+/// Space the output grain is added in.
+///
+/// The same noise looks very different depending on where it is added, most
+/// visibly in the shadows; scanners and grain plugins differ mostly in this.
+/// All spaces match in amplitude at 18% gray.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum GrainSpace {
+    /// Additive in linear light, scaled by √(1 - luminance) for Selwyn's law
+    /// with a floor below 5% luminance. Once encoded, the floor reads as
+    /// coarse noise in the deep shadows.
+    #[default]
+    Linear,
+    /// Additive on sRGB-encoded values, like noise in a gamma-encoded scan:
+    /// perceptually even across the tonal range.
+    Encoded,
+    /// Multiplicative: added to each channel's print density D = -log10(v)
+    /// with σ_D ∝ √D (Selwyn). Follows the signal, so grain peaks in the
+    /// midtones and sinks into the shadows, as in an optical print.
+    Density,
+}
+
+impl GrainSpace {
+    /// Every space, in UI order.
+    pub const ALL: [GrainSpace; 3] = [GrainSpace::Linear, GrainSpace::Encoded, GrainSpace::Density];
+
+    /// Display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            GrainSpace::Linear => "Linear",
+            GrainSpace::Encoded => "sRGB",
+            GrainSpace::Density => "Density",
+        }
+    }
+}
+
+/// Spatial structure of the grain noise.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum GrainStructure {
+    /// Independent noise per pixel, softened by [`GrainModel::blur_radius`].
+    /// Fine grain looks like this, coarse grain reads as digital noise.
+    #[default]
+    PerPixel,
+    /// Cellular noise: grain gathers into clumps `size` pixels across at
+    /// [`GRAIN_REFERENCE_PIXELS_PER_MM`], like the chunky grain of fast B&W
+    /// emulsions. Same overall strength as `PerPixel`.
+    Clumped { size: f32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct GrainModel {
     pub alpha: f32,                // Shot noise coefficient (scales with density)
     pub sigma_read: f32,           // Base noise (fog/scanner noise)
@@ -16,6 +68,15 @@ pub struct GrainModel {
     pub color_correlation: f32, // How strongly the RGB channels are correlated (0.0 = Independent, 1.0 = Monochrome)
     pub shadow_noise: f32,      // Photon shot noise strength (Poisson noise in shadows)
     pub highlight_coarseness: f32, // Factor to increase grain size (clumping) in highlights
+    #[serde(default)]
+    pub structure: GrainStructure, // Spatial structure of the noise
+}
+
+impl Default for GrainModel {
+    /// A medium-grained film, see [`GrainModel::medium_grain`].
+    fn default() -> Self {
+        Self::medium_grain()
+    }
 }
 
 impl GrainModel {
@@ -35,6 +96,7 @@ impl GrainModel {
             color_correlation: 0.93, // High correlation = mostly luminance grain (measured from real film)
             shadow_noise: 0.001,     // Default small amount of shot noise
             highlight_coarseness: 0.10, // Moderate highlight clumping
+            structure: GrainStructure::PerPixel,
         }
     }
 
@@ -49,6 +111,7 @@ impl GrainModel {
             color_correlation: 0.93,
             shadow_noise: 0.001,
             highlight_coarseness: 0.10,
+            structure: GrainStructure::PerPixel,
         }
     }
 
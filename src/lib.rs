@@ -1,5 +1,11 @@
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod chart;
 pub mod cie_data;
+pub mod contact_sheet;
 pub mod depth;
+pub mod dither;
+pub mod error;
 pub mod film;
 pub mod film_layer;
 pub mod filmic_curve;
@@ -9,21 +15,45 @@ pub mod gpu;
 pub mod gpu_pipelines;
 pub mod grain;
 pub mod light_leak;
+pub mod mask;
 pub mod metrics;
 pub mod physics;
 pub mod pipeline;
 pub mod presets;
 pub mod processor;
+pub mod recipe;
+#[cfg(feature = "reference")]
+pub mod reference;
+pub mod scanner;
 pub mod shake;
+pub mod shutter;
 pub mod spectral;
 pub mod spectral_engine;
 pub mod utils;
+#[cfg(feature = "xmp")]
+pub mod xmp;
 
-pub use film::{FilmStock, FilmStyle};
-pub use grain::GrainModel;
-pub use metrics::FilmMetrics;
+pub use chart::{
+    color_transform, color_transform_chart, color_transform_table, render_lut_preview,
+};
+pub use contact_sheet::{export_stock_comparison, make_contact_sheet};
+pub use dither::DitherMode;
+pub use error::FilmrError;
+pub use film::{FilmFormat, FilmStock, FilmStyle};
+pub use grain::{GrainModel, GrainSpace, GrainStructure};
+pub use mask::{MaskConfig, MaskRange};
+pub use metrics::{FilmMetrics, MatchNudges, RoiRect, TargetMatch};
+pub use pipeline::StageKind;
 pub use processor::{
-    estimate_exposure_time, process_image, process_image_async, process_image_with_depth,
-    OutputMode, SimulationConfig, SimulationMode, WhiteBalanceMode,
+    density_map, develop_pixel, estimate_exposure_time, linearize_image_async, process_image,
+    process_image_async, process_image_debug, process_image_gpu, process_image_sync,
+    process_image_with_depth, process_images_batch, process_luma_image,
+    process_luma_image_with_depth, shoulder_overflow, BwFilter, DevelopmentParams,
+    GpuPowerPreference, LensConfig, LumaOutput, NegativeInversion, OutputMode, OutputTransfer,
+    PipelineStages, PreviewSession, SimulationConfig, SimulationConfigBuilder, SimulationMode,
+    Toning, WhiteBalanceMode, WorkingSpace,
 };
+pub use recipe::{Recipe, StockOverrides};
+pub use scanner::ScannerProfile;
+pub use shutter::{relative_exposure, ShutterSpeed};
 pub use spectral::Spectrum;
@@ -1,11 +1,95 @@
+use crate::physics;
 use crate::pipeline::{PipelineContext, PipelineStage};
-use image::{ImageBuffer, Rgb};
+use image::{ImageBuffer, Rgb, RgbaImage};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct LightLeakConfig {
     pub enabled: bool,
     pub leaks: Vec<LightLeak>,
+    /// Scanned light-leak images blended over the developed frame.
+    #[serde(default)]
+    pub overlays: Vec<LightLeakOverlay>,
+    /// Seed for the time-varying drift and flicker.
+    #[serde(default)]
+    pub seed: u64,
+    /// Time in seconds for animated sequences. At 0.0 leaks are exactly as configured.
+    #[serde(default)]
+    pub time: f32,
+}
+
+impl LightLeakConfig {
+    /// Return a copy of this config at time `t` (seconds).
+    /// Calling this per frame with the same seed gives a coherent, reproducible animation.
+    pub fn with_time(mut self, t: f32) -> Self {
+        self.time = t;
+        self
+    }
+
+    /// Leaks as they appear at `self.time`: positions drift, intensity flickers
+    /// and linear streaks slowly rotate.
+    pub fn leaks_at_time(&self) -> Vec<LightLeak> {
+        if self.time == 0.0 {
+            return self.leaks.clone();
+        }
+        self.leaks
+            .iter()
+            .enumerate()
+            .map(|(i, leak)| {
+                let key = self.seed ^ (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                let mut leak = leak.clone();
+                leak.position.0 += 0.05 * drift(key, 0, self.time * 0.5);
+                leak.position.1 += 0.05 * drift(key, 1, self.time * 0.5);
+                leak.intensity *= (1.0 + 0.25 * drift(key, 2, self.time * 4.0)).max(0.0);
+                leak.rotation += 0.2 * drift(key, 3, self.time * 0.3);
+                leak
+            })
+            .collect()
+    }
+
+    /// Overlays whose image has not been loaded from their
+    /// [`source`](LightLeakOverlay::source) yet, e.g. after deserializing.
+    pub fn unloaded_overlays(&self) -> impl Iterator<Item = &LightLeakOverlay> {
+        self.overlays
+            .iter()
+            .filter(|overlay| overlay.image.is_none())
+    }
+
+    /// Overlay opacity at `self.time`, flickering like the procedural leaks.
+    pub(crate) fn overlay_opacity_at_time(&self, index: usize, opacity: f32) -> f32 {
+        if self.time == 0.0 {
+            return opacity;
+        }
+        let key = self.seed ^ (index as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        (opacity * (1.0 + 0.25 * drift(key, 4, self.time * 4.0))).clamp(0.0, 1.0)
+    }
+}
+
+/// Smooth deterministic 1D noise in [-1, 1], offset so that `drift(.., 0.0) == 0.0`.
+fn drift(key: u64, channel: u64, t: f32) -> f32 {
+    value_noise(key, channel, t) - value_noise(key, channel, 0.0)
+}
+
+fn value_noise(key: u64, channel: u64, t: f32) -> f32 {
+    let i = t.floor();
+    let f = t - i;
+    let u = f * f * (3.0 - 2.0 * f);
+    let a = hash_unit(key, channel, i as i64);
+    let b = hash_unit(key, channel, i as i64 + 1);
+    (a + (b - a) * u) * 0.5
+}
+
+/// SplitMix64 hash mapped to [-1, 1].
+fn hash_unit(key: u64, channel: u64, step: i64) -> f32 {
+    let mut z = key
+        .wrapping_add(channel.wrapping_mul(0xBF58_476D_1CE4_E5B9))
+        .wrapping_add((step as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -48,6 +132,135 @@ pub enum LightLeakShape {
     Plasma,  // Interference-like pattern
 }
 
+/// How an overlay combines with the developed frame.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum LightLeakBlendMode {
+    /// 1 - (1 - base)(1 - leak): brightens without blowing out.
+    #[default]
+    Screen,
+    /// base + leak: stronger, can clip.
+    Add,
+}
+
+/// A real scanned light leak (usually shot on black film) used as an overlay.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LightLeakOverlay {
+    /// Path the overlay was loaded from. Saved configs keep only this, so
+    /// the caller reloads [`image`](Self::image) from it after loading one.
+    pub source: String,
+    /// Decoded overlay in sRGB RGBA. The core has no image decoders,
+    /// so the caller loads it; it is stretched to cover the frame.
+    /// Overlays without an image are skipped.
+    #[serde(skip)]
+    pub image: Option<Arc<RgbaImage>>,
+    /// Overall opacity (0.0 to 1.0), multiplied with the overlay alpha.
+    pub opacity: f32,
+    /// Rotation around the frame center in radians
+    pub rotation: f32,
+    pub blend: LightLeakBlendMode,
+}
+
+impl Default for LightLeakOverlay {
+    fn default() -> Self {
+        Self {
+            source: String::new(),
+            image: None,
+            opacity: 0.8,
+            rotation: 0.0,
+            blend: LightLeakBlendMode::Screen,
+        }
+    }
+}
+
+/// Blend overlay leaks over a developed linear RGB buffer (`width * height * 3`).
+pub(crate) fn apply_overlays(
+    linear_buf: &mut [f32],
+    width: u32,
+    height: u32,
+    config: &LightLeakConfig,
+) {
+    if !config.enabled {
+        return;
+    }
+
+    let lut: Vec<f32> = (0..256)
+        .map(|v| physics::srgb_to_linear(v as f32 / 255.0))
+        .collect();
+
+    for (index, overlay) in config.overlays.iter().enumerate() {
+        let Some(leak_image) = overlay.image.as_deref() else {
+            continue;
+        };
+        let opacity = config.overlay_opacity_at_time(index, overlay.opacity);
+        if opacity <= 0.0 || leak_image.width() == 0 || leak_image.height() == 0 {
+            continue;
+        }
+
+        let ow = leak_image.width() as f32;
+        let oh = leak_image.height() as f32;
+        let cx = width as f32 * 0.5;
+        let cy = height as f32 * 0.5;
+        let scale_x = ow / width as f32;
+        let scale_y = oh / height as f32;
+        // Inverse rotation: find where each output pixel lands on the overlay
+        let (sin, cos) = (-overlay.rotation).sin_cos();
+
+        linear_buf
+            .par_chunks_mut(3)
+            .enumerate()
+            .for_each(|(i, px)| {
+                let dx = (i as u32 % width) as f32 + 0.5 - cx;
+                let dy = (i as u32 / width) as f32 + 0.5 - cy;
+                let ox = (dx * cos - dy * sin + cx) * scale_x - 0.5;
+                let oy = (dx * sin + dy * cos + cy) * scale_y - 0.5;
+                if ox < -0.5 || oy < -0.5 || ox > ow - 0.5 || oy > oh - 0.5 {
+                    return;
+                }
+
+                let leak = sample_overlay(leak_image, &lut, ox, oy);
+                let alpha = leak[3] * opacity;
+                for (base, &leak_c) in px.iter_mut().zip(&leak[..3]) {
+                    let l = leak_c * alpha;
+                    *base = match overlay.blend {
+                        LightLeakBlendMode::Screen => *base + l - *base * l,
+                        LightLeakBlendMode::Add => *base + l,
+                    };
+                }
+            });
+    }
+}
+
+/// Bilinear sample of an sRGB RGBA overlay, returning linear RGB and alpha.
+fn sample_overlay(image: &RgbaImage, lut: &[f32], x: f32, y: f32) -> [f32; 4] {
+    let max_x = image.width() - 1;
+    let max_y = image.height() - 1;
+    let x = x.max(0.0);
+    let y = y.max(0.0);
+    let x0 = (x as u32).min(max_x);
+    let y0 = (y as u32).min(max_y);
+    let x1 = (x0 + 1).min(max_x);
+    let y1 = (y0 + 1).min(max_y);
+    let fx = x - x0 as f32;
+    let fy = y - y0 as f32;
+
+    let fetch = |px: u32, py: u32| {
+        let p = image.get_pixel(px, py).0;
+        [
+            lut[p[0] as usize],
+            lut[p[1] as usize],
+            lut[p[2] as usize],
+            p[3] as f32 / 255.0,
+        ]
+    };
+    let (p00, p10, p01, p11) = (fetch(x0, y0), fetch(x1, y0), fetch(x0, y1), fetch(x1, y1));
+
+    std::array::from_fn(|c| {
+        let top = p00[c] + (p10[c] - p00[c]) * fx;
+        let bottom = p01[c] + (p11[c] - p01[c]) * fx;
+        top + (bottom - top) * fy
+    })
+}
+
 pub struct LightLeakStage;
 
 impl PipelineStage for LightLeakStage {
@@ -60,7 +273,7 @@ impl PipelineStage for LightLeakStage {
         let height = image.height() as f32;
         let min_dim = width.min(height);
 
-        for leak in &context.config.light_leak.leaks {
+        for leak in &context.config.light_leak.leaks_at_time() {
             let center_x = leak.position.0 * width;
             let center_y = leak.position.1 * height;
             let radius_px = leak.radius * min_dim;
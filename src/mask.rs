@@ -0,0 +1,204 @@
+//! Selective develop: blend the film look into the original by a mask.
+//!
+//! The mask is computed from the original pixels, either from their
+//! luminance or from their hue, with soft edges. Where it is 1 the output is
+//! the develop, where it is 0 the original, e.g. to keep skin tones as shot
+//! while the rest of the frame takes on a saturated slide stock.
+
+use crate::physics::linear_to_srgb;
+use image::{ImageBuffer, Rgb};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Pixels a [`MaskConfig`] selects.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MaskRange {
+    /// Rec. 709 luminance of the encoded original, from `min` to `max` in
+    /// 0.0-1.0.
+    Luminance { min: f32, max: f32 },
+    /// Hue in degrees, from `min` counterclockwise to `max`; `min > max`
+    /// wraps through red at 0°. Near-neutral pixels have no hue and are not
+    /// selected.
+    Hue { min: f32, max: f32 },
+}
+
+/// Where the develop replaces the original, see the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MaskConfig {
+    pub range: MaskRange,
+    /// Width of the soft edge outside each end of the range, in the
+    /// range's units (0.0-1.0 luminance or degrees of hue). 0.0 is a hard
+    /// edge.
+    #[serde(default)]
+    pub feather: f32,
+    /// Develop everything outside the range instead.
+    #[serde(default)]
+    pub invert: bool,
+}
+
+/// Saturation (HSV) below which a pixel counts as neutral for hue masks,
+/// fading in over the same width again.
+const NEUTRAL_SATURATION: f32 = 0.05;
+
+impl MaskConfig {
+    /// Mask of a luminance range.
+    pub fn luminance(min: f32, max: f32, feather: f32) -> Self {
+        Self {
+            range: MaskRange::Luminance { min, max },
+            feather,
+            invert: false,
+        }
+    }
+
+    /// Mask of a hue range in degrees.
+    pub fn hue(min: f32, max: f32, feather: f32) -> Self {
+        Self {
+            range: MaskRange::Hue { min, max },
+            feather,
+            invert: false,
+        }
+    }
+
+    /// Develop everything outside the range instead.
+    pub fn inverted(mut self) -> Self {
+        self.invert = !self.invert;
+        self
+    }
+
+    /// Mask value of an 8-bit original pixel: 1.0 to use the develop, 0.0 to
+    /// keep the original.
+    pub fn weight(&self, rgb: [u8; 3]) -> f32 {
+        let [r, g, b] = rgb.map(|v| v as f32 / 255.0);
+        let feather = self.feather.max(0.0);
+        let weight = match self.range {
+            MaskRange::Luminance { min, max } => {
+                let luma = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+                let outside = (min - luma).max(luma - max).max(0.0);
+                falloff(outside, feather)
+            }
+            MaskRange::Hue { min, max } => {
+                let (hue, saturation) = hue_saturation(r, g, b);
+                let chroma = smoothstep(NEUTRAL_SATURATION, 2.0 * NEUTRAL_SATURATION, saturation);
+                falloff(hue_distance(hue, min, max), feather) * chroma
+            }
+        };
+        if self.invert {
+            1.0 - weight
+        } else {
+            weight
+        }
+    }
+
+    /// Blend a linear RGB buffer toward `original`, the linearized input at
+    /// the same size, where the mask is below 1. The mask reads the original
+    /// sRGB-encoded, like [`MaskConfig::weight`]. Does nothing if their sizes
+    /// differ.
+    pub fn blend_linear(&self, linear_buf: &mut [f32], original: &ImageBuffer<Rgb<f32>, Vec<f32>>) {
+        if linear_buf.len() != original.len() {
+            return;
+        }
+        linear_buf
+            .par_chunks_mut(3)
+            .zip(original.as_raw().par_chunks(3))
+            .for_each(|(out, src)| {
+                let encoded = std::array::from_fn(|c| {
+                    (linear_to_srgb(src[c]).clamp(0.0, 1.0) * 255.0).round() as u8
+                });
+                let w = self.weight(encoded);
+                for (o, &s) in out.iter_mut().zip(src) {
+                    *o = *o * w + s * (1.0 - w);
+                }
+            });
+    }
+}
+
+/// 1.0 inside the range, easing to 0.0 at `feather` outside it.
+fn falloff(outside: f32, feather: f32) -> f32 {
+    if outside <= 0.0 {
+        1.0
+    } else if feather <= 0.0 {
+        0.0
+    } else {
+        1.0 - smoothstep(0.0, feather, outside)
+    }
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// HSV hue in degrees and saturation of an RGB color.
+fn hue_saturation(r: f32, g: f32, b: f32) -> (f32, f32) {
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    if delta <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (hue, delta / max)
+}
+
+/// Degrees from `hue` to the nearest end of the arc from `min` to `max`,
+/// or 0.0 inside it.
+fn hue_distance(hue: f32, min: f32, max: f32) -> f32 {
+    let (min, max) = (min.rem_euclid(360.0), max.rem_euclid(360.0));
+    let span = (max - min).rem_euclid(360.0);
+    if (hue - min).rem_euclid(360.0) <= span {
+        return 0.0;
+    }
+    let below = (min - hue).rem_euclid(360.0);
+    let above = (hue - max).rem_euclid(360.0);
+    below.min(above)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luminance_mask_feathers_its_edges() {
+        let mask = MaskConfig::luminance(0.4, 0.6, 0.1);
+        assert_eq!(mask.weight([128, 128, 128]), 1.0);
+        assert_eq!(mask.weight([255, 255, 255]), 0.0);
+        // Halfway through the feather
+        let edge = mask.weight([166, 166, 166]);
+        assert!(edge > 0.3 && edge < 0.7, "{edge}");
+        assert_eq!(mask.inverted().weight([128, 128, 128]), 0.0);
+    }
+
+    #[test]
+    fn test_hue_mask_wraps_through_red() {
+        let reds = MaskConfig::hue(330.0, 30.0, 0.0);
+        assert_eq!(reds.weight([255, 0, 0]), 1.0);
+        assert_eq!(reds.weight([255, 0, 64]), 1.0);
+        assert_eq!(reds.weight([0, 0, 255]), 0.0);
+        // Neutrals have no hue
+        assert_eq!(reds.weight([128, 128, 128]), 0.0);
+
+        let feathered = MaskConfig::hue(330.0, 30.0, 30.0);
+        let orange = feathered.weight([255, 192, 0]);
+        assert!(orange > 0.0 && orange < 1.0, "{orange}");
+    }
+
+    #[test]
+    fn test_blend_linear_keeps_the_original_outside_the_range() {
+        let original = ImageBuffer::from_fn(2, 1, |x, _| {
+            if x == 0 {
+                Rgb([0.01, 0.01, 0.01])
+            } else {
+                Rgb([0.8, 0.8, 0.8])
+            }
+        });
+        let mut developed = vec![0.2, 0.05, 0.0, 0.2, 0.05, 0.0];
+        MaskConfig::luminance(0.5, 1.0, 0.0).blend_linear(&mut developed, &original);
+        // Shadows keep the original, highlights the develop
+        assert_eq!(developed, [0.01, 0.01, 0.01, 0.2, 0.05, 0.0]);
+    }
+}
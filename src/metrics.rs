@@ -1,3 +1,7 @@
+use crate::film::FilmStock;
+use crate::physics;
+use crate::processor::{density_map, SimulationConfig};
+use crate::utils::resize_to_fit_linear;
 use image::RgbImage;
 use palette::{FromColor, Lab, Srgb};
 use rustfft::{num_complex::Complex, FftPlanner};
@@ -96,12 +100,201 @@ pub struct FilmMetrics {
     // Perceptual / Structure
     pub ssim: Option<f32>, // Needs reference
 
+    // Film
+    #[serde(default)]
+    pub density_usage: Option<DensityUsage>, // Needs stock
+
     // Raw Data
     #[serde(with = "hist_serde")]
     pub hist_rgb: [[u32; 256]; 3],
 }
 
+/// Axis [`FilmMetrics::histogram`] bins on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HistogramAxis {
+    /// 8-bit code values, as stored. sRGB encoding spreads the shadows over
+    /// many of the bins.
+    #[default]
+    Encoded,
+    /// Linear light, 0 to 1 in equal steps.
+    Linear,
+    /// Stops below white, over [`LOG_EXPOSURE_STOPS`]. Shows where the
+    /// scene sits on the film curve.
+    LogExposure,
+}
+
+/// Stops below white covered by [`HistogramAxis::LogExposure`].
+pub const LOG_EXPOSURE_STOPS: f32 = 12.0;
+
+impl HistogramAxis {
+    pub const ALL: [HistogramAxis; 3] = [Self::Encoded, Self::Linear, Self::LogExposure];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Encoded => "Encoded",
+            Self::Linear => "Linear",
+            Self::LogExposure => "Log Exposure",
+        }
+    }
+
+    /// Bin of an 8-bit sRGB code value on this axis. Black falls in the
+    /// first bin of the log axis.
+    pub fn bin(self, code: u8) -> usize {
+        let linear = || physics::srgb_to_linear(code as f32 / 255.0);
+        let position = match self {
+            Self::Encoded => return code as usize,
+            Self::Linear => linear(),
+            Self::LogExposure => (linear().log2() + LOG_EXPOSURE_STOPS) / LOG_EXPOSURE_STOPS,
+        };
+        (position.clamp(0.0, 1.0) * 255.0).round() as usize
+    }
+
+    /// Value of a bin in the axis' own units: code value, linear light or
+    /// stops relative to white.
+    pub fn bin_value(self, bin: usize) -> f32 {
+        let t = bin as f32 / 255.0;
+        match self {
+            Self::Encoded => bin as f32,
+            Self::Linear => t,
+            Self::LogExposure => (t - 1.0) * LOG_EXPOSURE_STOPS,
+        }
+    }
+}
+
+/// Kernel of [`smooth_histogram`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HistogramSmoothing {
+    #[default]
+    None,
+    /// Plain average over the window.
+    Box,
+    /// Gaussian weights, with sigma half the radius.
+    Gaussian,
+}
+
+impl HistogramSmoothing {
+    pub const ALL: [HistogramSmoothing; 3] = [Self::None, Self::Box, Self::Gaussian];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "No Smoothing",
+            Self::Box => "Box",
+            Self::Gaussian => "Gaussian",
+        }
+    }
+}
+
+/// Smooth a 256-bin histogram over `radius` bins on each side.
+///
+/// The first and last bins hold the clipped pixels, so they are kept as
+/// they are and left out of their neighbors' windows. Otherwise a clipping
+/// spike would be smeared into a ramp that looks like real tones.
+pub fn smooth_histogram(hist: &[u32; 256], kernel: HistogramSmoothing, radius: u32) -> [f64; 256] {
+    let mut out = hist.map(|v| v as f64);
+    let radius = radius as i32;
+    if kernel == HistogramSmoothing::None || radius == 0 {
+        return out;
+    }
+    let sigma = (radius as f64 / 2.0).max(0.5);
+    let weight = |d: i32| match kernel {
+        HistogramSmoothing::Gaussian => (-((d * d) as f64) / (2.0 * sigma * sigma)).exp(),
+        _ => 1.0,
+    };
+    for (i, bin) in out.iter_mut().enumerate().take(255).skip(1) {
+        let (mut sum, mut total) = (0.0, 0.0);
+        for j in (i as i32 - radius).max(1)..=(i as i32 + radius).min(254) {
+            let w = weight(j - i as i32);
+            sum += w * hist[j as usize] as f64;
+            total += w;
+        }
+        *bin = sum / total;
+    }
+    out
+}
+
+/// Rectangle of an image in pixels, for [`FilmMetrics::analyze_roi`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoiRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl RoiRect {
+    /// The whole of `img`.
+    pub fn full(img: &RgbImage) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: img.width(),
+            height: img.height(),
+        }
+    }
+
+    /// Rectangle from normalized (0-1) edges of a `width` x `height` image,
+    /// at least one pixel in each direction.
+    pub fn from_normalized(
+        left: f32,
+        top: f32,
+        right: f32,
+        bottom: f32,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let to_px = |v: f32, size: u32| (v.clamp(0.0, 1.0) * size as f32).round() as u32;
+        let x = to_px(left.min(right), width).min(width.saturating_sub(1));
+        let y = to_px(top.min(bottom), height).min(height.saturating_sub(1));
+        Self {
+            x,
+            y,
+            width: (to_px(left.max(right), width).saturating_sub(x)).max(1),
+            height: (to_px(top.max(bottom), height).saturating_sub(y)).max(1),
+        }
+    }
+
+    /// The part of the rectangle inside a `width` x `height` image.
+    pub fn clamped(self, width: u32, height: u32) -> Self {
+        let x = self.x.min(width);
+        let y = self.y.min(height);
+        Self {
+            x,
+            y,
+            width: self.width.min(width - x),
+            height: self.height.min(height - y),
+        }
+    }
+}
+
 impl FilmMetrics {
+    /// [`FilmMetrics::analyze`] of the part of `img` inside `rect`, e.g. to
+    /// meter skin or sky on their own. The rectangle is clipped to the image;
+    /// an empty one gives [`FilmMetrics::empty`].
+    pub fn analyze_roi(img: &RgbImage, rect: RoiRect) -> Self {
+        let rect = rect.clamped(img.width(), img.height());
+        if rect.width == 0 || rect.height == 0 {
+            return Self::empty();
+        }
+        if rect == RoiRect::full(img) {
+            return Self::analyze(img);
+        }
+        let region =
+            image::imageops::crop_imm(img, rect.x, rect.y, rect.width, rect.height).to_image();
+        Self::analyze(&region)
+    }
+
+    /// `hist_rgb` binned on `axis`. Each code value lands in a single bin,
+    /// so the counts are exact, but the stretched end of the axis has gaps.
+    pub fn histogram(&self, axis: HistogramAxis) -> [[u32; 256]; 3] {
+        let mut out = [[0u32; 256]; 3];
+        for (hist, rebinned) in self.hist_rgb.iter().zip(&mut out) {
+            for (code, &count) in hist.iter().enumerate() {
+                rebinned[axis.bin(code as u8)] += count;
+            }
+        }
+        out
+    }
+
     pub fn analyze(img: &RgbImage) -> Self {
         let count = (img.width() * img.height()) as f32;
         if count == 0.0 {
@@ -169,14 +362,14 @@ impl FilmMetrics {
         let lab_mean = [sum_lab[0] / count, sum_lab[1] / count, sum_lab[2] / count];
         let lab_std = [
             ((sq_sum_lab[0] / count) - (lab_mean[0] * lab_mean[0]))
-                .sqrt()
-                .max(0.0),
+                .max(0.0)
+                .sqrt(),
             ((sq_sum_lab[1] / count) - (lab_mean[1] * lab_mean[1]))
-                .sqrt()
-                .max(0.0),
+                .max(0.0)
+                .sqrt(),
             ((sq_sum_lab[2] / count) - (lab_mean[2] * lab_mean[2]))
-                .sqrt()
-                .max(0.0),
+                .max(0.0)
+                .sqrt(),
         ];
 
         let sat_mean = sum_sat / count;
@@ -274,10 +467,24 @@ impl FilmMetrics {
             lbp_hist: lbp,
             glcm_stats: glcm,
             ssim: None,
+            density_usage: None,
             hist_rgb,
         }
     }
 
+    /// [`FilmMetrics::analyze`] of the developed `img`, plus how `source`, the
+    /// scene it was developed from, maps onto the stock's density range.
+    pub fn analyze_with_stock(
+        img: &RgbImage,
+        source: &RgbImage,
+        film: &FilmStock,
+        config: &SimulationConfig,
+    ) -> Self {
+        let mut metrics = Self::analyze(img);
+        metrics.density_usage = DensityUsage::measure(source, film, config);
+        metrics
+    }
+
     pub fn empty() -> Self {
         Self {
             mean_rgb: [0.0; 3],
@@ -301,6 +508,7 @@ impl FilmMetrics {
             lbp_hist: [0.0; 10],
             glcm_stats: [0.0; 4],
             ssim: None,
+            density_usage: None,
             hist_rgb: [[0; 256]; 3],
         }
     }
@@ -417,11 +625,296 @@ fn calculate_cct_tint(img: &RgbImage) -> (f32, f32) {
     // McCamy's Formula
     let n: f32 = (xe - 0.3320) / (0.1858 - ye);
     let cct = 449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33;
+    // Undefined where the formula's epicenter line is crossed
+    if !cct.is_finite() {
+        return (0.0, ye);
+    }
 
     (cct, ye)
 }
 
-#[allow(dead_code)]
+/// Longest side a source is reduced to before measuring [`DensityUsage`].
+const DENSITY_USAGE_SIZE: u32 = 256;
+
+/// How a scene's densities sit inside the stock's `d_min`..`d_max` range.
+///
+/// All values are fractions of the stock's mean density range, measured on the
+/// 1st and 99th percentile of the per-pixel mean density.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DensityUsage {
+    /// Share of the density range the scene spans.
+    pub utilization: f32,
+    /// Distance from the brightest highlights to `d_max`.
+    pub highlight_headroom: f32,
+    /// Distance from the deepest shadows down to `d_min`.
+    pub shadow_headroom: f32,
+}
+
+impl DensityUsage {
+    /// Develops `source` with `film` and measures its densities, or `None` for
+    /// an empty image.
+    pub fn measure(source: &RgbImage, film: &FilmStock, config: &SimulationConfig) -> Option<Self> {
+        if source.width() == 0 || source.height() == 0 {
+            return None;
+        }
+        let small = resize_to_fit_linear(
+            source,
+            DENSITY_USAGE_SIZE,
+            image::imageops::FilterType::Triangle,
+        );
+        let mut densities: Vec<f32> = density_map(&small, film, config)
+            .pixels()
+            .map(|p| (p[0] + p[1] + p[2]) / 3.0)
+            .collect();
+        densities.sort_by(f32::total_cmp);
+        let percentile = |q: f32| densities[((densities.len() - 1) as f32 * q).round() as usize];
+        let (low, high) = (percentile(0.01), percentile(0.99));
+
+        let curves = [&film.r_curve, &film.g_curve, &film.b_curve];
+        let d_min = curves.iter().map(|c| c.d_min).sum::<f32>() / 3.0;
+        let d_max = curves.iter().map(|c| c.d_max).sum::<f32>() / 3.0;
+        let range = (d_max - d_min).max(1e-3);
+
+        Some(Self {
+            utilization: ((high - low) / range).clamp(0.0, 1.0),
+            highlight_headroom: ((d_max - high) / range).clamp(0.0, 1.0),
+            shadow_headroom: ((low - d_min) / range).clamp(0.0, 1.0),
+        })
+    }
+}
+
+/// Bins along the waveform's luma axis and along each vectorscope axis.
+pub const SCOPE_BINS: usize = 256;
+
+/// Luma waveform: the distribution of Rec. 709 luma in each column band.
+///
+/// Not part of [`FilmMetrics`], since it is only needed for display and
+/// costs a pass over every pixel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Waveform {
+    /// Number of column bands across the image.
+    pub columns: usize,
+    /// `columns * SCOPE_BINS` pixel counts. Band `c` is
+    /// `bins[c * SCOPE_BINS..(c + 1) * SCOPE_BINS]`, indexed by 8-bit luma.
+    pub bins: Vec<u32>,
+}
+
+impl Waveform {
+    /// Waveform of `img` in `columns` bands of equal width.
+    pub fn measure(img: &RgbImage, columns: usize) -> Self {
+        let columns = columns.max(1);
+        let mut bins = vec![0u32; columns * SCOPE_BINS];
+        let width = img.width().max(1) as usize;
+        for (x, _, p) in img.enumerate_pixels() {
+            let column = x as usize * columns / width;
+            let luma = 0.2126 * p[0] as f32 + 0.7152 * p[1] as f32 + 0.0722 * p[2] as f32;
+            bins[column * SCOPE_BINS + luma.round().min(255.0) as usize] += 1;
+        }
+        Self { columns, bins }
+    }
+
+    /// Luma distribution of band `c`.
+    pub fn column(&self, c: usize) -> &[u32] {
+        &self.bins[c * SCOPE_BINS..(c + 1) * SCOPE_BINS]
+    }
+}
+
+/// Vectorscope: 2D histogram of Rec. 709 Cb/Cr chroma.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Vectorscope {
+    /// `SCOPE_BINS * SCOPE_BINS` pixel counts, row by Cr and column by Cb.
+    /// Both map -0.5..=0.5 to 0..SCOPE_BINS, so neutral pixels land in the
+    /// middle.
+    pub bins: Vec<u32>,
+}
+
+impl Vectorscope {
+    pub fn measure(img: &RgbImage) -> Self {
+        let mut bins = vec![0u32; SCOPE_BINS * SCOPE_BINS];
+        for p in img.pixels() {
+            let (cb, cr) = Self::chroma([p[0], p[1], p[2]]);
+            bins[Self::bin(cr) * SCOPE_BINS + Self::bin(cb)] += 1;
+        }
+        Self { bins }
+    }
+
+    /// Rec. 709 Cb and Cr of an 8-bit color, each in -0.5..=0.5.
+    pub fn chroma(rgb: [u8; 3]) -> (f32, f32) {
+        let [r, g, b] = rgb.map(|v| v as f32 / 255.0);
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        ((b - y) / 1.8556, (r - y) / 1.5748)
+    }
+
+    /// Bin of a chroma value in -0.5..=0.5.
+    pub fn bin(v: f32) -> usize {
+        ((v + 0.5) * (SCOPE_BINS - 1) as f32)
+            .round()
+            .clamp(0.0, (SCOPE_BINS - 1) as f32) as usize
+    }
+
+    /// Pixel count at the given Cb and Cr.
+    pub fn at(&self, cb: f32, cr: f32) -> u32 {
+        self.bins[Self::bin(cr) * SCOPE_BINS + Self::bin(cb)]
+    }
+}
+
+/// Difference between an image and a reference of the same size.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImageDiff {
+    /// Mean CIE76 ΔE over a 4-pixel grid.
+    pub mean_delta_e: f32,
+    /// Largest difference of any channel of any pixel, in 8-bit steps.
+    pub max_channel_diff: u8,
+}
+
+/// Compare `img` with `reference`. Returns None if their sizes differ.
+pub fn compare(img: &RgbImage, reference: &RgbImage) -> Option<ImageDiff> {
+    if img.dimensions() != reference.dimensions() {
+        return None;
+    }
+    let max_channel_diff = img
+        .as_raw()
+        .iter()
+        .zip(reference.as_raw())
+        .map(|(a, b)| a.abs_diff(*b))
+        .max()
+        .unwrap_or(0);
+    Some(ImageDiff {
+        mean_delta_e: calculate_delta_e(img, reference),
+        max_channel_diff,
+    })
+}
+
+/// Stops of mean output luminance per stop of exposure, about what the
+/// default develop gives: the film's shoulder and toe compress the tones.
+const MATCH_EXPOSURE_RESPONSE: f32 = 0.7;
+
+/// Change of the output's mean `ln(R/B)` per unit of
+/// [`SimulationConfig::warmth`], about what the default develop gives.
+const MATCH_WARMTH_LOG_RB: f32 = 0.07;
+
+/// Largest reference aspect ratio mismatch still taken as the same frame.
+const MATCH_FRAME_ASPECT_TOLERANCE: f32 = 0.01;
+
+/// How a developed image differs from a reference look. Deltas are the
+/// result minus the reference.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TargetMatch {
+    /// Difference of the mean CIE Lab L*, a* and b*.
+    pub lab_delta: [f32; 3],
+    /// Difference of the L* standard deviation, a contrast measure.
+    pub contrast_delta: f32,
+    /// Difference of the mean Lab chroma.
+    pub saturation_delta: f32,
+    /// Difference of the correlated color temperature, in kelvin.
+    pub cct_delta: f32,
+    /// Difference of the tint.
+    pub tint_delta: f32,
+    /// CIE76 ΔE between the two mean colors.
+    pub mean_delta_e: f32,
+    /// Pixel comparison, when the reference is the same frame.
+    pub frame_diff: Option<ImageDiff>,
+    /// Suggested changes toward the reference.
+    pub nudges: MatchNudges,
+}
+
+/// Parameter changes that move a develop toward a reference, from a
+/// [`TargetMatch`]. They are estimates: apply them, develop again and
+/// repeat to converge.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MatchNudges {
+    /// Exposure change in stops.
+    pub exposure_ev: f32,
+    /// Change of [`SimulationConfig::warmth`].
+    pub warmth: f32,
+    /// Factor on [`SimulationConfig::saturation`].
+    pub saturation_scale: f32,
+}
+
+impl TargetMatch {
+    /// Compare the metrics of a develop with those of a reference. The
+    /// pictures can differ; only their overall statistics are compared.
+    pub fn measure(result: &FilmMetrics, reference: &FilmMetrics) -> Self {
+        let lab_delta: [f32; 3] =
+            std::array::from_fn(|c| result.lab_mean[c] - reference.lab_mean[c]);
+        Self {
+            lab_delta,
+            contrast_delta: result.lab_std[0] - reference.lab_std[0],
+            saturation_delta: result.saturation_mean - reference.saturation_mean,
+            cct_delta: result.cct_tint.0 - reference.cct_tint.0,
+            tint_delta: result.cct_tint.1 - reference.cct_tint.1,
+            mean_delta_e: lab_delta.iter().map(|d| d * d).sum::<f32>().sqrt(),
+            frame_diff: None,
+            nudges: MatchNudges::between(result, reference),
+        }
+    }
+
+    /// [`TargetMatch::measure`] of two images, with
+    /// [`TargetMatch::with_frame`].
+    pub fn analyze(result: &RgbImage, reference: &RgbImage) -> Self {
+        Self::measure(
+            &FilmMetrics::analyze(result),
+            &FilmMetrics::analyze(reference),
+        )
+        .with_frame(result, reference)
+    }
+
+    /// Add the pixel comparison of [`compare`] when `reference` has the
+    /// aspect ratio of `result` and so is taken as the same frame. It is
+    /// resized to `result` first.
+    pub fn with_frame(mut self, result: &RgbImage, reference: &RgbImage) -> Self {
+        let (width, height) = result.dimensions();
+        let aspect = |(w, h): (u32, u32)| w as f32 / h.max(1) as f32;
+        let mismatch = aspect(reference.dimensions()) / aspect((width, height)) - 1.0;
+        self.frame_diff =
+            (width > 0 && height > 0 && mismatch.abs() <= MATCH_FRAME_ASPECT_TOLERANCE)
+                .then(|| {
+                    let reference = crate::utils::resize_linear(
+                        reference,
+                        width,
+                        height,
+                        crate::utils::PREVIEW_FILTER,
+                    );
+                    compare(result, &reference)
+                })
+                .flatten();
+        self
+    }
+}
+
+impl MatchNudges {
+    /// Exposure, warmth and saturation changes from the `result` metrics
+    /// toward the `reference`.
+    fn between(result: &FilmMetrics, reference: &FilmMetrics) -> Self {
+        // L* back to relative luminance
+        let luminance = |l: f32| {
+            if l > 8.0 {
+                ((l + 16.0) / 116.0).powi(3)
+            } else {
+                l / 903.3
+            }
+            .max(1e-4)
+        };
+        let exposure_ev = (luminance(reference.lab_mean[0]) / luminance(result.lab_mean[0])).log2()
+            / MATCH_EXPOSURE_RESPONSE;
+
+        let log_rb = |m: &FilmMetrics| (m.mean_rgb[0].max(1.0) / m.mean_rgb[2].max(1.0)).ln();
+        let warmth = (log_rb(reference) - log_rb(result)) / MATCH_WARMTH_LOG_RB;
+
+        let saturation_scale = if result.saturation_mean > 1e-3 {
+            reference.saturation_mean / result.saturation_mean
+        } else {
+            1.0
+        };
+
+        Self {
+            exposure_ev,
+            warmth,
+            saturation_scale,
+        }
+    }
+}
+
 fn calculate_delta_e(img1: &RgbImage, img2: &RgbImage) -> f32 {
     let mut sum_de = 0.0;
     let mut count = 0.0;
@@ -723,3 +1216,59 @@ fn calculate_psd_slope(img: &RgbImage) -> f32 {
         0.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_match_nudges_toward_the_reference() {
+        let input = RgbImage::from_fn(48, 32, |x, y| {
+            image::Rgb([(x * 5) as u8, (y * 7) as u8, ((x + y) * 3) as u8])
+        });
+        let film = crate::presets::kodak::KODAK_PORTRA_400();
+        let develop = |exposure_time: f32, warmth: f32, saturation: f32| {
+            let config = SimulationConfig {
+                exposure_time,
+                warmth,
+                saturation,
+                enable_grain: false,
+                ..Default::default()
+            };
+            crate::process_image(&input, &film, &config)
+        };
+        let reference = develop(1.0, 0.0, 1.0);
+        let nudges = |exposure, warmth, saturation| {
+            TargetMatch::analyze(&develop(exposure, warmth, saturation), &reference).nudges
+        };
+
+        // One setting off at a time, the nudge undoes it
+        let ev = nudges(0.5, 0.0, 1.0).exposure_ev;
+        assert!((ev - 1.0).abs() < 0.3, "exposure {ev}");
+        let warmth = nudges(1.0, -0.6, 1.0).warmth;
+        assert!((warmth - 0.6).abs() < 0.2, "warmth {warmth}");
+        let scale = nudges(1.0, 0.0, 0.6).saturation_scale;
+        assert!((scale * 0.6 - 1.0).abs() < 0.2, "saturation {scale}");
+
+        // The same frame is compared pixel by pixel as well
+        let target = TargetMatch::analyze(&develop(0.5, -0.6, 0.6), &reference);
+        assert!(target.lab_delta[0] < 0.0 && target.saturation_delta < 0.0);
+        assert!(target.frame_diff.unwrap().mean_delta_e > 0.0);
+        let cropped = image::imageops::crop_imm(&reference, 0, 0, 24, 32).to_image();
+        assert!(TargetMatch::analyze(&reference, &cropped)
+            .frame_diff
+            .is_none());
+
+        // With everything off, following the nudges closes the gap
+        let (mut exposure, mut warmth, mut saturation) = (0.5, -0.6, 0.6);
+        for _ in 0..3 {
+            let nudges =
+                TargetMatch::analyze(&develop(exposure, warmth, saturation), &reference).nudges;
+            exposure *= 2f32.powf(nudges.exposure_ev);
+            warmth += nudges.warmth;
+            saturation *= nudges.saturation_scale;
+        }
+        let closed = TargetMatch::analyze(&develop(exposure, warmth, saturation), &reference);
+        assert!(closed.mean_delta_e < target.mean_delta_e / 4.0);
+    }
+}
@@ -53,6 +53,16 @@ pub fn linear_to_srgb(v: f32) -> f32 {
     }
 }
 
+/// ITU-R BT.709 OETF: Linear Light to Rec.709.
+#[inline]
+pub fn linear_to_rec709(v: f32) -> f32 {
+    if v < 0.018 {
+        4.5 * v
+    } else {
+        1.099 * v.powf(0.45) - 0.099
+    }
+}
+
 /// Error function approximation (Abramowitz and Stegun 7.1.26)
 /// Maximum error: 1.5e-7
 #[inline]
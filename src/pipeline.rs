@@ -1,9 +1,14 @@
+use crate::dither::DitherMode;
 use crate::film::{FilmStock, FilmType};
+use crate::grain::{GrainSpace, GrainStructure, GRAIN_REFERENCE_PIXELS_PER_MM};
 use crate::physics;
-use crate::processor::{OutputMode, SimulationConfig, WhiteBalanceMode};
+use crate::processor::{
+    OutputMode, OutputTransfer, SimulationConfig, WhiteBalanceMode, WorkingSpace,
+};
 use crate::utils;
-use image::{ImageBuffer, Rgb, RgbImage};
+use image::{ImageBuffer, Luma, Rgb, RgbImage};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info, instrument};
 use wide::f32x4;
 
@@ -21,6 +26,75 @@ pub trait PipelineStage {
     fn process(&self, image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>, context: &PipelineContext);
 }
 
+/// A stage that can be listed in [`SimulationConfig::pipeline`].
+///
+/// Listed stages run in order on the linear image, after linearization and
+/// before the output conversion (saturation, vignette, sRGB), which also
+/// adds the grain of a [`StageKind::Grain`] listed after the develop. The GPU
+/// backend runs the leading stages it implements and hands the rest to the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StageKind {
+    /// Diffusion filter glow, see [`LensConfig::diffusion`].
+    ///
+    /// [`LensConfig::diffusion`]: crate::processor::LensConfig::diffusion
+    Diffusion,
+    LightLeak,
+    Halation,
+    MicroMotion,
+    ObjectMotion,
+    DepthOfField,
+    RotationalBlur,
+    Mtf,
+    ChromaticAberration,
+    /// Full-spectrum development through the film layer stack.
+    Develop,
+    /// Film grain. After [`StageKind::Develop`], as in the default pipeline,
+    /// it is added during the output conversion; before it, to the linear
+    /// scene image, see [`SceneGrainStage`]. Leave it out for no grain.
+    Grain,
+}
+
+impl StageKind {
+    /// CPU implementation of this stage.
+    pub fn cpu_stage(self) -> &'static dyn PipelineStage {
+        match self {
+            StageKind::Diffusion => &DiffusionStage,
+            StageKind::LightLeak => &crate::light_leak::LightLeakStage,
+            StageKind::Halation => &HalationStage,
+            StageKind::MicroMotion => &MicroMotionStage,
+            StageKind::ObjectMotion => &ObjectMotionStage,
+            StageKind::DepthOfField => &DepthOfFieldStage,
+            StageKind::RotationalBlur => &RotationalBlurStage,
+            StageKind::Mtf => &MtfStage,
+            StageKind::ChromaticAberration => &ChromaticAberrationStage,
+            StageKind::Develop => &crate::processor::AccurateDevelopStage,
+            StageKind::Grain => &SceneGrainStage,
+        }
+    }
+
+    /// Whether the GPU backend implements this stage.
+    pub fn has_gpu(self) -> bool {
+        matches!(
+            self,
+            StageKind::Diffusion
+                | StageKind::LightLeak
+                | StageKind::Halation
+                | StageKind::ChromaticAberration
+        )
+    }
+}
+
+/// Run `stages` in order on the CPU.
+pub fn run_stages(
+    image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>,
+    stages: &[StageKind],
+    context: &PipelineContext,
+) {
+    for stage in stages {
+        stage.cpu_stage().process(image, context);
+    }
+}
+
 /// # Linearize Stage (Initializer)
 ///
 /// Converts sRGB input image to Linear RGB f32 format.
@@ -54,6 +128,71 @@ pub fn create_linear_image(input: &RgbImage) -> ImageBuffer<Rgb<f32>, Vec<f32>>
     linear_image
 }
 
+/// The linear image the pipeline starts from: `input` decoded from sRGB
+/// with [`create_linear_image`], or, with
+/// [`SimulationConfig::input_is_linear`], only scaled to 0-1.
+pub(crate) fn linearize_input(
+    input: &RgbImage,
+    config: &SimulationConfig,
+) -> ImageBuffer<Rgb<f32>, Vec<f32>> {
+    if !config.input_is_linear {
+        return create_linear_image(input);
+    }
+    let pixels = input
+        .as_raw()
+        .par_iter()
+        .map(|&v| linearize_input_value(v, config))
+        .collect();
+    ImageBuffer::from_raw(input.width(), input.height(), pixels).unwrap()
+}
+
+/// One 8-bit input value in linear light, decoded like [`linearize_input`].
+pub(crate) fn linearize_input_value(value: u8, config: &SimulationConfig) -> f32 {
+    let value = value as f32 / 255.0;
+    if config.input_is_linear {
+        value
+    } else {
+        physics::srgb_to_linear(value)
+    }
+}
+
+/// [`linearize_input`] for single-channel 16-bit input, see
+/// [`create_linear_image_luma16`].
+pub(crate) fn linearize_luma16_input(
+    input: &ImageBuffer<Luma<u16>, Vec<u16>>,
+    config: &SimulationConfig,
+) -> ImageBuffer<Rgb<f32>, Vec<f32>> {
+    if !config.input_is_linear {
+        return create_linear_image_luma16(input);
+    }
+    let pixels = input
+        .as_raw()
+        .par_iter()
+        .flat_map_iter(|&v| [v as f32 / 65535.0; 3])
+        .collect();
+    ImageBuffer::from_raw(input.width(), input.height(), pixels).unwrap()
+}
+
+/// Linearizes single-channel 16-bit input, repeated on all three channels.
+///
+/// Grayscale scans keep their full precision instead of going through an
+/// 8-bit RGB conversion first.
+#[instrument(skip(input))]
+pub fn create_linear_image_luma16(
+    input: &ImageBuffer<Luma<u16>, Vec<u16>>,
+) -> ImageBuffer<Rgb<f32>, Vec<f32>> {
+    debug!("Converting single-channel input image to linear space");
+    let pixels: Vec<f32> = input
+        .as_raw()
+        .par_iter()
+        .flat_map_iter(|&v| {
+            let linear = physics::srgb_to_linear(v as f32 / 65535.0);
+            [linear; 3]
+        })
+        .collect();
+    ImageBuffer::from_raw(input.width(), input.height(), pixels).unwrap()
+}
+
 /// # Micro Motion Stage
 ///
 /// Simulates hand-held camera shake via 3D rotation in linear light space.
@@ -326,6 +465,46 @@ impl PipelineStage for VignettingStage {
     }
 }
 
+/// Glow radius of the diffusion filter (Gaussian sigma) as a fraction of the image width.
+pub(crate) const DIFFUSION_SIGMA: f32 = 0.02;
+/// Luminance at which half of a pixel's light feeds the diffusion glow.
+pub(crate) const DIFFUSION_KNEE: f32 = 0.25;
+
+/// # Diffusion Stage
+///
+/// Simulates a diffusion filter (Pro-Mist style) in front of the lens.
+/// Part of the light from the brighter areas is scattered into a soft glow
+/// around them. The scattered light is taken from where it came from, so
+/// highlights soften while the shadows right next to them lift.
+pub struct DiffusionStage;
+
+impl PipelineStage for DiffusionStage {
+    #[instrument(skip(self, image, context))]
+    fn process(&self, image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>, context: &PipelineContext) {
+        let strength = context.config.lens.diffusion.clamp(0.0, 1.0);
+        if strength <= 0.0 {
+            debug!("Diffusion disabled (strength <= 0)");
+            return;
+        }
+        info!("Applying Diffusion effect");
+
+        // Light each pixel sends into the glow, weighted toward the highlights
+        let mut scattered = image.clone();
+        scattered.par_chunks_mut(3).for_each(|p| {
+            let lum = (0.2126 * p[0] + 0.7152 * p[1] + 0.0722 * p[2]).max(0.0);
+            let weight = lum / (lum + DIFFUSION_KNEE);
+            p.iter_mut().for_each(|v| *v *= weight);
+        });
+        let mut glow = scattered.clone();
+        utils::apply_gaussian_blur(&mut glow, image.width() as f32 * DIFFUSION_SIGMA);
+
+        image
+            .par_iter_mut()
+            .zip(scattered.as_raw().par_iter().zip(glow.as_raw().par_iter()))
+            .for_each(|(v, (s, g))| *v = (*v + strength * (g - s)).max(0.0));
+    }
+}
+
 /// # Halation Stage
 ///
 /// Simulates light reflecting off the film base back into the emulsion.
@@ -336,7 +515,7 @@ impl PipelineStage for HalationStage {
     #[instrument(skip(self, image, context))]
     fn process(&self, image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>, context: &PipelineContext) {
         let film = context.film;
-        if film.halation_strength <= 0.0 {
+        if film.halation_strength <= 0.0 && film.halation_strength2 <= 0.0 {
             debug!("Halation disabled (strength <= 0)");
             return;
         }
@@ -344,74 +523,115 @@ impl PipelineStage for HalationStage {
 
         let width = image.width();
         let threshold = film.halation_threshold;
+
+        let saturation_weight = film.halation_saturation_weight;
         let mut halation_map = image.clone();
 
-        // Apply threshold
+        // Bright mask from luminance above threshold. The same value goes into
+        // every channel; the per-layer split happens at the blend below.
         halation_map.par_chunks_mut(3).for_each(|p| {
-            let lum = 0.2126 * p[0] + 0.7152 * p[1] + 0.0722 * p[2];
-            if lum < threshold {
-                p[0] = 0.0;
-                p[1] = 0.0;
-                p[2] = 0.0;
-            } else {
-                p[0] = (p[0] - threshold).max(0.0);
-                p[1] = (p[1] - threshold).max(0.0);
-                p[2] = (p[2] - threshold).max(0.0);
-            }
+            let excess = halation_mask([p[0], p[1], p[2]], threshold, saturation_weight);
+            p[0] = excess;
+            p[1] = excess;
+            p[2] = excess;
         });
 
-        let blur_sigma = width as f32 * film.halation_sigma;
-        utils::apply_gaussian_blur(&mut halation_map, blur_sigma);
-
-        let tint = film.halation_tint;
-        let strength = film.halation_strength;
-
-        let factor_r = tint[0] * strength;
-        let factor_g = tint[1] * strength;
-        let factor_b = tint[2] * strength;
+        // Core bloom plus the optional wide skirt, both from the same bright mask
+        let skirt_strength = if film.halation_sigma2 > 0.0 {
+            film.halation_strength2
+        } else {
+            0.0
+        };
+        let backing = film.halation_backing_gain();
+        let scales = [
+            (film.halation_sigma, film.halation_strength * backing),
+            (film.halation_sigma2, skirt_strength * backing),
+        ];
+        for (sigma, strength) in scales {
+            if strength <= 0.0 {
+                continue;
+            }
+            let mut glow = halation_map.clone();
+            utils::apply_gaussian_blur(&mut glow, width as f32 * sigma);
+            add_halation_glow(image, &glow, film, strength);
+        }
+    }
+}
 
-        // SIMD constants for RGBRGB... pattern
-        let v0 = f32x4::from([factor_r, factor_g, factor_b, factor_r]);
-        let v1 = f32x4::from([factor_g, factor_b, factor_r, factor_g]);
-        let v2 = f32x4::from([factor_b, factor_r, factor_g, factor_b]);
+/// Halation bright mask of one pixel: scene-linear Rec.709 luminance above
+/// `threshold`, see [`crate::film::FilmStock::halation_threshold`], reduced
+/// by `saturation_weight` times the pixel's HSV saturation so that colored
+/// lights bloom less than whites of the same luminance.
+/// Must match `bright_mask` in halation.wgsl.
+pub(crate) fn halation_mask(rgb: [f32; 3], threshold: f32, saturation_weight: f32) -> f32 {
+    let [r, g, b] = rgb;
+    let lum = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let excess = (lum - threshold).max(0.0);
+    let max = r.max(g).max(b);
+    let saturation = if max > 0.0 {
+        ((max - r.min(g).min(b)) / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    excess * (1.0 - saturation_weight * saturation)
+}
 
-        // Process 4 pixels (12 floats) at a time to align with SIMD lanes
-        image
-            .par_chunks_mut(12)
-            .zip(halation_map.par_chunks(12))
-            .for_each(|(dest, src)| {
-                if dest.len() == 12 {
-                    // SIMD Path
-                    let d0_arr: [f32; 4] = dest[0..4].try_into().unwrap();
-                    let s0_arr: [f32; 4] = src[0..4].try_into().unwrap();
-                    let d0 = f32x4::from(d0_arr);
-                    let s0 = f32x4::from(s0_arr);
-                    let r0 = d0 + s0 * v0;
-                    dest[0..4].copy_from_slice(&<[f32; 4]>::from(r0));
-
-                    let d1_arr: [f32; 4] = dest[4..8].try_into().unwrap();
-                    let s1_arr: [f32; 4] = src[4..8].try_into().unwrap();
-                    let d1 = f32x4::from(d1_arr);
-                    let s1 = f32x4::from(s1_arr);
-                    let r1 = d1 + s1 * v1;
-                    dest[4..8].copy_from_slice(&<[f32; 4]>::from(r1));
-
-                    let d2_arr: [f32; 4] = dest[8..12].try_into().unwrap();
-                    let s2_arr: [f32; 4] = src[8..12].try_into().unwrap();
-                    let d2 = f32x4::from(d2_arr);
-                    let s2 = f32x4::from(s2_arr);
-                    let r2 = d2 + s2 * v2;
-                    dest[8..12].copy_from_slice(&<[f32; 4]>::from(r2));
-                } else {
-                    // Scalar Fallback
-                    for (d, s) in dest.chunks_mut(3).zip(src.chunks(3)) {
-                        d[0] += s[0] * factor_r;
-                        d[1] += s[1] * factor_g;
-                        d[2] += s[2] * factor_b;
-                    }
+/// Add a blurred halation mask to the image, split across layers by tint and channel gain.
+fn add_halation_glow(
+    image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>,
+    halation_map: &ImageBuffer<Rgb<f32>, Vec<f32>>,
+    film: &FilmStock,
+    strength: f32,
+) {
+    let tint = film.halation_tint;
+    let gain = film.halation_channel_gain;
+
+    // Reflected light mostly re-exposes the red-sensitive layer
+    let factor_r = tint[0] * gain[0] * strength;
+    let factor_g = tint[1] * gain[1] * strength;
+    let factor_b = tint[2] * gain[2] * strength;
+
+    // SIMD constants for RGBRGB... pattern
+    let v0 = f32x4::from([factor_r, factor_g, factor_b, factor_r]);
+    let v1 = f32x4::from([factor_g, factor_b, factor_r, factor_g]);
+    let v2 = f32x4::from([factor_b, factor_r, factor_g, factor_b]);
+
+    // Process 4 pixels (12 floats) at a time to align with SIMD lanes
+    image
+        .par_chunks_mut(12)
+        .zip(halation_map.par_chunks(12))
+        .for_each(|(dest, src)| {
+            if dest.len() == 12 {
+                // SIMD Path
+                let d0_arr: [f32; 4] = dest[0..4].try_into().unwrap();
+                let s0_arr: [f32; 4] = src[0..4].try_into().unwrap();
+                let d0 = f32x4::from(d0_arr);
+                let s0 = f32x4::from(s0_arr);
+                let r0 = d0 + s0 * v0;
+                dest[0..4].copy_from_slice(&<[f32; 4]>::from(r0));
+
+                let d1_arr: [f32; 4] = dest[4..8].try_into().unwrap();
+                let s1_arr: [f32; 4] = src[4..8].try_into().unwrap();
+                let d1 = f32x4::from(d1_arr);
+                let s1 = f32x4::from(s1_arr);
+                let r1 = d1 + s1 * v1;
+                dest[4..8].copy_from_slice(&<[f32; 4]>::from(r1));
+
+                let d2_arr: [f32; 4] = dest[8..12].try_into().unwrap();
+                let s2_arr: [f32; 4] = src[8..12].try_into().unwrap();
+                let d2 = f32x4::from(d2_arr);
+                let s2 = f32x4::from(s2_arr);
+                let r2 = d2 + s2 * v2;
+                dest[8..12].copy_from_slice(&<[f32; 4]>::from(r2));
+            } else {
+                // Scalar Fallback
+                for (d, s) in dest.chunks_mut(3).zip(src.chunks(3)) {
+                    d[0] += s[0] * factor_r;
+                    d[1] += s[1] * factor_g;
+                    d[2] += s[2] * factor_b;
                 }
-            });
-    }
+            }
+        });
 }
 
 /// # Depth of Field Stage
@@ -655,50 +875,71 @@ impl PipelineStage for RotationalBlurStage {
 
 /// # MTF (Modulation Transfer Function) Stage
 ///
-/// Simulates optical softness based on the film's resolving power (lp/mm).
-/// Applied before grain to simulate the physical blurring of the image.
+/// Simulates the softness of the emulsion from its resolving power
+/// ([`FilmStock::resolution_lp_mm`]). The film's MTF is taken as a Gaussian
+/// that falls to 10% at the rated resolution, which sets its width in mm;
+/// the film format's pixels per mm turn that into pixels. A 50 lp/mm stock is
+/// then visibly softer than a 200 lp/mm one at the same output size, and
+/// both sharpen up relative to the picture on larger formats.
+/// Applied before grain, which sits on top of the image in the emulsion.
 pub struct MtfStage;
 
+/// Blur below this sigma in pixels is invisible and skipped.
+const MTF_MIN_SIGMA: f32 = 0.1;
+
+impl MtfStage {
+    /// Gaussian sigma in mm of film for a resolving power of `lp_mm`.
+    fn sigma_mm(lp_mm: f32) -> f32 {
+        // exp(-2π²σ²f²) = 0.1 at the resolution limit f
+        (std::f32::consts::LN_10 / 2.0).sqrt() / (std::f32::consts::PI * lp_mm.max(1.0))
+    }
+}
+
 impl PipelineStage for MtfStage {
     #[instrument(skip(self, image, context))]
     fn process(&self, image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>, context: &PipelineContext) {
         let film = context.film;
-        let width = image.width();
-        let height = image.height();
-
-        let pixels_per_mm = width as f32 / 36.0;
-        let mtf_sigma = (0.5 / film.resolution_lp_mm) * pixels_per_mm;
+        let (width, height) = image.dimensions();
+        let pixels_per_mm = film.film_format.pixels_per_mm(width, height);
+        let sigma = Self::sigma_mm(film.resolution_lp_mm) * pixels_per_mm;
 
-        if mtf_sigma <= 0.5 {
-            debug!("MTF blur skipped (sigma too small: {:.2})", mtf_sigma);
+        if sigma < MTF_MIN_SIGMA || width == 0 || height == 0 {
+            debug!("MTF blur skipped (sigma too small: {:.2})", sigma);
             return;
         }
-
-        info!("Applying radial MTF blur (edge sigma: {:.2})", mtf_sigma);
-
-        // Blur a copy at full MTF sigma
-        let mut blurred = image.clone();
-        utils::apply_gaussian_blur(&mut blurred, mtf_sigma);
-
-        // Blend: center = sharp (original), edges = blurred
-        let cx = width as f32 / 2.0;
-        let cy = height as f32 / 2.0;
-        let r_max = (cx * cx + cy * cy).sqrt();
-
-        image
-            .par_chunks_mut(3)
-            .zip(blurred.par_chunks(3))
-            .enumerate()
-            .for_each(|(i, (orig, blur))| {
-                let x = (i as u32 % width) as f32 + 0.5 - cx;
-                let y = (i as u32 / width) as f32 + 0.5 - cy;
-                let r = (x * x + y * y).sqrt() / r_max;
-                // Quadratic falloff: center=0, corner=1
-                let t = (r * r).clamp(0.0, 1.0);
-                orig[0] = orig[0] * (1.0 - t) + blur[0] * t;
-                orig[1] = orig[1] * (1.0 - t) + blur[1] * t;
-                orig[2] = orig[2] * (1.0 - t) + blur[2] * t;
+        info!("Applying MTF blur (sigma: {:.2})", sigma);
+
+        // The kernel is a few pixels at most, so sample it exactly rather
+        // than with the box approximation, which cannot go below 1.4 px
+        let radius = (3.0 * sigma).ceil() as i32;
+        let weights: Vec<f32> = (-radius..=radius)
+            .map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp())
+            .collect();
+        let total: f32 = weights.iter().sum();
+        let weights: Vec<f32> = weights.iter().map(|w| w / total).collect();
+
+        let (w, h) = (width as i32, height as i32);
+        let blur_pass = |src: &[f32], dst: &mut [f32], horizontal: bool| {
+            dst.par_chunks_mut(3).enumerate().for_each(|(i, px)| {
+                let (x, y) = (i as i32 % w, i as i32 / w);
+                let mut sum = [0.0f32; 3];
+                for (offset, weight) in (-radius..=radius).zip(&weights) {
+                    let (sx, sy) = if horizontal {
+                        ((x + offset).clamp(0, w - 1), y)
+                    } else {
+                        (x, (y + offset).clamp(0, h - 1))
+                    };
+                    let j = (sy * w + sx) as usize * 3;
+                    for c in 0..3 {
+                        sum[c] += src[j + c] * weight;
+                    }
+                }
+                px.copy_from_slice(&sum);
             });
+        };
+        let mut pass = vec![0.0f32; image.len()];
+        blur_pass(image.as_raw(), &mut pass, true);
+        blur_pass(&pass, image, false);
     }
 }
 
@@ -706,19 +947,25 @@ impl PipelineStage for MtfStage {
 ///
 /// Simulates lateral chromatic aberration: R channel slightly magnified,
 /// B channel slightly demagnified relative to G. Produces RGB fringing at edges.
+/// The amount is [`LensConfig::chromatic_aberration`]; off by default.
+///
+/// [`LensConfig::chromatic_aberration`]: crate::processor::LensConfig::chromatic_aberration
 pub struct ChromaticAberrationStage;
 
 impl PipelineStage for ChromaticAberrationStage {
-    #[instrument(skip(self, image, _context))]
-    fn process(&self, image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>, _context: &PipelineContext) {
+    #[instrument(skip(self, image, context))]
+    fn process(&self, image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>, context: &PipelineContext) {
+        let lens = &context.config.lens;
+        if lens.chromatic_aberration == 0.0 {
+            return;
+        }
         let width = image.width() as usize;
         let height = image.height() as usize;
         let cx = width as f32 / 2.0;
         let cy = height as f32 / 2.0;
 
         // Scale factors: R slightly larger, B slightly smaller
-        let r_scale = 1.0015f32; // R magnified
-        let b_scale = 0.9985f32; // B demagnified
+        let (r_scale, b_scale) = lens.channel_scales();
 
         info!(
             "Applying chromatic aberration (R×{}, B×{})",
@@ -794,6 +1041,10 @@ impl PipelineStage for DevelopStage {
         // Precompute Spectral Matrix (3x3)
         // Maps Linear RGB -> Film Layer Exposure directly
         let spectral_matrix = film.compute_spectral_matrix();
+        // BW stocks are exposed by the scene's luminance, through the filter
+        let luma_weights = film
+            .is_monochrome()
+            .then(|| config.bw_filter.apply(film.bw_luminance_weights()));
 
         let apply_matrix = |r: f32, g: f32, b: f32| -> [f32; 3] {
             [
@@ -803,12 +1054,7 @@ impl PipelineStage for DevelopStage {
             ]
         };
 
-        let reciprocity_factor = if config.exposure_time > 1.0 {
-            1.0 + film.reciprocity.beta * config.exposure_time.log10().powi(2)
-        } else {
-            1.0
-        };
-        let t_eff = config.exposure_time / reciprocity_factor;
+        let t_eff = config.effective_exposure(film);
 
         // White Balance Calculation
         let wb_gains = match config.white_balance_mode {
@@ -851,21 +1097,14 @@ impl PipelineStage for DevelopStage {
                     ];
 
                     // Apply Warmth (Shift R/B)
-                    let warmth = config.warmth.clamp(-1.0, 1.0);
-                    [
-                        base_gains[0] * (1.0 + warmth * 0.1),
-                        base_gains[1],
-                        base_gains[2] * (1.0 - warmth * 0.1),
-                    ]
+                    let warmth = config.warmth_gains();
+                    std::array::from_fn(|c| base_gains[c] * warmth[c])
                 } else {
                     [1.0, 1.0, 1.0]
                 }
             }
-            _ => {
-                // Manual/Off mode still supports Warmth
-                let warmth = config.warmth.clamp(-1.0, 1.0);
-                [1.0 + warmth * 0.1, 1.0, 1.0 - warmth * 0.1]
-            }
+            // Manual/Off mode still supports Warmth
+            _ => crate::processor::manual_white_balance_gains(film, config),
         };
 
         // Transform in place: Linear -> Density
@@ -873,7 +1112,10 @@ impl PipelineStage for DevelopStage {
             let _x = (i as u32) % width;
             let _y = (i as u32) / width;
             // Current pixel is Linear RGB
-            let lin_pixel = [pixel[0], pixel[1], pixel[2]];
+            let lin_pixel = match luma_weights {
+                Some([wr, wg, wb]) => [wr * pixel[0] + wg * pixel[1] + wb * pixel[2]; 3],
+                None => [pixel[0], pixel[1], pixel[2]],
+            };
 
             let exposure_vals = apply_matrix(lin_pixel[0], lin_pixel[1], lin_pixel[2]);
 
@@ -981,48 +1223,31 @@ impl PipelineStage for GrainStage {
         let gm = &film.grain_model;
 
         // Physical grain size in pixels
-        let pixels_per_mm = width as f32 / 36.0;
-        let grain_sigma = (gm.blur_radius * 0.05 * pixels_per_mm).max(0.8);
+        let (grain_sigma, grain_scale) =
+            grain_sigma_and_scale(film, width, height, context.config.source_width);
 
         let mono = gm.monochrome;
         let n_textures = if mono { 1 } else { 4 }; // mono: 1 shared; color: shared + R/G/B
 
-        // Generate and blur noise textures
-        let gen_and_blur = |sigma: f32| -> Vec<f32> {
-            let mut tex = vec![0.0f32; (width * height) as usize];
-            tex.par_chunks_mut(1).for_each(|p| {
-                let mut rng = rand::thread_rng();
-                p[0] = rand_distr::Distribution::sample(
-                    &rand_distr::Normal::new(0.0f32, 1.0f32).unwrap(),
-                    &mut rng,
-                );
-            });
-            if sigma >= 0.5 {
-                let mut img: ImageBuffer<Rgb<f32>, Vec<f32>> = ImageBuffer::new(width, height);
-                img.chunks_mut(3).enumerate().for_each(|(i, pixel)| {
-                    pixel[0] = tex[i];
-                    pixel[1] = tex[i];
-                    pixel[2] = tex[i];
-                });
-                utils::apply_gaussian_blur(&mut img, sigma);
-                img.chunks(3).enumerate().for_each(|(i, pixel)| {
-                    tex[i] = pixel[0];
-                });
-            }
-            tex
-        };
-
-        let textures: Vec<Vec<f32>> = (0..n_textures).map(|_| gen_and_blur(grain_sigma)).collect();
+        let pixels_per_mm = film.film_format.pixels_per_mm(width, height);
+        let textures: Vec<Vec<f32>> = (0..n_textures)
+            .map(|k| {
+                grain_texture(
+                    gm.structure,
+                    width,
+                    height,
+                    pixels_per_mm,
+                    grain_sigma,
+                    context.config.grain_seed,
+                    k as u64,
+                )
+            })
+            .collect();
 
         // Grain strength: Selwyn law σ_D = alpha × √D
-        // In sRGB output space, this needs significant amplification.
-        // Real Portra 400 shows σ ≈ 8-20 in sRGB 8-bit; our density-space
-        // grain needs to produce similar output variation.
         let corr = gm.color_correlation;
         let alpha = gm.alpha;
-        // Scale factor: alpha is tiny (0.000125 for Portra), but real grain σ_D ≈ 0.02-0.05.
-        // boost converts alpha to physical σ_D scale.
-        let boost = 2000.0f32;
+        let boost = GRAIN_BOOST * grain_scale;
 
         image.par_chunks_mut(3).enumerate().for_each(|(i, pixel)| {
             let shared = textures[0][i];
@@ -1048,6 +1273,268 @@ impl PipelineStage for GrainStage {
     }
 }
 
+/// # Scene Grain Stage
+///
+/// [`StageKind::Grain`] listed before [`StageKind::Develop`]: grain added to
+/// the linear scene image, so the stages after it, such as halation, spread
+/// it. Listed after the develop, the output conversion adds the grain
+/// instead, in [`SimulationConfig::grain_space`], and this stage does nothing.
+pub struct SceneGrainStage;
+
+impl PipelineStage for SceneGrainStage {
+    #[instrument(skip(self, image, context))]
+    fn process(&self, image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>, context: &PipelineContext) {
+        let config = context.config;
+        if !config.enable_grain || config.grains_output() {
+            return;
+        }
+        info!("Applying grain to the scene image");
+        let film = context.film;
+        let (width, height) = image.dimensions();
+        let (grain_sigma, grain_scale) =
+            grain_sigma_and_scale(film, width, height, config.source_width);
+        let pixels_per_mm = film.film_format.pixels_per_mm(width, height);
+        let textures = grain_textures(
+            film,
+            width,
+            height,
+            pixels_per_mm,
+            grain_sigma,
+            config.grain_seed,
+        );
+        let base_strength = film.grain_model.alpha * LINEAR_GRAIN_BOOST * grain_scale;
+
+        // Scene light may exceed 1.0, so only negative values are clipped
+        image.par_chunks_mut(3).enumerate().for_each(|(i, px)| {
+            let lum = 0.2126 * px[0] + 0.7152 * px[1] + 0.0722 * px[2];
+            let strength = base_strength * linear_grain_envelope(lum);
+            for (v, n) in px.iter_mut().zip(grain_noise(&textures, film, i)) {
+                *v = (*v + strength * n).max(0.0);
+            }
+        });
+    }
+}
+
+/// Converts [`GrainModel::alpha`](crate::grain::GrainModel::alpha) to a physical σ_D.
+///
+/// In sRGB output space grain needs significant amplification: real Portra 400
+/// shows σ ≈ 8-20 in sRGB 8-bit, so alpha (0.000125 for Portra) is scaled up
+/// to the σ_D ≈ 0.02-0.05 of real grain.
+pub(crate) const GRAIN_BOOST: f32 = 2000.0;
+
+/// Grain blur radius in pixels and an amplitude factor for the current render size.
+///
+/// Grain has a fixed physical size and noise power per mm² of film, so both the
+/// blur radius and the per-pixel noise scale with pixels per mm of the film format.
+/// Grain then keeps the same size and contrast relative to the picture at any
+/// resolution, instead of depending on a reference image width.
+///
+/// When `source_width` (see [`SimulationConfig::source_width`]) marks this render as a downscaled preview, the noise
+/// is shaped like the full-resolution grain after the same downscale, so grain
+/// sliders read the same in the preview and in the final develop.
+pub(crate) fn grain_sigma_and_scale(
+    film: &FilmStock,
+    width: u32,
+    height: u32,
+    source_width: Option<u32>,
+) -> (f32, f32) {
+    let gm = &film.grain_model;
+    let sigma_at = |pixels_per_mm: f32| (gm.blur_radius * 0.05 * pixels_per_mm).max(0.8);
+    let pixels_per_mm = film.film_format.pixels_per_mm(width, height);
+
+    let downscale = source_width.map_or(1.0, |source| source as f32 / width as f32);
+    if downscale <= 1.0 {
+        return (
+            sigma_at(pixels_per_mm),
+            pixels_per_mm / GRAIN_REFERENCE_PIXELS_PER_MM,
+        );
+    }
+
+    // Full-res grain in full-res pixels, widened by the resampling low-pass
+    // (a Gaussian with the same noise reduction as averaging `downscale`² pixels).
+    let full_pixels_per_mm = pixels_per_mm * downscale;
+    let full_sigma = box_blur_sigma(sigma_at(full_pixels_per_mm))
+        .hypot(downscale / (2.0 * std::f32::consts::PI.sqrt()));
+    let target_std =
+        full_pixels_per_mm / GRAIN_REFERENCE_PIXELS_PER_MM * gaussian_noise_std(full_sigma);
+
+    let sigma = full_sigma / downscale;
+    (sigma, target_std / blurred_noise_std(sigma))
+}
+
+/// Unit Gaussian white noise for a grain texture.
+///
+/// Every value is hashed from `seed`, `stream` and its pixel position, so the
+/// noise is the same however rayon splits the work and on any number of
+/// threads. `stream` tells the textures of one render apart. Without a
+/// `seed` a fresh one is drawn.
+pub(crate) fn white_noise(width: u32, height: u32, seed: Option<u64>, stream: u64) -> Vec<f32> {
+    let seed = seed.unwrap_or_else(rand::random);
+    let mut tex = vec![0.0f32; (width * height) as usize];
+    tex.par_chunks_mut(width.max(1) as usize)
+        .enumerate()
+        .for_each(|(y, line)| {
+            for (x, p) in line.iter_mut().enumerate() {
+                *p = hash_normal(noise_hash(seed, stream, x as u64, y as u64));
+            }
+        });
+    tex
+}
+
+/// SplitMix64 finalizer.
+fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Hash of a noise sample's key: the seed, the texture stream and the pixel
+/// (or cell) position.
+fn noise_hash(seed: u64, stream: u64, x: u64, y: u64) -> u64 {
+    let row = mix64(seed ^ mix64(stream.wrapping_mul(0xD1B5_4A32_D192_ED03) ^ y));
+    mix64(row.wrapping_add(x.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+}
+
+/// Uniform value in [0, 1) from the top 24 bits of `hash`.
+fn hash_unit(hash: u64) -> f32 {
+    (hash >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// Unit Gaussian value from `hash`, by Box-Muller on its two halves.
+fn hash_normal(hash: u64) -> f32 {
+    let u1 = ((hash >> 40) as f32 + 0.5) / (1u64 << 24) as f32;
+    let u2 = hash_unit(hash << 32);
+    (-2.0 * u1.ln()).sqrt() * (std::f32::consts::TAU * u2).cos()
+}
+
+/// One grain texture: unit noise with the given `structure`, blurred to
+/// `sigma` pixels.
+///
+/// Clumped noise is scaled to the strength per-pixel noise has after the same
+/// blur, so [`grain_sigma_and_scale`] calibrates both. `pixels_per_mm` sizes
+/// the clumps; below a pixel they are plain per-pixel noise.
+pub(crate) fn grain_texture(
+    structure: GrainStructure,
+    width: u32,
+    height: u32,
+    pixels_per_mm: f32,
+    sigma: f32,
+    seed: Option<u64>,
+    stream: u64,
+) -> Vec<f32> {
+    let clump_size = match structure {
+        GrainStructure::PerPixel => None,
+        GrainStructure::Clumped { size } => {
+            Some(size * pixels_per_mm / GRAIN_REFERENCE_PIXELS_PER_MM).filter(|&size| size > 1.0)
+        }
+    };
+    let mut tex = match clump_size {
+        Some(size) => clumped_noise(width, height, size, seed, stream),
+        None => white_noise(width, height, seed, stream),
+    };
+    if sigma >= 0.5 {
+        let mut img: ImageBuffer<Rgb<f32>, Vec<f32>> = ImageBuffer::new(width, height);
+        img.chunks_mut(3).enumerate().for_each(|(i, pixel)| {
+            pixel[0] = tex[i];
+            pixel[1] = tex[i];
+            pixel[2] = tex[i];
+        });
+        utils::apply_gaussian_blur(&mut img, sigma);
+        img.chunks(3).enumerate().for_each(|(i, pixel)| {
+            tex[i] = pixel[0];
+        });
+    }
+    if clump_size.is_some() && !tex.is_empty() {
+        // Summed in order: a parallel float sum depends on the thread count
+        let n = tex.len() as f64;
+        let mean = tex.iter().map(|&v| v as f64).sum::<f64>() / n;
+        let var = tex.iter().map(|&v| (v as f64 - mean).powi(2)).sum::<f64>() / n;
+        let (mean, std) = (mean as f32, var.sqrt() as f32);
+        if std > 0.0 {
+            let gain = blurred_noise_std(sigma) / std;
+            tex.par_iter_mut().for_each(|v| *v = (*v - mean) * gain);
+        }
+    }
+    tex
+}
+
+/// Unit Gaussian cellular noise for [`GrainStructure::Clumped`].
+///
+/// The frame is split into cells of `size` pixels, each holding one clump at a
+/// random point with a random density; every pixel takes the density of the
+/// nearest clump. Clumps are hashed from their cell, like [`white_noise`].
+fn clumped_noise(width: u32, height: u32, size: f32, seed: Option<u64>, stream: u64) -> Vec<f32> {
+    let cols = (width as f32 / size).ceil().max(1.0) as usize;
+    let rows = (height as f32 / size).ceil().max(1.0) as usize;
+    let seed = seed.unwrap_or_else(rand::random);
+
+    // (x, y, density) of the clump in each cell, in cell units
+    let mut clumps = vec![(0.0f32, 0.0f32, 0.0f32); cols * rows];
+    clumps
+        .par_chunks_mut(cols)
+        .enumerate()
+        .for_each(|(row, line)| {
+            for (col, clump) in line.iter_mut().enumerate() {
+                let hash = noise_hash(seed, stream, col as u64, row as u64);
+                *clump = (
+                    col as f32 + hash_unit(hash),
+                    row as f32 + hash_unit(hash << 24),
+                    hash_normal(mix64(hash)),
+                );
+            }
+        });
+
+    let mut tex = vec![0.0f32; (width * height) as usize];
+    tex.par_chunks_mut(width.max(1) as usize)
+        .enumerate()
+        .for_each(|(y, line)| {
+            let py = (y as f32 + 0.5) / size;
+            let cy = py as usize;
+            for (x, v) in line.iter_mut().enumerate() {
+                let px = (x as f32 + 0.5) / size;
+                let cx = px as usize;
+                let mut nearest = f32::MAX;
+                for j in cy.saturating_sub(1)..(cy + 2).min(rows) {
+                    for i in cx.saturating_sub(1)..(cx + 2).min(cols) {
+                        let (qx, qy, density) = clumps[j * cols + i];
+                        let d2 = (qx - px).powi(2) + (qy - py).powi(2);
+                        if d2 < nearest {
+                            nearest = d2;
+                            *v = density;
+                        }
+                    }
+                }
+            }
+        });
+    tex
+}
+
+/// Effective Gaussian sigma of `utils::apply_gaussian_blur`.
+/// Its three box passes have a radius of at least 1, so small sigmas blur more than asked.
+pub(crate) fn box_blur_sigma(sigma: f32) -> f32 {
+    if sigma < 0.5 {
+        return 0.0;
+    }
+    let radius = (((4.0 * sigma * sigma + 1.0).sqrt() - 1.0) / 2.0)
+        .floor()
+        .max(1.0);
+    (radius * (radius + 1.0)).sqrt()
+}
+
+/// Standard deviation of unit white noise after a 2D Gaussian blur of `sigma` pixels.
+fn gaussian_noise_std(sigma: f32) -> f32 {
+    (0.5 / (std::f32::consts::PI.sqrt() * sigma)).min(1.0)
+}
+
+/// Standard deviation of unit white noise after the grain texture blur.
+fn blurred_noise_std(sigma: f32) -> f32 {
+    if sigma < 0.5 {
+        1.0
+    } else {
+        gaussian_noise_std(box_blur_sigma(sigma))
+    }
+}
+
 /// # Output Stage (Final Conversion)
 ///
 /// Converts Density to final output color space.
@@ -1059,108 +1546,134 @@ pub fn create_output_image(
     image: &ImageBuffer<Rgb<f32>, Vec<f32>>,
     context: &PipelineContext,
 ) -> RgbImage {
+    render_output(
+        image,
+        None,
+        context,
+        OutputSteps {
+            adjustments: true,
+            grain: context.config.grains_output(),
+        },
+    )
+}
+
+/// [`create_output_image`] mixed with `original`, the linearized input at
+/// the size of `image`, by [`SimulationConfig::effect_amount`] and
+/// [`SimulationConfig::mask`].
+pub(crate) fn create_blended_output_image(
+    image: &ImageBuffer<Rgb<f32>, Vec<f32>>,
+    original: Option<&ImageBuffer<Rgb<f32>, Vec<f32>>>,
+    context: &PipelineContext,
+) -> RgbImage {
+    render_output(
+        image,
+        original,
+        context,
+        OutputSteps {
+            adjustments: true,
+            grain: context.config.grains_output(),
+        },
+    )
+}
+
+/// Parts of the output conversion applied on top of the density-to-color scan.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OutputSteps {
+    /// Saturation, vignette, auto levels, light-leak overlays and the scanner.
+    pub adjustments: bool,
+    pub grain: bool,
+}
+
+/// Output conversion with only the given `steps`, mixed with `original`
+/// when given. Used for per-stage dumps.
+pub(crate) fn render_output(
+    image: &ImageBuffer<Rgb<f32>, Vec<f32>>,
+    original: Option<&ImageBuffer<Rgb<f32>, Vec<f32>>>,
+    context: &PipelineContext,
+    steps: OutputSteps,
+) -> RgbImage {
+    let mut linear_buf = render_linear_output(image, context, steps);
+    if let Some(original) = original {
+        blend_effect(&mut linear_buf, original, context.config.effect_amount);
+        if let Some(mask) = &context.config.mask {
+            mask.blend_linear(&mut linear_buf, original);
+        }
+    }
+    let (linear_buf, width, height) =
+        resample_to_target(linear_buf, image.width(), image.height(), context.config);
+    encode_output(
+        &linear_buf,
+        width,
+        height,
+        context.config.output_transfer,
+        context.config.dither,
+    )
+}
+
+/// Resample a linear image to `size` in linear light. Filter ringing below
+/// zero is clipped.
+pub(crate) fn resample_linear(
+    image: ImageBuffer<Rgb<f32>, Vec<f32>>,
+    size: (u32, u32),
+) -> ImageBuffer<Rgb<f32>, Vec<f32>> {
+    if image.dimensions() == size || image.width() == 0 || image.height() == 0 {
+        return image;
+    }
+    let mut out = image::imageops::resize(&image, size.0, size.1, utils::PREVIEW_FILTER);
+    out.par_iter_mut().for_each(|v| *v = v.max(0.0));
+    out
+}
+
+/// Downscale a linear output buffer developed at
+/// [`SimulationConfig::working_size`] to
+/// [`SimulationConfig::target_size`]. Returns the buffer and its size.
+pub(crate) fn resample_to_target(
+    linear_buf: Vec<f32>,
+    width: u32,
+    height: u32,
+    config: &SimulationConfig,
+) -> (Vec<f32>, u32, u32) {
+    let Some((target_w, target_h)) = config.target_size.map(|(w, h)| (w.max(1), h.max(1))) else {
+        return (linear_buf, width, height);
+    };
+    let image = ImageBuffer::from_raw(width, height, linear_buf).unwrap();
+    let resampled = resample_linear(image, (target_w, target_h));
+    (resampled.into_raw(), target_w, target_h)
+}
+
+/// Mix a linear output buffer back toward `original`, the linearized input
+/// at the same size, by `amount` (see [`SimulationConfig::effect_amount`]).
+pub(crate) fn blend_effect(
+    linear_buf: &mut [f32],
+    original: &ImageBuffer<Rgb<f32>, Vec<f32>>,
+    amount: f32,
+) {
+    let amount = amount.max(0.0);
+    if amount.is_nan() || amount >= 1.0 || linear_buf.len() != original.len() {
+        return;
+    }
+    linear_buf
+        .par_iter_mut()
+        .zip(original.as_raw().par_iter())
+        .for_each(|(v, &o)| *v = o + (*v - o) * amount);
+}
+
+/// [`render_output`] before encoding: linear RGB, three floats per pixel.
+pub(crate) fn render_linear_output(
+    image: &ImageBuffer<Rgb<f32>, Vec<f32>>,
+    context: &PipelineContext,
+    steps: OutputSteps,
+) -> Vec<f32> {
     info!("Converting to final output image");
+    if context.config.bypass_film {
+        return image.as_raw().clone();
+    }
     let width = image.width();
     let height = image.height();
     let film = context.film;
     let config = context.config;
 
-    let map_densities = |densities: [f32; 3]| -> (f32, f32, f32) {
-        let net_r = (densities[0] - film.r_curve.d_min).max(0.0);
-        let net_g = (densities[1] - film.g_curve.d_min).max(0.0);
-        let net_b = (densities[2] - film.b_curve.d_min).max(0.0);
-        match config.output_mode {
-            OutputMode::Negative => {
-                let t_r = physics::apply_dye_self_absorption(
-                    net_r,
-                    physics::density_to_transmission(net_r),
-                );
-                let t_g = physics::apply_dye_self_absorption(
-                    net_g,
-                    physics::density_to_transmission(net_g),
-                );
-                let t_b = physics::apply_dye_self_absorption(
-                    net_b,
-                    physics::density_to_transmission(net_b),
-                );
-                (
-                    t_r.clamp(0.0, 1.0),
-                    t_g.clamp(0.0, 1.0),
-                    t_b.clamp(0.0, 1.0),
-                )
-            }
-            OutputMode::Positive => {
-                // Filmic tone curve — three-segment (toe + linear + shoulder)
-                use crate::filmic_curve::FilmicCurve;
-                // Use 85% of theoretical range as effective range so highlights reach white
-                // (erf curve is asymptotic — density never truly reaches d_max)
-                let range_scale = 0.85;
-                let range_r = (film.r_curve.d_max - film.r_curve.d_min).max(0.01) * range_scale;
-                let range_g = (film.g_curve.d_max - film.g_curve.d_min).max(0.01) * range_scale;
-                let range_b = (film.b_curve.d_max - film.b_curve.d_min).max(0.01) * range_scale;
-                let curve = match film.film_type {
-                    FilmType::ColorSlide => FilmicCurve::slide(),
-                    _ => FilmicCurve::negative(),
-                };
-                (
-                    curve.map(net_r / range_r),
-                    curve.map(net_g / range_g),
-                    curve.map(net_b / range_b),
-                )
-            }
-        }
-    };
-
-    // Extract dye spectra from layer_stack (if available) for spectral output path
-    let dye_spectra = film.layer_stack.as_ref().and_then(|stack| {
-        use crate::film_layer::{EmulsionChannel, LayerKind};
-        let mut yellow = None;
-        let mut magenta = None;
-        let mut cyan = None;
-        for layer in &stack.layers {
-            if let LayerKind::Emulsion { channel } = layer.kind {
-                if let Some(ref dye) = layer.dye_spectrum {
-                    match channel {
-                        EmulsionChannel::Blue => yellow = Some(*dye),
-                        EmulsionChannel::Green => magenta = Some(*dye),
-                        EmulsionChannel::Red => cyan = Some(*dye),
-                    }
-                }
-            }
-        }
-        match (yellow, magenta, cyan) {
-            (Some(y), Some(m), Some(c)) => Some((y, m, c)),
-            _ => None,
-        }
-    });
-
-    // Precompute D65 × CIE XYZ for spectral output (if dye spectra available)
-    let spectral_output = dye_spectra.map(|(y_dye, m_dye, c_dye)| {
-        use crate::cie_data::{CIE_X, CIE_Y, CIE_Z, D65_SPD, XYZ_TO_SRGB};
-        use crate::spectral::{BINS, LAMBDA_STEP};
-        // Precompute D65 × CMF
-        let mut d65_x = [0.0f32; BINS];
-        let mut d65_y = [0.0f32; BINS];
-        let mut d65_z = [0.0f32; BINS];
-        for i in 0..BINS {
-            d65_x[i] = D65_SPD[i] * CIE_X[i] * LAMBDA_STEP as f32;
-            d65_y[i] = D65_SPD[i] * CIE_Y[i] * LAMBDA_STEP as f32;
-            d65_z[i] = D65_SPD[i] * CIE_Z[i] * LAMBDA_STEP as f32;
-        }
-        // White point normalization: Y of D65 should = 1.0
-        let y_sum: f32 = d65_y.iter().sum();
-        let y_norm = if y_sum > 0.0 { 1.0 / y_sum } else { 1.0 };
-        (
-            y_dye,
-            m_dye,
-            c_dye,
-            d65_x,
-            d65_y,
-            d65_z,
-            y_norm,
-            XYZ_TO_SRGB,
-        )
-    });
+    let scan = DensityScan::new(film, config);
 
     // First pass: compute linear RGB for all pixels
     let mut linear_buf: Vec<f32> = vec![0.0; (width * height * 3) as usize];
@@ -1171,56 +1684,14 @@ pub fn create_output_image(
         .for_each(|(i, out)| {
             let x = (i as u32) % width;
             let y = (i as u32) / width;
-            let d = image.get_pixel(x, y).0;
-
-            let (mut r_lin, mut g_lin, mut b_lin) = if let Some(ref sp) = spectral_output {
-                let (y_dye, m_dye, c_dye, d65_x, d65_y, d65_z, y_norm, xyz_to_srgb) = sp;
-                let net = [
-                    (d[0] - film.r_curve.d_min).max(0.0),
-                    (d[1] - film.g_curve.d_min).max(0.0),
-                    (d[2] - film.b_curve.d_min).max(0.0),
-                ];
-                let mut xyz = [0.0f32; 3];
-                for i in 0..crate::spectral::BINS {
-                    let od = net[0] * c_dye[i] + net[1] * m_dye[i] + net[2] * y_dye[i];
-                    let t = 10.0f32.powf(-od);
-                    xyz[0] += t * d65_x[i];
-                    xyz[1] += t * d65_y[i];
-                    xyz[2] += t * d65_z[i];
-                }
-                xyz[0] *= y_norm;
-                xyz[1] *= y_norm;
-                xyz[2] *= y_norm;
-                let mut r = xyz_to_srgb[0][0] * xyz[0]
-                    + xyz_to_srgb[0][1] * xyz[1]
-                    + xyz_to_srgb[0][2] * xyz[2];
-                let mut g = xyz_to_srgb[1][0] * xyz[0]
-                    + xyz_to_srgb[1][1] * xyz[1]
-                    + xyz_to_srgb[1][2] * xyz[2];
-                let mut b = xyz_to_srgb[2][0] * xyz[0]
-                    + xyz_to_srgb[2][1] * xyz[1]
-                    + xyz_to_srgb[2][2] * xyz[2];
-                if film.film_type == FilmType::ColorNegative
-                    || film.film_type == FilmType::BwNegative
-                {
-                    r = 1.0 - r;
-                    g = 1.0 - g;
-                    b = 1.0 - b;
-                }
-                (r, g, b)
-            } else {
-                map_densities(d)
-            };
-
-            if config.saturation != 1.0 {
-                let lum = 0.2126 * r_lin + 0.7152 * g_lin + 0.0722 * b_lin;
-                r_lin = lum + (r_lin - lum) * config.saturation;
-                g_lin = lum + (g_lin - lum) * config.saturation;
-                b_lin = lum + (b_lin - lum) * config.saturation;
+            let mut rgb = scan.scan(image.get_pixel(x, y).0);
+            if steps.adjustments {
+                rgb = adjust_color(film, config, rgb);
             }
+            let [mut r_lin, mut g_lin, mut b_lin] = rgb;
 
             let v_str = film.vignette_strength;
-            if v_str > 0.0 {
+            if steps.adjustments && v_str > 0.0 {
                 let px = i as u32 % width;
                 let py = i as u32 / width;
                 let dx = (px as f32 + 0.5) / width as f32 - 0.5;
@@ -1240,7 +1711,7 @@ pub fn create_output_image(
         });
 
     // Auto Levels in linear f32 space (no banding)
-    if config.auto_levels {
+    if steps.adjustments && config.auto_levels && !linear_buf.is_empty() {
         let n = linear_buf.len() / 3;
         let step = (n / 50_000).max(1);
         let mut lums: Vec<f32> = Vec::with_capacity(n / step + 1);
@@ -1266,84 +1737,485 @@ pub fn create_output_image(
         });
     }
 
+    // Scanned light-leak overlays sit on top of the developed frame
+    if steps.adjustments {
+        crate::light_leak::apply_overlays(&mut linear_buf, width, height, &config.light_leak);
+    }
+
     // Grain in linear output space (after tone mapping, before sRGB)
-    if config.enable_grain {
-        let gm = &film.grain_model;
-        let pixels_per_mm = width as f32 / 36.0;
-        let grain_sigma = (gm.blur_radius * 0.05 * pixels_per_mm).max(0.8);
-        let mono = gm.monochrome;
-        let n_tex = if mono { 1usize } else { 4 };
-
-        // Generate blurred noise textures
-        let gen_blur = |sigma: f32| -> Vec<f32> {
-            let mut tex = vec![0.0f32; (width * height) as usize];
-            tex.par_chunks_mut(1).for_each(|p| {
-                let mut rng = rand::thread_rng();
-                p[0] = rand_distr::Distribution::sample(
-                    &rand_distr::Normal::new(0.0f32, 1.0f32).unwrap(),
-                    &mut rng,
-                );
+    if steps.grain {
+        let (grain_sigma, grain_scale) =
+            grain_sigma_and_scale(film, width, height, config.source_width);
+        add_output_grain(
+            &mut linear_buf,
+            width,
+            height,
+            film,
+            film.film_format.pixels_per_mm(width, height),
+            grain_sigma,
+            grain_scale,
+            config.grain_seed,
+            config.grain_space,
+        );
+    }
+
+    // The scanner digitizes the grained frame
+    if let Some(scanner) = config.scanner.filter(|_| steps.adjustments) {
+        let downscale = config
+            .source_width
+            .map_or(1.0, |source| source as f32 / width as f32);
+        scanner.apply(&mut linear_buf, width, height, downscale, config.grain_seed);
+    }
+
+    linear_buf
+}
+
+/// Add output-space grain to an sRGB image that is a native-resolution
+/// region of a `frame_width` x `frame_height` develop.
+///
+/// Grain is sized for the whole frame rather than for the region, so a small
+/// crop shows the same texture as the final develop. Used to inspect grain at
+/// 100% without developing the full frame.
+pub fn apply_grain(
+    image: &RgbImage,
+    film: &FilmStock,
+    frame_width: u32,
+    frame_height: u32,
+    space: GrainSpace,
+) -> RgbImage {
+    let (width, height) = image.dimensions();
+    let mut linear = create_linear_image(image);
+    let (grain_sigma, grain_scale) = grain_sigma_and_scale(film, frame_width, frame_height, None);
+    add_output_grain(
+        &mut linear,
+        width,
+        height,
+        film,
+        film.film_format.pixels_per_mm(frame_width, frame_height),
+        grain_sigma,
+        grain_scale,
+        None,
+        space,
+    );
+    linear_to_srgb_image(&linear)
+}
+
+/// Add grain to a linear output buffer in `space`, see [`render_output`].
+#[allow(clippy::too_many_arguments)]
+fn add_output_grain(
+    linear_buf: &mut [f32],
+    width: u32,
+    height: u32,
+    film: &FilmStock,
+    pixels_per_mm: f32,
+    grain_sigma: f32,
+    grain_scale: f32,
+    seed: Option<u64>,
+    space: GrainSpace,
+) {
+    let textures = grain_textures(film, width, height, pixels_per_mm, grain_sigma, seed);
+    let base_strength = film.grain_model.alpha * LINEAR_GRAIN_BOOST * grain_scale;
+
+    // The other spaces are calibrated to the linear σ at 18% gray
+    let mid_strength = base_strength * linear_grain_envelope(GRAIN_MID_GRAY);
+    let encoded_strength = mid_strength * srgb_slope(GRAIN_MID_GRAY);
+    let mid_density = -GRAIN_MID_GRAY.log10();
+    let density_strength =
+        mid_strength / (GRAIN_MID_GRAY * std::f32::consts::LN_10 * mid_density.sqrt());
+
+    linear_buf
+        .par_chunks_mut(3)
+        .enumerate()
+        .for_each(|(i, px)| {
+            let noise = grain_noise(&textures, film, i);
+            match space {
+                GrainSpace::Linear => {
+                    let lum = 0.2126 * px[0] + 0.7152 * px[1] + 0.0722 * px[2];
+                    let strength = base_strength * linear_grain_envelope(lum);
+                    for (v, n) in px.iter_mut().zip(noise) {
+                        *v = (*v + strength * n).clamp(0.0, 1.0);
+                    }
+                }
+                GrainSpace::Encoded => {
+                    for (v, n) in px.iter_mut().zip(noise) {
+                        let encoded = physics::linear_to_srgb(v.clamp(0.0, 1.0));
+                        let grained = (encoded + encoded_strength * n).clamp(0.0, 1.0);
+                        *v = physics::srgb_to_linear(grained);
+                    }
+                }
+                GrainSpace::Density => {
+                    // Selwyn: σ_D = k √D, on the print density of each dye layer
+                    for (v, n) in px.iter_mut().zip(noise) {
+                        let density = -v.clamp(GRAIN_MIN_LINEAR, 1.0).log10();
+                        let sigma_d = density_strength * density.max(0.01).sqrt();
+                        *v = (*v * 10f32.powf(-sigma_d * n)).clamp(0.0, 1.0);
+                    }
+                }
+            }
+        });
+}
+
+/// Grain strength in linear output space per unit of
+/// [`GrainModel::alpha`](crate::grain::GrainModel::alpha). Real Portra 400
+/// σ ≈ 8-20 in sRGB 8-bit → σ ≈ 0.03-0.08 in linear; the strength is further
+/// scaled by pixel brightness (Selwyn: brighter = less grain).
+const LINEAR_GRAIN_BOOST: f32 = 1500.0;
+
+/// Grain noise textures of `film`: one shared texture, plus one per channel
+/// for color grain.
+fn grain_textures(
+    film: &FilmStock,
+    width: u32,
+    height: u32,
+    pixels_per_mm: f32,
+    grain_sigma: f32,
+    seed: Option<u64>,
+) -> Vec<Vec<f32>> {
+    let gm = &film.grain_model;
+    let n_tex = if gm.monochrome { 1 } else { 4 };
+    (0..n_tex)
+        .map(|k| {
+            grain_texture(
+                gm.structure,
+                width,
+                height,
+                pixels_per_mm,
+                grain_sigma,
+                seed,
+                k as u64,
+            )
+        })
+        .collect()
+}
+
+/// Per-channel grain noise of pixel `i` from [`grain_textures`].
+fn grain_noise(textures: &[Vec<f32>], film: &FilmStock, i: usize) -> [f32; 3] {
+    let shared = textures[0][i];
+    if film.grain_model.monochrome {
+        return [shared; 3];
+    }
+    let corr = film.grain_model.color_correlation;
+    [1, 2, 3].map(|k| corr * shared + (1.0 - corr) * textures[k][i])
+}
+
+/// Gray level the grain spaces are matched at.
+const GRAIN_MID_GRAY: f32 = 0.18;
+
+/// Darkest linear value density grain is computed for (density 4.0).
+const GRAIN_MIN_LINEAR: f32 = 1e-4;
+
+/// Linear grain amplitude relative to `alpha` at luminance `lum`.
+fn linear_grain_envelope(lum: f32) -> f32 {
+    // Selwyn law: σ_D ∝ √D. In output space, high density = low brightness,
+    // so grain is stronger in shadows, weaker in highlights. But cap absolute
+    // noise to avoid bright speckles in pure black.
+    let lum = lum.clamp(0.01, 1.0);
+    (1.0 - lum).sqrt() * lum.max(0.05)
+}
+
+/// Derivative of the sRGB encode at linear value `v`.
+fn srgb_slope(v: f32) -> f32 {
+    if v <= 0.0031308 {
+        12.92
+    } else {
+        1.055 / 2.4 * v.powf(1.0 / 2.4 - 1.0)
+    }
+}
+
+/// Scan a density pixel to linear output RGB, with saturation and toning
+/// applied. Position-dependent and whole-frame adjustments are not included.
+pub(crate) fn scan_pixel(film: &FilmStock, config: &SimulationConfig, d: [f32; 3]) -> [f32; 3] {
+    adjust_color(film, config, DensityScan::new(film, config).scan(d))
+}
+
+/// Saturation, and toning of B&W stocks, on a scanned linear RGB pixel.
+fn adjust_color(film: &FilmStock, config: &SimulationConfig, rgb: [f32; 3]) -> [f32; 3] {
+    let rgb = apply_saturation(rgb, config.saturation_scale());
+    if film.is_monochrome() {
+        config.toning.apply(rgb)
+    } else {
+        rgb
+    }
+}
+
+/// Linear sRGB to CIE XYZ (D65).
+const SRGB_TO_XYZ: [[f32; 3]; 3] = [
+    [0.412_456_4, 0.357_576_1, 0.180_437_5],
+    [0.212_672_9, 0.715_152_2, 0.072_175],
+    [0.019_333_9, 0.119_192, 0.950_304_1],
+];
+/// CIE XYZ (D65) to linear sRGB.
+const XYZ_TO_SRGB: [[f32; 3]; 3] = [
+    [3.240_454_2, -1.537_138_5, -0.498_531_4],
+    [-0.969_266, 1.876_010_8, 0.041_556],
+    [0.055_643_4, -0.204_025_9, 1.057_225_2],
+];
+/// D65 reference white in XYZ.
+const D65_WHITE: [f32; 3] = [0.950_47, 1.0, 1.088_83];
+
+/// Scale the Lab chroma (a*, b*) of a linear sRGB pixel, keeping L* and hue.
+fn apply_saturation(rgb: [f32; 3], saturation: f32) -> [f32; 3] {
+    if saturation == 1.0 {
+        return rgb;
+    }
+    const DELTA: f32 = 6.0 / 29.0;
+    let f = |t: f32| {
+        if t > DELTA * DELTA * DELTA {
+            t.cbrt()
+        } else {
+            t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+        }
+    };
+    let f_inv = |t: f32| {
+        if t > DELTA {
+            t * t * t
+        } else {
+            3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+        }
+    };
+    let mul = |m: &[[f32; 3]; 3], v: [f32; 3]| -> [f32; 3] {
+        std::array::from_fn(|i| m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2])
+    };
+
+    // a* ∝ fx - fy and b* ∝ fy - fz, so scaling chroma moves fx and fz toward fy
+    let xyz = mul(&SRGB_TO_XYZ, rgb);
+    let [fx, fy, fz]: [f32; 3] = std::array::from_fn(|c| f(xyz[c] / D65_WHITE[c]));
+    let scaled = [fy + (fx - fy) * saturation, fy, fy + (fz - fy) * saturation];
+    mul(
+        &XYZ_TO_SRGB,
+        std::array::from_fn(|c| f_inv(scaled[c]) * D65_WHITE[c]),
+    )
+}
+
+/// Dye spectra and D65-weighted color matching functions for spectral output.
+struct SpectralOutput {
+    yellow: [f32; crate::spectral::BINS],
+    magenta: [f32; crate::spectral::BINS],
+    cyan: [f32; crate::spectral::BINS],
+    d65_x: [f32; crate::spectral::BINS],
+    d65_y: [f32; crate::spectral::BINS],
+    d65_z: [f32; crate::spectral::BINS],
+    y_norm: f32,
+}
+
+/// Density → linear output RGB, precomputed once per frame.
+struct DensityScan<'a> {
+    film: &'a FilmStock,
+    output_mode: OutputMode,
+    /// Primaries of the curve output, converted to linear sRGB.
+    working_space: WorkingSpace,
+    /// Orange mask transmission, applied to negative output.
+    mask: [f32; 3],
+    /// Base transmission removed by an inverted scan.
+    base_removal: [f32; 3],
+    /// Print contrast of an inverted scan.
+    inversion_contrast: f32,
+    /// Present if the film's layer stack has yellow, magenta and cyan dye spectra.
+    spectral: Option<SpectralOutput>,
+}
+
+impl<'a> DensityScan<'a> {
+    fn new(film: &'a FilmStock, config: &SimulationConfig) -> Self {
+        // Extract dye spectra from layer_stack (if available) for spectral output path
+        let dye_spectra = film.layer_stack.as_ref().and_then(|stack| {
+            use crate::film_layer::{EmulsionChannel, LayerKind};
+            let mut yellow = None;
+            let mut magenta = None;
+            let mut cyan = None;
+            for layer in &stack.layers {
+                if let LayerKind::Emulsion { channel } = layer.kind {
+                    if let Some(ref dye) = layer.dye_spectrum {
+                        match channel {
+                            EmulsionChannel::Blue => yellow = Some(*dye),
+                            EmulsionChannel::Green => magenta = Some(*dye),
+                            EmulsionChannel::Red => cyan = Some(*dye),
+                        }
+                    }
+                }
+            }
+            match (yellow, magenta, cyan) {
+                (Some(y), Some(m), Some(c)) => Some((y, m, c)),
+                _ => None,
+            }
+        });
+
+        // Precompute D65 × CIE XYZ for spectral output (if dye spectra available)
+        let spectral = dye_spectra.map(|(yellow, magenta, cyan)| {
+            use crate::cie_data::{CIE_X, CIE_Y, CIE_Z, D65_SPD};
+            use crate::spectral::{BINS, LAMBDA_STEP};
+            // Precompute D65 × CMF
+            let mut d65_x = [0.0f32; BINS];
+            let mut d65_y = [0.0f32; BINS];
+            let mut d65_z = [0.0f32; BINS];
+            for i in 0..BINS {
+                d65_x[i] = D65_SPD[i] * CIE_X[i] * LAMBDA_STEP as f32;
+                d65_y[i] = D65_SPD[i] * CIE_Y[i] * LAMBDA_STEP as f32;
+                d65_z[i] = D65_SPD[i] * CIE_Z[i] * LAMBDA_STEP as f32;
+            }
+            // White point normalization: Y of D65 should = 1.0
+            let y_sum: f32 = d65_y.iter().sum();
+            let y_norm = if y_sum > 0.0 { 1.0 / y_sum } else { 1.0 };
+            SpectralOutput {
+                yellow,
+                magenta,
+                cyan,
+                d65_x,
+                d65_y,
+                d65_z,
+                y_norm,
+            }
+        });
+
+        Self {
+            film,
+            output_mode: config.output_mode,
+            working_space: config.working_space,
+            mask: film.mask_color(),
+            base_removal: config.inversion.base_removal(film),
+            inversion_contrast: config.inversion.contrast.max(0.0),
+            spectral,
+        }
+    }
+
+    /// Whether the negative is scanned and inverted rather than read directly.
+    fn inverts(&self) -> bool {
+        self.output_mode == OutputMode::Inverted && self.film.film_type != FilmType::ColorSlide
+    }
+
+    /// Lab inversion: divide the film base out of a negative's scanned
+    /// transmission and print it with the inversion contrast.
+    fn invert(&self, transmission: [f32; 3]) -> [f32; 3] {
+        std::array::from_fn(|c| {
+            (transmission[c] / self.base_removal[c])
+                .clamp(1e-4, 1.0)
+                .powf(self.inversion_contrast)
+        })
+    }
+
+    fn scan(&self, d: [f32; 3]) -> [f32; 3] {
+        let film = self.film;
+        let net = [
+            (d[0] - film.r_curve.d_min).max(0.0),
+            (d[1] - film.g_curve.d_min).max(0.0),
+            (d[2] - film.b_curve.d_min).max(0.0),
+        ];
+
+        if let Some(sp) = &self.spectral {
+            use crate::cie_data::XYZ_TO_SRGB;
+            let mut xyz = [0.0f32; 3];
+            for i in 0..crate::spectral::BINS {
+                let od = net[0] * sp.cyan[i] + net[1] * sp.magenta[i] + net[2] * sp.yellow[i];
+                let t = 10.0f32.powf(-od);
+                xyz[0] += t * sp.d65_x[i];
+                xyz[1] += t * sp.d65_y[i];
+                xyz[2] += t * sp.d65_z[i];
+            }
+            let xyz = xyz.map(|v| v * sp.y_norm);
+            let rgb: [f32; 3] = std::array::from_fn(|c| {
+                XYZ_TO_SRGB[c][0] * xyz[0] + XYZ_TO_SRGB[c][1] * xyz[1] + XYZ_TO_SRGB[c][2] * xyz[2]
             });
-            if sigma >= 0.5 {
-                let mut img: ImageBuffer<Rgb<f32>, Vec<f32>> = ImageBuffer::new(width, height);
-                img.chunks_mut(3).enumerate().for_each(|(i, px)| {
-                    px[0] = tex[i];
-                    px[1] = tex[i];
-                    px[2] = tex[i];
-                });
-                utils::apply_gaussian_blur(&mut img, sigma);
-                img.chunks(3).enumerate().for_each(|(i, px)| {
-                    tex[i] = px[0];
-                });
+            if self.output_mode == OutputMode::Negative {
+                return std::array::from_fn(|c| rgb[c] * self.mask[c]);
             }
-            tex
-        };
-        let textures: Vec<Vec<f32>> = (0..n_tex).map(|_| gen_blur(grain_sigma)).collect();
+            if self.inverts() {
+                let negative = std::array::from_fn(|c| rgb[c] * self.mask[c]);
+                return self.invert(negative).map(|t| 1.0 - t);
+            }
+            if film.film_type == FilmType::ColorNegative || film.film_type == FilmType::BwNegative {
+                return rgb.map(|v| 1.0 - v);
+            }
+            return rgb;
+        }
 
-        let corr = gm.color_correlation;
-        // Grain strength in linear output space.
-        // Real Portra 400 σ ≈ 8-20 in sRGB 8-bit → σ ≈ 0.03-0.08 in linear.
-        // Scale by alpha (preset-specific) and pixel brightness (Selwyn: brighter = less grain).
-        let base_strength = gm.alpha * 1500.0;
-
-        linear_buf
-            .par_chunks_mut(3)
-            .enumerate()
-            .for_each(|(i, px)| {
-                let shared = textures[0][i];
-                let (nr, ng, nb) = if mono {
-                    (shared, shared, shared)
+        let negative = |c: usize| {
+            let n = net[c];
+            (physics::apply_dye_self_absorption(n, physics::density_to_transmission(n))
+                * self.mask[c])
+                .clamp(0.0, 1.0)
+        };
+        let rgb = match self.output_mode {
+            OutputMode::Negative => std::array::from_fn(negative),
+            OutputMode::Positive | OutputMode::Inverted => {
+                // An inverted scan prints the densities read back from the negative
+                let net = if self.inverts() {
+                    self.invert(std::array::from_fn(negative))
+                        .map(|t| -t.log10())
                 } else {
-                    (
-                        corr * shared + (1.0 - corr) * textures[1][i],
-                        corr * shared + (1.0 - corr) * textures[2][i],
-                        corr * shared + (1.0 - corr) * textures[3][i],
-                    )
+                    net
                 };
-                // Selwyn in output space: grain stronger in shadows (low linear value)
-                // σ ∝ sqrt(1 - brightness) — shadows get more grain
-                let lum = (0.2126 * px[0] + 0.7152 * px[1] + 0.0722 * px[2]).clamp(0.01, 1.0);
-                // Selwyn law: σ_D ∝ √D. In output space, high density = low brightness.
-                // Grain stronger in shadows, weaker in highlights.
-                // But cap absolute noise to avoid bright speckles in pure black.
-                let selwyn = (1.0 - lum).sqrt();
-                let strength = base_strength * selwyn * lum.max(0.05);
-                px[0] = (px[0] + strength * nr).clamp(0.0, 1.0);
-                px[1] = (px[1] + strength * ng).clamp(0.0, 1.0);
-                px[2] = (px[2] + strength * nb).clamp(0.0, 1.0);
-            });
+                // Filmic tone curve — three-segment (toe + linear + shoulder)
+                use crate::filmic_curve::FilmicCurve;
+                // Use 85% of theoretical range as effective range so highlights reach white
+                // (erf curve is asymptotic — density never truly reaches d_max)
+                let range_scale = 0.85;
+                let range_r = (film.r_curve.d_max - film.r_curve.d_min).max(0.01) * range_scale;
+                let range_g = (film.g_curve.d_max - film.g_curve.d_min).max(0.01) * range_scale;
+                let range_b = (film.b_curve.d_max - film.b_curve.d_min).max(0.01) * range_scale;
+                let curve = match film.film_type {
+                    FilmType::ColorSlide => FilmicCurve::slide(),
+                    _ => FilmicCurve::negative(),
+                };
+                [
+                    curve.map(net[0] / range_r),
+                    curve.map(net[1] / range_g),
+                    curve.map(net[2] / range_b),
+                ]
+            }
+        };
+        self.working_space.to_linear_srgb(rgb)
     }
+}
 
-    // Final pass: linear → sRGB u8
+/// Encode a linear RGB image to sRGB for viewing, e.g. to dump a stage
+/// before development.
+pub fn linear_to_srgb_image(image: &ImageBuffer<Rgb<f32>, Vec<f32>>) -> RgbImage {
+    encode_output(
+        image.as_raw(),
+        image.width(),
+        image.height(),
+        OutputTransfer::Srgb,
+        DitherMode::None,
+    )
+}
+
+fn encode_output(
+    linear_buf: &[f32],
+    width: u32,
+    height: u32,
+    transfer: OutputTransfer,
+    dither: DitherMode,
+) -> RgbImage {
+    // Final pass: linear → encoded u8, dithered before rounding
     let mut pixels: Vec<u8> = vec![0; (width * height * 3) as usize];
-    pixels.par_chunks_mut(3).enumerate().for_each(|(i, chunk)| {
-        let idx = i * 3;
-        chunk[0] = (physics::linear_to_srgb(linear_buf[idx].clamp(0.0, 1.0)) * 255.0).round() as u8;
-        chunk[1] =
-            (physics::linear_to_srgb(linear_buf[idx + 1].clamp(0.0, 1.0)) * 255.0).round() as u8;
-        chunk[2] =
-            (physics::linear_to_srgb(linear_buf[idx + 2].clamp(0.0, 1.0)) * 255.0).round() as u8;
-    });
+    pixels
+        .par_chunks_mut(3)
+        .zip(linear_buf.par_chunks(3))
+        .enumerate()
+        .for_each(|(i, (chunk, linear))| {
+            let (x, y) = (i as u32 % width, i as u32 / width);
+            for (out, &v) in chunk.iter_mut().zip(linear) {
+                *out = dither.quantize(transfer.encode(v), x, y);
+            }
+        });
 
     RgbImage::from_raw(width, height, pixels).unwrap()
 }
+
+/// Single-channel counterpart of [`encode_output`]: Rec. 709 luminance,
+/// encoded to 16 bits.
+pub(crate) fn encode_luma_output(
+    linear_buf: &[f32],
+    width: u32,
+    height: u32,
+    transfer: OutputTransfer,
+) -> ImageBuffer<Luma<u16>, Vec<u16>> {
+    let pixels: Vec<u16> = linear_buf
+        .par_chunks(3)
+        .map(|rgb| {
+            let lum = 0.2126 * rgb[0] + 0.7152 * rgb[1] + 0.0722 * rgb[2];
+            (transfer.encode(lum) * 65535.0).round() as u16
+        })
+        .collect();
+
+    ImageBuffer::from_raw(width, height, pixels).unwrap()
+}
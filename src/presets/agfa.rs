@@ -2,16 +2,19 @@
 
 #![allow(non_snake_case)]
 
-use crate::film::{FilmStock, FilmType, ReciprocityFailure, SegmentedCurve};
+use crate::film::{
+    FilmFormat, FilmStock, FilmType, ReciprocityFailure, SegmentedCurve, DAYLIGHT_BALANCE_K,
+    FILM_STOCK_VERSION, REC709_LUMA,
+};
 use crate::film_layer::*;
-use crate::grain::GrainModel;
+use crate::grain::{GrainModel, GrainStructure};
 use crate::spectral::{FilmSpectralParams, BINS};
 
 /// Agfa Vista 400 (Consumer Color Negative)
 /// Source: Agfa Technical Data
 /// ISO: 400
 /// RMS: 12 -> Alpha = 0.0144
-/// Gamma: 0.65
+/// Gamma: R 0.67 / G 0.65 / B 0.62
 /// Dmax: 2.8, Dmin: 0.12
 /// Resolution: 115 lp/mm
 pub fn VISTA_400() -> FilmStock {
@@ -23,7 +26,7 @@ pub fn VISTA_400() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.13,
             d_max: 2.8,
-            gamma: 0.65,
+            gamma: 0.67,
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
@@ -37,7 +40,7 @@ pub fn VISTA_400() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.8,
-            gamma: 0.65,
+            gamma: 0.62,
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
@@ -46,6 +49,8 @@ pub fn VISTA_400() -> FilmStock {
             [-0.01, 1.03, -0.02],
             [-0.02, -0.01, 1.03],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000207,
@@ -56,7 +61,9 @@ pub fn VISTA_400() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 115.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -64,6 +71,12 @@ pub fn VISTA_400() -> FilmStock {
         halation_threshold: 0.83,
         halation_sigma: 0.015,
         halation_tint: [1.0, 0.68, 0.48],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -158,7 +171,7 @@ pub fn VISTA_400() -> FilmStock {
 /// Source: Agfa Technical Data
 /// ISO: 200
 /// RMS: 10 -> Alpha = 0.0100
-/// Gamma: 0.65
+/// Gamma: R 0.67 / G 0.65 / B 0.62
 /// Dmax: 2.7, Dmin: 0.12
 /// Resolution: 125 lp/mm
 pub fn VISTA_200() -> FilmStock {
@@ -170,7 +183,7 @@ pub fn VISTA_200() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.13,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.67,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -184,7 +197,7 @@ pub fn VISTA_200() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.62,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -193,6 +206,8 @@ pub fn VISTA_200() -> FilmStock {
             [-0.03, 1.06, -0.03],
             [-0.03, -0.03, 1.06],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000100,
@@ -203,7 +218,9 @@ pub fn VISTA_200() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 125.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -211,6 +228,12 @@ pub fn VISTA_200() -> FilmStock {
         halation_threshold: 0.85,
         halation_sigma: 0.014,
         halation_tint: [1.0, 0.68, 0.48],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -305,7 +328,7 @@ pub fn VISTA_200() -> FilmStock {
 /// Source: Agfa Technical Data
 /// ISO: 100
 /// RMS: 8 -> Alpha = 0.0064
-/// Gamma: 0.65
+/// Gamma: R 0.67 / G 0.65 / B 0.63
 /// Dmax: 2.6, Dmin: 0.12
 /// Resolution: 135 lp/mm
 pub fn VISTA_100() -> FilmStock {
@@ -317,7 +340,7 @@ pub fn VISTA_100() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.13,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.67,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -331,7 +354,7 @@ pub fn VISTA_100() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.63,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -340,6 +363,8 @@ pub fn VISTA_100() -> FilmStock {
             [-0.04, 1.08, -0.04],
             [-0.04, -0.04, 1.08],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -350,7 +375,9 @@ pub fn VISTA_100() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 135.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -358,6 +385,12 @@ pub fn VISTA_100() -> FilmStock {
         halation_threshold: 0.87,
         halation_sigma: 0.013,
         halation_tint: [1.0, 0.68, 0.48],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -455,6 +488,9 @@ pub fn VISTA_100() -> FilmStock {
 /// Gamma: 0.70
 /// Dmax: 2.2, Dmin: 0.10
 /// Resolution: 110 lp/mm
+///
+/// Reciprocity: Agfa asks for correction from 1 s; beta 0.35 gives about
+/// +0.4 stop at 10 s and +1.3 stops at 100 s.
 pub fn APX_400() -> FilmStock {
     FilmStock {
         manufacturer: "Agfa".to_string(),
@@ -482,7 +518,9 @@ pub fn APX_400() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000286,
@@ -493,14 +531,22 @@ pub fn APX_400() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 110.0,
         vignette_strength: 0.5,
-        reciprocity: ReciprocityFailure { beta: 0.05 },
+        reciprocity: ReciprocityFailure { beta: 0.35 },
         halation_strength: 0.19,
         halation_threshold: 0.81,
         halation_sigma: 0.016,
         halation_tint: [0.86, 0.86, 0.86],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -554,6 +600,10 @@ pub fn APX_400() -> FilmStock {
 /// Gamma: 0.75
 /// Dmax: 2.3, Dmin: 0.10
 /// Resolution: 135 lp/mm
+///
+/// Reciprocity: the thin emulsion fails early and was known to need
+/// generous correction; beta 0.6 gives about +0.7 stop at 10 s and +1.8
+/// stops at 100 s.
 pub fn APX_100() -> FilmStock {
     FilmStock {
         manufacturer: "Agfa".to_string(),
@@ -581,7 +631,9 @@ pub fn APX_100() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000066,
@@ -592,14 +644,22 @@ pub fn APX_100() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 135.0,
         vignette_strength: 0.5,
-        reciprocity: ReciprocityFailure { beta: 0.05 },
+        reciprocity: ReciprocityFailure { beta: 0.6 },
         halation_strength: 0.14,
         halation_threshold: 0.86,
         halation_sigma: 0.014,
         halation_tint: [0.91, 0.91, 0.91],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -650,7 +710,7 @@ pub fn APX_100() -> FilmStock {
 /// Source: Agfa Technical Data
 /// ISO: 100
 /// RMS: 8 -> Alpha = 0.0064
-/// Gamma: 1.3
+/// Gamma: R 1.28 / G 1.3 / B 1.33
 /// Dmax: 3.5, Dmin: 0.12
 /// Resolution: 145 lp/mm
 pub fn PRECISA_100() -> FilmStock {
@@ -662,7 +722,7 @@ pub fn PRECISA_100() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.5,
-            gamma: 1.3,
+            gamma: 1.28,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -676,7 +736,7 @@ pub fn PRECISA_100() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.5,
-            gamma: 1.3,
+            gamma: 1.33,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -685,6 +745,8 @@ pub fn PRECISA_100() -> FilmStock {
             [-0.06, 1.12, -0.06],
             [-0.06, -0.06, 1.12],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -695,7 +757,9 @@ pub fn PRECISA_100() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 145.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -703,6 +767,12 @@ pub fn PRECISA_100() -> FilmStock {
         halation_threshold: 0.89,
         halation_sigma: 0.011,
         halation_tint: [0.96, 0.96, 0.96],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.10, -0.05],
@@ -818,7 +888,9 @@ pub fn SCALA_200() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000100,
@@ -829,7 +901,9 @@ pub fn SCALA_200() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 130.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -837,6 +911,12 @@ pub fn SCALA_200() -> FilmStock {
         halation_threshold: 0.88,
         halation_sigma: 0.012,
         halation_tint: [0.93, 0.93, 0.93],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -887,7 +967,7 @@ pub fn SCALA_200() -> FilmStock {
 /// Source: Agfa Technical Data
 /// ISO: 200
 /// RMS: 10 -> Alpha = 0.0100
-/// Gamma: 0.65
+/// Gamma: R 0.66 / G 0.65 / B 0.63
 /// Dmax: 2.7, Dmin: 0.12
 /// Resolution: 120 lp/mm
 pub fn OPTIMA_200() -> FilmStock {
@@ -899,7 +979,7 @@ pub fn OPTIMA_200() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.13,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.66,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -913,7 +993,7 @@ pub fn OPTIMA_200() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.63,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -922,6 +1002,8 @@ pub fn OPTIMA_200() -> FilmStock {
             [-0.02, 1.04, -0.02],
             [-0.02, -0.02, 1.04],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000100,
@@ -932,7 +1014,9 @@ pub fn OPTIMA_200() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 120.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -940,6 +1024,12 @@ pub fn OPTIMA_200() -> FilmStock {
         halation_threshold: 0.85,
         halation_sigma: 0.014,
         halation_tint: [1.0, 0.68, 0.48],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -1030,6 +1120,160 @@ pub fn OPTIMA_200() -> FilmStock {
     }
 }
 
+/// Agfa Portrait 160 (Professional Color Negative)
+/// Source: Agfa Technical Data
+/// ISO: 160
+/// RMS: 7 -> Alpha = 0.0049
+/// Gamma: R 0.56 / G 0.58 / B 0.61 (low contrast for skin tones)
+/// Dmax: 2.6, Dmin: 0.12
+/// Resolution: 130 lp/mm
+pub fn PORTRAIT_160() -> FilmStock {
+    FilmStock {
+        manufacturer: "Agfa".to_string(),
+        name: "Portrait 160".to_string(),
+        film_type: FilmType::ColorNegative,
+        iso: 160.0,
+        r_curve: SegmentedCurve {
+            d_min: 0.13,
+            d_max: 2.6,
+            gamma: 0.56,
+            shoulder_point: 0.8,
+            exposure_offset: 0.125,
+        },
+        g_curve: SegmentedCurve {
+            d_min: 0.15,
+            d_max: 2.6,
+            gamma: 0.58,
+            shoulder_point: 0.8,
+            exposure_offset: 0.125,
+        },
+        b_curve: SegmentedCurve {
+            d_min: 0.18,
+            d_max: 2.6,
+            gamma: 0.61,
+            shoulder_point: 0.8,
+            exposure_offset: 0.125,
+        },
+        // Muted saturation, as for a portrait stock
+        color_matrix: [[0.98, 0.01, 0.01], [0.01, 0.98, 0.01], [0.01, 0.01, 0.98]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
+        spectral_params: FilmSpectralParams::new_color_negative_standard(),
+        grain_model: GrainModel {
+            alpha: 0.000049,
+            sigma_read: 0.005,
+            monochrome: false,
+            blur_radius: 0.5,
+            roughness: 0.4,
+            color_correlation: 0.8,
+            shadow_noise: 0.001,
+            highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
+        },
+        film_format: FilmFormat::Format35mm,
+        resolution_lp_mm: 130.0,
+        vignette_strength: 0.5,
+        reciprocity: ReciprocityFailure { beta: 0.05 },
+        halation_strength: 0.14,
+        halation_threshold: 0.85,
+        halation_sigma: 0.014,
+        halation_tint: [1.0, 0.68, 0.48],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
+        layer_stack: Some(FilmLayerStack {
+            inhibition: [
+                [0.00, -0.05, -0.03],
+                [-0.04, 0.00, -0.03],
+                [-0.04, -0.05, 0.00],
+            ],
+            layers: vec![
+                FilmLayer {
+                    name: "Overcoat".into(),
+                    kind: LayerKind::Overcoat,
+                    thickness_um: 1.0,
+                    refractive_index: 1.50,
+                    absorption: [0.0; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Blue Emulsion".into(),
+                    kind: LayerKind::Emulsion {
+                        channel: EmulsionChannel::Blue,
+                    },
+                    thickness_um: 5.0,
+                    refractive_index: 1.53,
+                    absorption: gaussian_absorption(450.0, 30.0, 0.12),
+                    scattering: 0.020,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Yellow Filter".into(),
+                    kind: LayerKind::YellowFilter,
+                    thickness_um: 1.0,
+                    refractive_index: 1.52,
+                    absorption: gaussian_absorption(440.0, 35.0, 0.85),
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Green Emulsion".into(),
+                    kind: LayerKind::Emulsion {
+                        channel: EmulsionChannel::Green,
+                    },
+                    thickness_um: 4.5,
+                    refractive_index: 1.53,
+                    absorption: gaussian_absorption(545.0, 32.0, 0.10),
+                    scattering: 0.020,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Interlayer".into(),
+                    kind: LayerKind::Interlayer,
+                    thickness_um: 1.0,
+                    refractive_index: 1.50,
+                    absorption: [0.0; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Red Emulsion".into(),
+                    kind: LayerKind::Emulsion {
+                        channel: EmulsionChannel::Red,
+                    },
+                    thickness_um: 4.5,
+                    refractive_index: 1.53,
+                    absorption: gaussian_absorption(640.0, 38.0, 0.09),
+                    scattering: 0.020,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Anti-Halation".into(),
+                    kind: LayerKind::AntiHalation,
+                    thickness_um: 2.0,
+                    refractive_index: 1.50,
+                    absorption: gaussian_absorption(600.0, 120.0, 0.5),
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Base".into(),
+                    kind: LayerKind::Base,
+                    thickness_um: 127.0,
+                    refractive_index: 1.65,
+                    absorption: [0.001; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+            ],
+        }),
+    }
+}
+
 /// Get all Agfa film stocks
 pub fn get_stocks() -> Vec<FilmStock> {
     vec![
@@ -1041,5 +1285,6 @@ pub fn get_stocks() -> Vec<FilmStock> {
         PRECISA_100(),
         SCALA_200(),
         OPTIMA_200(),
+        PORTRAIT_160(),
     ]
 }
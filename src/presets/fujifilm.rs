@@ -2,16 +2,19 @@
 
 #![allow(non_snake_case)]
 
-use crate::film::{FilmStock, FilmType, ReciprocityFailure, SegmentedCurve};
+use crate::film::{
+    FilmFormat, FilmStock, FilmType, ReciprocityFailure, SegmentedCurve, DAYLIGHT_BALANCE_K,
+    FILM_STOCK_VERSION, REC709_LUMA,
+};
 use crate::film_layer::*;
-use crate::grain::GrainModel;
+use crate::grain::{GrainModel, GrainStructure};
 use crate::spectral::{FilmSpectralParams, BINS};
 
 /// Fujifilm Superia 400 (Consumer Color Negative)
 /// Source: Fujifilm Technical Data
 /// ISO: 400
 /// RMS: 12 -> Alpha = 0.0144
-/// Gamma: 0.65
+/// Gamma: R 0.62 / G 0.65 / B 0.67
 /// Dmax: 2.8, Dmin: 0.12
 /// Resolution: 120 lp/mm
 pub fn SUPERIA_400() -> FilmStock {
@@ -23,7 +26,7 @@ pub fn SUPERIA_400() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.13,
             d_max: 2.8,
-            gamma: 0.65,
+            gamma: 0.62,
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
@@ -37,7 +40,7 @@ pub fn SUPERIA_400() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.17,
             d_max: 2.8,
-            gamma: 0.65,
+            gamma: 0.67,
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
@@ -46,6 +49,8 @@ pub fn SUPERIA_400() -> FilmStock {
             [-0.02, 1.05, -0.03],
             [-0.03, -0.02, 1.05],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000207,
@@ -56,7 +61,9 @@ pub fn SUPERIA_400() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 120.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -64,6 +71,12 @@ pub fn SUPERIA_400() -> FilmStock {
         halation_threshold: 0.84,
         halation_sigma: 0.015,
         halation_tint: [1.0, 0.65, 0.45],
+        halation_channel_gain: [0.9, 0.35, 0.12], // Fuji: slightly cool
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -158,7 +171,7 @@ pub fn SUPERIA_400() -> FilmStock {
 /// Source: Fujifilm Technical Data
 /// ISO: 200
 /// RMS: 10 -> Alpha = 0.0100
-/// Gamma: 0.65
+/// Gamma: R 0.62 / G 0.65 / B 0.67
 /// Dmax: 2.7, Dmin: 0.12
 /// Resolution: 130 lp/mm
 pub fn SUPERIA_200() -> FilmStock {
@@ -170,7 +183,7 @@ pub fn SUPERIA_200() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.13,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.62,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -184,7 +197,7 @@ pub fn SUPERIA_200() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.17,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.67,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -193,6 +206,8 @@ pub fn SUPERIA_200() -> FilmStock {
             [-0.04, 1.08, -0.04],
             [-0.04, -0.04, 1.08],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000100,
@@ -203,7 +218,9 @@ pub fn SUPERIA_200() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 130.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -211,6 +228,12 @@ pub fn SUPERIA_200() -> FilmStock {
         halation_threshold: 0.86,
         halation_sigma: 0.014,
         halation_tint: [1.0, 0.65, 0.45],
+        halation_channel_gain: [0.9, 0.35, 0.12], // Fuji: slightly cool
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -305,7 +328,7 @@ pub fn SUPERIA_200() -> FilmStock {
 /// Source: Fujifilm Technical Data
 /// ISO: 100
 /// RMS: 8 -> Alpha = 0.0064
-/// Gamma: 0.65
+/// Gamma: R 0.63 / G 0.65 / B 0.67
 /// Dmax: 2.6, Dmin: 0.12
 /// Resolution: 140 lp/mm
 pub fn SUPERIA_100() -> FilmStock {
@@ -317,7 +340,7 @@ pub fn SUPERIA_100() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.13,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.63,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -331,7 +354,7 @@ pub fn SUPERIA_100() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.17,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.67,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -340,6 +363,8 @@ pub fn SUPERIA_100() -> FilmStock {
             [-0.05, 1.10, -0.05],
             [-0.05, -0.05, 1.10],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -350,7 +375,9 @@ pub fn SUPERIA_100() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 140.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -358,6 +385,12 @@ pub fn SUPERIA_100() -> FilmStock {
         halation_threshold: 0.88,
         halation_sigma: 0.012,
         halation_tint: [1.0, 0.65, 0.45],
+        halation_channel_gain: [0.9, 0.35, 0.12], // Fuji: slightly cool
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.09, -0.05],
@@ -482,7 +515,9 @@ pub fn NEOPAN_400() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000384,
@@ -493,7 +528,9 @@ pub fn NEOPAN_400() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 110.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -501,6 +538,12 @@ pub fn NEOPAN_400() -> FilmStock {
         halation_threshold: 0.82,
         halation_sigma: 0.016,
         halation_tint: [0.85, 0.85, 0.85],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -581,7 +624,9 @@ pub fn NEOPAN_100() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000066,
@@ -592,7 +637,9 @@ pub fn NEOPAN_100() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 140.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -600,6 +647,12 @@ pub fn NEOPAN_100() -> FilmStock {
         halation_threshold: 0.85,
         halation_sigma: 0.014,
         halation_tint: [0.90, 0.90, 0.90],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -650,7 +703,7 @@ pub fn NEOPAN_100() -> FilmStock {
 /// Source: Fujifilm Technical Data
 /// ISO: 100
 /// RMS: 8 -> Alpha = 0.0064
-/// Gamma: 1.3
+/// Gamma: R 1.28 / G 1.3 / B 1.32
 /// Dmax: 3.5, Dmin: 0.12
 /// Resolution: 150 lp/mm
 pub fn PROVIA_100F() -> FilmStock {
@@ -662,7 +715,7 @@ pub fn PROVIA_100F() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.5,
-            gamma: 1.3,
+            gamma: 1.28,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -676,7 +729,7 @@ pub fn PROVIA_100F() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.5,
-            gamma: 1.3,
+            gamma: 1.32,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -685,6 +738,8 @@ pub fn PROVIA_100F() -> FilmStock {
             [-0.07, 1.15, -0.08],
             [-0.08, -0.07, 1.15],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -695,7 +750,9 @@ pub fn PROVIA_100F() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 150.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -703,6 +760,12 @@ pub fn PROVIA_100F() -> FilmStock {
         halation_threshold: 0.90,
         halation_sigma: 0.010,
         halation_tint: [0.95, 0.95, 0.95],
+        halation_channel_gain: [0.9, 0.35, 0.12], // Fuji: slightly cool
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.11, -0.06],
@@ -788,7 +851,7 @@ pub fn PROVIA_100F() -> FilmStock {
 /// Source: Fujifilm 2020 Data Sheet
 /// ISO: 50
 /// RMS: 9 -> Alpha = 0.000081
-/// Gamma: 1.2-1.4
+/// Gamma: R 1.38 / G 1.4 / B 1.45
 /// Dmax: 3.5-4.0, Dmin: 0.15
 /// Resolution: 160 lp/mm
 pub fn VELVIA_50() -> FilmStock {
@@ -800,7 +863,7 @@ pub fn VELVIA_50() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.15,
             d_max: 3.6,
-            gamma: 1.4,
+            gamma: 1.38,
             shoulder_point: 0.8,
             exposure_offset: 49.22617,
         },
@@ -814,7 +877,7 @@ pub fn VELVIA_50() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.15,
             d_max: 3.6,
-            gamma: 1.4,
+            gamma: 1.45,
             shoulder_point: 0.8,
             exposure_offset: 49.22617,
         },
@@ -823,6 +886,8 @@ pub fn VELVIA_50() -> FilmStock {
             [-0.15, 1.30, -0.15],
             [-0.15, -0.15, 1.30],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000081,
@@ -833,14 +898,23 @@ pub fn VELVIA_50() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 160.0,
         vignette_strength: 0.5,
-        reciprocity: ReciprocityFailure { beta: 0.05 },
+        // Fuji: +1/3 stop at 4 s, +1/2 stop at 8 s
+        reciprocity: ReciprocityFailure { beta: 0.5 },
         halation_strength: 0.08,
         halation_threshold: 0.92,
         halation_sigma: 0.008,
         halation_tint: [1.0, 0.4, 0.4],
+        halation_channel_gain: [0.9, 0.35, 0.12], // Fuji: slightly cool
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             // Slide film: stronger interimage for vivid colour separation
             inhibition: [
@@ -955,7 +1029,7 @@ pub fn VELVIA_50_ARTISTIC() -> FilmStock {
 /// Source: Fujifilm Technical Data
 /// ISO: 100
 /// RMS: 8 -> Alpha = 0.0064
-/// Gamma: 1.2
+/// Gamma: R 1.18 / G 1.2 / B 1.22
 /// Dmax: 3.4, Dmin: 0.12
 /// Resolution: 145 lp/mm
 pub fn ASTIA_100F() -> FilmStock {
@@ -967,7 +1041,7 @@ pub fn ASTIA_100F() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.4,
-            gamma: 1.2,
+            gamma: 1.18,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -981,7 +1055,7 @@ pub fn ASTIA_100F() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.4,
-            gamma: 1.2,
+            gamma: 1.22,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -990,6 +1064,8 @@ pub fn ASTIA_100F() -> FilmStock {
             [-0.02, 1.05, -0.03],
             [-0.03, -0.02, 1.05],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -1000,7 +1076,9 @@ pub fn ASTIA_100F() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 145.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -1008,6 +1086,12 @@ pub fn ASTIA_100F() -> FilmStock {
         halation_threshold: 0.91,
         halation_sigma: 0.009,
         halation_tint: [0.98, 0.98, 1.0],
+        halation_channel_gain: [0.9, 0.35, 0.12], // Fuji: slightly cool
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.09, -0.05],
@@ -2,9 +2,12 @@
 
 #![allow(non_snake_case)]
 
-use crate::film::{FilmStock, FilmType, ReciprocityFailure, SegmentedCurve};
+use crate::film::{
+    FilmFormat, FilmStock, FilmType, ReciprocityFailure, SegmentedCurve, DAYLIGHT_BALANCE_K,
+    FILM_STOCK_VERSION, REC709_LUMA,
+};
 use crate::film_layer::*;
-use crate::grain::GrainModel;
+use crate::grain::{GrainModel, GrainStructure};
 use crate::spectral::{FilmSpectralParams, BINS};
 
 /// Ilford HP5 Plus 400 (Professional B&W)
@@ -41,7 +44,9 @@ pub fn HP5_PLUS_400() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 34.22952,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000256,
@@ -52,7 +57,9 @@ pub fn HP5_PLUS_400() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 95.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -60,6 +67,12 @@ pub fn HP5_PLUS_400() -> FilmStock {
         halation_threshold: 0.83,
         halation_sigma: 0.015,
         halation_tint: [0.88, 0.88, 0.88],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -162,7 +175,9 @@ pub fn FP4_PLUS_125() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.16,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000066,
@@ -173,7 +188,9 @@ pub fn FP4_PLUS_125() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 140.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -181,6 +198,12 @@ pub fn FP4_PLUS_125() -> FilmStock {
         halation_threshold: 0.85,
         halation_sigma: 0.014,
         halation_tint: [0.92, 0.92, 0.92],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -261,7 +284,9 @@ pub fn DELTA_400_PROFESSIONAL() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000146,
@@ -272,7 +297,9 @@ pub fn DELTA_400_PROFESSIONAL() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 130.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -280,6 +307,12 @@ pub fn DELTA_400_PROFESSIONAL() -> FilmStock {
         halation_threshold: 0.84,
         halation_sigma: 0.015,
         halation_tint: [0.90, 0.90, 0.90],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -360,7 +393,9 @@ pub fn DELTA_100_PROFESSIONAL() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -371,7 +406,9 @@ pub fn DELTA_100_PROFESSIONAL() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 150.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -379,6 +416,12 @@ pub fn DELTA_100_PROFESSIONAL() -> FilmStock {
         halation_threshold: 0.88,
         halation_sigma: 0.012,
         halation_tint: [0.94, 0.94, 0.94],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -459,7 +502,9 @@ pub fn PAN_F_PLUS_50() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.40,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000013,
@@ -470,14 +515,23 @@ pub fn PAN_F_PLUS_50() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 170.0,
         vignette_strength: 0.5,
-        reciprocity: ReciprocityFailure { beta: 0.05 },
+        // Ilford: t_corrected = t^1.33, about +1 stop at 10 s
+        reciprocity: ReciprocityFailure { beta: 1.1 },
         halation_strength: 0.10,
         halation_threshold: 0.90,
         halation_sigma: 0.010,
         halation_tint: [0.96, 0.96, 0.96],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -558,7 +612,9 @@ pub fn XP2_SUPER_400() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000100,
@@ -569,7 +625,9 @@ pub fn XP2_SUPER_400() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 125.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -577,6 +635,12 @@ pub fn XP2_SUPER_400() -> FilmStock {
         halation_threshold: 0.86,
         halation_sigma: 0.014,
         halation_tint: [0.92, 0.92, 0.92],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -657,7 +721,9 @@ pub fn SFX_200() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: [0.40, 0.50, 0.10], // Extended red sensitivity
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000100,
@@ -668,7 +734,9 @@ pub fn SFX_200() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 130.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -676,6 +744,12 @@ pub fn SFX_200() -> FilmStock {
         halation_threshold: 0.84,
         halation_sigma: 0.015,
         halation_tint: [0.90, 0.90, 0.90],
+        halation_channel_gain: [1.5, 0.15, 0.0], // Extended red: very strong red bloom
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 0.3, // Weak backing: strong glow in the extended red
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -756,7 +830,9 @@ pub fn ORTHO_PLUS_80() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.25,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: [0.0, 0.70, 0.30], // Orthochromatic: blind to red
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -767,7 +843,9 @@ pub fn ORTHO_PLUS_80() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 140.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -775,6 +853,12 @@ pub fn ORTHO_PLUS_80() -> FilmStock {
         halation_threshold: 0.87,
         halation_sigma: 0.013,
         halation_tint: [0.93, 0.93, 0.93],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -2,9 +2,12 @@
 
 #![allow(non_snake_case)]
 
-use crate::film::{FilmStock, FilmType, ReciprocityFailure, SegmentedCurve};
+use crate::film::{
+    FilmFormat, FilmStock, FilmType, ReciprocityFailure, SegmentedCurve, DAYLIGHT_BALANCE_K,
+    FILM_STOCK_VERSION, REC709_LUMA,
+};
 use crate::film_layer::*;
-use crate::grain::GrainModel;
+use crate::grain::{GrainModel, GrainStructure};
 use crate::spectral::{FilmSpectralParams, BINS};
 
 /// Kodak Portra 400 (Professional Color Negative)
@@ -12,7 +15,7 @@ use crate::spectral::{FilmSpectralParams, BINS};
 /// Source: Kodak E-7053
 /// ISO: 400
 /// PGI: 35 -> RMS: 11.2 -> Alpha = 0.000125
-/// Gamma: 0.65
+/// Gamma: R 0.62 / G 0.65 / B 0.69
 /// Dmax: 2.9, Dmin: 0.15
 /// Resolution: 115 lp/mm
 pub fn KODAK_PORTRA_400() -> FilmStock {
@@ -24,7 +27,7 @@ pub fn KODAK_PORTRA_400() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14, // Orange mask residual: R most transparent
             d_max: 2.9,
-            gamma: 0.65,
+            gamma: 0.62,
             shoulder_point: 0.8,
             exposure_offset: 625.046_9,
         },
@@ -38,7 +41,7 @@ pub fn KODAK_PORTRA_400() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.19, // Orange mask residual: B most opaque
             d_max: 2.9,
-            gamma: 0.65,
+            gamma: 0.69,
             shoulder_point: 0.8,
             exposure_offset: 625.046_9,
         },
@@ -47,6 +50,8 @@ pub fn KODAK_PORTRA_400() -> FilmStock {
             [-0.03, 1.07, -0.04],
             [-0.04, -0.03, 1.07],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000125,
@@ -57,7 +62,9 @@ pub fn KODAK_PORTRA_400() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 115.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -65,6 +72,12 @@ pub fn KODAK_PORTRA_400() -> FilmStock {
         halation_threshold: 0.85,
         halation_sigma: 0.014,
         halation_tint: [1.0, 0.70, 0.50],
+        halation_channel_gain: [1.0, 0.15, 0.02], // Kodak: strongly red-biased
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.10, -0.05],
@@ -221,7 +234,7 @@ pub fn KODAK_PORTRA_400_ARTISTIC() -> FilmStock {
 /// Source: Kodak Technical Data
 /// ISO: 160
 /// RMS: 9 -> Alpha = 0.0081
-/// Gamma: 0.65
+/// Gamma: R 0.62 / G 0.65 / B 0.68
 /// Dmax: 2.7, Dmin: 0.12
 /// Resolution: 140 lp/mm
 pub fn KODAK_PORTRA_160() -> FilmStock {
@@ -233,7 +246,7 @@ pub fn KODAK_PORTRA_160() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.62,
             shoulder_point: 0.8,
             exposure_offset: 0.13,
         },
@@ -247,7 +260,7 @@ pub fn KODAK_PORTRA_160() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.19,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.68,
             shoulder_point: 0.8,
             exposure_offset: 0.13,
         },
@@ -256,6 +269,8 @@ pub fn KODAK_PORTRA_160() -> FilmStock {
             [-0.04, 1.09, -0.05],
             [-0.05, -0.04, 1.09],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000066,
@@ -266,7 +281,9 @@ pub fn KODAK_PORTRA_160() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 140.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -274,6 +291,12 @@ pub fn KODAK_PORTRA_160() -> FilmStock {
         halation_threshold: 0.87,
         halation_sigma: 0.013,
         halation_tint: [1.0, 0.70, 0.50],
+        halation_channel_gain: [1.0, 0.15, 0.02], // Kodak: strongly red-biased
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.09, -0.05],
@@ -368,7 +391,7 @@ pub fn KODAK_PORTRA_160() -> FilmStock {
 /// Source: Kodak Technical Data
 /// ISO: 800
 /// RMS: 13 -> Alpha = 0.0169
-/// Gamma: 0.65
+/// Gamma: R 0.63 / G 0.65 / B 0.70
 /// Dmax: 2.9, Dmin: 0.12
 /// Resolution: 110 lp/mm
 pub fn KODAK_PORTRA_800() -> FilmStock {
@@ -380,7 +403,7 @@ pub fn KODAK_PORTRA_800() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.9,
-            gamma: 0.65,
+            gamma: 0.63,
             shoulder_point: 0.8,
             exposure_offset: 0.03,
         },
@@ -394,7 +417,7 @@ pub fn KODAK_PORTRA_800() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.19,
             d_max: 2.9,
-            gamma: 0.65,
+            gamma: 0.70,
             shoulder_point: 0.8,
             exposure_offset: 0.03,
         },
@@ -403,6 +426,8 @@ pub fn KODAK_PORTRA_800() -> FilmStock {
             [-0.02, 1.05, -0.03],
             [-0.03, -0.02, 1.05],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000286,
@@ -413,7 +438,9 @@ pub fn KODAK_PORTRA_800() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 110.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -421,6 +448,12 @@ pub fn KODAK_PORTRA_800() -> FilmStock {
         halation_threshold: 0.83,
         halation_sigma: 0.015,
         halation_tint: [1.0, 0.70, 0.50],
+        halation_channel_gain: [1.0, 0.15, 0.02], // Kodak: strongly red-biased
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -552,7 +585,9 @@ pub fn KODAK_TRI_X_400() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 48.87788,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000289,
@@ -563,7 +598,9 @@ pub fn KODAK_TRI_X_400() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::Clumped { size: 2.5 },
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 100.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -571,6 +608,12 @@ pub fn KODAK_TRI_X_400() -> FilmStock {
         halation_threshold: 0.82,
         halation_sigma: 0.016,
         halation_tint: [0.85, 0.85, 0.85],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -689,7 +732,9 @@ pub fn KODAK_PLUS_X_125() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.16,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000066,
@@ -700,7 +745,9 @@ pub fn KODAK_PLUS_X_125() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 140.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -708,6 +755,12 @@ pub fn KODAK_PLUS_X_125() -> FilmStock {
         halation_threshold: 0.85,
         halation_sigma: 0.014,
         halation_tint: [0.92, 0.92, 0.92],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -758,7 +811,7 @@ pub fn KODAK_PLUS_X_125() -> FilmStock {
 /// Source: Kodak Technical Data
 /// ISO: 100
 /// RMS: 8 -> Alpha = 0.0064
-/// Gamma: 1.3
+/// Gamma: R 1.27 / G 1.3 / B 1.34
 /// Dmax: 3.5, Dmin: 0.12
 /// Resolution: 150 lp/mm
 pub fn KODAK_EKTACHROME_100() -> FilmStock {
@@ -770,7 +823,7 @@ pub fn KODAK_EKTACHROME_100() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.5,
-            gamma: 1.3,
+            gamma: 1.27,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -784,7 +837,7 @@ pub fn KODAK_EKTACHROME_100() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.5,
-            gamma: 1.3,
+            gamma: 1.34,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -793,6 +846,8 @@ pub fn KODAK_EKTACHROME_100() -> FilmStock {
             [-0.06, 1.13, -0.07],
             [-0.07, -0.06, 1.13],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -803,7 +858,9 @@ pub fn KODAK_EKTACHROME_100() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 150.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -811,6 +868,12 @@ pub fn KODAK_EKTACHROME_100() -> FilmStock {
         halation_threshold: 0.90,
         halation_sigma: 0.010,
         halation_tint: [0.95, 0.95, 0.95],
+        halation_channel_gain: [1.0, 0.15, 0.02], // Kodak: strongly red-biased
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.12, -0.06],
@@ -896,7 +959,7 @@ pub fn KODAK_EKTACHROME_100() -> FilmStock {
 /// Source: Kodak Technical Data
 /// ISO: 100
 /// RMS: 8 -> Alpha = 0.0064
-/// Gamma: 1.35
+/// Gamma: R 1.33 / G 1.35 / B 1.38
 /// Dmax: 3.5, Dmin: 0.12
 /// Resolution: 150 lp/mm
 pub fn KODAK_EKTACHROME_100VS() -> FilmStock {
@@ -908,7 +971,7 @@ pub fn KODAK_EKTACHROME_100VS() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.5,
-            gamma: 1.35,
+            gamma: 1.33,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -922,7 +985,7 @@ pub fn KODAK_EKTACHROME_100VS() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.5,
-            gamma: 1.35,
+            gamma: 1.38,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -931,6 +994,8 @@ pub fn KODAK_EKTACHROME_100VS() -> FilmStock {
             [-0.09, 1.18, -0.09],
             [-0.09, -0.09, 1.18],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -941,7 +1006,9 @@ pub fn KODAK_EKTACHROME_100VS() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 150.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -949,6 +1016,12 @@ pub fn KODAK_EKTACHROME_100VS() -> FilmStock {
         halation_threshold: 0.90,
         halation_sigma: 0.010,
         halation_tint: [0.95, 0.95, 0.95],
+        halation_channel_gain: [1.0, 0.15, 0.02], // Kodak: strongly red-biased
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.14, -0.07],
@@ -1034,7 +1107,7 @@ pub fn KODAK_EKTACHROME_100VS() -> FilmStock {
 /// Source: Kodak Technical Data
 /// ISO: 64
 /// RMS: 7 -> Alpha = 0.0049
-/// Gamma: 1.4
+/// Gamma: R 1.44 / G 1.4 / B 1.36
 /// Dmax: 3.6, Dmin: 0.10
 /// Resolution: 160 lp/mm
 pub fn KODAK_KODACHROME_64() -> FilmStock {
@@ -1046,7 +1119,7 @@ pub fn KODAK_KODACHROME_64() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.10,
             d_max: 3.6,
-            gamma: 1.4,
+            gamma: 1.44,
             shoulder_point: 0.8,
             exposure_offset: 0.31,
         },
@@ -1060,7 +1133,7 @@ pub fn KODAK_KODACHROME_64() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.10,
             d_max: 3.6,
-            gamma: 1.4,
+            gamma: 1.36,
             shoulder_point: 0.8,
             exposure_offset: 0.31,
         },
@@ -1069,6 +1142,8 @@ pub fn KODAK_KODACHROME_64() -> FilmStock {
             [-0.12, 1.25, -0.13],
             [-0.13, -0.12, 1.25],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000024,
@@ -1079,7 +1154,9 @@ pub fn KODAK_KODACHROME_64() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 160.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -1087,6 +1164,12 @@ pub fn KODAK_KODACHROME_64() -> FilmStock {
         halation_threshold: 0.92,
         halation_sigma: 0.008,
         halation_tint: [1.0, 0.35, 0.35],
+        halation_channel_gain: [1.0, 0.15, 0.02], // Kodak: strongly red-biased
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.12, -0.06],
@@ -1172,7 +1255,7 @@ pub fn KODAK_KODACHROME_64() -> FilmStock {
 /// Source: Kodak Technical Data
 /// ISO: 200
 /// RMS: 10 -> Alpha = 0.0100
-/// Gamma: 0.65
+/// Gamma: R 0.68 / G 0.65 / B 0.62
 /// Dmax: 2.7, Dmin: 0.12
 /// Resolution: 130 lp/mm
 pub fn KODAK_GOLD_200() -> FilmStock {
@@ -1184,7 +1267,7 @@ pub fn KODAK_GOLD_200() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.68,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -1198,7 +1281,7 @@ pub fn KODAK_GOLD_200() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.19,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.62,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -1207,6 +1290,8 @@ pub fn KODAK_GOLD_200() -> FilmStock {
             [-0.03, 1.06, -0.03],
             [-0.03, -0.03, 1.06],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000100,
@@ -1217,7 +1302,9 @@ pub fn KODAK_GOLD_200() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 130.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -1225,6 +1312,12 @@ pub fn KODAK_GOLD_200() -> FilmStock {
         halation_threshold: 0.86,
         halation_sigma: 0.014,
         halation_tint: [1.0, 0.72, 0.52],
+        halation_channel_gain: [1.0, 0.15, 0.02], // Kodak: strongly red-biased
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -1319,7 +1412,7 @@ pub fn KODAK_GOLD_200() -> FilmStock {
 /// Source: Kodak Technical Data
 /// ISO: 100
 /// RMS: 8 -> Alpha = 0.0064
-/// Gamma: 0.65
+/// Gamma: R 0.68 / G 0.65 / B 0.66
 /// Dmax: 2.6, Dmin: 0.12
 /// Resolution: 145 lp/mm
 pub fn KODAK_EKTAR_100() -> FilmStock {
@@ -1331,7 +1424,7 @@ pub fn KODAK_EKTAR_100() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.68,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -1345,7 +1438,7 @@ pub fn KODAK_EKTAR_100() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.19,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.66,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -1354,6 +1447,8 @@ pub fn KODAK_EKTAR_100() -> FilmStock {
             [-0.05, 1.10, -0.05],
             [-0.05, -0.05, 1.10],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -1364,7 +1459,9 @@ pub fn KODAK_EKTAR_100() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 145.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -1372,6 +1469,12 @@ pub fn KODAK_EKTAR_100() -> FilmStock {
         halation_threshold: 0.88,
         halation_sigma: 0.012,
         halation_tint: [1.0, 0.72, 0.52],
+        halation_channel_gain: [1.0, 0.15, 0.02], // Kodak: strongly red-biased
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.10, -0.06],
@@ -1466,7 +1569,7 @@ pub fn KODAK_EKTAR_100() -> FilmStock {
 /// Source: Kodak Technical Data
 /// ISO: 25
 /// RMS: 5 -> Alpha = 0.0025
-/// Gamma: 1.5
+/// Gamma: R 1.54 / G 1.5 / B 1.46
 /// Dmax: 3.8, Dmin: 0.08
 /// Resolution: 200 lp/mm
 pub fn KODAK_KODACHROME_25() -> FilmStock {
@@ -1478,7 +1581,7 @@ pub fn KODAK_KODACHROME_25() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.08,
             d_max: 3.8,
-            gamma: 1.5,
+            gamma: 1.54,
             shoulder_point: 0.8,
             exposure_offset: 0.60,
         },
@@ -1492,7 +1595,7 @@ pub fn KODAK_KODACHROME_25() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.08,
             d_max: 3.8,
-            gamma: 1.5,
+            gamma: 1.46,
             shoulder_point: 0.8,
             exposure_offset: 0.60,
         },
@@ -1501,6 +1604,8 @@ pub fn KODAK_KODACHROME_25() -> FilmStock {
             [-0.15, 1.30, -0.15],
             [-0.15, -0.15, 1.30],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000025,
@@ -1511,7 +1616,9 @@ pub fn KODAK_KODACHROME_25() -> FilmStock {
             color_correlation: 0.85,
             shadow_noise: 0.0005,
             highlight_coarseness: 0.03,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 200.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.08 },
@@ -1519,6 +1626,12 @@ pub fn KODAK_KODACHROME_25() -> FilmStock {
         halation_threshold: 0.94,
         halation_sigma: 0.006,
         halation_tint: [1.0, 0.30, 0.30],
+        halation_channel_gain: [1.0, 0.15, 0.02], // Kodak: strongly red-biased
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.12, -0.06],
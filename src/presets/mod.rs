@@ -1,7 +1,9 @@
 //! Film stock presets organized by manufacturer
 
-use crate::film::FilmStock;
+use crate::film::{FilmStock, FilmType};
+use std::collections::{BTreeMap, HashSet};
 use std::rc::Rc;
+use std::sync::OnceLock;
 
 pub mod agfa;
 pub mod fujifilm;
@@ -9,6 +11,8 @@ pub mod ilford;
 pub mod kodak;
 pub mod other;
 pub mod polaroid;
+pub mod rollei;
+pub mod specialty;
 
 /// Get all available film stock presets
 pub fn get_all_stocks() -> Vec<Rc<FilmStock>> {
@@ -19,8 +23,173 @@ pub fn get_all_stocks() -> Vec<Rc<FilmStock>> {
     stocks.extend(fujifilm::get_stocks().into_iter().map(Rc::from));
     stocks.extend(ilford::get_stocks().into_iter().map(Rc::from));
     stocks.extend(agfa::get_stocks().into_iter().map(Rc::from));
+    stocks.extend(rollei::get_stocks().into_iter().map(Rc::from));
     stocks.extend(polaroid::get_stocks().into_iter().map(Rc::from));
     stocks.extend(other::get_stocks().into_iter().map(Rc::from));
+    stocks.extend(specialty::get_stocks().into_iter().map(Rc::from));
 
     stocks
 }
+
+/// [`get_all_stocks`] with each full name once, keeping the first, so the
+/// names can be used as keys.
+pub fn all_unique() -> Vec<Rc<FilmStock>> {
+    let mut seen = HashSet::new();
+    let mut stocks = get_all_stocks();
+    stocks.retain(|stock| seen.insert(stock.full_name()));
+    stocks
+}
+
+/// Look up a preset by full name, e.g. "Kodak Portra 400". Case is ignored
+/// and '-' matches a space, so `kodak-portra-400` finds it too. A
+/// [`FilmStock::stable_id`] always finds its stock.
+pub fn by_name(name: &str) -> Option<Rc<FilmStock>> {
+    let key = normalize_name(name);
+    lookup_table()
+        .iter()
+        .find(|entry| entry.name == key || entry.id == name.trim())
+        .map(|entry| Rc::new(entry.stock.clone()))
+}
+
+/// A preset with its [`by_name`] keys worked out once.
+struct LookupEntry {
+    name: String,
+    id: String,
+    stock: FilmStock,
+}
+
+/// [`all_unique`] in registry order, built on the first lookup.
+fn lookup_table() -> &'static [LookupEntry] {
+    static TABLE: OnceLock<Vec<LookupEntry>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        all_unique()
+            .into_iter()
+            .map(|stock| LookupEntry {
+                name: normalize_name(&stock.full_name()),
+                id: stock.stable_id(),
+                stock: stock.as_ref().clone(),
+            })
+            .collect()
+    })
+}
+
+/// Group a stock is listed under: its manufacturer, or "Custom" for stocks
+/// without one.
+pub fn group_key(stock: &FilmStock) -> String {
+    if stock.manufacturer.is_empty() {
+        "Custom".to_string()
+    } else {
+        stock.manufacturer.clone()
+    }
+}
+
+/// [`all_unique`] grouped by [`group_key`], in registry order within a group.
+pub fn grouped() -> BTreeMap<String, Vec<Rc<FilmStock>>> {
+    let mut groups: BTreeMap<String, Vec<Rc<FilmStock>>> = BTreeMap::new();
+    for stock in all_unique() {
+        groups.entry(group_key(&stock)).or_default().push(stock);
+    }
+    groups
+}
+
+/// [`all_unique`] grouped by film type, in registry order within a group.
+pub fn grouped_by_type() -> BTreeMap<FilmType, Vec<Rc<FilmStock>>> {
+    let mut groups: BTreeMap<FilmType, Vec<Rc<FilmStock>>> = BTreeMap::new();
+    for stock in all_unique() {
+        groups.entry(stock.film_type).or_default().push(stock);
+    }
+    groups
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase().replace('-', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_name_round_trip() {
+        for stock in all_unique() {
+            let found = by_name(&stock.full_name()).unwrap();
+            assert_eq!(found.full_name(), stock.full_name());
+        }
+        let tri_x = by_name("kodak-tri-x-400").unwrap();
+        assert_eq!(tri_x.full_name(), "Kodak Tri-X 400");
+        assert!(by_name("Kodak Nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_stable_ids_unique() {
+        let mut ids = HashSet::new();
+        for stock in all_unique() {
+            let id = stock.stable_id();
+            assert!(ids.insert(id.clone()), "{} is not unique", id);
+            assert_eq!(by_name(&id).unwrap().full_name(), stock.full_name());
+        }
+    }
+
+    #[test]
+    fn test_full_names_unique() {
+        let mut names = HashSet::new();
+        for stock in get_all_stocks() {
+            let name = stock.full_name();
+            assert!(names.insert(name.clone()), "{} is registered twice", name);
+        }
+    }
+
+    #[test]
+    fn test_rollei_stocks_reach_into_the_near_ir() {
+        let rollei = &grouped()["Rollei"];
+        assert_eq!(rollei.len(), 3);
+        for stock in rollei {
+            assert!(stock.is_monochrome());
+            assert!(stock.spectral_params.r_peak > 700.0, "{}", stock.name);
+            assert!(stock.validate().is_ok(), "{}", stock.name);
+        }
+
+        // Long exposures: IR falls off fastest, APX 100 before APX 400
+        let at_10s = |name| by_name(name).unwrap().reciprocity.effective_time(10.0);
+        assert!(at_10s("Rollei Infrared 400") < at_10s("Rollei Retro 400S"));
+        assert!(at_10s("Agfa APX 100") < at_10s("Agfa APX 400"));
+        assert!(at_10s("Agfa APX 400") < 8.0);
+    }
+
+    #[test]
+    fn test_grouping_covers_every_stock() {
+        let count = all_unique().len();
+
+        let groups = grouped();
+        assert_eq!(groups.values().map(Vec::len).sum::<usize>(), count);
+        for (manufacturer, stocks) in &groups {
+            assert!(stocks.iter().all(|s| &s.manufacturer == manufacturer));
+        }
+        assert!(groups["Ilford"].iter().any(|s| s.name == "SFX 200"));
+        assert_eq!(groups["Specialty"].len(), 2);
+
+        let by_type = grouped_by_type();
+        assert_eq!(by_type.values().map(Vec::len).sum::<usize>(), count);
+        for (film_type, stocks) in &by_type {
+            assert!(stocks.iter().all(|s| s.film_type == *film_type));
+        }
+    }
+
+    #[test]
+    fn test_color_presets_have_channel_crossover() {
+        let crossover = |stock: &FilmStock| {
+            let gammas = [
+                stock.r_curve.gamma,
+                stock.g_curve.gamma,
+                stock.b_curve.gamma,
+            ];
+            gammas.iter().any(|g| (g - gammas[0]).abs() > 1e-3)
+        };
+
+        assert!(all_unique()
+            .iter()
+            .any(|s| !s.is_monochrome() && crossover(s)));
+        let portra = by_name("Kodak Portra 400").unwrap();
+        assert!(portra.b_curve.gamma > portra.g_curve.gamma);
+    }
+}
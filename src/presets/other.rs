@@ -2,9 +2,12 @@
 
 #![allow(non_snake_case)]
 
-use crate::film::{FilmStock, FilmType, ReciprocityFailure, SegmentedCurve};
+use crate::film::{
+    FilmFormat, FilmStock, FilmType, ReciprocityFailure, SegmentedCurve, DAYLIGHT_BALANCE_K,
+    FILM_STOCK_VERSION, REC709_LUMA, TUNGSTEN_BALANCE_K,
+};
 use crate::film_layer::*;
-use crate::grain::GrainModel;
+use crate::grain::{GrainModel, GrainStructure};
 use crate::spectral::{FilmSpectralParams, BINS};
 
 /// Standard Daylight Film (Generic)
@@ -36,6 +39,8 @@ pub fn STANDARD_DAYLIGHT() -> FilmStock {
             shoulder_point: 0.8,
         },
         color_matrix: [[1.00, 0.05, 0.02], [0.04, 1.00, 0.04], [0.01, 0.05, 1.00]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.0005,
@@ -46,7 +51,9 @@ pub fn STANDARD_DAYLIGHT() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 80.0,
         vignette_strength: 0.5,
         reciprocity: crate::film::ReciprocityFailure { beta: 0.05 },
@@ -54,6 +61,12 @@ pub fn STANDARD_DAYLIGHT() -> FilmStock {
         halation_threshold: 0.8,
         halation_sigma: 0.02,
         halation_tint: [1.0, 0.4, 0.2],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -148,7 +161,7 @@ pub fn STANDARD_DAYLIGHT() -> FilmStock {
 /// Source: CineStill Technical Data
 /// ISO: 800
 /// RMS: 13 -> Alpha = 0.0169
-/// Gamma: 0.65
+/// Gamma: R 0.61 / G 0.65 / B 0.69
 /// Dmax: 2.9, Dmin: 0.12
 /// Resolution: 110 lp/mm
 pub fn CINESTILL_800T() -> FilmStock {
@@ -160,7 +173,7 @@ pub fn CINESTILL_800T() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.9,
-            gamma: 0.65,
+            gamma: 0.61,
             shoulder_point: 0.8,
             exposure_offset: 0.03,
         },
@@ -174,7 +187,7 @@ pub fn CINESTILL_800T() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.9,
-            gamma: 0.65,
+            gamma: 0.69,
             shoulder_point: 0.8,
             exposure_offset: 0.03,
         },
@@ -183,6 +196,8 @@ pub fn CINESTILL_800T() -> FilmStock {
             [-0.02, 1.04, -0.02],
             [-0.02, -0.02, 1.04],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: TUNGSTEN_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000286,
@@ -193,7 +208,9 @@ pub fn CINESTILL_800T() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 110.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -201,6 +218,12 @@ pub fn CINESTILL_800T() -> FilmStock {
         halation_threshold: 0.83,
         halation_sigma: 0.015,
         halation_tint: [1.0, 0.65, 0.45],
+        halation_channel_gain: [1.0, 0.15, 0.02], // No rem-jet: strong red bloom
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 0.0, // Rem-jet removed
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -295,7 +318,7 @@ pub fn CINESTILL_800T() -> FilmStock {
 /// Source: CineStill Technical Data
 /// ISO: 50
 /// RMS: 6 -> Alpha = 0.0036
-/// Gamma: 0.65
+/// Gamma: R 0.63 / G 0.65 / B 0.68
 /// Dmax: 2.6, Dmin: 0.12
 /// Resolution: 145 lp/mm
 pub fn CINESTILL_50D() -> FilmStock {
@@ -307,7 +330,7 @@ pub fn CINESTILL_50D() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.63,
             shoulder_point: 0.8,
             exposure_offset: 0.40,
         },
@@ -321,7 +344,7 @@ pub fn CINESTILL_50D() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.68,
             shoulder_point: 0.8,
             exposure_offset: 0.40,
         },
@@ -330,6 +353,8 @@ pub fn CINESTILL_50D() -> FilmStock {
             [-0.04, 1.08, -0.04],
             [-0.04, -0.04, 1.08],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000013,
@@ -340,7 +365,9 @@ pub fn CINESTILL_50D() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 145.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -348,6 +375,12 @@ pub fn CINESTILL_50D() -> FilmStock {
         halation_threshold: 0.89,
         halation_sigma: 0.011,
         halation_tint: [1.0, 0.65, 0.45],
+        halation_channel_gain: [1.0, 0.15, 0.02], // No rem-jet: strong red bloom
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 0.0, // Rem-jet removed
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -442,7 +475,7 @@ pub fn CINESTILL_50D() -> FilmStock {
 /// Source: Lomography Technical Data
 /// ISO: 200
 /// RMS: 10 -> Alpha = 0.0100
-/// Gamma: 1.2
+/// Gamma: R 1.24 / G 1.2 / B 1.17
 /// Dmax: 3.3, Dmin: 0.12
 /// Resolution: 120 lp/mm
 pub fn LOMOGRAPHY_COLOR_CHROME() -> FilmStock {
@@ -454,7 +487,7 @@ pub fn LOMOGRAPHY_COLOR_CHROME() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.3,
-            gamma: 1.2,
+            gamma: 1.24,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -468,7 +501,7 @@ pub fn LOMOGRAPHY_COLOR_CHROME() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.12,
             d_max: 3.3,
-            gamma: 1.2,
+            gamma: 1.17,
             shoulder_point: 0.8,
             exposure_offset: 0.10,
         },
@@ -477,6 +510,8 @@ pub fn LOMOGRAPHY_COLOR_CHROME() -> FilmStock {
             [-0.09, 1.18, -0.09],
             [-0.09, -0.09, 1.18],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000100,
@@ -487,7 +522,9 @@ pub fn LOMOGRAPHY_COLOR_CHROME() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 120.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -495,6 +532,12 @@ pub fn LOMOGRAPHY_COLOR_CHROME() -> FilmStock {
         halation_threshold: 0.87,
         halation_sigma: 0.013,
         halation_tint: [0.95, 0.95, 0.95],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -589,7 +632,7 @@ pub fn LOMOGRAPHY_COLOR_CHROME() -> FilmStock {
 /// Source: Lomography Technical Data
 /// ISO: 400
 /// RMS: 12 -> Alpha = 0.0144
-/// Gamma: 0.70
+/// Gamma: R 0.66 / G 0.70 / B 0.75
 /// Dmax: 2.8, Dmin: 0.12
 /// Resolution: 110 lp/mm
 pub fn LOMOGRAPHY_LOMOCHROME_PURPLE() -> FilmStock {
@@ -601,7 +644,7 @@ pub fn LOMOGRAPHY_LOMOCHROME_PURPLE() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.8,
-            gamma: 0.70,
+            gamma: 0.66,
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
@@ -615,11 +658,13 @@ pub fn LOMOGRAPHY_LOMOCHROME_PURPLE() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.8,
-            gamma: 0.70,
+            gamma: 0.75,
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
         color_matrix: [[0.95, 0.05, 0.00], [0.00, 0.95, 0.05], [0.05, 0.00, 0.95]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000207,
@@ -630,7 +675,9 @@ pub fn LOMOGRAPHY_LOMOCHROME_PURPLE() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 110.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -638,6 +685,12 @@ pub fn LOMOGRAPHY_LOMOCHROME_PURPLE() -> FilmStock {
         halation_threshold: 0.84,
         halation_sigma: 0.015,
         halation_tint: [0.8, 0.5, 1.0],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -732,7 +785,7 @@ pub fn LOMOGRAPHY_LOMOCHROME_PURPLE() -> FilmStock {
 /// Source: Ferrania Technical Data
 /// ISO: 400
 /// RMS: 12 -> Alpha = 0.0144
-/// Gamma: 0.65
+/// Gamma: R 0.68 / G 0.65 / B 0.62
 /// Dmax: 2.8, Dmin: 0.12
 /// Resolution: 120 lp/mm
 pub fn FERRANIA_SOLARIS_400() -> FilmStock {
@@ -744,7 +797,7 @@ pub fn FERRANIA_SOLARIS_400() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.8,
-            gamma: 0.65,
+            gamma: 0.68,
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
@@ -758,7 +811,7 @@ pub fn FERRANIA_SOLARIS_400() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.8,
-            gamma: 0.65,
+            gamma: 0.62,
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
@@ -767,6 +820,8 @@ pub fn FERRANIA_SOLARIS_400() -> FilmStock {
             [-0.03, 1.06, -0.03],
             [-0.03, -0.03, 1.06],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000207,
@@ -777,7 +832,9 @@ pub fn FERRANIA_SOLARIS_400() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 120.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -785,6 +842,12 @@ pub fn FERRANIA_SOLARIS_400() -> FilmStock {
         halation_threshold: 0.85,
         halation_sigma: 0.014,
         halation_tint: [1.0, 0.70, 0.50],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -879,7 +942,7 @@ pub fn FERRANIA_SOLARIS_400() -> FilmStock {
 /// Source: Ferrania Technical Data
 /// ISO: 100
 /// RMS: 8 -> Alpha = 0.0064
-/// Gamma: 0.65
+/// Gamma: R 0.68 / G 0.65 / B 0.63
 /// Dmax: 2.6, Dmin: 0.12
 /// Resolution: 140 lp/mm
 pub fn FERRANIA_SOLARIS_100() -> FilmStock {
@@ -891,7 +954,7 @@ pub fn FERRANIA_SOLARIS_100() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.68,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -905,7 +968,7 @@ pub fn FERRANIA_SOLARIS_100() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.6,
-            gamma: 0.65,
+            gamma: 0.63,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -914,6 +977,8 @@ pub fn FERRANIA_SOLARIS_100() -> FilmStock {
             [-0.04, 1.09, -0.05],
             [-0.05, -0.04, 1.09],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -924,7 +989,9 @@ pub fn FERRANIA_SOLARIS_100() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 140.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -932,6 +999,12 @@ pub fn FERRANIA_SOLARIS_100() -> FilmStock {
         halation_threshold: 0.88,
         halation_sigma: 0.012,
         halation_tint: [1.0, 0.70, 0.50],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.08, -0.04],
@@ -1056,7 +1129,9 @@ pub fn ORWO_UN54() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.05,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000207,
@@ -1067,7 +1142,9 @@ pub fn ORWO_UN54() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 120.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -1075,6 +1152,12 @@ pub fn ORWO_UN54() -> FilmStock {
         halation_threshold: 0.83,
         halation_sigma: 0.015,
         halation_tint: [0.89, 0.89, 0.89],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 0.6, // Older emulsion, weaker backing
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -1155,7 +1238,9 @@ pub fn ORWO_UN64() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.31,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000024,
@@ -1166,7 +1251,9 @@ pub fn ORWO_UN64() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 150.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -1174,6 +1261,12 @@ pub fn ORWO_UN64() -> FilmStock {
         halation_threshold: 0.88,
         halation_sigma: 0.012,
         halation_tint: [0.94, 0.94, 0.94],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 0.6, // Older emulsion, weaker backing
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -1257,6 +1350,8 @@ pub fn RICOH_GR_STREET() -> FilmStock {
             [-0.03, 1.05, -0.02],
             [-0.05, -0.03, 1.03],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.0008,
@@ -1267,7 +1362,9 @@ pub fn RICOH_GR_STREET() -> FilmStock {
             color_correlation: 0.85, // mostly luminance grain
             shadow_noise: 0.002,
             highlight_coarseness: 0.08,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 90.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.03 },
@@ -1275,6 +1372,12 @@ pub fn RICOH_GR_STREET() -> FilmStock {
         halation_threshold: 0.88,
         halation_sigma: 0.012,
         halation_tint: [1.0, 0.6, 0.3], // warm orange halation
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             // Strong inhibition for punchy color separation
             inhibition: [
@@ -1404,6 +1507,8 @@ pub fn LUCKY_COLOR_200() -> FilmStock {
             [-0.02, 1.03, -0.01],
             [-0.04, -0.02, 1.01],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.0006, // coarse grain
@@ -1414,7 +1519,9 @@ pub fn LUCKY_COLOR_200() -> FilmStock {
             color_correlation: 0.93,
             shadow_noise: 0.001,
             highlight_coarseness: 0.08,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 80.0, // lower resolution than Japanese films
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.06 },
@@ -1422,6 +1529,12 @@ pub fn LUCKY_COLOR_200() -> FilmStock {
         halation_threshold: 0.85,
         halation_sigma: 0.015,
         halation_tint: [1.0, 0.5, 0.3],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             // Moderate inhibition
             inhibition: [
@@ -2,16 +2,19 @@
 
 #![allow(non_snake_case)]
 
-use crate::film::{FilmStock, FilmType, ReciprocityFailure, SegmentedCurve};
+use crate::film::{
+    FilmFormat, FilmStock, FilmType, ReciprocityFailure, SegmentedCurve, DAYLIGHT_BALANCE_K,
+    FILM_STOCK_VERSION, REC709_LUMA,
+};
 use crate::film_layer::*;
-use crate::grain::GrainModel;
+use crate::grain::{GrainModel, GrainStructure};
 use crate::spectral::{FilmSpectralParams, BINS};
 
 /// Polaroid 600 Color (Instant Color Film)
 /// Source: Polaroid Technical Data
 /// ISO: 600
 /// RMS: 15 -> Alpha = 0.0225
-/// Gamma: 0.60
+/// Gamma: R 0.63 / G 0.60 / B 0.56
 /// Dmax: 2.5, Dmin: 0.15
 /// Resolution: 80 lp/mm
 pub fn POLAROID_600_COLOR() -> FilmStock {
@@ -23,7 +26,7 @@ pub fn POLAROID_600_COLOR() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.5,
-            gamma: 0.60,
+            gamma: 0.63,
             shoulder_point: 0.8,
             exposure_offset: 0.02,
         },
@@ -37,7 +40,7 @@ pub fn POLAROID_600_COLOR() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.5,
-            gamma: 0.60,
+            gamma: 0.56,
             shoulder_point: 0.8,
             exposure_offset: 0.02,
         },
@@ -46,6 +49,8 @@ pub fn POLAROID_600_COLOR() -> FilmStock {
             [-0.01, 1.02, -0.01],
             [-0.01, -0.01, 1.02],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000225,
@@ -56,7 +61,9 @@ pub fn POLAROID_600_COLOR() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.002,
             highlight_coarseness: 0.08,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 80.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -64,6 +71,12 @@ pub fn POLAROID_600_COLOR() -> FilmStock {
         halation_threshold: 0.80,
         halation_sigma: 0.018,
         halation_tint: [1.0, 0.75, 0.55],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.05, -0.03],
@@ -131,7 +144,7 @@ pub fn POLAROID_600_COLOR() -> FilmStock {
 /// Source: Polaroid Technical Data
 /// ISO: 150
 /// RMS: 12 -> Alpha = 0.0144
-/// Gamma: 0.70
+/// Gamma: R 0.74 / G 0.70 / B 0.66
 /// Dmax: 2.8, Dmin: 0.15
 /// Resolution: 90 lp/mm
 pub fn POLAROID_SX70_COLOR() -> FilmStock {
@@ -143,7 +156,7 @@ pub fn POLAROID_SX70_COLOR() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.15,
             d_max: 2.8,
-            gamma: 0.70,
+            gamma: 0.74,
             shoulder_point: 0.8,
             exposure_offset: 0.13,
         },
@@ -157,7 +170,7 @@ pub fn POLAROID_SX70_COLOR() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.15,
             d_max: 2.8,
-            gamma: 0.70,
+            gamma: 0.66,
             shoulder_point: 0.8,
             exposure_offset: 0.13,
         },
@@ -166,6 +179,8 @@ pub fn POLAROID_SX70_COLOR() -> FilmStock {
             [-0.02, 1.04, -0.02],
             [-0.02, -0.02, 1.04],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000207,
@@ -176,7 +191,9 @@ pub fn POLAROID_SX70_COLOR() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.002,
             highlight_coarseness: 0.08,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 90.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -184,6 +201,12 @@ pub fn POLAROID_SX70_COLOR() -> FilmStock {
         halation_threshold: 0.82,
         halation_sigma: 0.016,
         halation_tint: [1.0, 0.75, 0.55],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.05, -0.03],
@@ -251,7 +274,7 @@ pub fn POLAROID_SX70_COLOR() -> FilmStock {
 /// Source: Polaroid Technical Data
 /// ISO: 640
 /// RMS: 16 -> Alpha = 0.0256
-/// Gamma: 0.62
+/// Gamma: R 0.65 / G 0.62 / B 0.58
 /// Dmax: 2.6, Dmin: 0.15
 /// Resolution: 85 lp/mm
 pub fn POLAROID_I_TYPE_COLOR() -> FilmStock {
@@ -263,7 +286,7 @@ pub fn POLAROID_I_TYPE_COLOR() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.6,
-            gamma: 0.62,
+            gamma: 0.65,
             shoulder_point: 0.8,
             exposure_offset: 0.02,
         },
@@ -277,7 +300,7 @@ pub fn POLAROID_I_TYPE_COLOR() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.6,
-            gamma: 0.62,
+            gamma: 0.58,
             shoulder_point: 0.8,
             exposure_offset: 0.02,
         },
@@ -286,6 +309,8 @@ pub fn POLAROID_I_TYPE_COLOR() -> FilmStock {
             [-0.00, 1.01, -0.01],
             [-0.01, -0.00, 1.01],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000256,
@@ -296,7 +321,9 @@ pub fn POLAROID_I_TYPE_COLOR() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.002,
             highlight_coarseness: 0.08,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 85.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -304,6 +331,12 @@ pub fn POLAROID_I_TYPE_COLOR() -> FilmStock {
         halation_threshold: 0.78,
         halation_sigma: 0.020,
         halation_tint: [1.0, 0.75, 0.55],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.05, -0.03],
@@ -401,7 +434,9 @@ pub fn POLAROID_BW_667() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.02,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000400,
@@ -412,7 +447,9 @@ pub fn POLAROID_BW_667() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.003,
             highlight_coarseness: 0.10,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 70.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -420,6 +457,12 @@ pub fn POLAROID_BW_667() -> FilmStock {
         halation_threshold: 0.77,
         halation_sigma: 0.020,
         halation_tint: [0.80, 0.80, 0.80],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -461,7 +504,7 @@ pub fn POLAROID_BW_667() -> FilmStock {
 /// Source: Polaroid Technical Data
 /// ISO: 640
 /// RMS: 16 -> Alpha = 0.0256
-/// Gamma: 0.62
+/// Gamma: R 0.64 / G 0.62 / B 0.59
 /// Dmax: 2.6, Dmin: 0.15
 /// Resolution: 85 lp/mm
 pub fn POLAROID_SPECTRA_COLOR() -> FilmStock {
@@ -473,7 +516,7 @@ pub fn POLAROID_SPECTRA_COLOR() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.6,
-            gamma: 0.62,
+            gamma: 0.64,
             shoulder_point: 0.8,
             exposure_offset: 0.02,
         },
@@ -487,7 +530,7 @@ pub fn POLAROID_SPECTRA_COLOR() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.6,
-            gamma: 0.62,
+            gamma: 0.59,
             shoulder_point: 0.8,
             exposure_offset: 0.02,
         },
@@ -496,6 +539,8 @@ pub fn POLAROID_SPECTRA_COLOR() -> FilmStock {
             [-0.01, 1.03, -0.02],
             [-0.02, -0.01, 1.03],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000256,
@@ -506,7 +551,9 @@ pub fn POLAROID_SPECTRA_COLOR() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.002,
             highlight_coarseness: 0.08,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 85.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -514,6 +561,12 @@ pub fn POLAROID_SPECTRA_COLOR() -> FilmStock {
         halation_threshold: 0.78,
         halation_sigma: 0.020,
         halation_tint: [1.0, 0.75, 0.55],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.05, -0.03],
@@ -581,7 +634,7 @@ pub fn POLAROID_SPECTRA_COLOR() -> FilmStock {
 /// Source: Polaroid Technical Data
 /// ISO: 100
 /// RMS: 10 -> Alpha = 0.0100
-/// Gamma: 0.65
+/// Gamma: R 0.67 / G 0.65 / B 0.62
 /// Dmax: 2.7, Dmin: 0.15
 /// Resolution: 95 lp/mm
 pub fn POLAROID_100_COLOR() -> FilmStock {
@@ -593,7 +646,7 @@ pub fn POLAROID_100_COLOR() -> FilmStock {
         r_curve: SegmentedCurve {
             d_min: 0.14,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.67,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -607,7 +660,7 @@ pub fn POLAROID_100_COLOR() -> FilmStock {
         b_curve: SegmentedCurve {
             d_min: 0.18,
             d_max: 2.7,
-            gamma: 0.65,
+            gamma: 0.62,
             shoulder_point: 0.8,
             exposure_offset: 0.20,
         },
@@ -616,6 +669,8 @@ pub fn POLAROID_100_COLOR() -> FilmStock {
             [-0.02, 1.05, -0.03],
             [-0.03, -0.02, 1.05],
         ],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_color_negative_standard(),
         grain_model: GrainModel {
             alpha: 0.000100,
@@ -626,7 +681,9 @@ pub fn POLAROID_100_COLOR() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.06,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 95.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -634,6 +691,12 @@ pub fn POLAROID_100_COLOR() -> FilmStock {
         halation_threshold: 0.84,
         halation_sigma: 0.015,
         halation_tint: [1.0, 0.75, 0.55],
+        halation_channel_gain: [1.0, 0.25, 0.05],
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [
                 [0.00, -0.05, -0.03],
@@ -731,7 +794,9 @@ pub fn POLAROID_55_BW() -> FilmStock {
             shoulder_point: 0.8,
             exposure_offset: 0.40,
         },
-        color_matrix: [[0.33, 0.33, 0.33], [0.33, 0.33, 0.33], [0.33, 0.33, 0.33]],
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: REC709_LUMA,
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
         spectral_params: FilmSpectralParams::new_panchromatic(),
         grain_model: GrainModel {
             alpha: 0.000041,
@@ -742,7 +807,9 @@ pub fn POLAROID_55_BW() -> FilmStock {
             color_correlation: 0.8,
             shadow_noise: 0.001,
             highlight_coarseness: 0.06,
+            structure: GrainStructure::PerPixel,
         },
+        film_format: FilmFormat::Format35mm,
         resolution_lp_mm: 100.0,
         vignette_strength: 0.5,
         reciprocity: ReciprocityFailure { beta: 0.05 },
@@ -750,6 +817,12 @@ pub fn POLAROID_55_BW() -> FilmStock {
         halation_threshold: 0.86,
         halation_sigma: 0.014,
         halation_tint: [0.88, 0.88, 0.88],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
         layer_stack: Some(FilmLayerStack {
             inhibition: [[0.0; 3]; 3],
             layers: vec![
@@ -0,0 +1,365 @@
+//! Rollei film stock presets
+//!
+//! Rollei's B&W films are superpanchromatic: their sensitivity runs on past
+//! the visible red to about 750 nm (Retro) or 820 nm (Infrared). Red subjects
+//! and foliage print lighter than on ordinary panchromatic film, and behind a
+//! deep red filter the emulsions take on an infrared look.
+
+#![allow(non_snake_case)]
+
+use crate::film::{
+    FilmFormat, FilmStock, FilmType, ReciprocityFailure, SegmentedCurve, DAYLIGHT_BALANCE_K,
+    FILM_STOCK_VERSION,
+};
+use crate::film_layer::*;
+use crate::grain::{GrainModel, GrainStructure};
+use crate::spectral::{FilmSpectralParams, BINS};
+
+/// Rollei Retro 80S (Superpanchromatic B&W, aerial emulsion)
+/// Source: Rollei Technical Data
+/// ISO: 80
+/// RMS: 8 -> Alpha = 0.0064
+/// Gamma: 0.80
+/// Dmax: 2.4, Dmin: 0.08
+/// Resolution: 160 lp/mm
+///
+/// Reciprocity: an aerial emulsion made for short exposures. Rollei asks for
+/// correction from 1 s; beta 0.5 gives about +0.6 stop at 10 s and +1.6
+/// stops at 100 s.
+pub fn RETRO_80S() -> FilmStock {
+    FilmStock {
+        manufacturer: "Rollei".to_string(),
+        name: "Retro 80S".to_string(),
+        film_type: FilmType::BwNegative,
+        iso: 80.0,
+        r_curve: SegmentedCurve {
+            d_min: 0.08,
+            d_max: 2.4,
+            gamma: 0.80,
+            shoulder_point: 0.8,
+            exposure_offset: 0.22,
+        },
+        g_curve: SegmentedCurve {
+            d_min: 0.08,
+            d_max: 2.4,
+            gamma: 0.80,
+            shoulder_point: 0.8,
+            exposure_offset: 0.22,
+        },
+        b_curve: SegmentedCurve {
+            d_min: 0.08,
+            d_max: 2.4,
+            gamma: 0.80,
+            shoulder_point: 0.8,
+            exposure_offset: 0.22,
+        },
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: [0.32, 0.58, 0.10], // Extended red lightens reds and foliage
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
+        spectral_params: FilmSpectralParams::new_infrared(),
+        grain_model: GrainModel {
+            alpha: 0.000055,
+            sigma_read: 0.004,
+            monochrome: true,
+            blur_radius: 0.5,
+            roughness: 0.35,
+            color_correlation: 0.8,
+            shadow_noise: 0.001,
+            highlight_coarseness: 0.04,
+            structure: GrainStructure::PerPixel,
+        },
+        film_format: FilmFormat::Format35mm,
+        resolution_lp_mm: 160.0,
+        vignette_strength: 0.5,
+        reciprocity: ReciprocityFailure { beta: 0.5 },
+        halation_strength: 0.12,
+        halation_threshold: 0.86,
+        halation_sigma: 0.013,
+        halation_tint: [0.92, 0.92, 0.92],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 1.0,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
+        layer_stack: Some(FilmLayerStack {
+            inhibition: [[0.0; 3]; 3],
+            layers: vec![
+                FilmLayer {
+                    name: "Overcoat".into(),
+                    kind: LayerKind::Overcoat,
+                    thickness_um: 1.0,
+                    refractive_index: 1.50,
+                    absorption: [0.0; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Superpanchromatic Emulsion".into(),
+                    kind: LayerKind::Emulsion {
+                        channel: EmulsionChannel::Green,
+                    },
+                    thickness_um: 5.5,
+                    refractive_index: 1.53,
+                    absorption: gaussian_absorption(580.0, 110.0, 0.10),
+                    scattering: 0.016,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Anti-Halation".into(),
+                    kind: LayerKind::AntiHalation,
+                    thickness_um: 2.0,
+                    refractive_index: 1.50,
+                    absorption: gaussian_absorption(620.0, 120.0, 0.45),
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Base".into(),
+                    kind: LayerKind::Base,
+                    thickness_um: 100.0,
+                    refractive_index: 1.64,
+                    absorption: [0.001; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+            ],
+        }),
+    }
+}
+
+/// Rollei Retro 400S (Superpanchromatic B&W)
+/// Source: Rollei Technical Data
+/// ISO: 400
+/// RMS: 12 -> Alpha = 0.0144
+/// Gamma: 0.72
+/// Dmax: 2.2, Dmin: 0.10
+/// Resolution: 120 lp/mm
+///
+/// Sensitive to about 750 nm, so behind an R72 filter it works as an
+/// infrared film at roughly ISO 12-25.
+/// Reciprocity: beta 0.35 gives about +0.4 stop at 10 s and +1.3 stops at
+/// 100 s.
+pub fn RETRO_400S() -> FilmStock {
+    FilmStock {
+        manufacturer: "Rollei".to_string(),
+        name: "Retro 400S".to_string(),
+        film_type: FilmType::BwNegative,
+        iso: 400.0,
+        r_curve: SegmentedCurve {
+            d_min: 0.10,
+            d_max: 2.2,
+            gamma: 0.72,
+            shoulder_point: 0.8,
+            exposure_offset: 0.05,
+        },
+        g_curve: SegmentedCurve {
+            d_min: 0.10,
+            d_max: 2.2,
+            gamma: 0.72,
+            shoulder_point: 0.8,
+            exposure_offset: 0.05,
+        },
+        b_curve: SegmentedCurve {
+            d_min: 0.10,
+            d_max: 2.2,
+            gamma: 0.72,
+            shoulder_point: 0.8,
+            exposure_offset: 0.05,
+        },
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: [0.34, 0.56, 0.10], // Extended red lightens reds and foliage
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
+        spectral_params: FilmSpectralParams::new_infrared(),
+        grain_model: GrainModel {
+            alpha: 0.000200,
+            sigma_read: 0.006,
+            monochrome: true,
+            blur_radius: 0.5,
+            roughness: 0.5,
+            color_correlation: 0.8,
+            shadow_noise: 0.001,
+            highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
+        },
+        film_format: FilmFormat::Format35mm,
+        resolution_lp_mm: 120.0,
+        vignette_strength: 0.5,
+        reciprocity: ReciprocityFailure { beta: 0.35 },
+        halation_strength: 0.18,
+        halation_threshold: 0.82,
+        halation_sigma: 0.016,
+        halation_tint: [0.88, 0.88, 0.88],
+        halation_channel_gain: [0.4, 0.4, 0.4], // Neutral: a single B&W emulsion layer
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 0.8,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
+        layer_stack: Some(FilmLayerStack {
+            inhibition: [[0.0; 3]; 3],
+            layers: vec![
+                FilmLayer {
+                    name: "Overcoat".into(),
+                    kind: LayerKind::Overcoat,
+                    thickness_um: 1.0,
+                    refractive_index: 1.50,
+                    absorption: [0.0; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Superpanchromatic Emulsion".into(),
+                    kind: LayerKind::Emulsion {
+                        channel: EmulsionChannel::Green,
+                    },
+                    thickness_um: 8.0,
+                    refractive_index: 1.54,
+                    absorption: gaussian_absorption(590.0, 120.0, 0.08),
+                    scattering: 0.034,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Anti-Halation".into(),
+                    kind: LayerKind::AntiHalation,
+                    thickness_um: 2.0,
+                    refractive_index: 1.50,
+                    absorption: gaussian_absorption(620.0, 120.0, 0.35),
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Base".into(),
+                    kind: LayerKind::Base,
+                    thickness_um: 100.0,
+                    refractive_index: 1.64,
+                    absorption: [0.001; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+            ],
+        }),
+    }
+}
+
+/// Rollei Infrared 400 (Near-infrared B&W, unfiltered)
+/// Source: Rollei Technical Data
+/// ISO: 400
+/// RMS: 13 -> Alpha = 0.0169
+/// Gamma: 0.70
+/// Dmax: 2.1, Dmin: 0.14
+/// Resolution: 110 lp/mm
+///
+/// Sensitive to about 820 nm. Shot without a filter it renders like a
+/// superpanchromatic 400 with very light reds; for the full Wood effect
+/// behind a 720 nm filter see
+/// [`INFRARED_720`](crate::presets::specialty::INFRARED_720). The backing
+/// barely absorbs near-IR, so highlights bloom with a wide halo.
+/// Reciprocity: infrared emulsions fall off quickly; beta 1.0 gives about
+/// +1 stop at 10 s and +2.3 stops at 100 s.
+pub fn INFRARED_400() -> FilmStock {
+    FilmStock {
+        manufacturer: "Rollei".to_string(),
+        name: "Infrared 400".to_string(),
+        film_type: FilmType::BwNegative,
+        iso: 400.0,
+        r_curve: SegmentedCurve {
+            d_min: 0.14,
+            d_max: 2.1,
+            gamma: 0.70,
+            shoulder_point: 0.8,
+            exposure_offset: 0.05,
+        },
+        g_curve: SegmentedCurve {
+            d_min: 0.14,
+            d_max: 2.1,
+            gamma: 0.70,
+            shoulder_point: 0.8,
+            exposure_offset: 0.05,
+        },
+        b_curve: SegmentedCurve {
+            d_min: 0.14,
+            d_max: 2.1,
+            gamma: 0.70,
+            shoulder_point: 0.8,
+            exposure_offset: 0.05,
+        },
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: [0.42, 0.50, 0.08], // Reds and foliage glow, blue sky darkens
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
+        spectral_params: FilmSpectralParams::new_infrared(),
+        grain_model: GrainModel {
+            alpha: 0.000260,
+            sigma_read: 0.007,
+            monochrome: true,
+            blur_radius: 0.6,
+            roughness: 0.55,
+            color_correlation: 0.8,
+            shadow_noise: 0.001,
+            highlight_coarseness: 0.06,
+            structure: GrainStructure::PerPixel,
+        },
+        film_format: FilmFormat::Format35mm,
+        resolution_lp_mm: 110.0,
+        vignette_strength: 0.5,
+        reciprocity: ReciprocityFailure { beta: 1.0 },
+        halation_strength: 0.24,
+        halation_threshold: 0.79,
+        halation_sigma: 0.020,
+        halation_tint: [0.90, 0.90, 0.90],
+        halation_channel_gain: [1.0, 0.8, 0.2], // Glow follows the red-bright channels
+        halation_strength2: 0.08,
+        halation_sigma2: 0.06,
+        anti_halation_backing: 0.25, // The backing is thin in the near IR
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
+        layer_stack: Some(FilmLayerStack {
+            inhibition: [[0.0; 3]; 3],
+            layers: vec![
+                FilmLayer {
+                    name: "Overcoat".into(),
+                    kind: LayerKind::Overcoat,
+                    thickness_um: 1.0,
+                    refractive_index: 1.50,
+                    absorption: [0.0; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Infrared Emulsion".into(),
+                    kind: LayerKind::Emulsion {
+                        channel: EmulsionChannel::Red,
+                    },
+                    thickness_um: 7.5,
+                    refractive_index: 1.53,
+                    absorption: gaussian_absorption(640.0, 140.0, 0.08),
+                    scattering: 0.032,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Anti-Halation".into(),
+                    kind: LayerKind::AntiHalation,
+                    thickness_um: 2.0,
+                    refractive_index: 1.50,
+                    absorption: gaussian_absorption(580.0, 110.0, 0.15),
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Base".into(),
+                    kind: LayerKind::Base,
+                    thickness_um: 100.0,
+                    refractive_index: 1.64,
+                    absorption: [0.001; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+            ],
+        }),
+    }
+}
+
+/// Get all Rollei film stocks
+pub fn get_stocks() -> Vec<FilmStock> {
+    vec![RETRO_80S(), RETRO_400S(), INFRARED_400()]
+}
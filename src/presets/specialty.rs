@@ -0,0 +1,252 @@
+//! Specialty emulsion presets
+//!
+//! These stocks are defined by what they are blind or sensitive to, not by a
+//! particular product. How they render depends on the scene: the input is an
+//! RGB image, so the spectral response only decides how its channels are
+//! weighted. An ortho stock can only darken what the camera recorded as red,
+//! and the infrared look is inferred from visible color. Green foliage stands
+//! in for the IR-bright chlorophyll, and blue sky for the IR-dark sky.
+//! Subjects that don't follow those rules render like visible light. The
+//! spectral develop reconstructs the scene from RGB, which holds no real IR,
+//! so there the infrared stock falls back to a deep red filter look.
+
+#![allow(non_snake_case)]
+
+use crate::film::{
+    FilmFormat, FilmStock, FilmType, ReciprocityFailure, SegmentedCurve, DAYLIGHT_BALANCE_K,
+    FILM_STOCK_VERSION,
+};
+use crate::film_layer::*;
+use crate::grain::{GrainModel, GrainStructure};
+use crate::spectral::{FilmSpectralParams, BINS};
+
+/// Orthochromatic B&W (blue and green sensitive)
+/// Modeled on classic ortho emulsions such as Rollei Ortho 25
+/// ISO: 25
+/// RMS: 7 -> Alpha = 0.0049
+/// Gamma: 0.80
+/// Dmax: 2.2, Dmin: 0.08
+/// Resolution: 160 lp/mm
+///
+/// Blind to red: lips and skin go dark, and red subjects turn almost black.
+/// Blue skies print light and lose their clouds.
+pub fn ORTHOCHROMATIC_25() -> FilmStock {
+    FilmStock {
+        manufacturer: "Specialty".to_string(),
+        name: "Orthochromatic 25".to_string(),
+        film_type: FilmType::BwNegative,
+        iso: 25.0,
+        r_curve: SegmentedCurve {
+            d_min: 0.08,
+            d_max: 2.2,
+            gamma: 0.80,
+            shoulder_point: 0.8,
+            exposure_offset: 0.25,
+        },
+        g_curve: SegmentedCurve {
+            d_min: 0.08,
+            d_max: 2.2,
+            gamma: 0.80,
+            shoulder_point: 0.8,
+            exposure_offset: 0.25,
+        },
+        b_curve: SegmentedCurve {
+            d_min: 0.08,
+            d_max: 2.2,
+            gamma: 0.80,
+            shoulder_point: 0.8,
+            exposure_offset: 0.25,
+        },
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: [0.0, 0.60, 0.40], // No red response
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
+        spectral_params: FilmSpectralParams::new_orthochromatic(),
+        grain_model: GrainModel {
+            alpha: 0.000049,
+            sigma_read: 0.004,
+            monochrome: true,
+            blur_radius: 0.5,
+            roughness: 0.3,
+            color_correlation: 0.8,
+            shadow_noise: 0.001,
+            highlight_coarseness: 0.05,
+            structure: GrainStructure::PerPixel,
+        },
+        film_format: FilmFormat::Format35mm,
+        resolution_lp_mm: 160.0,
+        vignette_strength: 0.5,
+        reciprocity: ReciprocityFailure { beta: 0.08 },
+        halation_strength: 0.12,
+        halation_threshold: 0.85,
+        halation_sigma: 0.012,
+        halation_tint: [0.90, 0.90, 0.90],
+        halation_channel_gain: [0.0, 0.5, 0.5], // Red never exposes the emulsion
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 0.9,
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
+        layer_stack: Some(FilmLayerStack {
+            inhibition: [[0.0; 3]; 3],
+            layers: vec![
+                FilmLayer {
+                    name: "Overcoat".into(),
+                    kind: LayerKind::Overcoat,
+                    thickness_um: 1.0,
+                    refractive_index: 1.50,
+                    absorption: [0.0; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Orthochromatic Emulsion".into(),
+                    kind: LayerKind::Emulsion {
+                        channel: EmulsionChannel::Green,
+                    },
+                    thickness_um: 5.0,
+                    refractive_index: 1.53,
+                    absorption: gaussian_absorption(490.0, 55.0, 0.10),
+                    scattering: 0.02,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Anti-Halation".into(),
+                    kind: LayerKind::AntiHalation,
+                    thickness_um: 2.0,
+                    refractive_index: 1.50,
+                    absorption: gaussian_absorption(500.0, 80.0, 0.50),
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Base".into(),
+                    kind: LayerKind::Base,
+                    thickness_um: 127.0,
+                    refractive_index: 1.65,
+                    absorption: [0.001; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+            ],
+        }),
+    }
+}
+
+/// Infrared B&W behind a 720nm filter
+/// Modeled on IR-sensitive emulsions such as Rollei Infrared 400 shot through
+/// a Wratten 89B
+/// ISO: 25 (effective, through the filter)
+/// RMS: 12 -> Alpha = 0.0144
+/// Gamma: 0.75
+/// Dmax: 2.3, Dmin: 0.12
+/// Resolution: 110 lp/mm
+///
+/// The Wood effect: foliage glows white and blue sky goes nearly black.
+/// Skin turns pale and smooth. The emulsion has a thin backing, so
+/// highlights bloom.
+pub fn INFRARED_720() -> FilmStock {
+    FilmStock {
+        manufacturer: "Specialty".to_string(),
+        name: "Infrared 720".to_string(),
+        film_type: FilmType::BwNegative,
+        iso: 25.0,
+        r_curve: SegmentedCurve {
+            d_min: 0.12,
+            d_max: 2.3,
+            gamma: 0.75,
+            shoulder_point: 0.8,
+            exposure_offset: 0.10,
+        },
+        g_curve: SegmentedCurve {
+            d_min: 0.12,
+            d_max: 2.3,
+            gamma: 0.75,
+            shoulder_point: 0.8,
+            exposure_offset: 0.10,
+        },
+        b_curve: SegmentedCurve {
+            d_min: 0.12,
+            d_max: 2.3,
+            gamma: 0.75,
+            shoulder_point: 0.8,
+            exposure_offset: 0.10,
+        },
+        color_matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        bw_channel_mix: [0.45, 0.55, 0.0], // Foliage (green) bright, sky (blue) black
+        native_white_balance_k: DAYLIGHT_BALANCE_K,
+        spectral_params: FilmSpectralParams::new_infrared_filtered(),
+        grain_model: GrainModel {
+            alpha: 0.000144,
+            sigma_read: 0.006,
+            monochrome: true,
+            blur_radius: 0.6,
+            roughness: 0.5,
+            color_correlation: 0.8,
+            shadow_noise: 0.001,
+            highlight_coarseness: 0.06,
+            structure: GrainStructure::PerPixel,
+        },
+        film_format: FilmFormat::Format35mm,
+        resolution_lp_mm: 110.0,
+        vignette_strength: 0.5,
+        reciprocity: ReciprocityFailure { beta: 0.05 },
+        halation_strength: 0.22,
+        halation_threshold: 0.80,
+        halation_sigma: 0.020,
+        halation_tint: [0.90, 0.90, 0.90],
+        halation_channel_gain: [1.0, 1.0, 0.2], // Glow follows the IR-bright channels
+        halation_strength2: 0.0,
+        halation_sigma2: 0.0,
+        anti_halation_backing: 0.2, // The backing is thin in the near IR
+        halation_saturation_weight: 0.0,
+        version: FILM_STOCK_VERSION,
+        layer_stack: Some(FilmLayerStack {
+            inhibition: [[0.0; 3]; 3],
+            layers: vec![
+                FilmLayer {
+                    name: "Overcoat".into(),
+                    kind: LayerKind::Overcoat,
+                    thickness_um: 1.0,
+                    refractive_index: 1.50,
+                    absorption: [0.0; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Infrared Emulsion".into(),
+                    kind: LayerKind::Emulsion {
+                        channel: EmulsionChannel::Red,
+                    },
+                    thickness_um: 7.0,
+                    refractive_index: 1.53,
+                    absorption: gaussian_absorption(760.0, 30.0, 0.09),
+                    scattering: 0.03,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Anti-Halation".into(),
+                    kind: LayerKind::AntiHalation,
+                    thickness_um: 2.0,
+                    refractive_index: 1.50,
+                    absorption: gaussian_absorption(580.0, 110.0, 0.20),
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+                FilmLayer {
+                    name: "Base".into(),
+                    kind: LayerKind::Base,
+                    thickness_um: 127.0,
+                    refractive_index: 1.65,
+                    absorption: [0.001; BINS],
+                    scattering: 0.0,
+                    dye_spectrum: None,
+                },
+            ],
+        }),
+    }
+}
+
+/// Get all specialty film stocks
+pub fn get_stocks() -> Vec<FilmStock> {
+    vec![ORTHOCHROMATIC_25(), INFRARED_720()]
+}
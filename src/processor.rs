@@ -1,23 +1,31 @@
+use crate::dither::DitherMode;
+use crate::error::FilmrError;
 use crate::film::FilmStock;
 use crate::film_layer::FilmLayerStack;
-use crate::light_leak::{LightLeakConfig, LightLeakStage};
+use crate::grain::GrainSpace;
+use crate::light_leak::LightLeakConfig;
+use crate::mask::MaskConfig;
 use crate::pipeline::{
-    create_linear_image, create_output_image, ChromaticAberrationStage, DepthOfFieldStage,
-    HalationStage, MicroMotionStage, MtfStage, ObjectMotionStage, PipelineContext, PipelineStage,
-    RotationalBlurStage,
+    blend_effect, create_blended_output_image, encode_luma_output, linear_to_srgb_image,
+    linearize_input, linearize_input_value, linearize_luma16_input, render_linear_output,
+    render_output, resample_linear, resample_to_target, run_stages, OutputSteps, PipelineContext,
+    PipelineStage, StageKind,
 };
+use crate::scanner::ScannerProfile;
+use crate::shutter::{relative_exposure, ShutterSpeed};
 use crate::spectral_engine;
-use image::RgbImage;
+use image::{DynamicImage, ImageBuffer, Luma, RgbImage};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use tracing::{info, instrument};
+use std::borrow::Cow;
+use tracing::{info, instrument, warn};
 
 #[cfg(feature = "compute-gpu")]
-use crate::gpu::get_gpu_context;
+use crate::gpu::get_gpu_context_with_preference;
 #[cfg(feature = "compute-gpu")]
 use crate::gpu_pipelines::{
-    get_gaussian_pipeline, get_halation_pipeline, get_light_leak_pipeline, get_linearize_pipeline,
-    read_gpu_buffer,
+    get_chromatic_aberration_pipeline, get_diffusion_pipeline, get_halation_pipeline,
+    get_light_leak_pipeline, get_linearize_pipeline, read_gpu_buffers,
 };
 
 /// Simulation fidelity mode.
@@ -30,6 +38,23 @@ pub enum SimulationMode {
     Accurate,
 }
 
+/// Which GPU adapter to prefer on systems with more than one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum GpuPowerPreference {
+    /// Discrete GPU when available.
+    #[default]
+    HighPerformance,
+    /// Integrated GPU, saves battery on laptops.
+    LowPower,
+}
+
+/// Red and blue exposure gain at full [`SimulationConfig::warmth`].
+const WARMTH_GAIN: f32 = 0.1;
+
+/// Largest factor [`SimulationConfig::target_size`] develops above a smaller
+/// target before downscaling to it.
+pub const TARGET_SUPERSAMPLE: u32 = 2;
+
 /// Configuration for the simulation run.
 /// Controls all aspects of the physical simulation pipeline.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -37,24 +62,72 @@ pub struct SimulationConfig {
     /// Simulation fidelity mode.
     #[serde(default)]
     pub simulation_mode: SimulationMode,
-    /// Exposure time (t) in seconds.
-    /// Used for Reciprocity Failure calculation (E = I * t).
-    pub exposure_time: f32, // t in E = I * t
+    /// Relative exposure (H = E * t): 1.0 exposes 18% gray as metered, each
+    /// doubling is a stop over. Scales the light reaching the film.
+    pub exposure_time: f32,
+    /// Shutter speed of the shot. Only used for reciprocity failure, which
+    /// depends on the real time in seconds; the amount of light is still set
+    /// by `exposure_time`.
+    #[serde(default)]
+    pub shutter_speed: Option<ShutterSpeed>,
     /// Enable or disable grain simulation.
     pub enable_grain: bool,
+    /// Space the output grain is added in.
+    #[serde(default)]
+    pub grain_space: GrainSpace,
     /// Enable GPU acceleration if available.
     #[serde(default)]
     pub use_gpu: bool,
-    /// Output mode: Negative (Transmission) or Positive (Scanned).
+    /// Adapter preference used when the GPU context is first created.
+    /// Falls back to the CPU pipeline if no suitable adapter exists. The
+    /// context is shared by the whole process, so only the first GPU render
+    /// picks the adapter; a later config asking for another one logs a
+    /// warning and uses the existing context.
+    #[serde(default)]
+    pub gpu_power_preference: GpuPowerPreference,
+    /// Output mode: Negative (Transmission), Positive (Scanned) or Inverted
+    /// (Negative scanned, then inverted).
     pub output_mode: OutputMode,
+    /// Mask removal and print contrast of [`OutputMode::Inverted`].
+    #[serde(default)]
+    pub inversion: NegativeInversion,
+    /// Transfer function of the final 8-bit encode.
+    #[serde(default)]
+    pub output_transfer: OutputTransfer,
+    /// Primaries the stock's color matrix and density curves produce.
+    #[serde(default)]
+    pub working_space: WorkingSpace,
+    /// Dithering of the 8-bit quantization, against banding in smooth
+    /// gradients.
+    #[serde(default)]
+    pub dither: DitherMode,
     /// White Balance mode.
     pub white_balance_mode: WhiteBalanceMode,
     /// Strength of White Balance correction (0.0 to 1.0).
     pub white_balance_strength: f32,
-    /// Warmth adjustment (-1.0 to 1.0).
+    /// Color temperature shift of the develop, from -1.0 (cooler) to 1.0
+    /// (warmer); 0.0 is neutral. Applied with the white balance as a gain on
+    /// the red and blue exposure, see [`SimulationConfig::warmth_gains`].
     pub warmth: f32,
-    /// Saturation adjustment (0.0 to 2.0).
+    /// Chroma scale of the scanned output in CIE Lab, from 0.0 (grayscale) to
+    /// 2.0; 1.0 leaves colors unchanged. Lightness and hue are kept, so unlike
+    /// scaling RGB away from gray it does not shift hues.
     pub saturation: f32,
+    /// Colored lens filter in front of B&W stocks. Ignored by color stocks.
+    #[serde(default)]
+    pub bw_filter: BwFilter,
+    /// Print toning of B&W output. Ignored by color stocks.
+    #[serde(default)]
+    pub toning: Toning,
+    /// Developer temperature and time; scales contrast and grain.
+    #[serde(default)]
+    pub development: DevelopmentParams,
+    /// Lens effects of the taking lens.
+    #[serde(default)]
+    pub lens: LensConfig,
+    /// Scanner sharpening, misregistration and noise applied after grain.
+    #[serde(default)]
+    pub scanner: Option<ScannerProfile>,
     /// Light leak simulation configuration.
     pub light_leak: LightLeakConfig,
     /// Motion blur amount (0.0 = off, 1.0 = default hand shake).
@@ -63,6 +136,10 @@ pub struct SimulationConfig {
     /// Motion blur random seed (same seed = same trajectory).
     #[serde(default)]
     pub motion_blur_seed: u64,
+    /// Grain noise seed. `None` draws fresh grain on every render; a fixed
+    /// seed makes the output reproducible.
+    #[serde(default)]
+    pub grain_seed: Option<u64>,
     /// Object motion amount (0.0 = off, 1.0 = default depth-based motion).
     #[serde(default)]
     pub object_motion_amount: f32,
@@ -81,20 +158,568 @@ pub struct SimulationConfig {
     /// Rotational blur amount (0.0 = off, simulates camera rotation).
     #[serde(default)]
     pub rotational_blur_amount: f32,
+    /// Width of the full-resolution source when processing a downscaled preview.
+    /// Grain is weighted as if developed at this width and then downscaled,
+    /// so the preview matches the final develop.
+    #[serde(default)]
+    pub source_width: Option<u32>,
+    /// Size of the output in pixels, when it differs from the input, e.g.
+    /// for a print at a given size. `None` develops at the input size.
+    ///
+    /// Grain and the MTF blur are sized in millimeters of film, so they only
+    /// look right at the resolution they are rendered at. A larger
+    /// target enlarges the input in linear light and develops at the target,
+    /// so grain is rendered at print resolution rather than magnified. A
+    /// smaller target develops at up to [`TARGET_SUPERSAMPLE`] times the
+    /// target (never more than the input) and downscales the result, like a
+    /// scan resized for print. The MTF blur follows the same size: it blurs
+    /// by the stock's resolving power per millimeter of the working image,
+    /// so enlarging past the film's resolution shows its softness as a real
+    /// enlargement would, and a small target hides it. Give the target the
+    /// input's aspect ratio; otherwise the picture is stretched.
+    #[serde(default)]
+    pub target_size: Option<(u32, u32)>,
+    /// Stages applied to the linear image, in order.
+    /// Remove or reorder entries to skip or rearrange stages.
+    #[serde(default = "SimulationConfig::default_pipeline")]
+    pub pipeline: Vec<StageKind>,
+    /// Skip the film entirely: the input is only linearized and encoded
+    /// again, so the output matches it up to quantization. For checking that
+    /// a color shift comes from the simulation and not from the plumbing.
+    #[serde(default)]
+    pub bypass_film: bool,
+    /// Apply the film only where the mask selects, keeping the original
+    /// elsewhere. Blended in linear light before the output is encoded,
+    /// after any GPU stages; ignored by [`process_luma_image`]. `None`
+    /// develops the whole frame.
+    #[serde(default)]
+    pub mask: Option<MaskConfig>,
+    /// How much of the film look to apply (0.0 = none, 1.0 = full). The
+    /// developed result is mixed with the linearized input in linear light
+    /// as the last step before encoding, after any GPU stages, so 0.0 gives
+    /// the same output as [`SimulationConfig::bypass_film`].
+    #[serde(default = "default_effect_amount")]
+    pub effect_amount: f32,
+    /// The input already holds linear light, as from a linear EXR or a RAW
+    /// decode, so it is only scaled to 0-1 instead of decoded from sRGB.
+    /// Applies on the CPU and the GPU.
+    #[serde(default)]
+    pub input_is_linear: bool,
+}
+
+impl SimulationConfig {
+    /// The standard stage order used by [`SimulationConfig::default`].
+    pub fn default_pipeline() -> Vec<StageKind> {
+        vec![
+            StageKind::Diffusion,
+            StageKind::LightLeak,
+            StageKind::Halation,
+            StageKind::MicroMotion,
+            StageKind::ObjectMotion,
+            StageKind::DepthOfField,
+            StageKind::RotationalBlur,
+            StageKind::Mtf,
+            StageKind::ChromaticAberration,
+            StageKind::Develop,
+            StageKind::Grain,
+        ]
+    }
+
+    /// The stages to run: [`SimulationConfig::pipeline`], or none when
+    /// [`SimulationConfig::bypass_film`] is set.
+    pub fn stages(&self) -> &[StageKind] {
+        if self.bypass_film {
+            &[]
+        } else {
+            &self.pipeline
+        }
+    }
+
+    /// Whether the output conversion adds grain: grain is enabled and
+    /// [`StageKind::Grain`] is listed after [`StageKind::Develop`].
+    pub(crate) fn grains_output(&self) -> bool {
+        let stages = self.stages();
+        let position = |kind| stages.iter().position(|&stage| stage == kind);
+        match (position(StageKind::Grain), position(StageKind::Develop)) {
+            (Some(grain), Some(develop)) => self.enable_grain && grain > develop,
+            _ => false,
+        }
+    }
+
+    /// Whether the output is mixed with the linearized input, see
+    /// [`SimulationConfig::effect_amount`] and [`SimulationConfig::mask`].
+    pub(crate) fn blends_original(&self) -> bool {
+        self.effect_amount < 1.0 || self.mask.is_some()
+    }
+
+    /// Size the pipeline develops a `source`-sized input at, see
+    /// [`SimulationConfig::target_size`].
+    pub fn working_size(&self, source: (u32, u32)) -> (u32, u32) {
+        let Some(target) = self.target_size else {
+            return source;
+        };
+        let target = (target.0.max(1), target.1.max(1));
+        if target.0 >= source.0 || target.1 >= source.1 {
+            return target;
+        }
+        let scale = (source.0 as f32 / target.0 as f32)
+            .min(source.1 as f32 / target.1 as f32)
+            .min(TARGET_SUPERSAMPLE as f32);
+        (
+            ((target.0 as f32 * scale).round() as u32).clamp(target.0, source.0),
+            ((target.1 as f32 * scale).round() as u32).clamp(target.1, source.1),
+        )
+    }
+
+    /// Per-channel exposure gains of [`SimulationConfig::warmth`], clamped to
+    /// its range. Full warmth raises red and lowers blue by 10%.
+    pub fn warmth_gains(&self) -> [f32; 3] {
+        let warmth = self.warmth.clamp(-1.0, 1.0);
+        [1.0 + warmth * WARMTH_GAIN, 1.0, 1.0 - warmth * WARMTH_GAIN]
+    }
+
+    /// [`SimulationConfig::saturation`] clamped to its range.
+    pub fn saturation_scale(&self) -> f32 {
+        self.saturation.clamp(0.0, 2.0)
+    }
+
+    /// `exposure_time` after the stock's reciprocity failure at the shutter
+    /// speed. Without a shutter speed there is no reciprocity loss.
+    pub fn effective_exposure(&self, film: &FilmStock) -> f32 {
+        let seconds = self.shutter_speed.map_or(0.0, ShutterSpeed::seconds);
+        if seconds > 0.0 {
+            self.exposure_time * film.reciprocity.effective_time(seconds) / seconds
+        } else {
+            self.exposure_time
+        }
+    }
+
+    /// Default config exposed like a real shot: f/`aperture` at `shutter` of
+    /// a scene metering `scene_ev` (EV at ISO 100), on `film` at its box speed.
+    ///
+    /// `exposure_time` is relative: 18% gray metered at box speed is 1.0, a
+    /// stop over is 2.0. The shutter is kept as `shutter_speed`, so the
+    /// develop applies the stock's reciprocity failure and a metered 30 s
+    /// exposure lands under, just as it would on film. Use
+    /// [`crate::shutter::relative_exposure`] to rate the film at another
+    /// speed.
+    pub fn from_exposure_triangle(
+        film: &FilmStock,
+        aperture: f32,
+        shutter: ShutterSpeed,
+        scene_ev: f32,
+    ) -> Self {
+        Self {
+            exposure_time: relative_exposure(aperture, shutter, film.iso, scene_ev),
+            shutter_speed: Some(shutter),
+            ..Default::default()
+        }
+    }
 }
 
 fn default_motion_blur() -> f32 {
     1.0
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub enum OutputMode {
-    Negative, // Transmission of the negative (Dark -> Bright, Bright -> Dark)
+    Negative, // Transmission of the negative, with the orange mask of color negatives
+    #[default]
     Positive, // Scanned/Inverted Positive (Dark -> Dark, Bright -> Bright)
+    Inverted, // Negative output scanned and inverted, see NegativeInversion
 }
 
+/// Lab inversion of a scanned negative, used by [`OutputMode::Inverted`].
+///
+/// The negative is rendered as in [`OutputMode::Negative`], the film base is
+/// divided out of the scan and the densities read back are printed to a
+/// positive. With the stock's own base fully removed this lands close to
+/// [`OutputMode::Positive`]; dye self-absorption still shifts the channels
+/// against each other, and any mask left behind tints the shadows the way an
+/// under-corrected lab scan does. Slides are not inverted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NegativeInversion {
+    /// Film base transmission divided out of the scan. `None` samples the
+    /// stock's own orange mask, as a lab does from the unexposed rebate.
+    #[serde(default)]
+    pub base: Option<[f32; 3]>,
+    /// How much of the base is removed, from 0.0 (none) to 1.0 (all).
+    pub mask_removal: f32,
+    /// Print contrast on the densities read back; 1.0 keeps the negative's.
+    pub contrast: f32,
+}
+
+impl Default for NegativeInversion {
+    fn default() -> Self {
+        Self {
+            base: None,
+            mask_removal: 1.0,
+            contrast: 1.0,
+        }
+    }
+}
+
+impl NegativeInversion {
+    /// Transmission divided out of a scan of `film`, per channel.
+    pub fn base_removal(&self, film: &FilmStock) -> [f32; 3] {
+        let base = self.base.unwrap_or_else(|| film.mask_color());
+        let removal = self.mask_removal.clamp(0.0, 1.0);
+        base.map(|b| b.max(1e-3).powf(removal))
+    }
+}
+
+/// Transfer function from linear light to the encoded output.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum OutputTransfer {
+    /// IEC 61966-2-1 sRGB, for display.
+    #[default]
+    Srgb,
+    /// Pure power law with the given display gamma, e.g. 2.2.
+    Gamma(f32),
+    /// ITU-R BT.709 camera OETF, for video.
+    Rec709,
+    /// No encoding, linear light for further grading.
+    Linear,
+}
+
+impl OutputTransfer {
+    /// Encode a linear value, clamped to 0-1.
+    pub fn encode(self, v: f32) -> f32 {
+        let v = v.clamp(0.0, 1.0);
+        match self {
+            OutputTransfer::Srgb => crate::physics::linear_to_srgb(v),
+            OutputTransfer::Gamma(gamma) => v.powf(1.0 / gamma.max(0.1)),
+            OutputTransfer::Rec709 => crate::physics::linear_to_rec709(v),
+            OutputTransfer::Linear => v,
+        }
+    }
+}
+
+/// RGB primaries the film's dye channels are read in when the scan turns
+/// densities back into color.
+///
+/// Input is decoded as sRGB and the develop reconstructs scene spectra from
+/// it, so exposure is the same in every space. After the density curves and
+/// the stock's `color_matrix`, each channel is taken as a primary of the
+/// working space and converted to linear sRGB for the output adjustments and
+/// the encode. Wider spaces give more saturated film colors, which
+/// integrators matching an ACEScg grade usually want. Stocks with measured
+/// dye spectra are scanned colorimetrically and are unaffected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum WorkingSpace {
+    /// Linear sRGB / Rec. 709 primaries, D65. The historical behavior.
+    #[default]
+    LinearSrgb,
+    /// Linear ITU-R BT.2020 primaries, D65.
+    LinearRec2020,
+    /// ACES AP1 primaries with the ACES white, Bradford-adapted to D65.
+    ACEScg,
+}
+
+impl WorkingSpace {
+    /// Every working space, in UI order.
+    pub const ALL: [WorkingSpace; 3] = [
+        WorkingSpace::LinearSrgb,
+        WorkingSpace::LinearRec2020,
+        WorkingSpace::ACEScg,
+    ];
+
+    /// Display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            WorkingSpace::LinearSrgb => "Linear sRGB",
+            WorkingSpace::LinearRec2020 => "Linear Rec.2020",
+            WorkingSpace::ACEScg => "ACEScg",
+        }
+    }
+
+    /// Row-major matrix from this space to linear sRGB. Rows sum to 1, so
+    /// neutrals stay neutral.
+    pub fn to_linear_srgb_matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            WorkingSpace::LinearSrgb => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            WorkingSpace::LinearRec2020 => [
+                [1.660_491, -0.587_641, -0.072_850],
+                [-0.124_550, 1.132_9, -0.008_349],
+                [-0.018_151, -0.100_579, 1.118_73],
+            ],
+            WorkingSpace::ACEScg => [
+                [1.705_051, -0.621_792, -0.083_259],
+                [-0.130_256, 1.140_805, -0.010_548],
+                [-0.024_003, -0.128_969, 1.152_972],
+            ],
+        }
+    }
+
+    /// Convert a linear pixel in this space to linear sRGB.
+    pub fn to_linear_srgb(self, rgb: [f32; 3]) -> [f32; 3] {
+        if self == WorkingSpace::LinearSrgb {
+            return rgb;
+        }
+        let m = self.to_linear_srgb_matrix();
+        std::array::from_fn(|i| m[i][0] * rgb[0] + m[i][1] * rgb[1] + m[i][2] * rgb[2])
+    }
+}
+
+/// Developer temperature at which stocks are rated, in °C.
+pub const STANDARD_DEVELOP_TEMPERATURE_C: f32 = 20.0;
+
+/// Curve gamma factor per stop of push, see [`DevelopmentParams::push_pull_stops`].
+pub const PUSH_GAMMA_PER_STOP: f32 = 1.12;
+
+/// Grain `alpha` factor per stop of push, see [`DevelopmentParams::push_pull_stops`].
+pub const PUSH_GRAIN_PER_STOP: f32 = 1.4;
+
+/// Development chemistry: how hot and how long the film is developed.
+///
+/// This changes how far the developer takes the exposed film, not the
+/// exposure. Temperature is folded into time with the usual
+/// time-temperature rule: about 10% more activity per °C, so the degree of
+/// development is `time_rel × 1.1^(temperature_c − 20)`. Contrast (curve
+/// gamma) follows its 0.3 power and grain `alpha` its 0.6 power, so grain
+/// grows faster than contrast. The standard of 20 °C at 1.0× time leaves
+/// the stock unchanged.
+///
+/// Push and pull processing couple the two on top of that: each stop pushed
+/// multiplies gamma by [`PUSH_GAMMA_PER_STOP`] and grain `alpha` by
+/// [`PUSH_GRAIN_PER_STOP`], and pulling divides by the same factors. Two
+/// stops take Tri-X from its 0.7 gamma to about 0.88, as at EI 1600.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DevelopmentParams {
+    /// Developer temperature in °C.
+    pub temperature_c: f32,
+    /// Development time relative to the stock's standard time.
+    pub time_rel: f32,
+    /// Stops the film is pushed (positive) or pulled (negative), clamped to
+    /// −2 to 3. The film is rated at `2^stops` times its box speed; meter
+    /// the input for that rating with the exposure, this only develops it.
+    #[serde(default)]
+    pub push_pull_stops: f32,
+    /// Let push and pull change only the contrast and leave grain to the
+    /// grain controls.
+    #[serde(default)]
+    pub decouple_push_grain: bool,
+}
+
+impl Default for DevelopmentParams {
+    fn default() -> Self {
+        Self {
+            temperature_c: STANDARD_DEVELOP_TEMPERATURE_C,
+            time_rel: 1.0,
+            push_pull_stops: 0.0,
+            decouple_push_grain: false,
+        }
+    }
+}
+
+impl DevelopmentParams {
+    /// Degree of development relative to the standard, clamped to 0.25–4.
+    pub fn degree(&self) -> f32 {
+        (self.time_rel.max(0.0) * 1.1f32.powf(self.temperature_c - STANDARD_DEVELOP_TEMPERATURE_C))
+            .clamp(0.25, 4.0)
+    }
+
+    /// Push (positive) or pull (negative) in stops, clamped to −2 to 3.
+    pub fn push_stops(&self) -> f32 {
+        if self.push_pull_stops.is_nan() {
+            return 0.0;
+        }
+        self.push_pull_stops.clamp(-2.0, 3.0)
+    }
+
+    /// Factor on curve gamma.
+    pub fn gamma_scale(&self) -> f32 {
+        self.degree().powf(0.3) * PUSH_GAMMA_PER_STOP.powf(self.push_stops())
+    }
+
+    /// Factor on grain `alpha`.
+    pub fn grain_scale(&self) -> f32 {
+        let push = if self.decouple_push_grain {
+            1.0
+        } else {
+            PUSH_GRAIN_PER_STOP.powf(self.push_stops())
+        };
+        self.degree().powf(0.6) * push
+    }
+
+    /// `film` as developed with these parameters. Borrows it unchanged at
+    /// the standard.
+    pub fn develop<'a>(&self, film: &'a FilmStock) -> Cow<'a, FilmStock> {
+        if *self == Self::default() {
+            return Cow::Borrowed(film);
+        }
+        let mut film = film.clone();
+        let gamma = self.gamma_scale();
+        film.r_curve.gamma *= gamma;
+        film.g_curve.gamma *= gamma;
+        film.b_curve.gamma *= gamma;
+        film.grain_model.alpha *= self.grain_scale();
+        Cow::Owned(film)
+    }
+}
+
+/// Optical character of the taking lens.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub struct LensConfig {
+    /// Lateral chromatic aberration: how much larger red, and smaller blue,
+    /// is imaged than green, in per mille (0.0 = off). The shift grows with
+    /// the distance from the center, fringing high-contrast edges toward the
+    /// corners; around 1.5 is typical of a vintage lens.
+    pub chromatic_aberration: f32,
+    /// Strength of a diffusion filter in front of the lens (0.0 = off, 1.0 =
+    /// heavy). Light from the brighter parts of the frame is spread into a
+    /// soft glow around them, about 2% of the image width across, softening
+    /// their edges and lifting the shadows next to them. Unlike halation it
+    /// needs no clipped highlights.
+    #[serde(default)]
+    pub diffusion: f32,
+}
+
+impl LensConfig {
+    /// Magnification of the red and blue channels relative to green.
+    pub fn channel_scales(&self) -> (f32, f32) {
+        let shift = self.chromatic_aberration / 1000.0;
+        (1.0 + shift, 1.0 - shift)
+    }
+}
+
+/// Toning of B&W prints. The output luminance is mapped onto a gradient
+/// from a shadow tint to a highlight tint; lightness is kept.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum Toning {
+    #[default]
+    None,
+    /// Warm brown, strongest in the highlights.
+    Sepia,
+    /// Cool purple-brown in the shadows, neutral highlights.
+    Selenium,
+    /// Prussian blue cyanotype.
+    Cyanotype,
+    /// Separate shadow and highlight tints, as linear RGB. Only their hue and
+    /// chroma matter; each is normalized to unit luminance.
+    Split {
+        shadow: [f32; 3],
+        highlight: [f32; 3],
+    },
+}
+
+impl Toning {
+    pub const PRESETS: [Toning; 4] = [
+        Toning::None,
+        Toning::Sepia,
+        Toning::Selenium,
+        Toning::Cyanotype,
+    ];
+
+    /// Classic split tone: cool shadows, warm highlights.
+    pub const CLASSIC_SPLIT: Toning = Toning::Split {
+        shadow: [0.85, 0.95, 1.2],
+        highlight: [1.15, 1.0, 0.8],
+    };
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Toning::None => "None",
+            Toning::Sepia => "Sepia",
+            Toning::Selenium => "Selenium",
+            Toning::Cyanotype => "Cyanotype",
+            Toning::Split { .. } => "Split",
+        }
+    }
+
+    /// Shadow and highlight tints as linear RGB, `None` when untoned.
+    pub fn tints(self) -> Option<([f32; 3], [f32; 3])> {
+        match self {
+            Toning::None => None,
+            Toning::Sepia => Some(([1.1, 0.97, 0.85], [1.25, 1.0, 0.7])),
+            Toning::Selenium => Some(([1.08, 0.94, 1.1], [1.0, 1.0, 1.0])),
+            Toning::Cyanotype => Some(([0.35, 0.8, 1.9], [0.85, 1.0, 1.25])),
+            Toning::Split { shadow, highlight } => Some((shadow, highlight)),
+        }
+    }
+
+    /// Tone a linear RGB pixel by its luminance.
+    pub fn apply(self, rgb: [f32; 3]) -> [f32; 3] {
+        let Some((shadow, highlight)) = self.tints() else {
+            return rgb;
+        };
+        let luminance = |c: [f32; 3]| 0.2126 * c[0] + 0.7152 * c[1] + 0.0722 * c[2];
+        let normalize = |c: [f32; 3]| {
+            let lum = luminance(c).max(1e-6);
+            c.map(|v| v.max(0.0) / lum)
+        };
+        let (shadow, highlight) = (normalize(shadow), normalize(highlight));
+        let lum = luminance(rgb).max(0.0);
+        // Blend on perceptual lightness so midtones sit halfway
+        let t = crate::physics::linear_to_srgb(lum.min(1.0));
+        std::array::from_fn(|c| lum * (shadow[c] + (highlight[c] - shadow[c]) * t))
+    }
+}
+
+/// Colored lens filter for B&W film. A filter passes its own color and holds
+/// back the others: red darkens blue skies and lightens red objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BwFilter {
+    #[default]
+    None,
+    Yellow,
+    Orange,
+    Red,
+    Green,
+    Blue,
+}
+
+impl BwFilter {
+    pub const ALL: [BwFilter; 6] = [
+        BwFilter::None,
+        BwFilter::Yellow,
+        BwFilter::Orange,
+        BwFilter::Red,
+        BwFilter::Green,
+        BwFilter::Blue,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BwFilter::None => "None",
+            BwFilter::Yellow => "Yellow",
+            BwFilter::Orange => "Orange",
+            BwFilter::Red => "Red",
+            BwFilter::Green => "Green",
+            BwFilter::Blue => "Blue",
+        }
+    }
+
+    /// Relative transmission of scene R, G and B, roughly Wratten #8, #21,
+    /// #25, #11 and #47.
+    pub fn transmission(self) -> [f32; 3] {
+        match self {
+            BwFilter::None => [1.0, 1.0, 1.0],
+            BwFilter::Yellow => [1.0, 0.9, 0.35],
+            BwFilter::Orange => [1.0, 0.55, 0.1],
+            BwFilter::Red => [1.0, 0.15, 0.03],
+            BwFilter::Green => [0.3, 1.0, 0.35],
+            BwFilter::Blue => [0.1, 0.3, 1.0],
+        }
+    }
+
+    /// A B&W channel mix seen through the filter, normalized to sum to 1.
+    /// This is the filter factor compensation: neutral gray keeps its value.
+    pub fn apply(self, weights: [f32; 3]) -> [f32; 3] {
+        let t = self.transmission();
+        let filtered: [f32; 3] = std::array::from_fn(|c| weights[c] * t[c]);
+        let sum: f32 = filtered.iter().sum();
+        if sum > 0.0 {
+            filtered.map(|w| w / sum)
+        } else {
+            weights
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub enum WhiteBalanceMode {
+    #[default]
     Auto,
     Gray,
     White,
@@ -106,22 +731,42 @@ impl Default for SimulationConfig {
         Self {
             simulation_mode: SimulationMode::default(),
             exposure_time: 1.0,
+            shutter_speed: None,
             enable_grain: true,
-            use_gpu: false,                    // Default to CPU for stability
-            output_mode: OutputMode::Positive, // Default to what users expect
-            white_balance_mode: WhiteBalanceMode::Auto,
+            grain_space: GrainSpace::Linear,
+            use_gpu: false, // Default to CPU for stability
+            gpu_power_preference: GpuPowerPreference::default(),
+            output_mode: OutputMode::default(), // Positive, what users expect
+            inversion: NegativeInversion::default(),
+            output_transfer: OutputTransfer::Srgb,
+            working_space: WorkingSpace::LinearSrgb,
+            dither: DitherMode::None,
+            white_balance_mode: WhiteBalanceMode::default(),
             white_balance_strength: 1.0,
             warmth: 0.0,
             saturation: 1.0,
+            bw_filter: BwFilter::None,
+            toning: Toning::None,
+            development: DevelopmentParams::default(),
+            lens: LensConfig::default(),
+            scanner: None,
             light_leak: LightLeakConfig::default(),
             motion_blur_amount: 1.0,
             motion_blur_seed: 42,
+            grain_seed: None,
             object_motion_amount: 0.0,
             auto_levels: false,
             dof_amount: 0.0,
             dof_focus: 0.5,
             dof_swirl: 0.0,
             rotational_blur_amount: 0.0,
+            source_width: None,
+            target_size: None,
+            pipeline: Self::default_pipeline(),
+            bypass_film: false,
+            mask: None,
+            effect_amount: 1.0,
+            input_is_linear: false,
         }
     }
 }
@@ -130,6 +775,272 @@ fn default_dof_focus() -> f32 {
     0.5
 }
 
+fn default_effect_amount() -> f32 {
+    1.0
+}
+
+/// Chainable construction of a [`SimulationConfig`].
+///
+/// Fields that are not set keep their [`SimulationConfig::default`] values,
+/// so code built this way keeps compiling when fields are added.
+///
+/// ```
+/// use filmr::{OutputMode, SimulationConfig};
+///
+/// let config = SimulationConfig::builder()
+///     .exposure_time(0.5)
+///     .output_mode(OutputMode::Positive)
+///     .enable_grain(false)
+///     .build();
+/// assert_eq!(config.exposure_time, 0.5);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SimulationConfigBuilder {
+    config: SimulationConfig,
+}
+
+impl SimulationConfig {
+    /// Start a [`SimulationConfigBuilder`] from the default configuration.
+    pub fn builder() -> SimulationConfigBuilder {
+        SimulationConfigBuilder::default()
+    }
+}
+
+impl SimulationConfigBuilder {
+    /// Simulation fidelity mode.
+    pub fn simulation_mode(mut self, simulation_mode: SimulationMode) -> Self {
+        self.config.simulation_mode = simulation_mode;
+        self
+    }
+
+    /// Relative exposure; 1.0 is metered.
+    pub fn exposure_time(mut self, exposure_time: f32) -> Self {
+        self.config.exposure_time = exposure_time;
+        self
+    }
+
+    /// Shutter speed, for reciprocity failure.
+    pub fn shutter_speed(mut self, shutter_speed: ShutterSpeed) -> Self {
+        self.config.shutter_speed = Some(shutter_speed);
+        self
+    }
+
+    /// Enable or disable grain simulation.
+    pub fn enable_grain(mut self, enable_grain: bool) -> Self {
+        self.config.enable_grain = enable_grain;
+        self
+    }
+
+    /// Space the output grain is added in.
+    pub fn grain_space(mut self, grain_space: GrainSpace) -> Self {
+        self.config.grain_space = grain_space;
+        self
+    }
+
+    /// Enable GPU acceleration if available.
+    pub fn use_gpu(mut self, use_gpu: bool) -> Self {
+        self.config.use_gpu = use_gpu;
+        self
+    }
+
+    /// Adapter preference used when the GPU context is first created.
+    pub fn gpu_power_preference(mut self, gpu_power_preference: GpuPowerPreference) -> Self {
+        self.config.gpu_power_preference = gpu_power_preference;
+        self
+    }
+
+    /// Negative (transmission), positive (scanned) or inverted output.
+    pub fn output_mode(mut self, output_mode: OutputMode) -> Self {
+        self.config.output_mode = output_mode;
+        self
+    }
+
+    /// Mask removal and print contrast of [`OutputMode::Inverted`].
+    pub fn inversion(mut self, inversion: NegativeInversion) -> Self {
+        self.config.inversion = inversion;
+        self
+    }
+
+    /// Transfer function of the final 8-bit encode.
+    pub fn output_transfer(mut self, output_transfer: OutputTransfer) -> Self {
+        self.config.output_transfer = output_transfer;
+        self
+    }
+
+    /// Primaries of the stock's dye channels.
+    pub fn working_space(mut self, working_space: WorkingSpace) -> Self {
+        self.config.working_space = working_space;
+        self
+    }
+
+    /// Dithering of the 8-bit quantization.
+    pub fn dither(mut self, dither: DitherMode) -> Self {
+        self.config.dither = dither;
+        self
+    }
+
+    /// White balance mode.
+    pub fn white_balance_mode(mut self, white_balance_mode: WhiteBalanceMode) -> Self {
+        self.config.white_balance_mode = white_balance_mode;
+        self
+    }
+
+    /// Strength of the white balance correction (0.0 to 1.0).
+    pub fn white_balance_strength(mut self, white_balance_strength: f32) -> Self {
+        self.config.white_balance_strength = white_balance_strength;
+        self
+    }
+
+    /// Warmth adjustment (-1.0 to 1.0).
+    pub fn warmth(mut self, warmth: f32) -> Self {
+        self.config.warmth = warmth;
+        self
+    }
+
+    /// Saturation adjustment (0.0 to 2.0).
+    pub fn saturation(mut self, saturation: f32) -> Self {
+        self.config.saturation = saturation;
+        self
+    }
+
+    /// Colored lens filter in front of B&W stocks.
+    pub fn bw_filter(mut self, bw_filter: BwFilter) -> Self {
+        self.config.bw_filter = bw_filter;
+        self
+    }
+
+    /// Print toning of B&W output.
+    pub fn toning(mut self, toning: Toning) -> Self {
+        self.config.toning = toning;
+        self
+    }
+
+    /// Developer temperature and time.
+    pub fn development(mut self, development: DevelopmentParams) -> Self {
+        self.config.development = development;
+        self
+    }
+
+    /// Lens effects of the taking lens.
+    pub fn lens(mut self, lens: LensConfig) -> Self {
+        self.config.lens = lens;
+        self
+    }
+
+    /// Scanner applied to the output after grain.
+    pub fn scanner(mut self, scanner: ScannerProfile) -> Self {
+        self.config.scanner = Some(scanner);
+        self
+    }
+
+    /// Light leak simulation configuration.
+    pub fn light_leak(mut self, light_leak: LightLeakConfig) -> Self {
+        self.config.light_leak = light_leak;
+        self
+    }
+
+    /// Motion blur amount (0.0 = off).
+    pub fn motion_blur_amount(mut self, motion_blur_amount: f32) -> Self {
+        self.config.motion_blur_amount = motion_blur_amount;
+        self
+    }
+
+    /// Motion blur random seed.
+    pub fn motion_blur_seed(mut self, motion_blur_seed: u64) -> Self {
+        self.config.motion_blur_seed = motion_blur_seed;
+        self
+    }
+
+    /// Fixed grain seed for reproducible output.
+    pub fn grain_seed(mut self, grain_seed: u64) -> Self {
+        self.config.grain_seed = Some(grain_seed);
+        self
+    }
+
+    /// Object motion amount (0.0 = off).
+    pub fn object_motion_amount(mut self, object_motion_amount: f32) -> Self {
+        self.config.object_motion_amount = object_motion_amount;
+        self
+    }
+
+    /// Auto black/white point stretch.
+    pub fn auto_levels(mut self, auto_levels: bool) -> Self {
+        self.config.auto_levels = auto_levels;
+        self
+    }
+
+    /// Depth of field blur amount (0.0 = off).
+    pub fn dof_amount(mut self, dof_amount: f32) -> Self {
+        self.config.dof_amount = dof_amount;
+        self
+    }
+
+    /// Depth of field focus point (0.0 = nearest, 1.0 = farthest).
+    pub fn dof_focus(mut self, dof_focus: f32) -> Self {
+        self.config.dof_focus = dof_focus;
+        self
+    }
+
+    /// Swirly bokeh amount (0.0 = off).
+    pub fn dof_swirl(mut self, dof_swirl: f32) -> Self {
+        self.config.dof_swirl = dof_swirl;
+        self
+    }
+
+    /// Rotational blur amount (0.0 = off).
+    pub fn rotational_blur_amount(mut self, rotational_blur_amount: f32) -> Self {
+        self.config.rotational_blur_amount = rotational_blur_amount;
+        self
+    }
+
+    /// Width of the full-resolution source of a downscaled preview.
+    pub fn source_width(mut self, source_width: u32) -> Self {
+        self.config.source_width = Some(source_width);
+        self
+    }
+
+    /// Output size in pixels, see [`SimulationConfig::target_size`].
+    pub fn target_size(mut self, width: u32, height: u32) -> Self {
+        self.config.target_size = Some((width, height));
+        self
+    }
+
+    /// Stages applied to the linear image, in order.
+    pub fn pipeline(mut self, pipeline: Vec<StageKind>) -> Self {
+        self.config.pipeline = pipeline;
+        self
+    }
+
+    /// Skip the film and only round-trip the input through linear light.
+    pub fn bypass_film(mut self, bypass_film: bool) -> Self {
+        self.config.bypass_film = bypass_film;
+        self
+    }
+
+    /// Apply the film only where the mask selects.
+    pub fn mask(mut self, mask: Option<MaskConfig>) -> Self {
+        self.config.mask = mask;
+        self
+    }
+
+    /// How much of the film look to apply (1.0 = full).
+    pub fn effect_amount(mut self, effect_amount: f32) -> Self {
+        self.config.effect_amount = effect_amount;
+        self
+    }
+
+    /// Treat the input as linear light rather than sRGB.
+    pub fn input_is_linear(mut self, input_is_linear: bool) -> Self {
+        self.config.input_is_linear = input_is_linear;
+        self
+    }
+
+    /// Finish the configuration.
+    pub fn build(self) -> SimulationConfig {
+        self.config
+    }
+}
+
 #[instrument(skip(input, film))]
 pub fn estimate_exposure_time(input: &RgbImage, film: &FilmStock) -> f32 {
     estimate_exposure_time_for_mode(input, film, SimulationMode::Accurate)
@@ -169,44 +1080,511 @@ pub fn process_image_with_depth(
 
     #[cfg(feature = "compute-gpu")]
     let gpu_result = if config.use_gpu {
-        process_gpu_pipeline(&context, input, film, config)
+        process_gpu_pipeline(input, film, config)
     } else {
         None
     };
 
     #[cfg(not(feature = "compute-gpu"))]
-    let gpu_result: Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>> = None;
+    let gpu_result: Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>> = None;
+
+    finish_image(input, gpu_result, &context)
+}
+
+/// Same as [`process_image`] but requires the GPU backend, ignoring
+/// `config.use_gpu`. Instead of silently falling back to the CPU it returns
+/// [`FilmrError::Gpu`] when no GPU is available, so callers can decide.
+///
+/// Creates the GPU context on first use, with `config.gpu_power_preference`,
+/// and logs a warning when no adapter is found. Linearization and the
+/// leading GPU stages of `config.pipeline` run on the GPU and are read back
+/// once; the remaining stages, develop and grain run on the CPU as in
+/// [`process_image`]. Without the `compute-gpu` feature it always errors,
+/// so tests and CI without a GPU can treat the error as a skip.
+#[instrument(skip(input, film, config))]
+pub fn process_image_gpu(
+    input: &RgbImage,
+    film: &FilmStock,
+    config: &SimulationConfig,
+) -> Result<RgbImage, FilmrError> {
+    #[cfg(feature = "compute-gpu")]
+    {
+        if input.is_empty() {
+            return Ok(input.clone());
+        }
+        let config = SimulationConfig {
+            use_gpu: true,
+            ..config.clone()
+        };
+        let linear = process_gpu_pipeline(input, film, &config)
+            .ok_or_else(|| FilmrError::Gpu("no GPU adapter available".to_string()))?;
+        let context = PipelineContext {
+            film,
+            config: &config,
+            depth_map: None,
+        };
+        Ok(finish_image(input, Some(linear), &context))
+    }
+
+    #[cfg(not(feature = "compute-gpu"))]
+    {
+        let _ = (input, film, config);
+        Err(FilmrError::Gpu(
+            "built without the compute-gpu feature".to_string(),
+        ))
+    }
+}
+
+/// Develops one source image again and again with changing settings, as
+/// while dragging a slider.
+///
+/// The source is linearized once and kept: on the GPU as a buffer when
+/// `config.use_gpu` is set and an adapter is available, otherwise as a CPU
+/// image. Each [`render`](Self::render) then only runs the pipeline stages
+/// and the output conversion, skipping the upload and linearization. Replace
+/// the source with [`set_source`](Self::set_source) when the image or its
+/// crop changes. The result is the same as [`process_image`] on the source.
+pub struct PreviewSession {
+    source: RgbImage,
+    /// [`SimulationConfig::input_is_linear`] of the cached buffers.
+    input_is_linear: bool,
+    linear: Option<ImageBuffer<image::Rgb<f32>, Vec<f32>>>,
+    #[cfg(feature = "compute-gpu")]
+    gpu: Option<(&'static crate::gpu::GpuContext, crate::gpu::GpuBuffer)>,
+}
+
+impl PreviewSession {
+    /// A session for `source`; nothing is linearized until the first render.
+    pub fn new(source: RgbImage) -> Self {
+        Self {
+            source,
+            input_is_linear: false,
+            linear: None,
+            #[cfg(feature = "compute-gpu")]
+            gpu: None,
+        }
+    }
+
+    /// The image this session develops.
+    pub fn source(&self) -> &RgbImage {
+        &self.source
+    }
+
+    /// Replace the source, dropping the cached linear buffers.
+    pub fn set_source(&mut self, source: RgbImage) {
+        self.source = source;
+        self.linear = None;
+        #[cfg(feature = "compute-gpu")]
+        self.release_gpu();
+    }
+
+    /// Develop the source with `film` and `config`, like [`process_image`].
+    pub fn render(&mut self, film: &FilmStock, config: &SimulationConfig) -> RgbImage {
+        self.render_with_depth(film, config, None)
+    }
+
+    /// [`render`](Self::render) with a depth map, like
+    /// [`process_image_with_depth`].
+    #[instrument(skip(self, film, config, depth_map))]
+    pub fn render_with_depth(
+        &mut self,
+        film: &FilmStock,
+        config: &SimulationConfig,
+        depth_map: Option<&crate::depth::DepthMap>,
+    ) -> RgbImage {
+        let context = PipelineContext {
+            film,
+            config,
+            depth_map,
+        };
+        // The cached buffers were linearized for the other kind of input
+        if config.input_is_linear != self.input_is_linear {
+            self.input_is_linear = config.input_is_linear;
+            self.linear = None;
+            #[cfg(feature = "compute-gpu")]
+            self.release_gpu();
+        }
+
+        #[cfg(feature = "compute-gpu")]
+        if config.use_gpu {
+            if let Some(linear) = self.render_gpu_stages(film, config) {
+                let done = gpu_stage_count(config.stages());
+                return finish_linear(linear, done, &self.source, &context);
+            }
+        }
+
+        let source = &self.source;
+        let linear = self
+            .linear
+            .get_or_insert_with(|| linearize_input(source, config))
+            .clone();
+        finish_linear(linear, 0, source, &context)
+    }
+
+    /// Run the GPU stages on a copy of the cached linear buffer, creating it
+    /// first if needed, and read the result back.
+    #[cfg(feature = "compute-gpu")]
+    fn render_gpu_stages(
+        &mut self,
+        film: &FilmStock,
+        config: &SimulationConfig,
+    ) -> Option<ImageBuffer<image::Rgb<f32>, Vec<f32>>> {
+        let gpu_ctx = get_gpu_context_with_preference(config.gpu_power_preference)?;
+        if self.gpu.is_none() {
+            info!("Linearizing preview source on GPU");
+            let buffer = get_linearize_pipeline(gpu_ctx).process_to_gpu_buffer(
+                gpu_ctx,
+                &self.source,
+                config.input_is_linear,
+            )?;
+            self.gpu = Some((gpu_ctx, buffer));
+        }
+        let (_, cached) = self.gpu.as_ref()?;
+
+        let mut encoder = gpu_ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Preview Session Encoder"),
+            });
+        // Stages write their input in place or retire it, so they get a copy
+        let working = crate::gpu::GpuBuffer {
+            buffer: gpu_ctx.acquire_image_buffer("Preview Working Buffer", cached.size),
+            width: cached.width,
+            height: cached.height,
+            size: cached.size,
+        };
+        encoder.copy_buffer_to_buffer(&cached.buffer, 0, &working.buffer, 0, cached.size);
+        let mut retired = Vec::new();
+        let output = encode_gpu_stages(gpu_ctx, &mut encoder, working, film, config, &mut retired);
+
+        gpu_ctx.queue.submit(Some(encoder.finish()));
+        for buffer in retired {
+            gpu_ctx.buffer_pool.release(buffer);
+        }
+
+        let result = crate::gpu::block_on(read_gpu_buffers(gpu_ctx, &[&output]))
+            .pop()
+            .flatten();
+        gpu_ctx.recycle(output);
+        result
+    }
+
+    /// Return the cached GPU buffer to the pool.
+    #[cfg(feature = "compute-gpu")]
+    fn release_gpu(&mut self) {
+        if let Some((gpu_ctx, buffer)) = self.gpu.take() {
+            gpu_ctx.recycle(buffer);
+        }
+    }
+}
+
+#[cfg(feature = "compute-gpu")]
+impl Drop for PreviewSession {
+    fn drop(&mut self) {
+        self.release_gpu();
+    }
+}
+
+/// Pixel format returned by [`process_luma_image`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LumaOutput {
+    /// 8-bit grayscale.
+    Luma8,
+    /// 16-bit grayscale, keeping the precision of a 16-bit scan.
+    #[default]
+    Luma16,
+    /// 8-bit RGB, as from [`process_image`]. The only format that keeps toning.
+    Rgb8,
+}
+
+/// Process a single-channel image, such as a scanned B&W negative or
+/// microfilm, without converting it to 8-bit RGB first.
+///
+/// The input is linearized at full 16-bit precision and repeated on three
+/// channels, then runs through the same RGB stages as [`process_image`], so
+/// this saves the 8-bit conversion, not work. With a grayscale `output`,
+/// toning is skipped and the result is the luminance of the develop, so
+/// channels cannot drift apart. Meant for monochrome stocks; a color stock
+/// develops the gray input as a neutral color image.
+/// Always runs on the CPU, ignoring `use_gpu`.
+pub fn process_luma_image(
+    input: &ImageBuffer<Luma<u16>, Vec<u16>>,
+    film: &FilmStock,
+    config: &SimulationConfig,
+    output: LumaOutput,
+) -> DynamicImage {
+    process_luma_image_with_depth(input, film, config, None, output)
+}
+
+/// [`process_luma_image`] with a depth map for the depth of field stage.
+#[instrument(skip(input, film, config, depth_map))]
+pub fn process_luma_image_with_depth(
+    input: &ImageBuffer<Luma<u16>, Vec<u16>>,
+    film: &FilmStock,
+    config: &SimulationConfig,
+    depth_map: Option<&crate::depth::DepthMap>,
+    output: LumaOutput,
+) -> DynamicImage {
+    info!("Starting film simulation processing (16-bit grayscale input)");
+    let config = &match output {
+        LumaOutput::Rgb8 => SimulationConfig {
+            mask: None,
+            ..config.clone()
+        },
+        LumaOutput::Luma8 | LumaOutput::Luma16 => SimulationConfig {
+            toning: Toning::None,
+            mask: None,
+            ..config.clone()
+        },
+    };
+    let film = &*config.development.develop(film);
+    let context = PipelineContext {
+        film,
+        config,
+        depth_map,
+    };
+
+    let working = config.working_size(input.dimensions());
+    let mut image_buffer = resample_linear(linearize_luma16_input(input, config), working);
+    let original = config.blends_original().then(|| image_buffer.clone());
+    run_stages(&mut image_buffer, config.stages(), &context);
+
+    if output == LumaOutput::Rgb8 {
+        return DynamicImage::ImageRgb8(create_blended_output_image(
+            &image_buffer,
+            original.as_ref(),
+            &context,
+        ));
+    }
+    let steps = OutputSteps {
+        adjustments: true,
+        grain: config.grains_output(),
+    };
+    let mut linear_buf = render_linear_output(&image_buffer, &context, steps);
+    if let Some(original) = &original {
+        blend_effect(&mut linear_buf, original, config.effect_amount);
+    }
+    let (linear_buf, width, height) = resample_to_target(
+        linear_buf,
+        image_buffer.width(),
+        image_buffer.height(),
+        config,
+    );
+    let luma = DynamicImage::ImageLuma16(encode_luma_output(
+        &linear_buf,
+        width,
+        height,
+        config.output_transfer,
+    ));
+    match output {
+        LumaOutput::Luma8 => DynamicImage::ImageLuma8(luma.into_luma8()),
+        _ => luma,
+    }
+}
+
+/// Runs the CPU pipeline directly on the calling thread, ignoring `use_gpu`.
+///
+/// The parallel loops run in a one-thread rayon pool made of the calling
+/// thread, so no worker threads are needed or used, not even those of the
+/// global pool. Meant for embedding the crate in a plain `wasm-bindgen` page
+/// without the worker bridge; a large image blocks the page until it is done,
+/// so keep inputs small, or prefer [`process_image_async`] and a worker where
+/// the responsiveness matters.
+pub fn process_image_sync(
+    input: &RgbImage,
+    film: &FilmStock,
+    config: &SimulationConfig,
+) -> RgbImage {
+    info!("Starting film simulation processing (Sync)");
+    let context = PipelineContext {
+        film,
+        config,
+        depth_map: None,
+    };
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .use_current_thread()
+        .build();
+    match pool {
+        Ok(pool) => pool.install(|| finish_image(input, None, &context)),
+        Err(e) => {
+            warn!("No current-thread pool ({}), running on the global pool", e);
+            finish_image(input, None, &context)
+        }
+    }
+}
+
+/// Intermediate images from [`process_image_debug`].
+#[derive(Debug, Clone)]
+pub struct PipelineStages {
+    /// Input after linearization, re-encoded to sRGB.
+    pub linearized: RgbImage,
+    /// After the stages up to and including halation (light leak, halation
+    /// in the default order), re-encoded to sRGB.
+    pub halated: RgbImage,
+    /// All pipeline stages developed and scanned, without output adjustments or grain.
+    pub developed: RgbImage,
+    /// `developed` with grain added (identical when grain is disabled).
+    pub grained: RgbImage,
+    /// Full output, as returned by [`process_image`].
+    pub final_image: RgbImage,
+}
+
+/// Like [`process_image`], but also returns the image at each stage boundary.
+///
+/// With `use_gpu`, the GPU stages are read back at each boundary. This costs
+/// extra submissions and readbacks, so use it only for debugging.
+/// Without a [`SimulationConfig::grain_seed`], grain is random per render, so
+/// `grained` and `final_image` do not share noise.
+#[instrument(skip(input, film, config))]
+pub fn process_image_debug(
+    input: &RgbImage,
+    film: &FilmStock,
+    config: &SimulationConfig,
+) -> PipelineStages {
+    let film = &*config.development.develop(film);
+    let context = PipelineContext {
+        film,
+        config,
+        depth_map: None,
+    };
+    let stages = config.stages();
+    let working = config.working_size(input.dimensions());
+
+    // The image after the first `count` stages, starting on the GPU when enabled.
+    let run_prefix = |count: usize| {
+        #[cfg(feature = "compute-gpu")]
+        if config.use_gpu {
+            let gpu_count = gpu_stage_count(&stages[..count]);
+            let prefix_config = SimulationConfig {
+                pipeline: stages[..gpu_count].to_vec(),
+                ..config.clone()
+            };
+            if let Some(buffer) = process_gpu_pipeline(input, film, &prefix_config) {
+                let mut buffer = resample_linear(buffer, working);
+                run_stages(&mut buffer, &stages[gpu_count..count], &context);
+                return buffer;
+            }
+        }
+        let mut buffer = resample_linear(linearize_input(input, config), working);
+        run_stages(&mut buffer, &stages[..count], &context);
+        buffer
+    };
+
+    let halation_end = stages
+        .iter()
+        .position(|&stage| stage == StageKind::Halation)
+        .map_or(0, |i| i + 1);
+
+    let original = run_prefix(0);
+    let linearized = linear_to_srgb_image(&original);
+    let mut image_buffer = run_prefix(halation_end);
+    let halated = linear_to_srgb_image(&image_buffer);
+
+    run_stages(&mut image_buffer, &stages[halation_end..], &context);
+    let output = |adjustments, grain| {
+        render_output(
+            &image_buffer,
+            None,
+            &context,
+            OutputSteps { adjustments, grain },
+        )
+    };
+
+    PipelineStages {
+        linearized,
+        halated,
+        developed: output(false, false),
+        grained: output(false, config.grains_output()),
+        final_image: create_blended_output_image(&image_buffer, Some(&original), &context),
+    }
+}
+
+/// Process several images, sharing one GPU submission and one readback.
+///
+/// The GPU stages of every item with `use_gpu` are recorded into a single
+/// command encoder, which amortizes the submit and map latency that dominates
+/// small images such as thumbnails. The remaining stages run per image on the
+/// CPU. Results are returned in input order.
+#[instrument(skip(items), fields(count = items.len()))]
+pub fn process_images_batch(items: &[(&RgbImage, &FilmStock, &SimulationConfig)]) -> Vec<RgbImage> {
+    #[cfg(feature = "compute-gpu")]
+    let gpu_results = process_gpu_batch(items);
+
+    #[cfg(not(feature = "compute-gpu"))]
+    let gpu_results: Vec<Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>>> =
+        items.iter().map(|_| None).collect();
 
-    let mut image_buffer = if let Some(buffer) = gpu_result {
-        buffer
-    } else {
-        let mut buffer = create_linear_image(input);
-        process_cpu_fallback(&mut buffer, &context);
-        buffer
-    };
+    items
+        .iter()
+        .zip(gpu_results)
+        .map(|(&(input, film, config), gpu_result)| {
+            let context = PipelineContext {
+                film,
+                config,
+                depth_map: None,
+            };
+            finish_image(input, gpu_result, &context)
+        })
+        .collect()
+}
 
-    // 3. Other Stages
-    // Both Fast and Accurate now use the full-spectrum pipeline
-    let pre_stages: Vec<Box<dyn PipelineStage>> = vec![
-        Box::new(MicroMotionStage),
-        Box::new(ObjectMotionStage),
-        Box::new(DepthOfFieldStage),
-        Box::new(RotationalBlurStage),
-        Box::new(MtfStage),
-        Box::new(ChromaticAberrationStage),
-    ];
-    for stage in pre_stages.iter() {
-        stage.process(&mut image_buffer, &context);
+/// Number of leading pipeline stages the GPU path runs.
+fn gpu_stage_count(stages: &[StageKind]) -> usize {
+    stages.iter().take_while(|stage| stage.has_gpu()).count()
+}
+
+/// Run the stages not covered by the GPU result and produce the output.
+/// Without a GPU result the image is linearized on the CPU first.
+fn finish_image(
+    input: &RgbImage,
+    gpu_result: Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>>,
+    context: &PipelineContext,
+) -> RgbImage {
+    match gpu_result {
+        Some(buffer) => {
+            let done = gpu_stage_count(context.config.stages());
+            finish_linear(buffer, done, input, context)
+        }
+        None => finish_linear(linearize_input(input, context.config), 0, input, context),
     }
-    AccurateDevelopStage.process(&mut image_buffer, &context);
+}
+
+/// Run the pipeline stages after the first `done` on a linear image and
+/// produce the output. The image is resampled to the working size of
+/// [`SimulationConfig::target_size`] first. `source` is the input the linear
+/// image came from, for [`SimulationConfig::effect_amount`].
+fn finish_linear(
+    image_buffer: image::ImageBuffer<image::Rgb<f32>, Vec<f32>>,
+    done: usize,
+    source: &RgbImage,
+    context: &PipelineContext,
+) -> RgbImage {
+    let working = context.config.working_size(image_buffer.dimensions());
+    let mut image_buffer = resample_linear(image_buffer, working);
+    // Before any stage ran the image is still the linearized input
+    let original = context.config.blends_original().then(|| {
+        if done == 0 {
+            image_buffer.clone()
+        } else {
+            resample_linear(linearize_input(source, context.config), working)
+        }
+    });
+    let film = context.config.development.develop(context.film);
+    let context = &PipelineContext {
+        film: &film,
+        config: context.config,
+        depth_map: context.depth_map,
+    };
+    run_stages(&mut image_buffer, &context.config.stages()[done..], context);
 
-    create_output_image(&image_buffer, &context)
+    create_blended_output_image(&image_buffer, original.as_ref(), context)
 }
 
 /// # Accurate Develop Stage
 ///
 /// Full-spectrum per-wavelength propagation through the film layer stack.
-struct AccurateDevelopStage;
+pub(crate) struct AccurateDevelopStage;
 
 impl PipelineStage for AccurateDevelopStage {
     #[instrument(skip(self, image, context))]
@@ -219,6 +1597,120 @@ impl PipelineStage for AccurateDevelopStage {
         let film = context.film;
         let config = context.config;
 
+        let model = DevelopModel::new(film, config);
+        let width = image.width();
+
+        // Pass 1: per-pixel spectral propagation → RGB exposure.
+        // BW films: single emulsion layer only produces one channel of exposure,
+        // so RGB is merged to mono here and all subsequent stages see grayscale.
+        image.par_chunks_mut(3).for_each(|pixel| {
+            let rgb = model.expose([pixel[0], pixel[1], pixel[2]]);
+            pixel.copy_from_slice(&rgb);
+        });
+
+        // Pass 2: scattering spatial diffusion (Gaussian blur per emulsion scatter)
+        // Total scatter sigma ≈ sum of (thickness * scattering) across emulsion layers,
+        // converted from µm to pixels assuming 35mm width.
+        let scatter_um: f32 = model
+            .stack
+            .layers
+            .iter()
+            .map(|l| l.thickness_um * l.scattering)
+            .sum();
+        if scatter_um > 0.0 {
+            let pixels_per_um = width as f32 / 36_000.0; // 36mm = 36000µm
+            let sigma_px = scatter_um * pixels_per_um;
+            if sigma_px > 0.3 {
+                info!(
+                    "Applying scattering diffusion blur (sigma: {:.2}px)",
+                    sigma_px
+                );
+                crate::utils::apply_gaussian_blur(image, sigma_px);
+            }
+        }
+
+        // Pass 2.5: White balance + warmth (same logic as Fast mode DevelopStage)
+        let wb_gains = match config.white_balance_mode {
+            crate::processor::WhiteBalanceMode::Auto => {
+                let step = (width * image.height() / 1000).max(1);
+                let mut sum_r = 0.0f32;
+                let mut sum_g = 0.0f32;
+                let mut sum_b = 0.0f32;
+                let mut count = 0.0f32;
+                for (i, pixel) in image.chunks(3).enumerate() {
+                    if (i as u32).is_multiple_of(step) {
+                        sum_r += pixel[0];
+                        sum_g += pixel[1];
+                        sum_b += pixel[2];
+                        count += 1.0;
+                    }
+                }
+                if count > 0.0 {
+                    let avg_r = sum_r / count;
+                    let avg_g = sum_g / count;
+                    let avg_b = sum_b / count;
+                    let lum = (avg_r + avg_g + avg_b) / 3.0;
+                    let eps = 1e-9;
+                    let s = config.white_balance_strength.clamp(0.0, 1.0);
+                    let warmth = config.warmth_gains();
+                    [
+                        (1.0 + (lum / avg_r.max(eps) - 1.0) * s) * warmth[0],
+                        (1.0 + (lum / avg_g.max(eps) - 1.0) * s) * warmth[1],
+                        (1.0 + (lum / avg_b.max(eps) - 1.0) * s) * warmth[2],
+                    ]
+                } else {
+                    [1.0, 1.0, 1.0]
+                }
+            }
+            _ => manual_white_balance_gains(film, config),
+        };
+
+        image.par_chunks_mut(3).for_each(|pixel| {
+            pixel[0] *= wb_gains[0];
+            pixel[1] *= wb_gains[1];
+            pixel[2] *= wb_gains[2];
+        });
+
+        // Pass 3: log-exposure → density via H-D curves + color matrix + inhibition
+        image.par_chunks_mut(3).for_each(|pixel| {
+            let d = model.density([pixel[0], pixel[1], pixel[2]]);
+            pixel.copy_from_slice(&d);
+        });
+    }
+}
+
+/// White balance gains without auto white balance: the warmth slider, and
+/// with white balance off the stock's native balance cast.
+pub(crate) fn manual_white_balance_gains(film: &FilmStock, config: &SimulationConfig) -> [f32; 3] {
+    let gains = config.warmth_gains();
+    match config.white_balance_mode {
+        WhiteBalanceMode::Off => {
+            let cast = film.native_balance_cast();
+            std::array::from_fn(|c| gains[c] * cast[c])
+        }
+        _ => gains,
+    }
+}
+
+/// Per-pixel math of [`AccurateDevelopStage`], precomputed once per frame.
+struct DevelopModel<'a> {
+    film: &'a FilmStock,
+    stack: FilmLayerStack,
+    fwd_coeffs: Vec<spectral_engine::LayerCoeffs>,
+    bwd_coeffs: Vec<spectral_engine::LayerCoeffs>,
+    base_r: f32,
+    /// Uplift × D65 matrix: 3 input channels → 81 spectral bins.
+    uplift_d65: [[f32; crate::spectral::BINS]; 3],
+    /// Per-channel exposure calibration times the user exposure time.
+    gain: [f32; 3],
+    /// BW spectral response weights, None for color films.
+    bw_weights: Option<[f32; 3]>,
+    /// Scene luminance weights through the B&W filter, None for color films.
+    luma_weights: Option<[f32; 3]>,
+}
+
+impl<'a> DevelopModel<'a> {
+    fn new(film: &'a FilmStock, config: &SimulationConfig) -> Self {
         let stack = film.layer_stack.clone().unwrap_or_else(|| {
             use crate::film::FilmType;
             match film.film_type {
@@ -245,8 +1737,7 @@ impl PipelineStage for AccurateDevelopStage {
         let acc_gray_avg = (acc_gray[0] + acc_gray[1] + acc_gray[2]) / 3.0;
 
         // BW weights (precompute once, used in simulate_gray and pixel path)
-        let is_bw = film.grain_model.monochrome;
-        let bw_w = if is_bw { film.bw_weights() } else { [0.0; 3] };
+        let bw_weights = film.is_monochrome().then(|| film.bw_weights());
 
         // Simulate full pipeline for a single gray pixel at a given scale
         let simulate_gray = |scale: f32| -> f32 {
@@ -256,7 +1747,7 @@ impl PipelineStage for AccurateDevelopStage {
                 acc_gray[2] * scale,
             ];
             // BW: merge channels before density mapping (same as pixel path)
-            if is_bw {
+            if let Some(bw_w) = bw_weights {
                 let v = bw_w[0] * exposure[0] + bw_w[1] * exposure[1] + bw_w[2] * exposure[2];
                 exposure = [v, v, v];
             }
@@ -304,28 +1795,18 @@ impl PipelineStage for AccurateDevelopStage {
         }
         let optimal_scale = (lo * hi).sqrt();
 
+        // Exposure after reciprocity (1.0 = neutral for Accurate mode)
+        let t_eff = config.effective_exposure(film);
+
         // norm = optimal_scale / acc_gray (per-channel for white balance)
-        let norm = [
-            if acc_gray[0] > 1e-10 {
-                optimal_scale * acc_gray_avg / acc_gray[0]
-            } else {
-                1.0
-            },
-            if acc_gray[1] > 1e-10 {
-                optimal_scale * acc_gray_avg / acc_gray[1]
-            } else {
-                1.0
-            },
-            if acc_gray[2] > 1e-10 {
-                optimal_scale * acc_gray_avg / acc_gray[2]
+        let gain = std::array::from_fn(|c| {
+            let norm = if acc_gray[c] > 1e-10 {
+                optimal_scale * acc_gray_avg / acc_gray[c]
             } else {
                 1.0
-            },
-        ];
-
-        // exposure_time: user EV adjustment (1.0 = neutral for Accurate mode)
-        let t_eff = config.exposure_time;
-        let width = image.width();
+            };
+            norm * t_eff
+        });
 
         // Precompute layer coefficients (once per frame, not per pixel)
         let (fwd_coeffs, bwd_coeffs) = spectral_engine::precompute(&stack);
@@ -336,7 +1817,6 @@ impl PipelineStage for AccurateDevelopStage {
             .unwrap_or(1.0);
         let base_r = ((base_n - 1.0) / (base_n + 1.0)).powi(2);
 
-        // Precompute uplift × D65 matrix: 3 input channels → 81 spectral bins
         let uplift_d65 = {
             let r_spec = camera.uplift(1.0, 0.0, 0.0);
             let g_spec = camera.uplift(0.0, 1.0, 0.0);
@@ -357,224 +1837,305 @@ impl PipelineStage for AccurateDevelopStage {
             m
         };
 
-        // Pass 1: per-pixel spectral propagation → RGB exposure
-        image.par_chunks_mut(3).for_each(|pixel| {
-            // Inline uplift × D65 (3 multiplies + 2 adds per bin instead of full uplift)
-            let mut scaled = [0.0f32; crate::spectral::BINS];
-            let (r, g, b) = (pixel[0], pixel[1], pixel[2]);
-            for (i, s) in scaled.iter_mut().enumerate() {
-                *s = r * uplift_d65[0][i] + g * uplift_d65[1][i] + b * uplift_d65[2][i];
-            }
-            let exposure =
-                spectral_engine::propagate_fast(&fwd_coeffs, &bwd_coeffs, base_r, &scaled);
-            let rgb = spectral_engine::integrate_exposure(&exposure);
-            pixel[0] = rgb[0] * norm[0] * t_eff;
-            pixel[1] = rgb[1] * norm[1] * t_eff;
-            pixel[2] = rgb[2] * norm[2] * t_eff;
-        });
-
-        // BW films: single emulsion layer only produces one channel of exposure.
-        // Merge RGB→mono using the film's spectral response weights before
-        // density mapping, so all subsequent stages operate on grayscale.
-        if is_bw {
-            let [wr, wg, wb] = bw_w;
-            image.par_chunks_mut(3).for_each(|pixel| {
-                let v = wr * pixel[0] + wg * pixel[1] + wb * pixel[2];
-                pixel[0] = v;
-                pixel[1] = v;
-                pixel[2] = v;
-            });
+        Self {
+            film,
+            stack,
+            fwd_coeffs,
+            bwd_coeffs,
+            base_r,
+            uplift_d65,
+            gain,
+            bw_weights,
+            luma_weights: film
+                .is_monochrome()
+                .then(|| config.bw_filter.apply(film.bw_luminance_weights())),
         }
+    }
 
-        // Pass 2: scattering spatial diffusion (Gaussian blur per emulsion scatter)
-        // Total scatter sigma ≈ sum of (thickness * scattering) across emulsion layers,
-        // converted from µm to pixels assuming 35mm width.
-        let scatter_um: f32 = stack
-            .layers
-            .iter()
-            .map(|l| l.thickness_um * l.scattering)
-            .sum();
-        if scatter_um > 0.0 {
-            let pixels_per_um = width as f32 / 36_000.0; // 36mm = 36000µm
-            let sigma_px = scatter_um * pixels_per_um;
-            if sigma_px > 0.3 {
-                info!(
-                    "Applying scattering diffusion blur (sigma: {:.2}px)",
-                    sigma_px
-                );
-                crate::utils::apply_gaussian_blur(image, sigma_px);
-            }
+    /// Linear scene RGB → calibrated RGB exposure (merged to mono for BW).
+    fn expose(&self, [r, g, b]: [f32; 3]) -> [f32; 3] {
+        // BW: the emulsion sees the scene's luminance by the stock's channel mix
+        let [r, g, b] = match self.luma_weights {
+            Some([wr, wg, wb]) => [wr * r + wg * g + wb * b; 3],
+            None => [r, g, b],
+        };
+        // Inline uplift × D65 (3 multiplies + 2 adds per bin instead of full uplift)
+        let mut scaled = [0.0f32; crate::spectral::BINS];
+        for (i, s) in scaled.iter_mut().enumerate() {
+            *s = r * self.uplift_d65[0][i] + g * self.uplift_d65[1][i] + b * self.uplift_d65[2][i];
         }
-
-        // Pass 2.5: White balance + warmth (same logic as Fast mode DevelopStage)
-        let wb_gains = match config.white_balance_mode {
-            crate::processor::WhiteBalanceMode::Auto => {
-                let step = (width * image.height() / 1000).max(1);
-                let mut sum_r = 0.0f32;
-                let mut sum_g = 0.0f32;
-                let mut sum_b = 0.0f32;
-                let mut count = 0.0f32;
-                for (i, pixel) in image.chunks(3).enumerate() {
-                    if (i as u32).is_multiple_of(step) {
-                        sum_r += pixel[0];
-                        sum_g += pixel[1];
-                        sum_b += pixel[2];
-                        count += 1.0;
-                    }
-                }
-                if count > 0.0 {
-                    let avg_r = sum_r / count;
-                    let avg_g = sum_g / count;
-                    let avg_b = sum_b / count;
-                    let lum = (avg_r + avg_g + avg_b) / 3.0;
-                    let eps = 1e-9;
-                    let s = config.white_balance_strength.clamp(0.0, 1.0);
-                    let warmth = config.warmth.clamp(-1.0, 1.0);
-                    [
-                        (1.0 + (lum / avg_r.max(eps) - 1.0) * s) * (1.0 + warmth * 0.1),
-                        1.0 + (lum / avg_g.max(eps) - 1.0) * s,
-                        (1.0 + (lum / avg_b.max(eps) - 1.0) * s) * (1.0 - warmth * 0.1),
-                    ]
-                } else {
-                    [1.0, 1.0, 1.0]
-                }
-            }
-            _ => {
-                let warmth = config.warmth.clamp(-1.0, 1.0);
-                [1.0 + warmth * 0.1, 1.0, 1.0 - warmth * 0.1]
+        let exposure = spectral_engine::propagate_fast(
+            &self.fwd_coeffs,
+            &self.bwd_coeffs,
+            self.base_r,
+            &scaled,
+        );
+        let rgb = spectral_engine::integrate_exposure(&exposure);
+        let rgb: [f32; 3] = std::array::from_fn(|c| rgb[c] * self.gain[c]);
+        match self.bw_weights {
+            Some([wr, wg, wb]) => {
+                let v = wr * rgb[0] + wg * rgb[1] + wb * rgb[2];
+                [v, v, v]
             }
-        };
+            None => rgb,
+        }
+    }
 
-        image.par_chunks_mut(3).for_each(|pixel| {
-            pixel[0] *= wb_gains[0];
-            pixel[1] *= wb_gains[1];
-            pixel[2] *= wb_gains[2];
-        });
+    /// White-balanced exposure → density via H-D curves + color matrix + inhibition.
+    fn density(&self, exposure: [f32; 3]) -> [f32; 3] {
+        let epsilon = 1e-6;
+        let log_e = exposure.map(|e| e.max(epsilon).log10());
+        let d = self.film.map_log_exposure(log_e);
 
-        // Pass 3: log-exposure → density via H-D curves + color matrix + inhibition
-        let inhibition = stack.inhibition;
-        image.par_chunks_mut(3).for_each(|pixel| {
-            let epsilon = 1e-6;
-            let log_e = [
-                pixel[0].max(epsilon).log10(),
-                pixel[1].max(epsilon).log10(),
-                pixel[2].max(epsilon).log10(),
-            ];
-            let d = film.map_log_exposure(log_e);
+        // Interlayer interimage effect: inhibition based on density DEVIATION
+        // from the mean. This ensures neutral gray is unaffected while
+        // colour differences are enhanced (physically: DIR couplers respond
+        // to development rate differences between adjacent layers).
+        let inhibition = self.stack.inhibition;
+        let d_mean = (d[0] + d[1] + d[2]) / 3.0;
+        let dd = [d[0] - d_mean, d[1] - d_mean, d[2] - d_mean];
+        std::array::from_fn(|c| {
+            (d[c] + inhibition[c][0] * dd[0] + inhibition[c][1] * dd[1] + inhibition[c][2] * dd[2])
+                .max(0.0)
+        })
+    }
+}
 
-            // Interlayer interimage effect: inhibition based on density DEVIATION
-            // from the mean. This ensures neutral gray is unaffected while
-            // colour differences are enhanced (physically: DIR couplers respond
-            // to development rate differences between adjacent layers).
-            let d_mean = (d[0] + d[1] + d[2]) / 3.0;
-            let dd = [d[0] - d_mean, d[1] - d_mean, d[2] - d_mean];
-            pixel[0] = (d[0]
-                + inhibition[0][0] * dd[0]
-                + inhibition[0][1] * dd[1]
-                + inhibition[0][2] * dd[2])
-                .max(0.0);
-            pixel[1] = (d[1]
-                + inhibition[1][0] * dd[0]
-                + inhibition[1][1] * dd[1]
-                + inhibition[1][2] * dd[2])
-                .max(0.0);
-            pixel[2] = (d[2]
-                + inhibition[2][0] * dd[0]
-                + inhibition[2][1] * dd[1]
-                + inhibition[2][2] * dd[2])
-                .max(0.0);
-        });
+/// Develop a single input pixel: returns the linearized scene value, the
+/// film density and the sRGB output. The input is decoded like the full
+/// develop, so it is taken as linear with
+/// [`SimulationConfig::input_is_linear`].
+///
+/// This is the per-pixel part of [`process_image`], cheap enough to call on
+/// hover. Steps that depend on the rest of the frame are left out: spatial
+/// stages (halation, blur, MTF), scatter diffusion, auto white balance
+/// (warmth and the stock's native balance cast still apply), vignette, auto levels, light-leak overlays and grain.
+pub fn develop_pixel(
+    film: &FilmStock,
+    config: &SimulationConfig,
+    rgb: [u8; 3],
+) -> ([f32; 3], [f32; 3], [u8; 3]) {
+    let film = &*config.development.develop(film);
+    let linear = rgb.map(|v| linearize_input_value(v, config));
+    let model = DevelopModel::new(film, config);
+    let gains = manual_white_balance_gains(film, config);
+    let exposure = model.expose(linear);
+    let density = model.density(std::array::from_fn(|c| exposure[c] * gains[c]));
+    let mut scanned = crate::pipeline::scan_pixel(film, config, density);
+    if config.blends_original() {
+        let amount = config.effect_amount.max(0.0);
+        scanned = std::array::from_fn(|c| linear[c] + (scanned[c] - linear[c]) * amount);
     }
+    let out = scanned.map(|v| (config.output_transfer.encode(v) * 255.0).round() as u8);
+    (linear, density, out)
 }
 
-/// Helper function to execute CPU fallback stages
-#[allow(dead_code)] // Suppress unused warning if gpu feature is disabled or always used
-fn process_cpu_fallback(
-    image_buffer: &mut image::ImageBuffer<image::Rgb<f32>, Vec<f32>>,
-    context: &PipelineContext,
-) {
-    // 1. Light Leak (CPU)
-    let light_leak = LightLeakStage;
-    light_leak.process(image_buffer, context);
+/// Film density of each pixel, per channel, as the develop produces it.
+///
+/// Uses the per-pixel develop of [`develop_pixel`], so spatial and
+/// whole-frame steps are left out.
+pub fn density_map(
+    input: &RgbImage,
+    film: &FilmStock,
+    config: &SimulationConfig,
+) -> image::ImageBuffer<image::Rgb<f32>, Vec<f32>> {
+    let film = &*config.development.develop(film);
+    let model = DevelopModel::new(film, config);
+    let gains = manual_white_balance_gains(film, config);
+    let mut densities = vec![0.0f32; input.as_raw().len()];
+    densities
+        .par_chunks_mut(3)
+        .zip(input.as_raw().par_chunks(3))
+        .for_each(|(out, rgb)| {
+            let linear: [f32; 3] = std::array::from_fn(|c| linearize_input_value(rgb[c], config));
+            let exposure = model.expose(linear);
+            out.copy_from_slice(&model.density(std::array::from_fn(|c| exposure[c] * gains[c])));
+        });
+    image::ImageBuffer::from_raw(input.width(), input.height(), densities).unwrap()
+}
 
-    // 2. Halation (CPU)
-    let halation = HalationStage;
-    halation.process(image_buffer, context);
+/// How far each pixel's density runs into the stock's shoulder, the largest
+/// over the channels: 0 up to the curve's shoulder density, 1 at `d_max`.
+/// Past the shoulder the curve flattens and highlight detail is lost; on
+/// slide film this is where the frame clips. See [`density_map`].
+pub fn shoulder_overflow(
+    input: &RgbImage,
+    film: &FilmStock,
+    config: &SimulationConfig,
+) -> image::ImageBuffer<image::Luma<f32>, Vec<f32>> {
+    let curves = [&film.r_curve, &film.g_curve, &film.b_curve];
+    let densities = density_map(input, film, config);
+    let overflow: Vec<f32> = densities
+        .as_raw()
+        .par_chunks(3)
+        .map(|density| {
+            (0..3)
+                .map(|c| {
+                    let curve = curves[c];
+                    let shoulder = curve.shoulder_density();
+                    let span = (curve.d_max - shoulder).max(1e-3);
+                    ((density[c] - shoulder) / span).clamp(0.0, 1.0)
+                })
+                .fold(0.0, f32::max)
+        })
+        .collect();
+    image::ImageBuffer::from_raw(input.width(), input.height(), overflow).unwrap()
 }
 
 #[cfg(feature = "compute-gpu")]
 fn process_gpu_pipeline(
-    _context: &PipelineContext,
     input: &RgbImage,
     film: &FilmStock,
     config: &SimulationConfig,
 ) -> Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>> {
-    use crate::gpu::GpuBuffer;
+    process_gpu_batch(&[(input, film, config)]).pop().flatten()
+}
 
-    let gpu_ctx = get_gpu_context()?;
-    let mut gpu_buffer: Option<GpuBuffer>;
+/// Run the GPU stages for every item with `use_gpu` in one submission.
+/// Items without `use_gpu` or without pixels, or all items when no GPU is
+/// available, yield `None`.
+#[cfg(feature = "compute-gpu")]
+fn process_gpu_batch(
+    items: &[(&RgbImage, &FilmStock, &SimulationConfig)],
+) -> Vec<Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>>> {
+    let none = || items.iter().map(|_| None).collect::<Vec<_>>();
 
-    // Linearization
-    {
-        let _span = tracing::info_span!("GPU Linearization").entered();
-        info!("Attempting GPU Linearization...");
-        let pipeline = get_linearize_pipeline(gpu_ctx);
-        gpu_buffer = pipeline.process_to_gpu_buffer(gpu_ctx, input);
-    }
+    let Some(preference) = items
+        .iter()
+        .find(|(_, _, config)| config.use_gpu)
+        .map(|(_, _, config)| config.gpu_power_preference)
+    else {
+        return none();
+    };
+    let Some(gpu_ctx) = get_gpu_context_with_preference(preference) else {
+        return none();
+    };
 
-    gpu_buffer.as_ref()?;
+    let mut encoder = gpu_ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Filmr Pipeline Encoder"),
+        });
+    let mut retired = Vec::new();
+
+    let outputs: Vec<Option<crate::gpu::GpuBuffer>> = items
+        .iter()
+        .map(|&(input, film, config)| {
+            // Empty images have nothing to upload and finish on the CPU
+            (config.use_gpu && !input.is_empty()).then(|| {
+                encode_gpu_pipeline(gpu_ctx, &mut encoder, input, film, config, &mut retired)
+            })
+        })
+        .collect();
 
-    // Light Leak
-    if let Some(ref mut buffer) = gpu_buffer {
-        let _span = tracing::info_span!("GPU Light Leak").entered();
-        info!("Applying Light Leak on GPU");
-        let pipeline = get_light_leak_pipeline(gpu_ctx);
-        pipeline.process(gpu_ctx, buffer, &config.light_leak);
+    gpu_ctx.queue.submit(Some(encoder.finish()));
+    for buffer in retired {
+        gpu_ctx.buffer_pool.release(buffer);
     }
 
-    // Halation
-    if let Some(buffer) = gpu_buffer.take() {
-        if film.halation_strength > 0.0 {
-            let _span = tracing::info_span!("GPU Halation").entered();
-            info!("Applying Halation on GPU");
-            let pipeline = get_halation_pipeline(gpu_ctx);
-            if let Some(out_buffer) = pipeline.process(gpu_ctx, &buffer, film) {
-                gpu_buffer = Some(out_buffer);
-            } else {
-                gpu_buffer = Some(buffer);
-            }
-        } else {
-            gpu_buffer = Some(buffer);
-        }
+    // Readback
+    let to_read: Vec<&crate::gpu::GpuBuffer> = outputs.iter().flatten().collect();
+    let mut read = {
+        let _span = tracing::info_span!("GPU Readback").entered();
+        info!("Reading back {} image(s) from GPU pipeline", to_read.len());
+        crate::gpu::block_on(read_gpu_buffers(gpu_ctx, &to_read)).into_iter()
+    };
+
+    let results = outputs
+        .iter()
+        .map(|output| output.as_ref().and_then(|_| read.next().flatten()))
+        .collect();
+    for buffer in outputs.into_iter().flatten() {
+        gpu_ctx.recycle(buffer);
     }
+    results
+}
 
-    // MTF
-    if let Some(buffer) = gpu_buffer.take() {
-        let pixels_per_mm = buffer.width as f32 / 36.0;
-        let mtf_sigma = (0.5 / film.resolution_lp_mm) * pixels_per_mm;
+/// Record linearization and the leading GPU stages of `config.pipeline`
+/// for one image into `encoder`.
+#[cfg(feature = "compute-gpu")]
+fn encode_gpu_pipeline(
+    gpu_ctx: &crate::gpu::GpuContext,
+    encoder: &mut wgpu::CommandEncoder,
+    input: &RgbImage,
+    film: &FilmStock,
+    config: &SimulationConfig,
+    retired: &mut Vec<wgpu::Buffer>,
+) -> crate::gpu::GpuBuffer {
+    // Linearization
+    let buffer = {
+        let _span = tracing::info_span!("GPU Linearization").entered();
+        info!("Attempting GPU Linearization...");
+        get_linearize_pipeline(gpu_ctx).encode(gpu_ctx, encoder, input, config.input_is_linear)
+    };
+    encode_gpu_stages(gpu_ctx, encoder, buffer, film, config, retired)
+}
 
-        if mtf_sigma > 0.5 {
-            let _span = tracing::info_span!("GPU MTF Blur").entered();
-            info!("Applying MTF Blur on GPU (sigma: {:.2})", mtf_sigma);
-            let pipeline = get_gaussian_pipeline(gpu_ctx);
-            if let Some(out_buffer) = pipeline.process(gpu_ctx, &buffer, mtf_sigma) {
-                gpu_buffer = Some(out_buffer);
-            } else {
-                gpu_buffer = Some(buffer);
+/// Record the leading GPU stages of `config.pipeline` on a linear `buffer`.
+/// Stages may write `buffer` in place or retire it, so it must not be used
+/// again by the caller.
+#[cfg(feature = "compute-gpu")]
+fn encode_gpu_stages(
+    gpu_ctx: &crate::gpu::GpuContext,
+    encoder: &mut wgpu::CommandEncoder,
+    mut buffer: crate::gpu::GpuBuffer,
+    film: &FilmStock,
+    config: &SimulationConfig,
+    retired: &mut Vec<wgpu::Buffer>,
+) -> crate::gpu::GpuBuffer {
+    for stage in &config.stages()[..gpu_stage_count(config.stages())] {
+        match stage {
+            StageKind::Diffusion => {
+                if config.lens.diffusion > 0.0 {
+                    let _span = tracing::info_span!("GPU Diffusion").entered();
+                    info!("Applying Diffusion on GPU");
+                    let out = get_diffusion_pipeline(gpu_ctx).encode(
+                        gpu_ctx,
+                        encoder,
+                        &buffer,
+                        &config.lens,
+                        retired,
+                    );
+                    retired.push(std::mem::replace(&mut buffer, out).buffer);
+                }
             }
-        } else {
-            gpu_buffer = Some(buffer);
+            StageKind::LightLeak => {
+                let _span = tracing::info_span!("GPU Light Leak").entered();
+                info!("Applying Light Leak on GPU");
+                get_light_leak_pipeline(gpu_ctx).encode(
+                    gpu_ctx,
+                    encoder,
+                    &mut buffer,
+                    &config.light_leak,
+                );
+            }
+            StageKind::Halation => {
+                if film.halation_strength > 0.0 || film.halation_strength2 > 0.0 {
+                    let _span = tracing::info_span!("GPU Halation").entered();
+                    info!("Applying Halation on GPU");
+                    let out = get_halation_pipeline(gpu_ctx)
+                        .encode(gpu_ctx, encoder, &buffer, film, retired);
+                    retired.push(std::mem::replace(&mut buffer, out).buffer);
+                }
+            }
+            StageKind::ChromaticAberration => {
+                if config.lens.chromatic_aberration != 0.0 {
+                    let _span = tracing::info_span!("GPU Chromatic Aberration").entered();
+                    info!("Applying Chromatic Aberration on GPU");
+                    let out = get_chromatic_aberration_pipeline(gpu_ctx).encode(
+                        gpu_ctx,
+                        encoder,
+                        &buffer,
+                        &config.lens,
+                    );
+                    retired.push(std::mem::replace(&mut buffer, out).buffer);
+                }
+            }
+            other => unreachable!("{:?} has no GPU implementation", other),
         }
     }
 
-    // Readback
-    if let Some(ref buffer) = gpu_buffer {
-        let _span = tracing::info_span!("GPU Readback").entered();
-        info!("Reading back from GPU pipeline");
-        crate::gpu::block_on(read_gpu_buffer(gpu_ctx, buffer))
-    } else {
-        None
-    }
+    buffer
 }
 
 /// Main processor function (Async).
@@ -591,44 +2152,36 @@ pub async fn process_image_async(
         depth_map: None,
     };
 
+    let linear = linearize_image_async(input, config).await;
+    finish_linear(linear, 0, input, &context)
+}
+
+/// Decode sRGB input to linear light like [`utils::srgb_to_linear`], on the
+/// GPU when `config.use_gpu` is set and one is available. With
+/// [`SimulationConfig::input_is_linear`] the input is only scaled to 0-1.
+///
+/// Both backends use the same transfer function, so the result matches the
+/// CPU decode up to float rounding.
+///
+/// [`utils::srgb_to_linear`]: crate::utils::srgb_to_linear
+pub async fn linearize_image_async(
+    input: &RgbImage,
+    config: &SimulationConfig,
+) -> ImageBuffer<image::Rgb<f32>, Vec<f32>> {
     #[cfg(feature = "compute-gpu")]
-    let gpu_result = if config.use_gpu {
-        if let Some(gpu_ctx) = get_gpu_context() {
+    if config.use_gpu {
+        if let Some(gpu_ctx) = get_gpu_context_with_preference(config.gpu_power_preference) {
             info!("Attempting GPU Linearization...");
             let pipeline = get_linearize_pipeline(gpu_ctx);
-            pipeline.process_image_async(gpu_ctx, input).await
-        } else {
-            None
+            if let Some(buffer) = pipeline
+                .process_image_async(gpu_ctx, input, config.input_is_linear)
+                .await
+            {
+                info!("Used GPU for linearization");
+                return buffer;
+            }
         }
-    } else {
-        None
-    };
-
-    #[cfg(not(feature = "compute-gpu"))]
-    let gpu_result: Option<image::ImageBuffer<image::Rgb<f32>, Vec<f32>>> = None;
-
-    let mut image_buffer = if let Some(buffer) = gpu_result {
-        info!("Used GPU for linearization");
-        buffer
-    } else {
-        create_linear_image(input)
-    };
-
-    // Sequential Stage Execution
-    let stages: Vec<Box<dyn PipelineStage>> = vec![
-        Box::new(LightLeakStage),
-        Box::new(HalationStage),
-        Box::new(MicroMotionStage),
-        Box::new(DepthOfFieldStage),
-        Box::new(RotationalBlurStage),
-        Box::new(MtfStage),
-        Box::new(ChromaticAberrationStage),
-    ];
-
-    for stage in stages {
-        stage.process(&mut image_buffer, &context);
     }
-    AccurateDevelopStage.process(&mut image_buffer, &context);
 
-    create_output_image(&image_buffer, &context)
+    linearize_input(input, config)
 }
@@ -0,0 +1,315 @@
+//! Develop recipes: a film stock reference plus every develop parameter.
+//!
+//! A [`FilmStock`] captures the emulsion; a [`Recipe`] captures how it was
+//! shot and developed, so a look can be saved by name and shared. Recipes
+//! refer to their stock by [`FilmStock::full_name`] instead of embedding it,
+//! the parameter-side counterpart of a
+//! [`FilmStockCollection`](crate::film::FilmStockCollection).
+
+use crate::error::FilmrError;
+use crate::film::{FilmStock, FilmStyle};
+use crate::pipeline::StageKind;
+use crate::processor::SimulationConfig;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Schema version written by [`Recipe::save_to_file`].
+///
+/// - 1: files written without a version.
+/// - 2: [`SimulationConfig::pipeline`] lists [`StageKind::Grain`]. Older
+///   recipes get it after [`StageKind::Develop`], where grain always ran.
+pub const RECIPE_VERSION: u32 = 2;
+
+/// Files written before versioning are version 1.
+fn default_version() -> u32 {
+    1
+}
+
+fn default_gamma_scale() -> f32 {
+    1.0
+}
+
+/// Adjustments a recipe makes to its stock. Unset fields keep the stock's value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StockOverrides {
+    #[serde(default)]
+    pub style: FilmStyle,
+    /// Multiplier on the gamma of all three curves.
+    #[serde(default = "default_gamma_scale")]
+    pub gamma_scale: f32,
+    #[serde(default)]
+    pub halation_strength: Option<f32>,
+    #[serde(default)]
+    pub halation_threshold: Option<f32>,
+    #[serde(default)]
+    pub halation_sigma: Option<f32>,
+    #[serde(default)]
+    pub grain_alpha: Option<f32>,
+    #[serde(default)]
+    pub grain_sigma_read: Option<f32>,
+    #[serde(default)]
+    pub grain_roughness: Option<f32>,
+    #[serde(default)]
+    pub grain_blur_radius: Option<f32>,
+}
+
+impl Default for StockOverrides {
+    fn default() -> Self {
+        Self {
+            style: FilmStyle::default(),
+            gamma_scale: 1.0,
+            halation_strength: None,
+            halation_threshold: None,
+            halation_sigma: None,
+            grain_alpha: None,
+            grain_sigma_read: None,
+            grain_roughness: None,
+            grain_blur_radius: None,
+        }
+    }
+}
+
+impl StockOverrides {
+    /// `film` with the style and overrides applied.
+    pub fn apply(&self, film: &FilmStock) -> FilmStock {
+        let mut film = film.clone().with_style(self.style);
+        let set = |value: Option<f32>, field: &mut f32| {
+            if let Some(value) = value {
+                *field = value;
+            }
+        };
+        set(self.halation_strength, &mut film.halation_strength);
+        set(self.halation_threshold, &mut film.halation_threshold);
+        set(self.halation_sigma, &mut film.halation_sigma);
+        set(self.grain_alpha, &mut film.grain_model.alpha);
+        set(self.grain_sigma_read, &mut film.grain_model.sigma_read);
+        set(self.grain_roughness, &mut film.grain_model.roughness);
+        set(self.grain_blur_radius, &mut film.grain_model.blur_radius);
+        film.r_curve.gamma *= self.gamma_scale;
+        film.g_curve.gamma *= self.gamma_scale;
+        film.b_curve.gamma *= self.gamma_scale;
+        film
+    }
+}
+
+/// A named look: which stock to use, how to adjust it and how to develop.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recipe {
+    /// Schema version; newer files than [`RECIPE_VERSION`] are rejected.
+    #[serde(default = "default_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub name: String,
+    /// [`FilmStock::full_name`] of the stock the recipe was made with.
+    pub stock_name: String,
+    pub config: SimulationConfig,
+    #[serde(default)]
+    pub overrides: StockOverrides,
+}
+
+impl Recipe {
+    pub fn new(
+        name: impl Into<String>,
+        stock: &FilmStock,
+        config: SimulationConfig,
+        overrides: StockOverrides,
+    ) -> Self {
+        Self {
+            version: RECIPE_VERSION,
+            name: name.into(),
+            stock_name: stock.full_name(),
+            config,
+            overrides,
+        }
+    }
+
+    /// The recipe's stock from `stocks` with the overrides applied, or
+    /// `None` if it is not among them.
+    pub fn film<'a>(&self, stocks: impl IntoIterator<Item = &'a FilmStock>) -> Option<FilmStock> {
+        stocks
+            .into_iter()
+            .find(|stock| stock.full_name() == self.stock_name)
+            .map(|stock| self.overrides.apply(stock))
+    }
+
+    /// Save the recipe to a JSON file
+    pub fn save_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), FilmrError> {
+        let file = std::fs::File::create(path)?;
+        let writer = std::io::BufWriter::new(file);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+
+    /// Load a recipe from a JSON file
+    pub fn load_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, FilmrError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Single-line JSON, e.g. to embed in the metadata of an exported image.
+    pub fn to_json(&self) -> Result<String, FilmrError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Read a recipe from JSON, migrating older versions like
+    /// [`Recipe::load_from_file`].
+    pub fn from_json(json: &str) -> Result<Self, FilmrError> {
+        let mut recipe: Recipe = serde_json::from_str(json)?;
+        if recipe.version > RECIPE_VERSION {
+            return Err(FilmrError::UnsupportedFormat(format!(
+                "recipe version {} is newer than supported version {}",
+                recipe.version, RECIPE_VERSION
+            )));
+        }
+        recipe.migrate();
+        let light_leak = &recipe.config.light_leak;
+        if light_leak.enabled {
+            for overlay in light_leak.unloaded_overlays() {
+                warn!(
+                    "Recipe {:?} uses light-leak overlay {:?}, which must be loaded before developing",
+                    recipe.name, overlay.source
+                );
+            }
+        }
+        Ok(recipe)
+    }
+
+    /// Bring a recipe read from an older file up to [`RECIPE_VERSION`].
+    pub fn migrate(&mut self) {
+        let pipeline = &mut self.config.pipeline;
+        if self.version < 2 && !pipeline.contains(&StageKind::Grain) {
+            let after_develop = pipeline
+                .iter()
+                .position(|&stage| stage == StageKind::Develop)
+                .map_or(pipeline.len(), |develop| develop + 1);
+            pipeline.insert(after_develop, StageKind::Grain);
+        }
+        self.version = self.version.max(RECIPE_VERSION);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::Toning;
+
+    #[test]
+    fn test_recipe_round_trip() {
+        let stock = crate::presets::kodak::KODAK_TRI_X_400();
+        let config = SimulationConfig::builder()
+            .exposure_time(0.5)
+            .toning(Toning::Selenium)
+            .build();
+        let overrides = StockOverrides {
+            gamma_scale: 1.2,
+            grain_alpha: Some(0.02),
+            ..Default::default()
+        };
+        let recipe = Recipe::new("Selenium Tri-X", &stock, config, overrides);
+
+        let path = std::env::temp_dir().join("filmr_recipe_round_trip.json");
+        recipe.save_to_file(&path).unwrap();
+        let loaded = Recipe::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded, recipe);
+
+        let film = loaded.film([&stock]).unwrap();
+        assert_eq!(film.grain_model.alpha, 0.02);
+        assert_eq!(film.halation_strength, stock.halation_strength);
+        assert!((film.g_curve.gamma - stock.g_curve.gamma * 1.2).abs() < 1e-6);
+        assert!(loaded
+            .film([&crate::presets::kodak::KODAK_PORTRA_400()])
+            .is_none());
+    }
+
+    #[test]
+    fn test_recipe_json_is_one_line_and_round_trips() {
+        let recipe = Recipe::new(
+            "Pushed",
+            &crate::presets::kodak::KODAK_TRI_X_400(),
+            SimulationConfig::builder().exposure_time(2.0).build(),
+            StockOverrides::default(),
+        );
+        let json = recipe.to_json().unwrap();
+        assert!(!json.contains('\n'));
+        assert_eq!(Recipe::from_json(&json).unwrap(), recipe);
+        assert!(Recipe::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn test_newer_recipe_version_is_rejected() {
+        let mut recipe = Recipe::new(
+            "Future",
+            &crate::presets::kodak::KODAK_PORTRA_400(),
+            SimulationConfig::default(),
+            StockOverrides::default(),
+        );
+        recipe.version = RECIPE_VERSION + 1;
+
+        let path = std::env::temp_dir().join("filmr_recipe_future.json");
+        recipe.save_to_file(&path).unwrap();
+        let loaded = Recipe::load_from_file(&path);
+        std::fs::remove_file(&path).ok();
+        assert!(matches!(loaded, Err(FilmrError::UnsupportedFormat(_))));
+    }
+
+    #[test]
+    fn test_legacy_recipe_keeps_its_grain() {
+        let mut recipe = Recipe::new(
+            "Old",
+            &crate::presets::kodak::KODAK_PORTRA_400(),
+            SimulationConfig {
+                pipeline: vec![StageKind::Halation, StageKind::Develop, StageKind::Mtf],
+                ..Default::default()
+            },
+            StockOverrides::default(),
+        );
+        recipe.version = 1;
+
+        let path = std::env::temp_dir().join("filmr_recipe_legacy.json");
+        recipe.save_to_file(&path).unwrap();
+        let loaded = Recipe::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded.version, RECIPE_VERSION);
+        assert_eq!(
+            loaded.config.pipeline,
+            [
+                StageKind::Halation,
+                StageKind::Develop,
+                StageKind::Grain,
+                StageKind::Mtf
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlay_source_survives_json() {
+        use crate::light_leak::{LightLeakConfig, LightLeakOverlay};
+
+        let overlay = LightLeakOverlay {
+            source: "leaks/red_edge.png".into(),
+            image: Some(std::sync::Arc::new(image::RgbaImage::new(4, 4))),
+            ..Default::default()
+        };
+        let config = SimulationConfig {
+            light_leak: LightLeakConfig {
+                enabled: true,
+                overlays: vec![overlay],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let recipe = Recipe::new(
+            "Leaky",
+            &crate::presets::kodak::KODAK_PORTRA_400(),
+            config,
+            StockOverrides::default(),
+        );
+        assert_eq!(recipe.config.light_leak.unloaded_overlays().count(), 0);
+
+        // The pixels stay behind; the path tells the caller what to reload
+        let loaded = Recipe::from_json(&recipe.to_json().unwrap()).unwrap();
+        let unloaded: Vec<_> = loaded.config.light_leak.unloaded_overlays().collect();
+        assert_eq!(unloaded.len(), 1);
+        assert_eq!(unloaded[0].source, "leaks/red_edge.png");
+    }
+}
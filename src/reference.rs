@@ -0,0 +1,133 @@
+//! Shader-exact CPU references for the GPU stages.
+//!
+//! The pipeline's CPU stages are free to approximate (halation blurs with
+//! three box passes, for example), so they cannot tell a shader bug from an
+//! approximation. These functions repeat the math of `develop.wgsl` and
+//! `halation.wgsl` step by step, so GPU output can be checked against them
+//! down to float rounding. They are slow and single purpose; use
+//! [`process_image`](crate::processor::process_image) for real work.
+//!
+//! Grain draws its noise from different generators on the CPU and GPU and
+//! has no reference; compare its statistics instead.
+
+use crate::film::FilmStock;
+use crate::pipeline::halation_mask;
+use image::{ImageBuffer, Rgb};
+
+type LinearImage = ImageBuffer<Rgb<f32>, Vec<f32>>;
+
+/// Largest blur radius the halation shader samples, in pixels.
+const HALATION_MAX_RADIUS: i32 = 50;
+
+/// `develop.wgsl`: linear RGB to film densities with the same inputs as
+/// `DevelopPipeline::process`.
+pub fn develop(
+    image: &LinearImage,
+    film: &FilmStock,
+    spectral_matrix: &[[f32; 3]; 3],
+    wb_gains: [f32; 3],
+    t_eff: f32,
+) -> LinearImage {
+    // B&W layers all see the scene luminance, as the pipeline sets it up
+    let matrix: [[f32; 3]; 3] = if film.is_monochrome() {
+        let weights = film.bw_luminance_weights();
+        std::array::from_fn(|c| {
+            let gray: f32 = spectral_matrix[c].iter().sum();
+            weights.map(|w| w * gray)
+        })
+    } else {
+        *spectral_matrix
+    };
+
+    let mut out = image.clone();
+    out.pixels_mut().for_each(|p| {
+        let log_e: [f32; 3] = std::array::from_fn(|c| {
+            let exposure = matrix[c][0] * p[0] + matrix[c][1] * p[1] + matrix[c][2] * p[2];
+            let exposure = (exposure * wb_gains[c]).max(0.0) * t_eff;
+            exposure.max(1e-6).ln() / std::f32::consts::LN_10
+        });
+        p.0 = film.map_log_exposure(log_e);
+    });
+    out
+}
+
+/// `halation.wgsl`: the core bloom, then the optional wide skirt, each a
+/// truncated Gaussian of the bright mask added over the previous result.
+pub fn halation(image: &LinearImage, film: &FilmStock) -> LinearImage {
+    let backing = film.halation_backing_gain();
+    let core = halation_scale(
+        image,
+        image,
+        film,
+        film.halation_sigma,
+        film.halation_strength * backing,
+    );
+    if film.halation_strength2 > 0.0 && film.halation_sigma2 > 0.0 {
+        halation_scale(
+            image,
+            &core,
+            film,
+            film.halation_sigma2,
+            film.halation_strength2 * backing,
+        )
+    } else {
+        core
+    }
+}
+
+/// One halation scale: mask `input`, blur in x then y with edge clamping,
+/// and add the glow to `base`.
+fn halation_scale(
+    input: &LinearImage,
+    base: &LinearImage,
+    film: &FilmStock,
+    sigma_fraction: f32,
+    strength: f32,
+) -> LinearImage {
+    let (width, height) = input.dimensions();
+    let sigma = width as f32 * sigma_fraction;
+    let radius = ((3.0 * sigma).ceil() as i32).min(HALATION_MAX_RADIUS);
+    let weights: Vec<f32> = (-radius..=radius)
+        .map(|i| (-(i as f32 * i as f32) / (2.0 * sigma * sigma)).exp())
+        .collect();
+
+    // Normalized sum of `sample(offset)` over the kernel
+    let blur = |sample: &dyn Fn(i32) -> f32| {
+        let mut sum = 0.0;
+        let mut weight_sum = 0.0;
+        for (i, w) in (-radius..=radius).zip(&weights) {
+            sum += sample(i) * w;
+            weight_sum += w;
+        }
+        if weight_sum > 0.0 {
+            sum / weight_sum
+        } else {
+            sum
+        }
+    };
+    let clamp_x = |x: i32| x.clamp(0, width as i32 - 1) as u32;
+    let clamp_y = |y: i32| y.clamp(0, height as i32 - 1) as u32;
+
+    let mask = |x: u32, y: u32| {
+        let p = input.get_pixel(x, y);
+        halation_mask(
+            p.0,
+            film.halation_threshold,
+            film.halation_saturation_weight,
+        )
+    };
+    let blurred_x: Vec<f32> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| blur(&|i| mask(clamp_x(x as i32 + i), y)))
+        .collect();
+
+    let tint = film.halation_tint;
+    let gain = film.halation_channel_gain;
+    ImageBuffer::from_fn(width, height, |x, y| {
+        let glow = blur(&|i| blurred_x[(clamp_y(y as i32 + i) * width + x) as usize]);
+        let original = base.get_pixel(x, y);
+        Rgb(std::array::from_fn(|c| {
+            original[c] + glow * tint[c] * gain[c] * strength
+        }))
+    })
+}
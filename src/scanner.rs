@@ -0,0 +1,148 @@
+//! Film scanner simulation.
+//!
+//! Much of the scanned film look comes from the scanner rather than the film:
+//! unsharp-mask halos, slight misregistration between the color channels and
+//! the sensor's own noise floor. A [`ScannerProfile`] set on
+//! [`SimulationConfig::scanner`] applies these to the output after grain, so
+//! the scanner digitizes the grained frame.
+//!
+//! Profile values are in pixels of the full-resolution scan; downscaled
+//! previews (see [`SimulationConfig::source_width`]) shrink them to match.
+//!
+//! [`SimulationConfig::scanner`]: crate::processor::SimulationConfig::scanner
+//! [`SimulationConfig::source_width`]: crate::processor::SimulationConfig::source_width
+
+use crate::pipeline::white_noise;
+use crate::utils;
+use image::{ImageBuffer, Rgb};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Noise streams of the scanner noise floor, clear of the grain streams.
+const NOISE_STREAM: u64 = 16;
+
+/// Characteristic processing of a film scanner.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScannerProfile {
+    /// Unsharp mask amount (0.0 = off).
+    pub sharpen_amount: f32,
+    /// Unsharp mask radius (Gaussian sigma) in scan pixels.
+    pub sharpen_radius: f32,
+    /// Offset of the R, G and B channels as `[x, y]` in scan pixels.
+    pub misregistration: [[f32; 2]; 3],
+    /// Standard deviation of the sensor noise, in linear output units.
+    pub noise_floor: f32,
+}
+
+impl ScannerProfile {
+    /// Noritsu-style minilab scan: crisp, fine sharpening and a clean sensor.
+    pub fn noritsu() -> Self {
+        Self {
+            sharpen_amount: 0.6,
+            sharpen_radius: 1.0,
+            misregistration: [[0.15, 0.0], [0.0, 0.0], [-0.15, 0.0]],
+            noise_floor: 0.003,
+        }
+    }
+
+    /// Frontier-style minilab scan: strong sharpening with visible halos.
+    pub fn frontier() -> Self {
+        Self {
+            sharpen_amount: 1.0,
+            sharpen_radius: 1.5,
+            misregistration: [[0.0, 0.25], [0.0, 0.0], [0.0, -0.25]],
+            noise_floor: 0.005,
+        }
+    }
+
+    /// Consumer flatbed: soft, loosely registered and noisy.
+    pub fn flatbed() -> Self {
+        Self {
+            sharpen_amount: 0.25,
+            sharpen_radius: 2.0,
+            misregistration: [[0.5, 0.3], [0.0, 0.0], [-0.4, -0.5]],
+            noise_floor: 0.01,
+        }
+    }
+
+    /// Apply the scanner to a linear output buffer of `width` x `height`.
+    /// `downscale` is scan pixels per buffer pixel (1.0 at full resolution).
+    pub(crate) fn apply(
+        &self,
+        linear_buf: &mut [f32],
+        width: u32,
+        height: u32,
+        downscale: f32,
+        seed: Option<u64>,
+    ) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let downscale = downscale.max(1.0);
+        self.misregister(linear_buf, width, height, downscale);
+        self.sharpen(linear_buf, width, height, downscale);
+
+        if self.noise_floor > 0.0 {
+            // Averaging `downscale`² scan pixels lowers the noise by `downscale`
+            let std = self.noise_floor / downscale;
+            let noise: Vec<Vec<f32>> = (0..3)
+                .map(|c| white_noise(width, height, seed, NOISE_STREAM + c))
+                .collect();
+            linear_buf
+                .par_chunks_mut(3)
+                .enumerate()
+                .for_each(|(i, px)| {
+                    for (v, channel) in px.iter_mut().zip(&noise) {
+                        *v = (*v + std * channel[i]).clamp(0.0, 1.0);
+                    }
+                });
+        }
+    }
+
+    /// Shift each channel by its offset, with bilinear sampling.
+    fn misregister(&self, linear_buf: &mut [f32], width: u32, height: u32, downscale: f32) {
+        if self.misregistration.iter().flatten().all(|&o| o == 0.0) {
+            return;
+        }
+        let src = linear_buf.to_vec();
+        let (w, h) = (width as usize, height as usize);
+        let sample = |c: usize, x: f32, y: f32| {
+            let x = x.clamp(0.0, (w - 1) as f32);
+            let y = y.clamp(0.0, (h - 1) as f32);
+            let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+            let (x1, y1) = ((x0 + 1).min(w - 1), (y0 + 1).min(h - 1));
+            let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+            let at = |x: usize, y: usize| src[(y * w + x) * 3 + c];
+            let top = at(x0, y0) + (at(x1, y0) - at(x0, y0)) * fx;
+            let bottom = at(x0, y1) + (at(x1, y1) - at(x0, y1)) * fx;
+            top + (bottom - top) * fy
+        };
+        let offsets = self
+            .misregistration
+            .map(|[dx, dy]| [dx / downscale, dy / downscale]);
+        linear_buf
+            .par_chunks_mut(3)
+            .enumerate()
+            .for_each(|(i, px)| {
+                let (x, y) = ((i % w) as f32, (i / w) as f32);
+                for (c, [dx, dy]) in offsets.iter().enumerate() {
+                    px[c] = sample(c, x - dx, y - dy);
+                }
+            });
+    }
+
+    /// Unsharp mask: push each pixel away from its blurred surroundings.
+    fn sharpen(&self, linear_buf: &mut [f32], width: u32, height: u32, downscale: f32) {
+        if self.sharpen_amount <= 0.0 {
+            return;
+        }
+        let mut blurred: ImageBuffer<Rgb<f32>, Vec<f32>> =
+            ImageBuffer::from_raw(width, height, linear_buf.to_vec()).unwrap();
+        utils::apply_gaussian_blur(&mut blurred, (self.sharpen_radius / downscale).max(0.5));
+        let amount = self.sharpen_amount;
+        linear_buf
+            .par_iter_mut()
+            .zip(blurred.as_raw().par_iter())
+            .for_each(|(v, b)| *v = (*v + amount * (*v - b)).clamp(0.0, 1.0));
+    }
+}
@@ -28,7 +28,11 @@ fn gaussian(x: f32, sigma: f32) -> f32 {
     return exp(-(x * x) / (2.0 * sigma * sigma));
 }
 
-@compute @workgroup_size(16, 16, 1)
+// Workgroup size, set from WORKGROUP_SIZE in gpu_pipelines/common.rs
+override WORKGROUP_X: u32 = 16u;
+override WORKGROUP_Y: u32 = 16u;
+
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
 fn main_x(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let x = global_id.x;
     let y = global_id.y;
@@ -56,7 +60,7 @@ fn main_x(@builtin(global_invocation_id) global_id: vec3<u32>) {
     write_pixel(x, y, sum);
 }
 
-@compute @workgroup_size(16, 16, 1)
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
 fn main_y(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let x = global_id.x;
     let y = global_id.y;
@@ -0,0 +1,47 @@
+struct Uniforms {
+    width: u32,
+    height: u32,
+    r_scale: f32,
+    b_scale: f32,
+}
+
+@group(0) @binding(0) var<storage, read> input_buffer: array<f32>;
+@group(0) @binding(1) var<storage, read_write> output_buffer: array<f32>;
+@group(0) @binding(2) var<uniform> uniforms: Uniforms;
+
+fn read_channel(x: i32, y: i32, c: u32) -> f32 {
+    let cx = u32(clamp(x, 0, i32(uniforms.width) - 1));
+    let cy = u32(clamp(y, 0, i32(uniforms.height) - 1));
+    return input_buffer[(cy * uniforms.width + cx) * 3u + c];
+}
+
+// Bilinear sample of one channel at pixel-center coordinates, clamped at the edges.
+fn sample_channel(p: vec2<f32>, c: u32) -> f32 {
+    let q = p - vec2<f32>(0.5);
+    let i = vec2<i32>(floor(q));
+    let f = q - floor(q);
+    let top = mix(read_channel(i.x, i.y, c), read_channel(i.x + 1, i.y, c), f.x);
+    let bottom = mix(read_channel(i.x, i.y + 1, c), read_channel(i.x + 1, i.y + 1, c), f.x);
+    return mix(top, bottom, f.y);
+}
+
+// Workgroup size, set from WORKGROUP_SIZE in gpu_pipelines/common.rs
+override WORKGROUP_X: u32 = 16u;
+override WORKGROUP_Y: u32 = 16u;
+
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let x = global_id.x;
+    let y = global_id.y;
+
+    if (x >= uniforms.width || y >= uniforms.height) { return; }
+
+    let center = vec2<f32>(f32(uniforms.width), f32(uniforms.height)) * 0.5;
+    let d = vec2<f32>(f32(x), f32(y)) + vec2<f32>(0.5) - center;
+
+    // R magnified (inward lookup), G unchanged, B demagnified (outward lookup)
+    let idx = (y * uniforms.width + x) * 3u;
+    output_buffer[idx] = sample_channel(center + d / uniforms.r_scale, 0u);
+    output_buffer[idx + 1u] = input_buffer[idx + 1u];
+    output_buffer[idx + 2u] = sample_channel(center + d / uniforms.b_scale, 2u);
+}
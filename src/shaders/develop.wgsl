@@ -74,16 +74,11 @@ fn map_curve(log_e: f32, curve: Curve) -> f32 {
     return curve.d_min + range * sigmoid;
 }
 
-// Space charge limit compression at high densities
-fn shoulder_softening(density: f32, shoulder_point: f32) -> f32 {
-    if (density > shoulder_point) {
-        let excess = density - shoulder_point;
-        return density - (excess * excess) / (shoulder_point + excess);
-    }
-    return density;
-}
+// Workgroup size, set from WORKGROUP_SIZE in gpu_pipelines/common.rs
+override WORKGROUP_X: u32 = 16u;
+override WORKGROUP_Y: u32 = 16u;
 
-@compute @workgroup_size(16, 16, 1)
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let x = global_id.x;
     let y = global_id.y;
@@ -113,24 +108,22 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let log_g = log(max(g_exposure, epsilon)) / log(10.0);
     let log_b = log(max(b_exposure, epsilon)) / log(10.0);
 
-    var d_r = map_curve(log_r, uniforms.curve_r);
-    var d_g = map_curve(log_g, uniforms.curve_g);
-    var d_b = map_curve(log_b, uniforms.curve_b);
-
-    // 5. Shoulder softening
-    d_r = shoulder_softening(d_r, uniforms.curve_r.shoulder_point);
-    d_g = shoulder_softening(d_g, uniforms.curve_g.shoulder_point);
-    d_b = shoulder_softening(d_b, uniforms.curve_b.shoulder_point);
+    // The sigmoid already has a shoulder; no extra softening, as in
+    // FilmStock::map_log_exposure
+    let d_r = map_curve(log_r, uniforms.curve_r);
+    let d_g = map_curve(log_g, uniforms.curve_g);
+    let d_b = map_curve(log_b, uniforms.curve_b);
 
-    // 6. Net density -> Color coupling matrix -> Final density
+    // 5. Net density -> Color coupling matrix -> Final density
     let net_r = max(d_r - uniforms.curve_r.d_min, 0.0);
     let net_g = max(d_g - uniforms.curve_g.d_min, 0.0);
     let net_b = max(d_b - uniforms.curve_b.d_min, 0.0);
     let net = vec3<f32>(net_r, net_g, net_b);
 
-    let out_r = dot(uniforms.color_r, net) + uniforms.curve_r.d_min;
-    let out_g = dot(uniforms.color_g, net) + uniforms.curve_g.d_min;
-    let out_b = dot(uniforms.color_b, net) + uniforms.curve_b.d_min;
+    // No layer goes below its base, as in FilmStock::map_log_exposure
+    let out_r = max(dot(uniforms.color_r, net), 0.0) + uniforms.curve_r.d_min;
+    let out_g = max(dot(uniforms.color_g, net), 0.0) + uniforms.curve_g.d_min;
+    let out_b = max(dot(uniforms.color_b, net), 0.0) + uniforms.curve_b.d_min;
 
     write_pixel(x, y, vec3<f32>(out_r, out_g, out_b));
 }
@@ -0,0 +1,59 @@
+struct DiffusionUniforms {
+    width: u32,
+    height: u32,
+    strength: f32,
+    knee: f32,
+}
+
+@group(0) @binding(0) var<storage, read> input_buffer: array<f32>;
+@group(0) @binding(1) var<storage, read_write> output_buffer: array<f32>;
+@group(0) @binding(2) var<uniform> uniforms: DiffusionUniforms;
+// Blurred scattered light, only read by `blend`
+@group(0) @binding(3) var<storage, read> glow_buffer: array<f32>;
+
+fn read_pixel(idx: u32) -> vec3<f32> {
+    return vec3<f32>(input_buffer[idx], input_buffer[idx+1u], input_buffer[idx+2u]);
+}
+
+fn write_pixel(idx: u32, color: vec3<f32>) {
+    output_buffer[idx] = color.r;
+    output_buffer[idx+1u] = color.g;
+    output_buffer[idx+2u] = color.b;
+}
+
+// Light a pixel sends into the glow, weighted toward the highlights
+fn scattered(color: vec3<f32>) -> vec3<f32> {
+    let lum = max(dot(color, vec3<f32>(0.2126, 0.7152, 0.0722)), 0.0);
+    return color * (lum / (lum + uniforms.knee));
+}
+
+// Workgroup size, set from WORKGROUP_SIZE in gpu_pipelines/common.rs
+override WORKGROUP_X: u32 = 16u;
+override WORKGROUP_Y: u32 = 16u;
+
+// Pass 1: scattered light, written to output_buffer for the blur.
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
+fn weight(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let x = global_id.x;
+    let y = global_id.y;
+
+    if (x >= uniforms.width || y >= uniforms.height) { return; }
+
+    let idx = (y * uniforms.width + x) * 3u;
+    write_pixel(idx, scattered(read_pixel(idx)));
+}
+
+// Pass 2: move the scattered light into the blurred glow.
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
+fn blend(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let x = global_id.x;
+    let y = global_id.y;
+
+    if (x >= uniforms.width || y >= uniforms.height) { return; }
+
+    let idx = (y * uniforms.width + x) * 3u;
+    let color = read_pixel(idx);
+    let glow = vec3<f32>(glow_buffer[idx], glow_buffer[idx+1u], glow_buffer[idx+2u]);
+    let out = color + uniforms.strength * (glow - scattered(color));
+    write_pixel(idx, max(out, vec3<f32>(0.0)));
+}
@@ -2,15 +2,15 @@ struct Uniforms {
     width: u32,
     height: u32,
     threshold: f32,
-    _pad1: f32,
+    saturation_weight: f32,
     sigma: f32,
     strength: f32,
     tint_r: f32,
     tint_g: f32,
     tint_b: f32,
-    _pad2: f32,
-    _pad3: f32,
-    _pad4: f32,
+    gain_r: f32,
+    gain_g: f32,
+    gain_b: f32,
 }
 
 @group(0) @binding(0) var<storage, read> input_buffer: array<f32>;
@@ -37,12 +37,31 @@ fn luminance(c: vec3<f32>) -> f32 {
     return 0.2126 * c.r + 0.7152 * c.g + 0.0722 * c.b;
 }
 
+// Linear luminance above threshold (both scene-linear, the input is not
+// encoded here), reduced for saturated colors so that neon
+// lights bloom less than whites of the same luminance.
+// Must match halation_mask in pipeline.rs.
+fn bright_mask(c: vec3<f32>) -> f32 {
+    let excess = max(luminance(c) - uniforms.threshold, 0.0);
+    let hi = max(max(c.r, c.g), c.b);
+    let lo = min(min(c.r, c.g), c.b);
+    var saturation = 0.0;
+    if (hi > 0.0) {
+        saturation = clamp((hi - lo) / hi, 0.0, 1.0);
+    }
+    return excess * (1.0 - uniforms.saturation_weight * saturation);
+}
+
 fn gaussian(x: f32, sigma: f32) -> f32 {
     return exp(-(x * x) / (2.0 * sigma * sigma));
 }
 
+// Workgroup size, set from WORKGROUP_SIZE in gpu_pipelines/common.rs
+override WORKGROUP_X: u32 = 16u;
+override WORKGROUP_Y: u32 = 16u;
+
 // Pass 1: Threshold + Horizontal Blur
-@compute @workgroup_size(16, 16, 1)
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
 fn main_x(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let x = global_id.x;
     let y = global_id.y;
@@ -60,14 +79,9 @@ fn main_x(@builtin(global_invocation_id) global_id: vec3<u32>) {
         let sample_x = i32(x) + i;
         let pixel = read_pixel(sample_x, i32(y));
         
-        // Apply thresholding on the fly during the first pass read
-        let lum = luminance(pixel);
-        var thresholded = pixel;
-        if (lum < uniforms.threshold) {
-            thresholded = vec3<f32>(0.0);
-        } else {
-            thresholded = max(pixel - vec3<f32>(uniforms.threshold), vec3<f32>(0.0));
-        }
+        // Luminance bright mask, computed on the fly during the first pass read.
+        // Stored in all channels; the per-layer split happens in the blend pass.
+        let thresholded = vec3<f32>(bright_mask(pixel));
 
         let w = gaussian(f32(i), uniforms.sigma);
         sum += thresholded * w;
@@ -97,7 +111,7 @@ fn read_original_pixel(x: u32, y: u32) -> vec3<f32> {
     return vec3<f32>(original_buffer[idx], original_buffer[idx+1u], original_buffer[idx+2u]);
 }
 
-@compute @workgroup_size(16, 16, 1)
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
 fn main_y(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let x = global_id.x;
     let y = global_id.y;
@@ -128,7 +142,9 @@ fn main_y(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let halation = sum;
     
     let tint = vec3<f32>(uniforms.tint_r, uniforms.tint_g, uniforms.tint_b);
-    let result = original + halation * tint * uniforms.strength;
+    // Reflected light mostly re-exposes the red-sensitive layer
+    let gain = vec3<f32>(uniforms.gain_r, uniforms.gain_g, uniforms.gain_b);
+    let result = original + halation * tint * gain * uniforms.strength;
 
     write_pixel(x, y, result);
 }
@@ -4,6 +4,8 @@
 struct Uniforms {
     width: u32,
     height: u32,
+    input_is_linear: u32,
+    _pad: u32,
 }
 @group(0) @binding(2) var<uniform> uniforms: Uniforms;
 
@@ -23,7 +25,19 @@ fn read_u8(byte_index: u32) -> f32 {
     return f32(byte_val) / 255.0;
 }
 
-@compute @workgroup_size(16, 16, 1)
+fn decode(byte_index: u32) -> f32 {
+    let v = read_u8(byte_index);
+    if (uniforms.input_is_linear != 0u) {
+        return v;
+    }
+    return srgb_to_linear(v);
+}
+
+// Workgroup size, set from WORKGROUP_SIZE in gpu_pipelines/common.rs
+override WORKGROUP_X: u32 = 16u;
+override WORKGROUP_Y: u32 = 16u;
+
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let x = global_id.x;
     let y = global_id.y;
@@ -36,9 +50,9 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let base_byte_index = pixel_index * 3u;
 
     // Read RGB
-    let r = srgb_to_linear(read_u8(base_byte_index));
-    let g = srgb_to_linear(read_u8(base_byte_index + 1u));
-    let b = srgb_to_linear(read_u8(base_byte_index + 2u));
+    let r = decode(base_byte_index);
+    let g = decode(base_byte_index + 1u);
+    let b = decode(base_byte_index + 2u);
 
     // Write packed RGB f32
     let base_float_index = pixel_index * 3u;
@@ -19,6 +19,38 @@ struct OutputUniforms {
 @group(0) @binding(1) var<storage, read_write> output_buffer: array<f32>;
 @group(0) @binding(2) var<uniform> uniforms: OutputUniforms;
 
+// Same Lab chroma scale as `apply_saturation` in pipeline.rs
+const D65_WHITE = vec3<f32>(0.95047, 1.0, 1.08883);
+const LAB_DELTA: f32 = 6.0 / 29.0;
+
+fn lab_f(t: vec3<f32>) -> vec3<f32> {
+    let cube = LAB_DELTA * LAB_DELTA * LAB_DELTA;
+    let linear = t / (3.0 * LAB_DELTA * LAB_DELTA) + 4.0 / 29.0;
+    return select(linear, pow(max(t, vec3<f32>(0.0)), vec3<f32>(1.0 / 3.0)), t > vec3<f32>(cube));
+}
+
+fn lab_f_inv(t: vec3<f32>) -> vec3<f32> {
+    let linear = 3.0 * LAB_DELTA * LAB_DELTA * (t - 4.0 / 29.0);
+    return select(linear, t * t * t, t > vec3<f32>(LAB_DELTA));
+}
+
+fn apply_saturation(rgb: vec3<f32>, saturation: f32) -> vec3<f32> {
+    // WGSL matrices are column-major: each vec3 is a column
+    let srgb_to_xyz = mat3x3<f32>(
+        vec3<f32>(0.4124564, 0.2126729, 0.0193339),
+        vec3<f32>(0.3575761, 0.7151522, 0.1191920),
+        vec3<f32>(0.1804375, 0.0721750, 0.9503041),
+    );
+    let xyz_to_srgb = mat3x3<f32>(
+        vec3<f32>(3.2404542, -0.9692660, 0.0556434),
+        vec3<f32>(-1.5371385, 1.8760108, -0.2040259),
+        vec3<f32>(-0.4985314, 0.0415560, 1.0572252),
+    );
+    let f = lab_f(srgb_to_xyz * rgb / D65_WHITE);
+    let scaled = vec3<f32>(f.y) + (f - vec3<f32>(f.y)) * saturation;
+    return xyz_to_srgb * (lab_f_inv(scaled) * D65_WHITE);
+}
+
 fn read_pixel(x: u32, y: u32) -> vec3<f32> {
     if (x >= uniforms.width || y >= uniforms.height) { return vec3<f32>(0.0); }
     let idx = (y * uniforms.width + x) * 3u;
@@ -53,7 +85,11 @@ fn linear_to_srgb(v: f32) -> f32 {
     }
 }
 
-@compute @workgroup_size(16, 16, 1)
+// Workgroup size, set from WORKGROUP_SIZE in gpu_pipelines/common.rs
+override WORKGROUP_X: u32 = 16u;
+override WORKGROUP_Y: u32 = 16u;
+
+@compute @workgroup_size(WORKGROUP_X, WORKGROUP_Y, 1)
 fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
     let x = global_id.x;
     let y = global_id.y;
@@ -100,12 +136,12 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
         b_lin = pow(n_b, uniforms.paper_gamma);
     }
     
-    // Saturation
+    // Saturation: Lab chroma scale, keeping lightness and hue
     if (uniforms.saturation != 1.0) {
-        let lum = 0.2126 * r_lin + 0.7152 * g_lin + 0.0722 * b_lin;
-        r_lin = lum + (r_lin - lum) * uniforms.saturation;
-        g_lin = lum + (g_lin - lum) * uniforms.saturation;
-        b_lin = lum + (b_lin - lum) * uniforms.saturation;
+        let rgb = apply_saturation(vec3<f32>(r_lin, g_lin, b_lin), uniforms.saturation);
+        r_lin = rgb.r;
+        g_lin = rgb.g;
+        b_lin = rgb.b;
     }
     
     // Convert to sRGB
@@ -0,0 +1,185 @@
+//! Shutter speeds.
+//!
+//! A [`ShutterSpeed`] is the exposure time as set on a camera: one of the
+//! marked speeds, or bulb for anything longer. Set on a [`SimulationConfig`]
+//! it drives the stock's reciprocity failure, so long speeds lose density;
+//! how much light reaches the film is still the relative `exposure_time`.
+//!
+//! [`relative_exposure`] goes the other way for a real shot's settings:
+//! aperture, shutter and film speed against the scene's light level give the
+//! relative `exposure_time`, where 1.0 is 18% gray metered at box speed.
+//!
+//! [`SimulationConfig`]: crate::processor::SimulationConfig
+
+use serde::{Deserialize, Serialize};
+
+/// Marked shutter speeds in seconds, full stops from 1/8000 to 30 s.
+pub const SHUTTER_STOPS: [f32; 19] = [
+    1.0 / 8000.0,
+    1.0 / 4000.0,
+    1.0 / 2000.0,
+    1.0 / 1000.0,
+    1.0 / 500.0,
+    1.0 / 250.0,
+    1.0 / 125.0,
+    1.0 / 60.0,
+    1.0 / 30.0,
+    1.0 / 15.0,
+    1.0 / 8.0,
+    1.0 / 4.0,
+    1.0 / 2.0,
+    1.0,
+    2.0,
+    4.0,
+    8.0,
+    15.0,
+    30.0,
+];
+
+/// Shutter speed of an exposure.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ShutterSpeed {
+    /// A marked speed from [`SHUTTER_STOPS`], in seconds.
+    Stop(f32),
+    /// Shutter held open for the given number of seconds.
+    Bulb(f32),
+}
+
+impl Default for ShutterSpeed {
+    fn default() -> Self {
+        ShutterSpeed::Stop(1.0 / 125.0)
+    }
+}
+
+impl ShutterSpeed {
+    /// Exposure time in seconds.
+    pub fn seconds(self) -> f32 {
+        match self {
+            ShutterSpeed::Stop(t) | ShutterSpeed::Bulb(t) => t,
+        }
+    }
+
+    /// The marked speed closest to `seconds` in stops, or bulb past the
+    /// longest one.
+    pub fn nearest(seconds: f32) -> Self {
+        let longest = SHUTTER_STOPS[SHUTTER_STOPS.len() - 1];
+        if seconds > longest * 2f32.sqrt() {
+            return ShutterSpeed::Bulb(seconds);
+        }
+        let log_t = seconds.max(f32::MIN_POSITIVE).log2();
+        let stop = SHUTTER_STOPS
+            .iter()
+            .copied()
+            .min_by(|a, b| {
+                (a.log2() - log_t)
+                    .abs()
+                    .total_cmp(&(b.log2() - log_t).abs())
+            })
+            .unwrap_or(longest);
+        ShutterSpeed::Stop(stop)
+    }
+
+    /// Label as marked on a camera, e.g. "1/125", "2\"" or "B 45\"".
+    pub fn label(self) -> String {
+        match self {
+            ShutterSpeed::Stop(t) if t < 1.0 => format!("1/{}", (1.0 / t).round()),
+            ShutterSpeed::Stop(t) => format!("{}\"", t),
+            ShutterSpeed::Bulb(t) => format!("B {:.0}\"", t),
+        }
+    }
+}
+
+impl From<f32> for ShutterSpeed {
+    fn from(seconds: f32) -> Self {
+        ShutterSpeed::nearest(seconds)
+    }
+}
+
+impl From<ShutterSpeed> for f32 {
+    fn from(speed: ShutterSpeed) -> Self {
+        speed.seconds()
+    }
+}
+
+/// Exposure of a shot at f/`aperture` and `shutter` on film rated at `iso`,
+/// relative to what a meter set to `iso` calls correct for a scene of
+/// `scene_ev` (EV at ISO 100). Each stop over or under doubles or halves it.
+///
+/// This is the `exposure_time` scale of [`SimulationConfig`]: 18% gray
+/// exposed at the stock's box speed is 1.0. Reciprocity failure is not
+/// included; see [`SimulationConfig::from_exposure_triangle`].
+///
+/// [`SimulationConfig`]: crate::processor::SimulationConfig
+/// [`SimulationConfig::from_exposure_triangle`]: crate::processor::SimulationConfig::from_exposure_triangle
+pub fn relative_exposure(aperture: f32, shutter: ShutterSpeed, iso: f32, scene_ev: f32) -> f32 {
+    // A meter picks N²/t = 2^EV * ISO / 100
+    let metered = 2f32.powf(scene_ev) * iso / 100.0;
+    shutter.seconds() * metered / (aperture * aperture).max(f32::MIN_POSITIVE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processor::SimulationConfig;
+
+    #[test]
+    fn test_nearest_snaps_to_marked_speeds() {
+        assert_eq!(
+            ShutterSpeed::from(1.0 / 120.0),
+            ShutterSpeed::Stop(1.0 / 125.0)
+        );
+        assert_eq!(ShutterSpeed::from(3.0), ShutterSpeed::Stop(4.0));
+        assert_eq!(ShutterSpeed::from(120.0), ShutterSpeed::Bulb(120.0));
+        assert_eq!(f32::from(ShutterSpeed::Bulb(120.0)), 120.0);
+        assert_eq!(ShutterSpeed::Stop(1.0 / 125.0).label(), "1/125");
+    }
+
+    #[test]
+    fn test_long_shutter_triggers_reciprocity() {
+        let film = crate::presets::kodak::KODAK_TRI_X_400();
+        let config = |shutter_speed| SimulationConfig {
+            shutter_speed,
+            ..Default::default()
+        };
+
+        // A short shutter leaves the exposure alone
+        let short = config(Some(ShutterSpeed::Stop(1.0 / 125.0)));
+        assert_eq!(short.effective_exposure(&film), 1.0);
+
+        let long = config(Some(ShutterSpeed::Stop(30.0)));
+        assert!(long.effective_exposure(&film) < 1.0);
+
+        // Without a shutter speed there is no reciprocity loss
+        assert_eq!(config(None).effective_exposure(&film), 1.0);
+    }
+
+    #[test]
+    fn test_exposure_triangle_meters_to_one() {
+        // EV 15 meters f/16 at 1/128 for ISO 100, 1/512 for ISO 400
+        let film = crate::presets::kodak::KODAK_TRI_X_400();
+        let sunny = relative_exposure(16.0, ShutterSpeed::Bulb(1.0 / 512.0), 400.0, 15.0);
+        assert!((sunny - 1.0).abs() < 1e-3, "metered exposure gave {sunny}");
+
+        // One stop open doubles, and the config uses the stock's box speed
+        let open = SimulationConfig::from_exposure_triangle(
+            &film,
+            16.0 / 2f32.sqrt(),
+            ShutterSpeed::Bulb(1.0 / 512.0),
+            15.0,
+        );
+        assert!((open.exposure_time / sunny - 2.0).abs() < 1e-3);
+
+        // A metered 30 s night exposure comes out under from reciprocity
+        let night_ev = (8.0f32 * 8.0 / 30.0 / 4.0).log2();
+        let night_shutter = ShutterSpeed::Stop(30.0);
+        assert!((relative_exposure(8.0, night_shutter, 400.0, night_ev) - 1.0).abs() < 1e-3);
+        let night = SimulationConfig::from_exposure_triangle(&film, 8.0, night_shutter, night_ev);
+        assert!((night.exposure_time - 1.0).abs() < 1e-3);
+        let loss = night.effective_exposure(&film);
+        let expected = film.reciprocity.effective_time(30.0) / 30.0;
+        assert!(
+            (loss - expected).abs() < 1e-4,
+            "reciprocity applied as {loss}"
+        );
+    }
+}
@@ -457,6 +457,19 @@ impl FilmSpectralParams {
             b_width: 40.0,
         }
     }
+
+    /// Create infrared response behind a 720nm (Wratten 89B) filter. The
+    /// filter blocks visible light, so only the near-IR tail is left.
+    pub const fn new_infrared_filtered() -> Self {
+        Self {
+            r_peak: 760.0,
+            r_width: 25.0,
+            g_peak: 0.0,
+            g_width: 0.0,
+            b_peak: 0.0,
+            b_width: 0.0,
+        }
+    }
 }
 
 impl FilmSensitivities {
@@ -1,11 +1,217 @@
-use image::{ImageBuffer, Rgb};
+use image::imageops::FilterType;
+use image::{ImageBuffer, Rgb, RgbImage};
 use rayon::prelude::*;
 use wide::f32x4;
 
+/// Filter used for every preview downscale, with [`resize_to_fit_linear`].
+/// Lanczos3 keeps fine patterns from aliasing into false grain.
+pub const PREVIEW_FILTER: FilterType = FilterType::Lanczos3;
+
+/// Downscale so the longer side is at most `max_side`, preserving aspect ratio.
+/// Images already within the limit are returned as a copy.
+pub fn resize_to_fit(image: &RgbImage, max_side: u32, filter: FilterType) -> RgbImage {
+    let (w, h) = image.dimensions();
+    if w <= max_side && h <= max_side {
+        return image.clone();
+    }
+    let scale = max_side as f32 / w.max(h) as f32;
+    let nw = ((w as f32 * scale).round() as u32).max(1);
+    let nh = ((h as f32 * scale).round() as u32).max(1);
+    image::imageops::resize(image, nw, nh, filter)
+}
+
+/// [`resize_to_fit`] in linear light: decode, resize, encode again.
+///
+/// Filtering encoded values darkens fine high-contrast detail. A 1px
+/// black/white checker averages to code 128, about 22% linear, instead of
+/// the 50% it reflects, so a preview resized that way develops darker than
+/// the full frame. Large reductions are first box-averaged in linear light
+/// to at most twice the target size, which keeps the working buffer small,
+/// then `filter` does the rest.
+pub fn resize_to_fit_linear(image: &RgbImage, max_side: u32, filter: FilterType) -> RgbImage {
+    let (w, h) = image.dimensions();
+    if w <= max_side && h <= max_side {
+        return image.clone();
+    }
+    let scale = max_side as f32 / w.max(h) as f32;
+    let nw = ((w as f32 * scale).round() as u32).max(1);
+    let nh = ((h as f32 * scale).round() as u32).max(1);
+    resize_linear(image, nw, nh, filter)
+}
+
+/// Resize to exactly `width` x `height` in linear light, up or down, with the
+/// same box pre-pass as [`resize_to_fit_linear`] for large reductions.
+pub fn resize_linear(image: &RgbImage, width: u32, height: u32, filter: FilterType) -> RgbImage {
+    let (w, h) = image.dimensions();
+    if (w, h) == (width, height) {
+        return image.clone();
+    }
+    if w == 0 || h == 0 || width == 0 || height == 0 {
+        return RgbImage::new(width, height);
+    }
+    let reduction = if w >= h {
+        w as f32 / width as f32
+    } else {
+        h as f32 / height as f32
+    };
+    let factor = (reduction / 2.0).floor().max(1.0) as u32;
+    let linear = if factor > 1 {
+        box_downsample_linear(image, factor)
+    } else {
+        srgb_to_linear(image)
+    };
+    linear_to_srgb(&image::imageops::resize(&linear, width, height, filter))
+}
+
+/// Average `factor` × `factor` blocks in linear light. Blocks at the right
+/// and bottom edges average the pixels they have.
+fn box_downsample_linear(image: &RgbImage, factor: u32) -> ImageBuffer<Rgb<f32>, Vec<f32>> {
+    let lut: Vec<f32> = (0..=255)
+        .map(|i| crate::physics::srgb_to_linear(i as f32 / 255.0))
+        .collect();
+    let (w, h) = image.dimensions();
+    let (ow, oh) = (w.div_ceil(factor), h.div_ceil(factor));
+    let mut out: ImageBuffer<Rgb<f32>, Vec<f32>> = ImageBuffer::new(ow, oh);
+    out.par_chunks_mut(ow as usize * 3)
+        .enumerate()
+        .for_each(|(oy, row)| {
+            let y0 = oy as u32 * factor;
+            let y1 = (y0 + factor).min(h);
+            for (ox, pixel) in row.chunks_mut(3).enumerate() {
+                let x0 = ox as u32 * factor;
+                let x1 = (x0 + factor).min(w);
+                let mut sum = [0.0f32; 3];
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        for (s, &v) in sum.iter_mut().zip(&image.get_pixel(x, y).0) {
+                            *s += lut[v as usize];
+                        }
+                    }
+                }
+                let count = ((x1 - x0) * (y1 - y0)) as f32;
+                for (out, s) in pixel.iter_mut().zip(sum) {
+                    *out = s / count;
+                }
+            }
+        });
+    out
+}
+
+/// Decode an 8-bit sRGB image to linear light, the space the film pipeline
+/// works in.
+///
+/// Uses the exact IEC 61966-2-1 sRGB curve, per channel:
+/// `v / 12.92` for `v <= 0.04045`, otherwise `((v + 0.055) / 1.055)^2.4`,
+/// with `v` the 8-bit value divided by 255. This is the same decode
+/// [`process_image`](crate::process_image) applies on the CPU and on the GPU.
+pub fn srgb_to_linear(image: &RgbImage) -> ImageBuffer<Rgb<f32>, Vec<f32>> {
+    crate::pipeline::create_linear_image(image)
+}
+
+/// Encode a linear-light image back to 8-bit sRGB, the inverse of
+/// [`srgb_to_linear`].
+///
+/// Values are clamped to 0-1, encoded as `12.92 * v` for `v <= 0.0031308`,
+/// otherwise `1.055 * v^(1 / 2.4) - 0.055`, and rounded to 8 bits. For other
+/// output curves, see [`OutputTransfer`](crate::OutputTransfer).
+pub fn linear_to_srgb(image: &ImageBuffer<Rgb<f32>, Vec<f32>>) -> RgbImage {
+    crate::pipeline::linear_to_srgb_image(image)
+}
+
+/// Largest gain [`apply_flat_field`] applies, against dividing by the black
+/// corners of a flat.
+const FLAT_FIELD_MAX_GAIN: f32 = 8.0;
+
+/// Remove uneven scanner illumination by dividing `image` by a flat-field
+/// reference, the standard calibration step before developing home scans.
+///
+/// `flat` is a scan of the light source with nothing (or clear film base)
+/// in the holder, e.g. from [`flat_field_from_blank`]. Each channel of the
+/// flat is normalized to its own mean in linear light, so only the falloff
+/// is corrected and the overall color and brightness stay. A flat of another
+/// size is resized to the image first.
+pub fn apply_flat_field(image: &RgbImage, flat: &RgbImage) -> RgbImage {
+    let (w, h) = image.dimensions();
+    if w == 0 || h == 0 || flat.width() == 0 || flat.height() == 0 {
+        return image.clone();
+    }
+    let flat = if flat.dimensions() == (w, h) {
+        srgb_to_linear(flat)
+    } else {
+        image::imageops::resize(&srgb_to_linear(flat), w, h, FilterType::Triangle)
+    };
+
+    let count = (w as f64) * (h as f64);
+    let means: [f32; 3] = std::array::from_fn(|c| {
+        let sum: f64 = flat.par_chunks(3).map(|p| p[c] as f64).sum();
+        (sum / count) as f32
+    });
+
+    let mut linear = srgb_to_linear(image);
+    linear
+        .par_chunks_mut(3)
+        .zip(flat.par_chunks(3))
+        .for_each(|(px, flat)| {
+            for c in 0..3 {
+                if means[c] > 0.0 {
+                    let gain = (means[c] / flat[c].max(f32::MIN_POSITIVE)).min(FLAT_FIELD_MAX_GAIN);
+                    px[c] *= gain;
+                }
+            }
+        });
+    linear_to_srgb(&linear)
+}
+
+/// Radius of the median window [`flat_field_from_blank`] removes dust with.
+const FLAT_FIELD_MEDIAN_RADIUS: u32 = 2;
+
+/// Per-channel median over a `(2 * radius + 1)` square window, clamped at
+/// the borders. Unlike a blur it rejects small outliers instead of
+/// smearing them into their neighbours.
+fn median_filter(image: &RgbImage, radius: u32) -> RgbImage {
+    let (w, h) = image.dimensions();
+    let r = radius as i64;
+    let mut out = RgbImage::new(w, h);
+    out.par_chunks_mut(w as usize * 3)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let mut window = Vec::with_capacity(((2 * r + 1) * (2 * r + 1)) as usize);
+            for x in 0..w as i64 {
+                for c in 0..3 {
+                    window.clear();
+                    for dy in -r..=r {
+                        let sy = (y as i64 + dy).clamp(0, h as i64 - 1) as u32;
+                        for dx in -r..=r {
+                            let sx = (x + dx).clamp(0, w as i64 - 1) as u32;
+                            window.push(image.get_pixel(sx, sy)[c]);
+                        }
+                    }
+                    let mid = window.len() / 2;
+                    row[x as usize * 3 + c] = *window.select_nth_unstable(mid).1;
+                }
+            }
+        });
+    out
+}
+
+/// A smooth flat-field reference from a blank scan, for [`apply_flat_field`].
+///
+/// Scanning without film (or a blank frame) records the illumination, but
+/// also dust, grain of the base and sensor noise, which would be stamped
+/// into every corrected image. A median filter first drops dust specks
+/// without spreading them, then blurring in linear light with a sigma of 2%
+/// of the long side keeps only the slow falloff of the light.
+pub fn flat_field_from_blank(blank: &RgbImage) -> RgbImage {
+    let mut linear = srgb_to_linear(&median_filter(blank, FLAT_FIELD_MEDIAN_RADIUS));
+    let sigma = blank.width().max(blank.height()) as f32 * 0.02;
+    apply_gaussian_blur(&mut linear, sigma);
+    linear_to_srgb(&linear)
+}
+
 /// Helper to apply Gaussian blur (Approx) using 3 Box Blurs
 /// Optimized to minimize allocations and use SIMD
 pub fn apply_gaussian_blur(image: &mut ImageBuffer<Rgb<f32>, Vec<f32>>, sigma: f32) {
-    if sigma <= 0.0 {
+    if sigma <= 0.0 || image.is_empty() {
         return;
     }
 
@@ -154,3 +360,43 @@ fn vertical_blur_pass(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_field_evens_out_scanner_falloff() {
+        // Light falls off from the center of the scanner bed
+        let scan = |level: f32| {
+            RgbImage::from_fn(256, 128, |x, y| {
+                let (dx, dy) = (x as f32 / 127.5 - 1.0, y as f32 / 63.5 - 1.0);
+                let v = (level * (1.0 - 0.15 * (dx * dx + dy * dy))).round() as u8;
+                Rgb([v, v, v])
+            })
+        };
+        let spread = |image: &RgbImage| {
+            let raw = image.as_raw();
+            raw.iter().max().unwrap() - raw.iter().min().unwrap()
+        };
+
+        // A gray card scanned under the same light comes out even
+        let card = scan(150.0);
+        assert!(spread(&card) > 20);
+        let corrected = apply_flat_field(&card, &scan(240.0));
+        assert!(spread(&corrected) <= 4, "spread {}", spread(&corrected));
+        // The flat is normalized, so the card keeps its overall level
+        let mean = |image: &RgbImage| {
+            image.as_raw().iter().map(|&v| v as f32).sum::<f32>() / image.as_raw().len() as f32
+        };
+        assert!((mean(&corrected) - mean(&card)).abs() < 6.0);
+
+        // Dust on the blank scan is smoothed out of the synthetic flat
+        let mut blank = scan(240.0);
+        for i in (0..blank.width() * blank.height()).step_by(97) {
+            blank.put_pixel(i % 256, i / 256, Rgb([0, 0, 0]));
+        }
+        let corrected = apply_flat_field(&card, &flat_field_from_blank(&blank));
+        assert!(spread(&corrected) <= 8, "spread {}", spread(&corrected));
+    }
+}
@@ -0,0 +1,231 @@
+//! XMP / Lightroom Develop Preset Import
+//!
+//! Approximates a Camera Raw (`crs:`) develop preset as a film stock.
+//! XMP describes edits on a display-referred image while Filmr models a
+//! negative, so the mapping is deliberately coarse:
+//! - Contrast, parametric tone curve and point curve midtone slope -> curve gamma
+//! - Highlights / parametric highlights -> shoulder point
+//! - Temperature / tint -> per-layer exposure offsets (a Kelvin white balance
+//!   evaluated through the film's own spectral sensitivities)
+//! - Saturation / vibrance -> color matrix saturation
+
+use crate::error::FilmrError;
+use crate::film::{reduce_saturation, FilmStock};
+
+/// Reference white the base stock is balanced for (Lightroom "As Shot" daylight).
+const REFERENCE_KELVIN: f32 = crate::film::DAYLIGHT_BALANCE_K;
+
+impl FilmStock {
+    /// Build a film stock approximating an XMP develop preset.
+    pub fn from_xmp<P: AsRef<std::path::Path>>(path: P) -> Result<Self, FilmrError> {
+        let path = path.as_ref();
+        let xml = std::fs::read_to_string(path)?;
+        let mut stock = Self::from_xmp_str(&xml)?;
+        if stock.name.is_empty() {
+            stock.name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+        Ok(stock)
+    }
+
+    /// Same as [`FilmStock::from_xmp`], from an in-memory XMP document.
+    pub fn from_xmp_str(xml: &str) -> Result<Self, FilmrError> {
+        if !xml.contains("crs:") {
+            return Err(FilmrError::UnsupportedFormat(
+                "no Camera Raw (crs:) settings found in XMP".to_string(),
+            ));
+        }
+
+        let mut stock = crate::presets::other::STANDARD_DAYLIGHT();
+        stock.manufacturer = "XMP".to_string();
+        stock.name = xmp_value(xml, "Name")
+            .map(|v| strip_tags(&v))
+            .unwrap_or_default();
+
+        // Tone: contrast and curve slope scale gamma, highlights move the shoulder.
+        let contrast = xmp_number(xml, "Contrast2012")
+            .or_else(|| xmp_number(xml, "Contrast"))
+            .unwrap_or(0.0);
+        let lights = xmp_number(xml, "ParametricLights").unwrap_or(0.0);
+        let darks = xmp_number(xml, "ParametricDarks").unwrap_or(0.0);
+        let highlights = xmp_number(xml, "Highlights2012").unwrap_or(0.0)
+            + xmp_number(xml, "ParametricHighlights").unwrap_or(0.0);
+
+        let mut gamma_scale = (1.0 + contrast / 200.0) * (1.0 + (lights - darks) / 400.0);
+        if let Some(points) = xmp_tone_curve(xml) {
+            gamma_scale *= curve_midtone_slope(&points);
+        }
+        let gamma_scale = gamma_scale.clamp(0.25, 4.0);
+        let shoulder_scale = (1.0 + highlights / 400.0).clamp(0.5, 1.5);
+
+        for curve in [&mut stock.r_curve, &mut stock.g_curve, &mut stock.b_curve] {
+            curve.gamma *= gamma_scale;
+            curve.shoulder_point = (curve.shoulder_point * shoulder_scale).min(1.0);
+        }
+
+        // White balance: neutralize the preset's Kelvin through the film's sensitivities.
+        let kelvin = xmp_number(xml, "Temperature").or_else(|| {
+            xmp_number(xml, "IncrementalTemperature")
+                .map(|inc| REFERENCE_KELVIN * (1.0 + inc / 200.0))
+        });
+        let tint = xmp_number(xml, "Tint")
+            .or_else(|| xmp_number(xml, "IncrementalTint"))
+            .unwrap_or(0.0);
+
+        let mut gains = match kelvin {
+            Some(k) => stock.kelvin_gains(k.clamp(2000.0, 50000.0)),
+            None => [1.0; 3],
+        };
+        // Positive tint is magenta, i.e. less green exposure.
+        gains[1] *= (1.0 - tint / 300.0).max(0.1);
+
+        for (curve, gain) in [&mut stock.r_curve, &mut stock.g_curve, &mut stock.b_curve]
+            .into_iter()
+            .zip(gains)
+        {
+            curve.exposure_offset /= gain;
+        }
+
+        // Saturation: vibrance counts half since it spares already-saturated colors.
+        let saturation = xmp_number(xml, "Saturation").unwrap_or(0.0)
+            + 0.5 * xmp_number(xml, "Vibrance").unwrap_or(0.0);
+        let amount = (1.0 + saturation / 100.0).clamp(0.0, 2.0);
+        stock.color_matrix = reduce_saturation(stock.color_matrix, amount);
+
+        Ok(stock)
+    }
+}
+
+/// Slope of a 0-255 point curve through the midtones (1.0 = identity).
+fn curve_midtone_slope(points: &[(f32, f32)]) -> f32 {
+    let lo = interpolate_curve(points, 96.0);
+    let hi = interpolate_curve(points, 160.0);
+    ((hi - lo) / 64.0).max(0.0)
+}
+
+fn interpolate_curve(points: &[(f32, f32)], x: f32) -> f32 {
+    match points.iter().position(|&(px, _)| px >= x) {
+        Some(0) => points[0].1,
+        Some(i) => {
+            let (x0, y0) = points[i - 1];
+            let (x1, y1) = points[i];
+            if x1 > x0 {
+                y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+            } else {
+                y1
+            }
+        }
+        None => points.last().map(|p| p.1).unwrap_or(x),
+    }
+}
+
+/// Read a `crs:` setting written either as an attribute or as an element.
+fn xmp_value(xml: &str, key: &str) -> Option<String> {
+    let attr = format!("crs:{}=\"", key);
+    if let Some(start) = xml.find(&attr) {
+        let rest = &xml[start + attr.len()..];
+        return rest.find('"').map(|end| rest[..end].to_string());
+    }
+
+    let open = format!("<crs:{}>", key);
+    let close = format!("</crs:{}>", key);
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&close)?;
+    Some(xml[start..start + end].trim().to_string())
+}
+
+/// Text content of a value, e.g. the `rdf:li` inside a localized `rdf:Alt` name.
+fn strip_tags(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut in_tag = false;
+    for ch in value.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+fn xmp_number(xml: &str, key: &str) -> Option<f32> {
+    // Lightroom writes positive values with a leading '+'.
+    xmp_value(xml, key)?.trim_start_matches('+').parse().ok()
+}
+
+/// Parse the `ToneCurvePV2012` point list (`<rdf:li>x, y</rdf:li>` entries).
+fn xmp_tone_curve(xml: &str) -> Option<Vec<(f32, f32)>> {
+    let body = xmp_value(xml, "ToneCurvePV2012")?;
+    let mut points = Vec::new();
+    for item in body.split("<rdf:li>").skip(1) {
+        let item = item.split("</rdf:li>").next()?;
+        let mut parts = item.split(',').map(|v| v.trim().parse::<f32>());
+        if let (Some(Ok(x)), Some(Ok(y))) = (parts.next(), parts.next()) {
+            points.push((x, y));
+        }
+    }
+    if points.len() < 2 {
+        return None;
+    }
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    Some(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WARM_CONTRASTY: &str = r#"
+        <x:xmpmeta xmlns:x="adobe:ns:meta/">
+         <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+          <rdf:Description rdf:about=""
+            xmlns:crs="http://ns.adobe.com/camera-raw-settings/1.0/"
+            crs:Temperature="7500"
+            crs:Tint="+10"
+            crs:Contrast2012="+40"
+            crs:Highlights2012="-50"
+            crs:Saturation="-30">
+           <crs:Name>
+            <rdf:Alt>
+             <rdf:li xml:lang="x-default">Warm Fade</rdf:li>
+            </rdf:Alt>
+           </crs:Name>
+           <crs:ToneCurvePV2012>
+            <rdf:Seq>
+             <rdf:li>0, 20</rdf:li>
+             <rdf:li>128, 128</rdf:li>
+             <rdf:li>255, 240</rdf:li>
+            </rdf:Seq>
+           </crs:ToneCurvePV2012>
+          </rdf:Description>
+         </rdf:RDF>
+        </x:xmpmeta>"#;
+
+    #[test]
+    fn test_xmp_maps_tone_wb_and_saturation() {
+        let base = crate::presets::other::STANDARD_DAYLIGHT();
+        let stock = FilmStock::from_xmp_str(WARM_CONTRASTY).unwrap();
+
+        assert_eq!(stock.name, "Warm Fade");
+        assert!(stock.validate().is_ok());
+        // +40 contrast outweighs the slightly flattened point curve.
+        assert!(stock.g_curve.gamma > base.g_curve.gamma);
+        // Negative highlights pull the shoulder down.
+        assert!(stock.g_curve.shoulder_point < base.g_curve.shoulder_point);
+        // A high Kelvin setting warms the image: red gets more exposure than blue.
+        assert!(
+            stock.r_curve.exposure_offset / base.r_curve.exposure_offset
+                < stock.b_curve.exposure_offset / base.b_curve.exposure_offset
+        );
+        // Desaturation moves off-diagonal terms towards gray.
+        assert!(stock.color_matrix[0][1] > base.color_matrix[0][1]);
+    }
+
+    #[test]
+    fn test_xmp_without_camera_raw_settings_is_rejected() {
+        assert!(FilmStock::from_xmp_str("<x:xmpmeta/>").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use filmr::presets::kodak::{KODAK_PORTRA_400, KODAK_TRI_X_400};
+use filmr::utils::resize_to_fit_linear;
+use filmr::{export_stock_comparison, make_contact_sheet, process_image, SimulationConfig};
+use image::{Rgb, RgbImage};
+use std::rc::Rc;
+
+#[test]
+fn test_contact_sheet_lays_out_developed_frames() {
+    let frames: Vec<RgbImage> = (0..5u8)
+        .map(|i| RgbImage::from_pixel(72, 48, Rgb([40 + i * 40, 120, 200 - i * 30])))
+        .collect();
+    let film = KODAK_PORTRA_400();
+    let config = SimulationConfig {
+        enable_grain: false,
+        ..Default::default()
+    };
+
+    let sheet = make_contact_sheet(&frames, &film, &config, 3);
+    // 48 / 12 = 4, raised to the 6 px minimum
+    let border = 6;
+    let (cell_w, cell_h) = (72 + 2 * border, 48 + 2 * border);
+    assert_eq!(sheet.dimensions(), (3 * cell_w, 2 * cell_h));
+
+    // Frame 5 is the second cell of the second row, developed like the rest
+    let developed = process_image(&frames[4], &film, &config);
+    let (x0, y0) = (cell_w + border, cell_h + border);
+    assert_eq!(
+        sheet.get_pixel(x0 + 10, y0 + 10),
+        developed.get_pixel(10, 10)
+    );
+
+    // The empty sixth cell is bare rebate; the first has a burned frame number
+    let rebate = *sheet.get_pixel(2 * cell_w + 1, cell_h + 1);
+    assert!((2 * cell_w..3 * cell_w).all(|x| *sheet.get_pixel(x, cell_h + cell_h / 2) == rebate));
+    let number_row = border + 48;
+    assert!(
+        (number_row..cell_h)
+            .any(|y| (border..2 * border).any(|x| *sheet.get_pixel(x, y) != rebate)),
+        "frame number should be burned into the lower rebate"
+    );
+
+    assert_eq!(
+        make_contact_sheet(&[], &film, &config, 3).dimensions(),
+        (0, 0)
+    );
+}
+
+#[test]
+fn test_stock_comparison_tiles_each_stock_with_a_label() {
+    let source = RgbImage::from_fn(240, 160, |x, y| Rgb([(x / 2) as u8, (y / 2) as u8, 128]));
+    let stocks = [
+        Rc::new(KODAK_PORTRA_400()),
+        Rc::new(KODAK_TRI_X_400()),
+        Rc::new(KODAK_PORTRA_400()),
+    ];
+    let config = SimulationConfig {
+        enable_grain: false,
+        ..Default::default()
+    };
+
+    let sheet = export_stock_comparison(&source, &stocks, &config, 120, 2);
+    // Tiles are 120x80; 80 / 24 = 3, raised to the 6 px minimum, and the
+    // label border is twice as deep
+    let border = 6;
+    let (cell_w, cell_h) = (120 + 2 * border, 80 + 3 * border);
+    assert_eq!(sheet.dimensions(), (2 * cell_w, 2 * cell_h));
+
+    // The second tile is Tri-X, developed from the scaled-down source
+    let tile = resize_to_fit_linear(&source, 120, image::imageops::FilterType::Lanczos3);
+    let developed = process_image(&tile, &stocks[1], &config);
+    assert_eq!(
+        sheet.get_pixel(cell_w + border + 30, border + 30),
+        developed.get_pixel(30, 30)
+    );
+    let pixel = sheet.get_pixel(cell_w + border + 30, border + 30);
+    assert!(pixel[0] == pixel[1] && pixel[1] == pixel[2]);
+
+    // Every tile has a label burned under it
+    let rebate = *sheet.get_pixel(1, 1);
+    for i in 0..3u32 {
+        let (x0, y0) = ((i % 2) * cell_w + border, (i / 2) * cell_h + border + 80);
+        assert!(
+            (y0..y0 + 2 * border).any(|y| (x0..x0 + 120).any(|x| *sheet.get_pixel(x, y) != rebate)),
+            "tile {i} should be labeled"
+        );
+    }
+
+    let no_stocks: [Rc<filmr::FilmStock>; 0] = [];
+    assert_eq!(
+        export_stock_comparison(&source, &no_stocks, &config, 120, 2).dimensions(),
+        (0, 0)
+    );
+}
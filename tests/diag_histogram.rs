@@ -154,3 +154,58 @@ fn histogram_synthetic_scene() {
     compare(&img, "Daylight", &STANDARD_DAYLIGHT());
     compare(&img, "Portra400", &KODAK_PORTRA_400());
 }
+
+#[test]
+fn test_histogram_axes_rebin_counts() {
+    use filmr::metrics::HistogramAxis;
+
+    let input = make_scene(64, 32);
+    let metrics = filmr::FilmMetrics::analyze(&input);
+    assert_eq!(metrics.histogram(HistogramAxis::Encoded), metrics.hist_rgb);
+    for axis in HistogramAxis::ALL {
+        let total: u32 = metrics.histogram(axis)[1].iter().sum();
+        assert_eq!(total, input.width() * input.height(), "{axis:?}");
+    }
+
+    // Mid code values are dark in linear light, white is the top of every axis
+    assert!(HistogramAxis::Linear.bin(128) < 64);
+    assert_eq!(HistogramAxis::LogExposure.bin(255), 255);
+    assert_eq!(HistogramAxis::LogExposure.bin(0), 0);
+    // 18% gray sits about 2.5 stops below white
+    let gray = HistogramAxis::LogExposure.bin(118);
+    let stops = HistogramAxis::LogExposure.bin_value(gray);
+    assert!((stops + 2.47).abs() < 0.1, "{stops}");
+}
+
+#[test]
+fn test_histogram_smoothing_keeps_clipping_spikes() {
+    use filmr::metrics::{smooth_histogram, HistogramSmoothing};
+
+    let mut hist = [10u32; 256];
+    hist[0] = 5000;
+    hist[255] = 8000;
+    hist[128] = 110;
+
+    assert_eq!(
+        smooth_histogram(&hist, HistogramSmoothing::None, 4),
+        hist.map(|v| v as f64)
+    );
+    for kernel in [HistogramSmoothing::Box, HistogramSmoothing::Gaussian] {
+        let smoothed = smooth_histogram(&hist, kernel, 4);
+        // Clipped bins stay put and do not leak into their neighbors
+        assert_eq!(smoothed[0], 5000.0);
+        assert_eq!(smoothed[255], 8000.0);
+        assert!((smoothed[1] - 10.0).abs() < 1e-9, "{kernel:?}");
+        assert!((smoothed[254] - 10.0).abs() < 1e-9, "{kernel:?}");
+        // A lone spike in the midtones is spread out, keeping its mass
+        assert!(smoothed[128] < 110.0 && smoothed[127] > 10.0, "{kernel:?}");
+        let mass: f64 = smoothed[120..=136].iter().sum();
+        assert!(
+            (mass - (17.0 * 10.0 + 100.0)).abs() < 1.0,
+            "{kernel:?}: {mass}"
+        );
+    }
+    // Box is a plain 9-bin average
+    let boxed = smooth_histogram(&hist, HistogramSmoothing::Box, 4);
+    assert!((boxed[128] - (8.0 * 10.0 + 110.0) / 9.0).abs() < 1e-9);
+}
@@ -0,0 +1,108 @@
+//! Golden-image tests: the synthetic chart through a few stocks, compared
+//! with reference renders in `tests/golden/`.
+//!
+//! After an intended change of the develop math, regenerate the references
+//! with `FILMR_BLESS=1 cargo test --test golden_images` and review the PNGs.
+//! A missing reference fails the test unless `FILMR_BLESS` is set, so a
+//! reference left out of a commit cannot pass unnoticed.
+
+use filmr::chart::LUT_PREVIEW_GRAIN_SEED;
+use filmr::metrics::compare;
+use filmr::presets::fujifilm::VELVIA_50;
+use filmr::presets::kodak::{KODAK_PORTRA_400, KODAK_TRI_X_400};
+use filmr::{process_image, render_lut_preview, FilmStock, SimulationConfig};
+use image::{Rgb, RgbImage};
+use std::path::PathBuf;
+
+/// Mean ΔE allowed against the reference, for float differences between
+/// platforms.
+const MAX_MEAN_DELTA_E: f32 = 0.5;
+
+/// Largest single channel difference allowed, in 8-bit steps.
+const MAX_CHANNEL_DIFF: u8 = 8;
+
+fn check_golden(name: &str, render: RgbImage) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.png"));
+
+    if std::env::var_os("FILMR_BLESS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        render.save(&path).unwrap();
+        eprintln!("Wrote reference {}", path.display());
+        return;
+    }
+    assert!(
+        path.exists(),
+        "{name}: no reference at {}; write it with FILMR_BLESS=1",
+        path.display()
+    );
+
+    let reference = image::open(&path).unwrap().to_rgb8();
+    let diff = compare(&render, &reference).unwrap_or_else(|| {
+        panic!(
+            "{name}: render is {:?}, reference is {:?}",
+            render.dimensions(),
+            reference.dimensions()
+        )
+    });
+    assert!(
+        diff.mean_delta_e <= MAX_MEAN_DELTA_E && diff.max_channel_diff <= MAX_CHANNEL_DIFF,
+        "{name} differs from {}: {diff:?}",
+        path.display()
+    );
+}
+
+#[test]
+fn test_render_lut_preview_is_deterministic() {
+    let film = KODAK_PORTRA_400();
+    let diff = compare(&render_lut_preview(&film), &render_lut_preview(&film)).unwrap();
+    assert_eq!(diff.max_channel_diff, 0);
+}
+
+#[test]
+fn test_golden_portra_400() {
+    check_golden("kodak_portra_400", render_lut_preview(&KODAK_PORTRA_400()));
+}
+
+#[test]
+fn test_golden_velvia_50() {
+    check_golden("fujifilm_velvia_50", render_lut_preview(&VELVIA_50()));
+}
+
+#[test]
+fn test_golden_tri_x_400() {
+    check_golden("kodak_tri_x_400", render_lut_preview(&KODAK_TRI_X_400()));
+}
+
+/// A clipped highlight on a dark background, developed with seeded grain so
+/// the halation around it can be compared.
+fn render_halation(film: &FilmStock) -> RgbImage {
+    let scene = RgbImage::from_fn(128, 128, |x, y| {
+        let (dx, dy) = (x as i32 - 64, y as i32 - 64);
+        if dx * dx + dy * dy < 12 * 12 {
+            Rgb([255, 255, 255])
+        } else {
+            Rgb([24, 24, 24])
+        }
+    });
+    let config = SimulationConfig {
+        use_gpu: false,
+        grain_seed: Some(LUT_PREVIEW_GRAIN_SEED),
+        ..Default::default()
+    };
+    process_image(&scene, film, &config)
+}
+
+#[test]
+fn test_golden_halation_tri_x_400() {
+    check_golden(
+        "halation_kodak_tri_x_400",
+        render_halation(&KODAK_TRI_X_400()),
+    );
+}
+
+#[test]
+fn test_golden_halation_velvia_50() {
+    check_golden("halation_fujifilm_velvia_50", render_halation(&VELVIA_50()));
+}
@@ -0,0 +1,115 @@
+use filmr::pipeline::{HalationStage, PipelineContext, PipelineStage};
+use filmr::presets::other::STANDARD_DAYLIGHT;
+use filmr::SimulationConfig;
+use image::{ImageBuffer, Rgb};
+
+/// A white highlight on black, returning the glow added 5px away.
+fn glow_next_to_highlight(channel_gain: [f32; 3]) -> [f32; 3] {
+    let mut film = STANDARD_DAYLIGHT();
+    film.halation_strength = 1.0;
+    film.halation_threshold = 0.5;
+    film.halation_sigma = 0.05;
+    film.halation_tint = [1.0, 1.0, 1.0];
+    film.halation_channel_gain = channel_gain;
+    let config = SimulationConfig::default();
+    let context = PipelineContext {
+        film: &film,
+        config: &config,
+        depth_map: None,
+    };
+
+    let mut image = ImageBuffer::<Rgb<f32>, Vec<f32>>::from_fn(64, 64, |x, y| {
+        if (30..34).contains(&x) && (30..34).contains(&y) {
+            Rgb([4.0, 4.0, 4.0])
+        } else {
+            Rgb([0.0, 0.0, 0.0])
+        }
+    });
+    HalationStage.process(&mut image, &context);
+    image.get_pixel(38, 32).0
+}
+
+#[test]
+fn test_halation_glow_follows_layer_gain() {
+    let glow = glow_next_to_highlight([1.0, 0.25, 0.05]);
+    assert!(glow[0] > 0.0, "Highlight should bloom into neighbors");
+    assert!(glow[0] > glow[1] && glow[1] > glow[2]);
+    assert!((glow[1] / glow[0] - 0.25).abs() < 1e-3);
+}
+
+#[test]
+fn test_halation_neutral_tint_is_warm_with_default_gain() {
+    // A gray tint (e.g. Tri-X) still blooms warm because only the red layer sees the base.
+    let glow = glow_next_to_highlight(filmr::film::default_halation_channel_gain());
+    assert!(glow[0] > glow[2]);
+}
+
+#[test]
+fn test_halation_skirt_widens_glow() {
+    let run = |strength2: f32| {
+        let mut film = STANDARD_DAYLIGHT();
+        film.halation_strength = 0.5;
+        film.halation_threshold = 0.5;
+        film.halation_sigma = 0.02;
+        film.halation_strength2 = strength2;
+        film.halation_sigma2 = 0.1;
+        let config = SimulationConfig::default();
+        let context = PipelineContext {
+            film: &film,
+            config: &config,
+            depth_map: None,
+        };
+        let mut image = ImageBuffer::<Rgb<f32>, Vec<f32>>::from_fn(128, 128, |x, y| {
+            if (62..66).contains(&x) && (62..66).contains(&y) {
+                Rgb([4.0, 4.0, 4.0])
+            } else {
+                Rgb([0.0, 0.0, 0.0])
+            }
+        });
+        HalationStage.process(&mut image, &context);
+        image.get_pixel(64 + 20, 64).0[0]
+    };
+
+    // 20px out the tight core has died off; only the skirt reaches that far.
+    let core_only = run(0.0);
+    let with_skirt = run(0.3);
+    assert!(with_skirt > core_only * 2.0 + 1e-4);
+}
+
+#[test]
+fn test_saturated_highlight_blooms_less_than_white() {
+    let run = |highlight: [f32; 3], saturation_weight: f32| {
+        let mut film = STANDARD_DAYLIGHT();
+        film.halation_strength = 1.0;
+        film.halation_threshold = 0.5;
+        film.halation_sigma = 0.05;
+        film.halation_saturation_weight = saturation_weight;
+        let config = SimulationConfig::default();
+        let context = PipelineContext {
+            film: &film,
+            config: &config,
+            depth_map: None,
+        };
+        let mut image = ImageBuffer::<Rgb<f32>, Vec<f32>>::from_fn(64, 64, |x, y| {
+            if (30..34).contains(&x) && (30..34).contains(&y) {
+                Rgb(highlight)
+            } else {
+                Rgb([0.0, 0.0, 0.0])
+            }
+        });
+        HalationStage.process(&mut image, &context);
+        image.get_pixel(38, 32).0[0]
+    };
+
+    // Clipped pure red and white with the same Rec.709 luminance of 4.0
+    let red = [4.0 / 0.2126, 0.0, 0.0];
+    let white = [4.0, 4.0, 4.0];
+
+    // The mask is luminance, so without weighting both bloom alike
+    let (red_glow, white_glow) = (run(red, 0.0), run(white, 0.0));
+    assert!(white_glow > 0.0);
+    assert!((red_glow - white_glow).abs() < white_glow * 1e-3);
+
+    let (red_glow, white_glow) = (run(red, 0.7), run(white, 0.7));
+    assert!((red_glow / white_glow - 0.3).abs() < 1e-2);
+}
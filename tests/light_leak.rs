@@ -0,0 +1,79 @@
+use filmr::light_leak::{LightLeak, LightLeakBlendMode, LightLeakConfig, LightLeakOverlay};
+use filmr::presets::other::STANDARD_DAYLIGHT;
+use filmr::{process_image, SimulationConfig};
+use image::{Rgb, RgbImage, Rgba, RgbaImage};
+use std::sync::Arc;
+
+fn config_with_overlay(pixel: Rgba<u8>, opacity: f32) -> SimulationConfig {
+    SimulationConfig {
+        enable_grain: false,
+        light_leak: LightLeakConfig {
+            enabled: true,
+            overlays: vec![LightLeakOverlay {
+                source: "test".to_string(),
+                image: Some(Arc::new(RgbaImage::from_pixel(8, 8, pixel))),
+                opacity,
+                rotation: 0.0,
+                blend: LightLeakBlendMode::Screen,
+            }],
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_white_overlay_screens_to_white() {
+    let input = RgbImage::from_pixel(32, 32, Rgb([40, 40, 40]));
+    let config = config_with_overlay(Rgba([255, 255, 255, 255]), 1.0);
+    let output = process_image(&input, &STANDARD_DAYLIGHT(), &config);
+    assert!(output.pixels().all(|p| p.0.iter().all(|&c| c >= 254)));
+}
+
+#[test]
+fn test_overlay_alpha_and_opacity_scale_the_leak() {
+    let input = RgbImage::from_pixel(32, 32, Rgb([40, 40, 40]));
+    let film = STANDARD_DAYLIGHT();
+    let base = process_image(
+        &input,
+        &film,
+        &config_with_overlay(Rgba([255, 0, 0, 255]), 0.0),
+    );
+    let half = process_image(
+        &input,
+        &film,
+        &config_with_overlay(Rgba([255, 0, 0, 128]), 1.0),
+    );
+    let full = process_image(
+        &input,
+        &film,
+        &config_with_overlay(Rgba([255, 0, 0, 255]), 1.0),
+    );
+
+    let red = |img: &RgbImage| img.get_pixel(16, 16)[0];
+    assert!(red(&base) < red(&half) && red(&half) < red(&full));
+    // Screening pure red leaves green untouched
+    assert_eq!(base.get_pixel(16, 16)[1], full.get_pixel(16, 16)[1]);
+}
+
+#[test]
+fn test_leak_animation_is_deterministic_and_starts_static() {
+    let config = LightLeakConfig {
+        enabled: true,
+        leaks: vec![LightLeak::default(), LightLeak::default()],
+        seed: 42,
+        ..Default::default()
+    };
+
+    assert_eq!(config.clone().with_time(0.0).leaks_at_time(), config.leaks);
+
+    let a = config.clone().with_time(1.7).leaks_at_time();
+    let b = config.clone().with_time(1.7).leaks_at_time();
+    assert_eq!(a, b, "same seed and time must give the same frame");
+    assert_ne!(a[0], config.leaks[0], "leaks should move over time");
+    assert_ne!(a[0], a[1], "each leak drifts independently");
+
+    // Consecutive frames at 24fps stay close together.
+    let next = config.clone().with_time(1.7 + 1.0 / 24.0).leaks_at_time();
+    assert!((next[0].position.0 - a[0].position.0).abs() < 0.01);
+}
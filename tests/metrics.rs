@@ -0,0 +1,93 @@
+use filmr::presets::kodak::KODAK_PORTRA_400;
+use filmr::processor::SimulationConfig;
+use filmr::{FilmMetrics, RoiRect};
+use image::{Rgb, RgbImage};
+
+fn test_input() -> RgbImage {
+    // Gradient with a white patch
+    RgbImage::from_fn(48, 32, |x, y| {
+        if (20..28).contains(&x) && (12..20).contains(&y) {
+            Rgb([255, 255, 255])
+        } else {
+            Rgb([(x * 5) as u8, (y * 7) as u8, ((x + y) * 3) as u8])
+        }
+    })
+}
+
+#[test]
+fn test_density_usage_grows_with_scene_contrast() {
+    let film = KODAK_PORTRA_400();
+    let config = SimulationConfig::default();
+    let flat = RgbImage::from_pixel(32, 32, Rgb([118, 118, 118]));
+    let ramp = RgbImage::from_fn(256, 8, |x, _| Rgb([x as u8; 3]));
+
+    let flat_usage = FilmMetrics::analyze_with_stock(&flat, &flat, &film, &config)
+        .density_usage
+        .unwrap();
+    let ramp_usage = FilmMetrics::analyze_with_stock(&ramp, &ramp, &film, &config)
+        .density_usage
+        .unwrap();
+    assert!(flat_usage.utilization < 0.01);
+    assert!(ramp_usage.utilization > flat_usage.utilization + 0.2);
+    assert!(ramp_usage.highlight_headroom < flat_usage.highlight_headroom);
+    assert!(ramp_usage.shadow_headroom < flat_usage.shadow_headroom);
+}
+
+#[test]
+fn test_analyze_roi_matches_analyze() {
+    let input = test_input();
+    let json = |m: &FilmMetrics| serde_json::to_value(m).unwrap();
+
+    // The full rectangle, or one hanging past the edges, meters the whole image
+    let whole = json(&FilmMetrics::analyze(&input));
+    assert_eq!(
+        json(&FilmMetrics::analyze_roi(&input, RoiRect::full(&input))),
+        whole
+    );
+    let oversized = RoiRect {
+        x: 0,
+        y: 0,
+        width: 1000,
+        height: 1000,
+    };
+    assert_eq!(json(&FilmMetrics::analyze_roi(&input, oversized)), whole);
+
+    // A region meters only its own pixels
+    let patch = RoiRect {
+        x: 20,
+        y: 12,
+        width: 8,
+        height: 8,
+    };
+    let metrics = FilmMetrics::analyze_roi(&input, patch);
+    assert!(metrics.mean_rgb.iter().all(|&v| v > 254.0));
+    let cropped = image::imageops::crop_imm(&input, 20, 12, 8, 8).to_image();
+    assert_eq!(json(&metrics), json(&FilmMetrics::analyze(&cropped)));
+}
+
+#[test]
+fn test_scopes_place_known_colors() {
+    use filmr::metrics::{Vectorscope, Waveform, SCOPE_BINS};
+
+    // Left half mid gray, right half white
+    let input = RgbImage::from_fn(64, 16, |x, _| {
+        if x < 32 {
+            Rgb([128, 128, 128])
+        } else {
+            Rgb([255, 255, 255])
+        }
+    });
+    let waveform = Waveform::measure(&input, 4);
+    assert_eq!(waveform.bins.len(), 4 * SCOPE_BINS);
+    assert_eq!(waveform.column(0)[128], 16 * 16);
+    assert_eq!(waveform.column(3)[255], 16 * 16);
+    assert_eq!(waveform.bins.iter().sum::<u32>(), 64 * 16);
+
+    // Neutrals sit in the middle, red is high in Cr and low in Cb
+    let scope = Vectorscope::measure(&input);
+    assert_eq!(scope.at(0.0, 0.0), 64 * 16);
+    let red = RgbImage::from_pixel(8, 8, Rgb([255, 0, 0]));
+    let (cb, cr) = Vectorscope::chroma([255, 0, 0]);
+    assert!(cr > 0.45 && cb < -0.1);
+    assert_eq!(Vectorscope::measure(&red).at(cb, cr), 64);
+}
@@ -0,0 +1,1336 @@
+use filmr::film::TUNGSTEN_BALANCE_K;
+use filmr::presets::fujifilm::{SUPERIA_400, VELVIA_50};
+use filmr::presets::kodak::{KODAK_EKTAR_100, KODAK_PORTRA_400, KODAK_TRI_X_400};
+use filmr::presets::other::STANDARD_DAYLIGHT;
+use filmr::processor::{
+    develop_pixel, process_image, process_image_debug, process_image_sync, process_luma_image,
+    DevelopmentParams, LensConfig, LumaOutput, SimulationConfig, Toning, WhiteBalanceMode,
+};
+use filmr::{
+    shoulder_overflow, BwFilter, DitherMode, FilmMetrics, NegativeInversion, OutputMode,
+    OutputTransfer, ScannerProfile, ShutterSpeed, SimulationMode, StageKind, WorkingSpace,
+};
+use image::{ImageBuffer, Luma, Rgb, RgbImage};
+
+fn test_input() -> RgbImage {
+    // Gradient with a white patch so halation has highlights to bloom from
+    RgbImage::from_fn(48, 32, |x, y| {
+        if (20..28).contains(&x) && (12..20).contains(&y) {
+            Rgb([255, 255, 255])
+        } else {
+            Rgb([(x * 5) as u8, (y * 7) as u8, ((x + y) * 3) as u8])
+        }
+    })
+}
+
+#[test]
+fn test_default_pipeline_is_the_default_order() {
+    assert_eq!(
+        SimulationConfig::default().pipeline,
+        SimulationConfig::default_pipeline()
+    );
+    // Grain follows the develop, so the output conversion adds it
+    assert!(SimulationConfig::default_pipeline().ends_with(&[StageKind::Develop, StageKind::Grain]));
+}
+
+#[test]
+fn test_default_config_is_the_app_baseline() {
+    let config = SimulationConfig::default();
+    assert_eq!(config.output_mode, OutputMode::Positive);
+    assert_eq!(config.white_balance_mode, WhiteBalanceMode::Auto);
+    assert_eq!(config.white_balance_strength, 1.0);
+    assert!(config.enable_grain);
+
+    // Round-trips through serde for session and sidecar persistence
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(
+        serde_json::from_str::<SimulationConfig>(&json).unwrap(),
+        config
+    );
+}
+
+#[test]
+fn test_builder_matches_struct_literal() {
+    assert_eq!(
+        SimulationConfig::builder().build(),
+        SimulationConfig::default()
+    );
+    let built = SimulationConfig::builder()
+        .exposure_time(0.25)
+        .enable_grain(false)
+        .white_balance_mode(WhiteBalanceMode::Off)
+        .grain_seed(7)
+        .pipeline(vec![StageKind::Develop])
+        .build();
+    let literal = SimulationConfig {
+        exposure_time: 0.25,
+        enable_grain: false,
+        white_balance_mode: WhiteBalanceMode::Off,
+        grain_seed: Some(7),
+        pipeline: vec![StageKind::Develop],
+        ..Default::default()
+    };
+    assert_eq!(built, literal);
+}
+
+#[test]
+fn test_removing_stage_matches_disabling_it() {
+    let input = test_input();
+    let mut film = STANDARD_DAYLIGHT();
+    film.halation_strength = 0.6;
+
+    let config = SimulationConfig {
+        enable_grain: false,
+        ..Default::default()
+    };
+    let without_stage = SimulationConfig {
+        pipeline: config
+            .pipeline
+            .iter()
+            .copied()
+            .filter(|&stage| stage != StageKind::Halation)
+            .collect(),
+        ..config.clone()
+    };
+
+    let mut no_halation_film = film.clone();
+    no_halation_film.halation_strength = 0.0;
+    no_halation_film.halation_strength2 = 0.0;
+
+    let skipped = process_image(&input, &film, &without_stage);
+    let disabled = process_image(&input, &no_halation_film, &config);
+    assert_eq!(skipped.as_raw(), disabled.as_raw());
+
+    let with_halation = process_image(&input, &film, &config);
+    assert_ne!(with_halation.as_raw(), skipped.as_raw());
+}
+
+#[test]
+fn test_grain_stage_moves_before_halation() {
+    let input = test_input();
+    let mut film = STANDARD_DAYLIGHT();
+    film.halation_strength = 0.6;
+    let config = SimulationConfig {
+        grain_seed: Some(3),
+        ..Default::default()
+    };
+    let without = |kind| -> Vec<StageKind> {
+        config
+            .pipeline
+            .iter()
+            .copied()
+            .filter(|&stage| stage != kind)
+            .collect()
+    };
+    let grain_first = SimulationConfig {
+        pipeline: [vec![StageKind::Grain], without(StageKind::Grain)].concat(),
+        ..config.clone()
+    };
+    let no_grain = SimulationConfig {
+        pipeline: without(StageKind::Grain),
+        ..config.clone()
+    };
+
+    let output_grain = process_image(&input, &film, &config);
+    let scene_grain = process_image(&input, &film, &grain_first);
+    let clean = process_image(&input, &film, &no_grain);
+    assert_ne!(scene_grain.as_raw(), output_grain.as_raw());
+    assert_ne!(scene_grain.as_raw(), clean.as_raw());
+
+    // Removing the stage matches disabling grain, wherever it is listed
+    for pipeline in [&config, &grain_first] {
+        let disabled = SimulationConfig {
+            enable_grain: false,
+            ..pipeline.clone()
+        };
+        assert_eq!(
+            process_image(&input, &film, &disabled).as_raw(),
+            clean.as_raw()
+        );
+    }
+}
+
+#[test]
+fn test_debug_stages_match_process_image() {
+    let input = test_input();
+    let mut film = STANDARD_DAYLIGHT();
+    film.halation_strength = 0.6;
+    let config = SimulationConfig {
+        enable_grain: false,
+        ..Default::default()
+    };
+
+    let stages = process_image_debug(&input, &film, &config);
+
+    // Linearizing and re-encoding round-trips the input
+    assert!(stages
+        .linearized
+        .as_raw()
+        .iter()
+        .zip(input.as_raw())
+        .all(|(&a, &b)| a.abs_diff(b) <= 1));
+    // Halation only brightens around the highlights
+    assert_ne!(stages.halated.as_raw(), stages.linearized.as_raw());
+    assert_eq!(stages.grained.as_raw(), stages.developed.as_raw());
+    assert_eq!(
+        stages.final_image.as_raw(),
+        process_image(&input, &film, &config).as_raw()
+    );
+}
+
+#[test]
+fn test_develop_pixel_matches_flat_image() {
+    let mut film = STANDARD_DAYLIGHT();
+    film.vignette_strength = 0.0;
+    let config = SimulationConfig {
+        enable_grain: false,
+        white_balance_mode: WhiteBalanceMode::Off,
+        warmth: 0.3,
+        pipeline: vec![StageKind::Develop],
+        ..Default::default()
+    };
+
+    for rgb in [[40, 90, 160], [128, 128, 128], [230, 200, 60]] {
+        let input = RgbImage::from_pixel(8, 8, Rgb(rgb));
+        let expected = process_image(&input, &film, &config).get_pixel(4, 4).0;
+        let (linear, density, out) = filmr::develop_pixel(&film, &config, rgb);
+
+        assert!(linear.iter().all(|v| (0.0..=1.0).contains(v)));
+        assert!(density.iter().all(|d| d.is_finite() && *d >= 0.0));
+        for c in 0..3 {
+            assert!(
+                (out[c] as i32 - expected[c] as i32).abs() <= 1,
+                "{:?}: develop_pixel {:?} vs process_image {:?}",
+                rgb,
+                out,
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn test_red_filter_darkens_blue_sky() {
+    let film = KODAK_TRI_X_400();
+    // Blue sky above a red object
+    let input = RgbImage::from_fn(32, 32, |_, y| {
+        if y < 16 {
+            Rgb([90, 140, 220])
+        } else {
+            Rgb([200, 40, 30])
+        }
+    });
+    let render = |bw_filter| {
+        let config = SimulationConfig {
+            bw_filter,
+            white_balance_mode: WhiteBalanceMode::Off,
+            enable_grain: false,
+            pipeline: vec![StageKind::Develop],
+            ..Default::default()
+        };
+        process_image(&input, &film, &config)
+    };
+    // Away from the boundary, which scatter blurs
+    let mean = |img: &RgbImage, rows: std::ops::Range<u32>| {
+        let n = rows.len() as f32 * img.width() as f32;
+        rows.flat_map(|y| (0..img.width()).map(move |x| (x, y)))
+            .map(|(x, y)| img.get_pixel(x, y)[1] as f32)
+            .sum::<f32>()
+            / n
+    };
+
+    let plain = render(BwFilter::None);
+    let red = render(BwFilter::Red);
+    let (sky_plain, sky_red) = (mean(&plain, 2..14), mean(&red, 2..14));
+    let (object_plain, object_red) = (mean(&plain, 18..30), mean(&red, 18..30));
+    assert!(
+        sky_red < sky_plain - 5.0,
+        "red filter should darken the sky: {sky_red} vs {sky_plain}"
+    );
+    assert!(
+        object_red > object_plain + 5.0,
+        "red filter should lighten red: {object_red} vs {object_plain}"
+    );
+}
+
+#[test]
+fn test_tungsten_stock_turns_daylight_blue() {
+    let input = RgbImage::from_pixel(16, 16, Rgb([128, 128, 128]));
+    let config = SimulationConfig {
+        white_balance_mode: WhiteBalanceMode::Off,
+        enable_grain: false,
+        pipeline: vec![StageKind::Develop],
+        ..Default::default()
+    };
+    let mut film = STANDARD_DAYLIGHT();
+    let daylight = *process_image(&input, &film, &config).get_pixel(8, 8);
+    film.native_white_balance_k = TUNGSTEN_BALANCE_K;
+    let tungsten = *process_image(&input, &film, &config).get_pixel(8, 8);
+
+    let blue_bias = |p: Rgb<u8>| p[2] as i32 - p[0] as i32;
+    assert!(
+        blue_bias(tungsten) > blue_bias(daylight) + 10,
+        "tungsten stock in daylight should be blue: {tungsten:?} vs {daylight:?}"
+    );
+}
+
+#[test]
+fn test_output_transfers_encode_mid_gray_differently() {
+    let transfers = [
+        OutputTransfer::Srgb,
+        OutputTransfer::Gamma(2.2),
+        OutputTransfer::Rec709,
+        OutputTransfer::Linear,
+    ];
+    let codes: Vec<u8> = transfers
+        .iter()
+        .map(|t| (t.encode(0.5) * 255.0).round() as u8)
+        .collect();
+    for i in 0..codes.len() {
+        for j in i + 1..codes.len() {
+            assert_ne!(
+                codes[i], codes[j],
+                "{:?} and {:?} encode 0.5 the same",
+                transfers[i], transfers[j]
+            );
+        }
+    }
+    assert_eq!(codes[3], 128);
+}
+
+#[test]
+fn test_shoulder_overflow_flags_overexposed_slide() {
+    let mut input = RgbImage::from_pixel(8, 8, Rgb([10, 10, 10]));
+    input.put_pixel(0, 0, Rgb([255, 255, 255]));
+    let config = SimulationConfig {
+        exposure_time: 8.0,
+        ..Default::default()
+    };
+    let overflow = shoulder_overflow(&input, &VELVIA_50(), &config);
+    assert!(
+        overflow.get_pixel(0, 0)[0] > 0.0,
+        "white should pass the shoulder"
+    );
+    assert_eq!(overflow.get_pixel(4, 4)[0], 0.0, "shadows stay below it");
+}
+
+#[test]
+fn test_kodak_halation_is_redder_than_fuji() {
+    // Clipped highlight on black: the glow is all that lights the ring around it
+    let input = RgbImage::from_fn(96, 96, |x, _| {
+        if (40..56).contains(&x) {
+            Rgb([255, 255, 255])
+        } else {
+            Rgb([0, 0, 0])
+        }
+    });
+    let config = SimulationConfig {
+        enable_grain: false,
+        ..Default::default()
+    };
+    let red_bias = |mut film: filmr::FilmStock| {
+        // Same glow shape, so only the channel gains differ
+        film.halation_strength = 0.5;
+        film.halation_threshold = 0.5;
+        film.halation_sigma = 0.05;
+        film.halation_strength2 = 0.0;
+        film.halation_tint = [1.0; 3];
+        let halated = process_image_debug(&input, &film, &config).halated;
+        let p = halated.get_pixel(34, 48);
+        p[0] as f32 / (p[2] as f32).max(1.0)
+    };
+    let kodak = red_bias(KODAK_PORTRA_400());
+    let fuji = red_bias(SUPERIA_400());
+    assert!(
+        kodak > fuji,
+        "Kodak glow should be redder: {kodak} vs {fuji}"
+    );
+}
+
+#[test]
+fn test_sync_runs_while_the_global_pool_is_busy() {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    let input = test_input();
+    let film = KODAK_PORTRA_400();
+    let config = SimulationConfig {
+        grain_seed: Some(7),
+        ..Default::default()
+    };
+
+    // Hold every global rayon thread until the sync develop is done
+    let done = Arc::new(AtomicBool::new(false));
+    let blocked = Arc::new(AtomicUsize::new(0));
+    let blocker = {
+        let (done, blocked) = (done.clone(), blocked.clone());
+        std::thread::spawn(move || {
+            rayon::broadcast(|_| {
+                blocked.fetch_add(1, Ordering::SeqCst);
+                let start = Instant::now();
+                while !done.load(Ordering::SeqCst) {
+                    if start.elapsed() > Duration::from_secs(30) {
+                        return false;
+                    }
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                true
+            })
+        })
+    };
+    while blocked.load(Ordering::SeqCst) < rayon::current_num_threads() {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    let sync = process_image_sync(&input, &film, &config);
+    done.store(true, Ordering::SeqCst);
+
+    // Released by the develop finishing, not by the timeout
+    assert!(blocker.join().unwrap().into_iter().all(|released| released));
+    assert_eq!(sync, process_image(&input, &film, &config));
+}
+
+#[test]
+fn test_development_scales_contrast_and_grain() {
+    let film = KODAK_TRI_X_400();
+    let config = |temperature_c, time_rel| SimulationConfig {
+        development: DevelopmentParams {
+            temperature_c,
+            time_rel,
+            ..Default::default()
+        },
+        grain_seed: Some(11),
+        ..Default::default()
+    };
+
+    // Standard development leaves the output untouched
+    let input = test_input();
+    assert_eq!(
+        process_image(&input, &film, &config(20.0, 1.0)),
+        process_image(
+            &input,
+            &film,
+            &SimulationConfig {
+                grain_seed: Some(11),
+                ..Default::default()
+            }
+        )
+    );
+
+    // Hotter and longer: denser highlights, thinner shadows, more grain
+    let spread = |config: &SimulationConfig| {
+        develop_pixel(&film, config, [220; 3]).1[1] - develop_pixel(&film, config, [30; 3]).1[1]
+    };
+    let pushed = config(24.0, 1.5);
+    assert!(spread(&pushed) > spread(&config(20.0, 1.0)));
+    assert!(pushed.development.grain_scale() > pushed.development.gamma_scale());
+    assert!(config(18.0, 0.7).development.degree() < 1.0);
+}
+
+#[test]
+fn test_push_raises_grain_and_contrast_together() {
+    let film = KODAK_TRI_X_400();
+    let input = RgbImage::from_pixel(128, 96, Rgb([118, 118, 118]));
+    let config = |push_pull_stops, decouple_push_grain| SimulationConfig {
+        development: DevelopmentParams {
+            push_pull_stops,
+            decouple_push_grain,
+            ..Default::default()
+        },
+        white_balance_mode: WhiteBalanceMode::Off,
+        grain_seed: Some(3),
+        ..Default::default()
+    };
+    let spread = |config: &SimulationConfig| {
+        develop_pixel(&film, config, [220; 3]).1[1] - develop_pixel(&film, config, [30; 3]).1[1]
+    };
+    // RMS of the grain alone, against the same develop without it
+    let grain = |config: &SimulationConfig| {
+        let grained = process_image(&input, &film, config);
+        let clean = process_image(
+            &input,
+            &film,
+            &SimulationConfig {
+                enable_grain: false,
+                ..config.clone()
+            },
+        );
+        let sum: f32 = grained
+            .as_raw()
+            .iter()
+            .zip(clean.as_raw())
+            .map(|(&a, &b)| (a as f32 - b as f32).powi(2))
+            .sum();
+        (sum / grained.as_raw().len() as f32).sqrt()
+    };
+
+    // Pulled, box speed, then one to three stops pushed
+    let mut previous: Option<(f32, f32)> = None;
+    for stops in [-1.0, 0.0, 1.0, 2.0, 3.0] {
+        let config = config(stops, false);
+        let (contrast, noise) = (spread(&config), grain(&config));
+        if let Some((last_contrast, last_noise)) = previous {
+            assert!(
+                contrast > last_contrast,
+                "{stops} stops: contrast {contrast}"
+            );
+            assert!(noise > last_noise, "{stops} stops: grain {noise}");
+        }
+        previous = Some((contrast, noise));
+    }
+
+    // Tri-X at EI 1600 develops to about the published 0.9 gamma
+    let pushed = config(2.0, false).development;
+    assert!((film.g_curve.gamma * pushed.gamma_scale() - 0.88).abs() < 0.01);
+
+    // Decoupled, push still steepens the curve but leaves grain alone
+    let decoupled = config(2.0, true);
+    assert_eq!(decoupled.development.grain_scale(), 1.0);
+    assert!(spread(&decoupled) > spread(&config(0.0, false)));
+}
+
+#[test]
+fn test_saturated_blue_through_ektar_develops_cleanly() {
+    // Ektar's negative off-diagonals pull the red and green layers down
+    // hardest where only the blue layer is exposed
+    let film = KODAK_EKTAR_100();
+    let input = RgbImage::from_pixel(16, 16, Rgb([0, 0, 255]));
+    let config = SimulationConfig {
+        enable_grain: false,
+        white_balance_mode: WhiteBalanceMode::Off,
+        ..Default::default()
+    };
+
+    let densities = filmr::density_map(&input, &film, &config);
+    assert!(
+        densities.iter().all(|d| d.is_finite() && *d >= 0.0),
+        "density {:?}",
+        densities.get_pixel(8, 8).0
+    );
+
+    let output = process_image(&input, &film, &config);
+    let px = output.get_pixel(8, 8).0;
+    assert!(px[2] > px[0] && px[2] > 64, "blue developed to {:?}", px);
+}
+
+#[test]
+fn test_low_resolution_stock_renders_softer() {
+    // Fine checkerboard across a 35mm frame scanned 2048px wide
+    let input = RgbImage::from_fn(2048, 64, |x, y| {
+        if (x + y) % 2 == 0 {
+            Rgb([200, 200, 200])
+        } else {
+            Rgb([60, 60, 60])
+        }
+    });
+    let config = SimulationConfig {
+        enable_grain: false,
+        white_balance_mode: WhiteBalanceMode::Off,
+        pipeline: vec![StageKind::Mtf, StageKind::Develop],
+        ..Default::default()
+    };
+    let sharpness = |resolution_lp_mm| {
+        let mut film = STANDARD_DAYLIGHT();
+        film.vignette_strength = 0.0;
+        film.resolution_lp_mm = resolution_lp_mm;
+        FilmMetrics::analyze(&process_image(&input, &film, &config)).laplacian_variance
+    };
+
+    let (soft, sharp) = (sharpness(50.0), sharpness(200.0));
+    assert!(
+        soft < sharp * 0.8,
+        "50 lp/mm variance {soft} vs 200 lp/mm {sharp}"
+    );
+}
+
+#[test]
+fn test_inverted_scan_tracks_positive_with_mask_in_shadows() {
+    let film = KODAK_PORTRA_400();
+    let develop = |output_mode, mask_removal, rgb| {
+        let config = SimulationConfig {
+            white_balance_mode: WhiteBalanceMode::Off,
+            output_mode,
+            inversion: NegativeInversion {
+                mask_removal,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        develop_pixel(&film, &config, rgb).2
+    };
+
+    // With the base fully removed the round trip lands on the direct positive
+    for rgb in [[60; 3], [128; 3], [200, 150, 90]] {
+        let direct = develop(OutputMode::Positive, 1.0, rgb);
+        let inverted = develop(OutputMode::Inverted, 1.0, rgb);
+        for c in 0..3 {
+            assert!(
+                (direct[c] as i32 - inverted[c] as i32).abs() <= 6,
+                "{:?}: positive {:?} vs inverted {:?}",
+                rgb,
+                direct,
+                inverted
+            );
+        }
+    }
+
+    // Orange mask left on the scan inverts to blue shadows
+    let cast = |px: [u8; 3]| px[2] as i32 - px[0] as i32;
+    let clean = develop(OutputMode::Inverted, 1.0, [60; 3]);
+    let residue = develop(OutputMode::Inverted, 0.0, [60; 3]);
+    assert!(
+        cast(residue) > cast(clean) + 2,
+        "clean {:?} vs residue {:?}",
+        clean,
+        residue
+    );
+}
+
+#[test]
+fn test_scanner_sharpens_and_misregisters_edges() {
+    // Vertical gray edge: misregistration along y leaves it alone, along x splits it
+    let input = RgbImage::from_fn(64, 32, |x, _| {
+        if x < 32 {
+            Rgb([60, 60, 60])
+        } else {
+            Rgb([190, 190, 190])
+        }
+    });
+    let film = STANDARD_DAYLIGHT();
+    let scan = |scanner| {
+        let config = SimulationConfig {
+            enable_grain: false,
+            white_balance_mode: WhiteBalanceMode::Off,
+            scanner,
+            grain_seed: Some(5),
+            ..Default::default()
+        };
+        process_image(&input, &film, &config)
+    };
+    let plain = scan(None);
+    let flatbed = scan(Some(ScannerProfile {
+        noise_floor: 0.0,
+        ..ScannerProfile::flatbed()
+    }));
+    let frontier = scan(Some(ScannerProfile {
+        noise_floor: 0.0,
+        misregistration: [[0.0; 2]; 3],
+        ..ScannerProfile::frontier()
+    }));
+
+    // Unsharp mask overshoots just past the bright side of the edge
+    assert!(frontier.get_pixel(33, 16)[1] > plain.get_pixel(33, 16)[1]);
+    // Red shifted right, blue left: they disagree across the edge
+    let (edge, before) = (flatbed.get_pixel(32, 16), plain.get_pixel(32, 16));
+    let shift = |c: usize| edge[c] as i32 - before[c] as i32;
+    assert!(
+        shift(0) < shift(2),
+        "channels should split at the edge: {edge:?}"
+    );
+}
+
+#[test]
+fn test_chromatic_aberration_fringes_toward_corners() {
+    // Gray square edge near the right of the frame, far from the center
+    let input = RgbImage::from_fn(96, 64, |x, _| {
+        if x < 80 {
+            Rgb([40, 40, 40])
+        } else {
+            Rgb([200, 200, 200])
+        }
+    });
+    let film = STANDARD_DAYLIGHT();
+    let render = |chromatic_aberration| {
+        let config = SimulationConfig {
+            enable_grain: false,
+            white_balance_mode: WhiteBalanceMode::Off,
+            lens: LensConfig {
+                chromatic_aberration,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        process_image(&input, &film, &config)
+    };
+
+    let without_stage = SimulationConfig {
+        enable_grain: false,
+        white_balance_mode: WhiteBalanceMode::Off,
+        pipeline: SimulationConfig::default_pipeline()
+            .into_iter()
+            .filter(|&stage| stage != StageKind::ChromaticAberration)
+            .collect(),
+        ..Default::default()
+    };
+    let plain = render(0.0);
+    assert_eq!(plain, process_image(&input, &film, &without_stage));
+
+    // Blue is imaged smaller, so the bright side reaches in past the edge in blue first
+    let fringed = render(5.0);
+    let (edge, before) = (fringed.get_pixel(79, 32), plain.get_pixel(79, 32));
+    let shift = |c: usize| edge[c] as i32 - before[c] as i32;
+    assert!(
+        shift(0) < shift(2),
+        "edge should fringe: {edge:?} vs {before:?}"
+    );
+}
+
+#[test]
+fn test_diffusion_glows_into_shadows_around_highlights() {
+    // Bright square on a dark frame, with no clipped highlights for halation
+    let input = RgbImage::from_fn(96, 64, |x, y| {
+        if (40..56).contains(&x) && (24..40).contains(&y) {
+            Rgb([200, 200, 200])
+        } else {
+            Rgb([60, 60, 60])
+        }
+    });
+    let film = STANDARD_DAYLIGHT();
+    let render = |diffusion| {
+        let config = SimulationConfig {
+            enable_grain: false,
+            white_balance_mode: WhiteBalanceMode::Off,
+            lens: LensConfig {
+                diffusion,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        process_image(&input, &film, &config)
+    };
+
+    let without_stage = SimulationConfig {
+        enable_grain: false,
+        white_balance_mode: WhiteBalanceMode::Off,
+        pipeline: SimulationConfig::default_pipeline()
+            .into_iter()
+            .filter(|&stage| stage != StageKind::Diffusion)
+            .collect(),
+        ..Default::default()
+    };
+    let plain = render(0.0);
+    assert_eq!(plain, process_image(&input, &film, &without_stage));
+
+    // The glow lifts the shadows next to the square and softens its edge
+    let diffused = render(1.0);
+    let shadow = (diffused.get_pixel(38, 32)[1], plain.get_pixel(38, 32)[1]);
+    let highlight = (diffused.get_pixel(41, 32)[1], plain.get_pixel(41, 32)[1]);
+    assert!(shadow.0 > shadow.1, "shadow should lift: {shadow:?}");
+    assert!(
+        highlight.0 < highlight.1,
+        "highlight should soften: {highlight:?}"
+    );
+}
+
+#[test]
+fn test_saturation_one_is_identity_and_zero_is_grayscale() {
+    let input = RgbImage::from_fn(48, 32, |x, y| {
+        Rgb([(x * 5) as u8, (y * 7) as u8, 255 - (x * 5) as u8])
+    });
+    let film = KODAK_PORTRA_400();
+    let render = |saturation| {
+        let config = SimulationConfig {
+            enable_grain: false,
+            white_balance_mode: WhiteBalanceMode::Off,
+            saturation,
+            ..Default::default()
+        };
+        process_image(&input, &film, &config)
+    };
+
+    // Just off 1.0 runs the Lab round trip, which must not drift
+    let plain = render(1.0);
+    let round_trip = render(1.0 + 1e-6);
+    let diff = filmr::metrics::compare(&plain, &round_trip).unwrap();
+    assert!(diff.max_channel_diff <= 1, "{diff:?}");
+
+    for p in render(0.0).pixels() {
+        let spread = p.0.iter().max().unwrap() - p.0.iter().min().unwrap();
+        assert!(spread <= 1, "saturation 0 should be gray: {p:?}");
+    }
+}
+
+#[test]
+fn test_toning_tints_bw_output_only() {
+    let input = RgbImage::from_fn(48, 32, |x, _| {
+        let v = (x * 5) as u8;
+        Rgb([v, v, v])
+    });
+    let render = |film: &filmr::FilmStock, toning| {
+        // Two stops over, so the top of the ramp prints as real highlights
+        let config = SimulationConfig {
+            enable_grain: false,
+            white_balance_mode: WhiteBalanceMode::Off,
+            exposure_time: 4.0,
+            toning,
+            ..Default::default()
+        };
+        process_image(&input, film, &config)
+    };
+
+    let bw = KODAK_TRI_X_400();
+    let sepia = render(&bw, Toning::Sepia);
+    let mid = sepia.get_pixel(24, 16);
+    assert!(mid[0] > mid[2], "sepia should be warm: {mid:?}");
+    let cyanotype = render(&bw, Toning::Cyanotype);
+    let mid = cyanotype.get_pixel(24, 16);
+    assert!(mid[2] > mid[0], "cyanotype should be blue: {mid:?}");
+
+    // Split toning: cool shadows, warm highlights
+    let split = render(&bw, Toning::CLASSIC_SPLIT);
+    let (shadow, highlight) = (split.get_pixel(12, 16), split.get_pixel(44, 16));
+    assert!(shadow[2] > shadow[0], "shadows should be cool: {shadow:?}");
+    assert!(
+        highlight[0] > highlight[2],
+        "highlights should be warm: {highlight:?}"
+    );
+
+    let color = KODAK_PORTRA_400();
+    assert_eq!(render(&color, Toning::Sepia), render(&color, Toning::None));
+}
+
+#[test]
+fn test_luma_input_matches_rgb_develop() {
+    let rgb = RgbImage::from_fn(48, 32, |x, _| {
+        let v = (x * 5) as u8;
+        Rgb([v, v, v])
+    });
+    let luma = ImageBuffer::from_fn(48, 32, |x, _| Luma([(x * 5) as u16 * 257]));
+    let config = SimulationConfig {
+        enable_grain: false,
+        white_balance_mode: WhiteBalanceMode::Off,
+        toning: Toning::Sepia,
+        ..Default::default()
+    };
+    let film = KODAK_TRI_X_400();
+
+    let expected = process_image(&rgb, &film, &config);
+    let toned = process_luma_image(&luma, &film, &config, LumaOutput::Rgb8).into_rgb8();
+    for (a, b) in toned.pixels().zip(expected.pixels()) {
+        for c in 0..3 {
+            assert!(a[c].abs_diff(b[c]) <= 1, "{a:?} vs {b:?}");
+        }
+    }
+
+    // Grayscale output drops the toning and stays single channel
+    let untoned = process_image(
+        &rgb,
+        &film,
+        &SimulationConfig {
+            toning: Toning::None,
+            ..config.clone()
+        },
+    );
+    let gray = process_luma_image(&luma, &film, &config, LumaOutput::Luma8);
+    let gray = gray.as_luma8().expect("Luma8 output");
+    for (a, b) in gray.pixels().zip(untoned.pixels()) {
+        assert!(a[0].abs_diff(b[1]) <= 1, "{a:?} vs {b:?}");
+    }
+    let deep = process_luma_image(&luma, &film, &config, LumaOutput::Luma16);
+    let deep = deep.as_luma16().expect("Luma16 output");
+    assert!(
+        deep.pixels().any(|p| p[0] % 257 != 0),
+        "16-bit output should use more than 8 bits"
+    );
+}
+
+#[test]
+fn test_linearize_round_trips_and_matches_transfer() {
+    let input = test_input();
+    let linear = filmr::utils::srgb_to_linear(&input);
+    assert_eq!(filmr::utils::linear_to_srgb(&linear), input);
+
+    let pixel = linear.get_pixel(10, 3);
+    assert_eq!(pixel[0], filmr::physics::srgb_to_linear(50.0 / 255.0));
+    let white = linear.get_pixel(24, 16);
+    assert!(white.0.iter().all(|&v| (v - 1.0).abs() < 1e-6));
+}
+
+#[test]
+fn test_bypass_film_round_trips_input() {
+    let input = test_input();
+    let config = SimulationConfig::builder().bypass_film(true).build();
+
+    for stock in [KODAK_PORTRA_400(), KODAK_TRI_X_400()] {
+        let output = process_image(&input, &stock, &config);
+        let max_diff = output
+            .as_raw()
+            .iter()
+            .zip(input.as_raw())
+            .map(|(&a, &b)| a.abs_diff(b))
+            .max()
+            .unwrap();
+        assert!(
+            max_diff <= 1,
+            "{} moved a value by {}",
+            stock.name,
+            max_diff
+        );
+
+        let stages = process_image_debug(&input, &stock, &config);
+        assert_eq!(stages.final_image, output);
+    }
+}
+
+#[test]
+fn test_effect_amount_mixes_develop_with_original() {
+    let input = test_input();
+    let film = KODAK_PORTRA_400();
+    let config = |effect_amount| SimulationConfig {
+        grain_seed: Some(5),
+        effect_amount,
+        ..Default::default()
+    };
+    let bypass = SimulationConfig::builder().bypass_film(true).build();
+
+    // None of the film is the linearized original, all of it the full develop
+    let original = process_image(&input, &film, &bypass);
+    let full = process_image(&input, &film, &config(1.0));
+    assert_eq!(process_image(&input, &film, &config(0.0)), original);
+
+    // In between, every value lies between the two, mixed in linear light
+    let half = process_image(&input, &film, &config(0.5));
+    let linear = |v: u8| filmr::physics::srgb_to_linear(v as f32 / 255.0);
+    for ((&h, &o), &f) in half
+        .as_raw()
+        .iter()
+        .zip(original.as_raw())
+        .zip(full.as_raw())
+    {
+        assert!(h >= o.min(f) && h <= o.max(f), "{h} outside {o}..{f}");
+        let mixed = 0.5 * (linear(o) + linear(f));
+        assert!((linear(h) - mixed).abs() < 0.01, "{h} vs {o} and {f}");
+    }
+
+    // The debug path and the per-pixel develop agree with the full render
+    let stages = process_image_debug(&input, &film, &config(0.5));
+    assert_eq!(stages.final_image, half);
+    let px = input.get_pixel(8, 8).0;
+    let (_, _, mixed_px) = develop_pixel(&film, &config(0.0), px);
+    assert_eq!(mixed_px, original.get_pixel(8, 8).0);
+}
+
+#[test]
+fn test_linear_input_is_not_decoded_twice() {
+    let input = RgbImage::from_fn(256, 4, |x, _| Rgb([x as u8; 3]));
+    let film = KODAK_PORTRA_400();
+    let config = |input_is_linear| {
+        SimulationConfig::builder()
+            .bypass_film(true)
+            .input_is_linear(input_is_linear)
+            .build()
+    };
+
+    // Linear values come out sRGB encoded once, where sRGB input round trips
+    let linear = process_image(&input, &film, &config(true));
+    let srgb = process_image(&input, &film, &config(false));
+    for x in 0..256u32 {
+        let encoded = filmr::physics::linear_to_srgb(x as f32 / 255.0) * 255.0;
+        let out = linear.get_pixel(x, 0)[0] as f32;
+        assert!((out - encoded).abs() <= 1.0, "{x}: {out} vs {encoded}");
+        assert!((srgb.get_pixel(x, 0)[0] as i32 - x as i32).abs() <= 1);
+    }
+    assert_ne!(linear, srgb);
+
+    // The debug path starts from the same linear image
+    let stages = process_image_debug(&input, &film, &config(true));
+    assert_eq!(stages.final_image, linear);
+
+    // So do the per-pixel develop and the density map
+    let developed = SimulationConfig::builder().input_is_linear(true).build();
+    let (decoded, density, _) = develop_pixel(&film, &developed, [128; 3]);
+    assert_eq!(decoded, [128.0 / 255.0; 3]);
+    let densities = filmr::density_map(&input, &film, &developed);
+    assert_eq!(densities.get_pixel(128, 0).0, density);
+    let (_, srgb_density, _) = develop_pixel(&film, &SimulationConfig::default(), [128; 3]);
+    assert_ne!(density, srgb_density);
+}
+
+#[test]
+fn test_wide_working_space_saturates_film_colors() {
+    let input = RgbImage::from_fn(48, 32, |x, _| match x / 16 {
+        0 => Rgb([200, 60, 50]),
+        1 => Rgb([60, 160, 70]),
+        _ => Rgb([128, 128, 128]),
+    });
+    let render = |working_space| {
+        let config = SimulationConfig {
+            enable_grain: false,
+            white_balance_mode: WhiteBalanceMode::Off,
+            working_space,
+            ..Default::default()
+        };
+        process_image(&input, &KODAK_PORTRA_400(), &config)
+    };
+    let chroma = |p: &Rgb<u8>| p.0.iter().max().unwrap() - p.0.iter().min().unwrap();
+
+    let srgb = render(WorkingSpace::LinearSrgb);
+    assert_eq!(srgb, render(WorkingSpace::default()));
+    let aces = render(WorkingSpace::ACEScg);
+    for x in [8, 24] {
+        let (narrow, wide) = (srgb.get_pixel(x, 16), aces.get_pixel(x, 16));
+        assert!(
+            chroma(wide) > chroma(narrow),
+            "ACEScg should be more saturated: {wide:?} vs {narrow:?}"
+        );
+    }
+
+    // Rows of the conversion sum to one, so neutrals stay neutral
+    for space in WorkingSpace::ALL {
+        let gray = space.to_linear_srgb([0.5; 3]);
+        assert!(
+            gray.iter().all(|v| (v - 0.5).abs() < 1e-4),
+            "{space:?}: {gray:?}"
+        );
+    }
+}
+
+#[test]
+fn test_dithering_breaks_up_gradient_bands() {
+    // A smooth horizontal ramp across four output levels, 512 x 16 px
+    let ramp = |x: u32| (100.0 + 4.0 * x as f32 / 512.0) / 255.0;
+    let quantize = |dither: DitherMode| -> Vec<Vec<u8>> {
+        (0..512)
+            .map(|x| (0..16).map(|y| dither.quantize(ramp(x), x, y)).collect())
+            .collect()
+    };
+    // Banding: the widest run of identical columns
+    let widest_band = |columns: &[Vec<u8>]| {
+        columns
+            .chunk_by(|a, b| a == b)
+            .map(<[Vec<u8>]>::len)
+            .max()
+            .unwrap()
+    };
+    // Block averages over 32 px should follow the ramp, not a staircase
+    let staircase_error = |columns: &[Vec<u8>]| {
+        columns
+            .chunks(32)
+            .enumerate()
+            .map(|(i, block)| {
+                let sum: u32 = block.iter().flatten().map(|&v| v as u32).sum();
+                let mean = sum as f32 / (32.0 * 16.0);
+                (mean - ramp(i as u32 * 32 + 16) * 255.0).abs()
+            })
+            .fold(0.0f32, f32::max)
+    };
+
+    let plain = quantize(DitherMode::None);
+    assert!(widest_band(&plain) >= 120);
+    for dither in [DitherMode::Ordered, DitherMode::BlueNoise] {
+        let dithered = quantize(dither);
+        assert!(
+            widest_band(&dithered) < 16,
+            "{dither:?}: {}",
+            widest_band(&dithered)
+        );
+        assert!(
+            staircase_error(&dithered) < staircase_error(&plain) / 2.0,
+            "{dither:?}: {} vs {}",
+            staircase_error(&dithered),
+            staircase_error(&plain)
+        );
+    }
+
+    // The develop output is dithered without shifting its brightness
+    let input = RgbImage::from_fn(128, 16, |x, _| {
+        let v = 100 + (x / 16) as u8;
+        Rgb([v, v, v])
+    });
+    let render = |dither| {
+        let config = SimulationConfig {
+            enable_grain: false,
+            dither,
+            ..Default::default()
+        };
+        process_image(&input, &KODAK_PORTRA_400(), &config)
+    };
+    let mean = |image: &RgbImage| {
+        image.as_raw().iter().map(|&v| v as f32).sum::<f32>() / image.as_raw().len() as f32
+    };
+    let (plain, dithered) = (render(DitherMode::None), render(DitherMode::BlueNoise));
+    assert_ne!(plain, dithered);
+    assert!((mean(&plain) - mean(&dithered)).abs() < 0.5);
+}
+
+#[test]
+fn test_mask_keeps_the_original_outside_its_range() {
+    let input = test_input();
+    let film = VELVIA_50();
+    let mask = filmr::MaskConfig::luminance(0.9, 1.0, 0.0);
+    let config = SimulationConfig::builder()
+        .enable_grain(false)
+        .mask(Some(mask))
+        .build();
+    let output = process_image(&input, &film, &config);
+    let developed = process_image(
+        &input,
+        &film,
+        &SimulationConfig {
+            mask: None,
+            ..config.clone()
+        },
+    );
+
+    for ((src, out), dev) in input.pixels().zip(output.pixels()).zip(developed.pixels()) {
+        match mask.weight(src.0) {
+            0.0 => assert_eq!(out, src),
+            1.0 => assert_eq!(out, dev),
+            _ => {}
+        }
+    }
+    // The white patch is developed, the dark corner is untouched
+    assert_eq!(output.get_pixel(24, 16), developed.get_pixel(24, 16));
+    assert_eq!(output.get_pixel(0, 0), input.get_pixel(0, 0));
+
+    // The async path the app worker uses masks the same way
+    let async_output =
+        futures::executor::block_on(filmr::process_image_async(&input, &film, &config));
+    assert_eq!(async_output, output);
+}
+
+#[test]
+fn test_mask_encodes_the_original_with_the_output_transfer() {
+    let input = test_input();
+    let film = VELVIA_50();
+    let mask = filmr::MaskConfig::luminance(0.9, 1.0, 0.0);
+    for transfer in [OutputTransfer::Linear, OutputTransfer::Rec709] {
+        let config = SimulationConfig::builder()
+            .enable_grain(false)
+            .mask(Some(mask))
+            .output_transfer(transfer)
+            .build();
+        let output = process_image(&input, &film, &config);
+
+        // Outside the mask the original goes through the same encoding
+        for (src, out) in input.pixels().zip(output.pixels()) {
+            if mask.weight(src.0) == 0.0 {
+                let expected = src.0.map(|v| {
+                    let linear = filmr::physics::srgb_to_linear(v as f32 / 255.0);
+                    (transfer.encode(linear) * 255.0).round() as u8
+                });
+                assert_eq!(out.0, expected, "{transfer:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_linear_resize_keeps_checker_brightness() {
+    use filmr::utils::{resize_to_fit, resize_to_fit_linear, PREVIEW_FILTER};
+
+    // 1px black/white checker reflects 50% of the light
+    let checker = RgbImage::from_fn(256, 128, |x, y| {
+        if (x + y) % 2 == 0 {
+            Rgb([255, 255, 255])
+        } else {
+            Rgb([0, 0, 0])
+        }
+    });
+    let mean = |image: &RgbImage| {
+        image.as_raw().iter().map(|&v| v as f32).sum::<f32>() / image.as_raw().len() as f32
+    };
+    // 50% linear encodes to about code 188
+    for size in [128, 32] {
+        let linear = resize_to_fit_linear(&checker, size, PREVIEW_FILTER);
+        assert_eq!(linear.width(), size);
+        assert!((mean(&linear) - 188.0).abs() < 3.0, "{}", mean(&linear));
+    }
+    // Resizing the encoded values lands near code 128 instead
+    assert!(mean(&resize_to_fit(&checker, 128, PREVIEW_FILTER)) < 140.0);
+}
+
+#[test]
+fn test_degenerate_images_develop_without_nan() {
+    let film = KODAK_PORTRA_400();
+    let config = SimulationConfig {
+        auto_levels: true,
+        grain_seed: Some(3),
+        ..Default::default()
+    };
+    let finite = |metrics: &FilmMetrics| {
+        let values = metrics
+            .mean_rgb
+            .iter()
+            .chain(&metrics.std_rgb)
+            .chain(&metrics.skewness_rgb)
+            .chain(&metrics.kurtosis_rgb)
+            .chain(&metrics.lab_mean)
+            .chain(&metrics.lab_std)
+            .chain(&metrics.lbp_hist)
+            .chain(&metrics.glcm_stats)
+            .chain(&[
+                metrics.entropy,
+                metrics.dynamic_range,
+                metrics.rg_ratio,
+                metrics.bg_ratio,
+                metrics.saturation_mean,
+                metrics.saturation_skew,
+                metrics.cct_tint.0,
+                metrics.cct_tint.1,
+                metrics.laplacian_variance,
+                metrics.psd_slope,
+            ])
+            .copied()
+            .collect::<Vec<f32>>();
+        values.iter().all(|v| v.is_finite())
+    };
+
+    let inputs = [
+        ("black", RgbImage::from_pixel(32, 32, Rgb([0, 0, 0]))),
+        ("white", RgbImage::from_pixel(32, 32, Rgb([255, 255, 255]))),
+        ("1x1", RgbImage::from_pixel(1, 1, Rgb([90, 140, 200]))),
+        ("empty", RgbImage::new(0, 0)),
+    ];
+    for (name, input) in &inputs {
+        assert_eq!(filmr::estimate_exposure_time(input, &film), 1.0, "{name}");
+        assert!(finite(&FilmMetrics::analyze(input)), "{name} input metrics");
+
+        let output = process_image(input, &film, &config);
+        assert_eq!(output.dimensions(), input.dimensions(), "{name}");
+        assert!(
+            finite(&FilmMetrics::analyze(&output)),
+            "{name} output metrics"
+        );
+    }
+}
+
+#[test]
+fn test_empty_image_through_every_entry_point() {
+    let film = KODAK_PORTRA_400();
+    let empty = RgbImage::new(0, 0);
+    for use_gpu in [false, true] {
+        let config = SimulationConfig {
+            use_gpu,
+            auto_levels: true,
+            grain_seed: Some(3),
+            ..Default::default()
+        };
+        let empty_output = |image: &RgbImage| image.dimensions() == (0, 0);
+
+        assert!(empty_output(&process_image(&empty, &film, &config)));
+        assert!(empty_output(&process_image_sync(&empty, &film, &config)));
+        assert!(empty_output(&futures::executor::block_on(
+            filmr::process_image_async(&empty, &film, &config)
+        )));
+        let stages = process_image_debug(&empty, &film, &config);
+        for image in [
+            &stages.linearized,
+            &stages.halated,
+            &stages.developed,
+            &stages.grained,
+            &stages.final_image,
+        ] {
+            assert!(empty_output(image));
+        }
+        if let Ok(output) = filmr::process_image_gpu(&empty, &film, &config) {
+            assert!(empty_output(&output));
+        }
+        let batch = filmr::process_images_batch(&[(&empty, &film, &config)]);
+        assert!(empty_output(&batch[0]));
+        let mut session = filmr::PreviewSession::new(empty.clone());
+        assert!(empty_output(&session.render(&film, &config)));
+        let luma = process_luma_image(&ImageBuffer::new(0, 0), &film, &config, LumaOutput::Luma16);
+        assert_eq!((luma.width(), luma.height()), (0, 0));
+    }
+}
+
+#[test]
+fn test_target_size_develops_at_print_resolution() {
+    use filmr::utils::{resize_linear, PREVIEW_FILTER};
+
+    let input = RgbImage::from_pixel(256, 192, Rgb([128, 128, 128]));
+    let film = KODAK_PORTRA_400();
+    let config = |target_size| SimulationConfig {
+        white_balance_mode: WhiteBalanceMode::Off,
+        grain_seed: Some(11),
+        target_size,
+        ..Default::default()
+    };
+
+    // Enlargements develop at the target, reductions at up to twice the target
+    assert_eq!(config(None).working_size((256, 192)), (256, 192));
+    assert_eq!(
+        config(Some((512, 384))).working_size((256, 192)),
+        (512, 384)
+    );
+    assert_eq!(config(Some((64, 48))).working_size((256, 192)), (128, 96));
+    assert_eq!(
+        config(Some((160, 120))).working_size((256, 192)),
+        (256, 192)
+    );
+
+    let plain = process_image(&input, &film, &config(None));
+    let enlarged = process_image(&input, &film, &config(Some((512, 384))));
+    let reduced = process_image(&input, &film, &config(Some((64, 48))));
+    assert_eq!(enlarged.dimensions(), (512, 384));
+    assert_eq!(reduced.dimensions(), (64, 48));
+
+    // Grain is rendered at print resolution rather than magnified, so an
+    // enlarged develop stays crisper than a develop that is upscaled after
+    let upscaled = resize_linear(&plain, 512, 384, PREVIEW_FILTER);
+    let sharpness = |image: &RgbImage| FilmMetrics::analyze(image).laplacian_variance;
+    assert!(
+        sharpness(&enlarged) > sharpness(&upscaled) * 1.5,
+        "enlarged {} vs upscaled {}",
+        sharpness(&enlarged),
+        sharpness(&upscaled)
+    );
+    // Tone does not depend on the working size
+    let mean = |image: &RgbImage| FilmMetrics::analyze(image).mean_rgb[1];
+    for image in [&enlarged, &reduced] {
+        assert!((mean(image) - mean(&plain)).abs() < 3.0, "{}", mean(image));
+    }
+}
+
+#[test]
+fn test_shutter_speed_only_drives_reciprocity() {
+    let input = test_input();
+    let film = KODAK_TRI_X_400();
+    for simulation_mode in [SimulationMode::Fast, SimulationMode::Accurate] {
+        let render = |config: SimulationConfig| {
+            let config = SimulationConfig {
+                simulation_mode,
+                enable_grain: false,
+                ..config
+            };
+            process_image(&input, &film, &config)
+        };
+
+        // A short shutter develops like no shutter at all, not stops under
+        let plain = render(SimulationConfig::default());
+        let short = render(SimulationConfig {
+            shutter_speed: Some(ShutterSpeed::Stop(1.0 / 125.0)),
+            ..Default::default()
+        });
+        assert_eq!(plain, short, "{simulation_mode:?}");
+
+        // A 30 s shutter loses the stock's reciprocity factor
+        let long = render(SimulationConfig {
+            shutter_speed: Some(ShutterSpeed::Stop(30.0)),
+            ..Default::default()
+        });
+        let once = render(SimulationConfig {
+            exposure_time: film.reciprocity.effective_time(30.0) / 30.0,
+            ..Default::default()
+        });
+        assert_eq!(long, once, "{simulation_mode:?}");
+        assert_ne!(long, plain, "{simulation_mode:?}");
+
+        // A metered 30 s shot through the exposure triangle loses it once
+        let night_ev = (8.0f32 * 8.0 / 30.0 / 4.0).log2();
+        let night = render(SimulationConfig::from_exposure_triangle(
+            &film,
+            8.0,
+            ShutterSpeed::Stop(30.0),
+            night_ev,
+        ));
+        let mean = |image: &RgbImage| FilmMetrics::analyze(image).mean_rgb[1];
+        assert!(
+            (mean(&night) - mean(&once)).abs() < 0.5,
+            "{simulation_mode:?}: {} vs {}",
+            mean(&night),
+            mean(&once)
+        );
+    }
+}
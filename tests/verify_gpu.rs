@@ -16,6 +16,7 @@ fn test_gpu_full_pipeline() {
         light_leak: filmr::light_leak::LightLeakConfig {
             enabled: true,
             leaks: vec![filmr::light_leak::LightLeak::default()],
+            ..Default::default()
         },
         ..Default::default()
     };
@@ -83,3 +84,405 @@ fn test_gpu_halation_effect() {
         "Halation tint is red, so R should be > B"
     );
 }
+
+#[cfg(feature = "compute-gpu")]
+#[test]
+fn test_gpu_buffers_are_reused_across_previews() {
+    let Some(ctx) = filmr::gpu::get_gpu_context() else {
+        println!("No GPU adapter, skipping buffer pool test");
+        return;
+    };
+
+    let input = RgbImage::from_fn(64, 64, |x, y| Rgb([(x * 4) as u8, (y * 4) as u8, 128]));
+    let mut film = STANDARD_DAYLIGHT();
+    film.halation_strength2 = 0.2;
+    film.halation_sigma2 = 0.05;
+    let config = SimulationConfig {
+        use_gpu: true,
+        enable_grain: false,
+        ..Default::default()
+    };
+
+    // Warm up so every buffer size has been allocated once.
+    process_image(&input, &film, &config);
+    let warm = ctx.buffer_pool.allocation_count();
+
+    for _ in 0..200 {
+        process_image(&input, &film, &config);
+    }
+    assert_eq!(
+        ctx.buffer_pool.allocation_count(),
+        warm,
+        "steady-state previews should not allocate new image buffers"
+    );
+}
+
+#[cfg(feature = "compute-gpu")]
+#[test]
+fn test_other_power_preference_gets_the_live_context() {
+    use filmr::processor::GpuPowerPreference;
+
+    let Some(ctx) = filmr::gpu::get_gpu_context() else {
+        println!("No GPU adapter, skipping power preference test");
+        return;
+    };
+    let other = match ctx.power_preference {
+        GpuPowerPreference::HighPerformance => GpuPowerPreference::LowPower,
+        GpuPowerPreference::LowPower => GpuPowerPreference::HighPerformance,
+    };
+    // The adapter is chosen once per process; the request only logs a warning
+    let again = filmr::gpu::get_gpu_context_with_preference(other).unwrap();
+    assert!(std::ptr::eq(ctx, again));
+    assert_ne!(again.power_preference, other);
+}
+
+#[cfg(feature = "compute-gpu")]
+#[test]
+fn test_buffer_pool_drops_the_oldest_sizes_past_its_budget() {
+    use filmr::gpu::{BufferPool, IMAGE_BUFFER_USAGE};
+
+    let Some(ctx) = filmr::gpu::get_gpu_context() else {
+        println!("No GPU adapter, skipping buffer pool test");
+        return;
+    };
+
+    // A fresh pool, so other tests sharing the context do not interfere
+    let pool = BufferPool::default();
+    let mib = 1u64 << 20;
+    let count = BufferPool::MAX_FREE_BYTES / (4 * mib) + 8;
+    let sizes: Vec<u64> = (0..count).map(|i| 4 * mib + i * 256).collect();
+    for &size in &sizes {
+        let buffer = pool.acquire(&ctx.device, "pool test", size, IMAGE_BUFFER_USAGE);
+        pool.release(buffer);
+    }
+    assert!(pool.free_bytes() <= BufferPool::MAX_FREE_BYTES);
+
+    // The last size is still pooled, the first one was dropped
+    let allocated = pool.allocation_count();
+    let last = sizes[sizes.len() - 1];
+    pool.release(pool.acquire(&ctx.device, "pool test", last, IMAGE_BUFFER_USAGE));
+    assert_eq!(pool.allocation_count(), allocated);
+    pool.acquire(&ctx.device, "pool test", sizes[0], IMAGE_BUFFER_USAGE);
+    assert_eq!(pool.allocation_count(), allocated + 1);
+}
+
+#[test]
+fn test_batch_matches_individual_processing_in_order() {
+    let inputs: Vec<RgbImage> = (0..3u32)
+        .map(|i| {
+            RgbImage::from_fn(32 + i * 8, 24, |x, y| {
+                Rgb([(x * 6) as u8, (y * 9) as u8, (i * 80) as u8])
+            })
+        })
+        .collect();
+    let films = [
+        STANDARD_DAYLIGHT(),
+        filmr::presets::kodak::KODAK_PORTRA_400(),
+        filmr::presets::fujifilm::SUPERIA_200(),
+    ];
+    let gpu = SimulationConfig {
+        use_gpu: true,
+        enable_grain: false,
+        ..Default::default()
+    };
+    let cpu = SimulationConfig {
+        use_gpu: false,
+        ..gpu.clone()
+    };
+    let configs = [&gpu, &cpu, &gpu];
+
+    let items: Vec<_> = inputs
+        .iter()
+        .zip(&films)
+        .zip(configs)
+        .map(|((input, film), config)| (input, film, config))
+        .collect();
+    let batch = filmr::process_images_batch(&items);
+
+    assert_eq!(batch.len(), items.len());
+    for ((input, film, config), output) in items.iter().zip(&batch) {
+        assert_eq!(output.dimensions(), input.dimensions());
+        assert_eq!(output, &process_image(input, film, config));
+    }
+}
+
+#[cfg(feature = "compute-gpu")]
+#[test]
+fn test_gpu_grain_matches_cpu_grain_texture() {
+    use filmr::pipeline::{GrainStage, PipelineContext, PipelineStage};
+
+    let Some(ctx) = filmr::gpu::get_gpu_context() else {
+        println!("No GPU adapter, skipping grain comparison");
+        return;
+    };
+
+    let film = filmr::presets::kodak::KODAK_PORTRA_400();
+    let config = SimulationConfig {
+        grain_seed: Some(3),
+        ..Default::default()
+    };
+    let flat: image::ImageBuffer<Rgb<f32>, Vec<f32>> =
+        image::ImageBuffer::from_pixel(512, 512, Rgb([1.0, 1.0, 1.0]));
+
+    let mut cpu = flat.clone();
+    let context = PipelineContext {
+        film: &film,
+        config: &config,
+        depth_map: None,
+    };
+    GrainStage.process(&mut cpu, &context);
+
+    let size = (flat.as_raw().len() * 4) as u64;
+    let patch = filmr::gpu::GpuBuffer {
+        buffer: ctx.acquire_image_buffer("Flat Patch", size),
+        width: flat.width(),
+        height: flat.height(),
+        size,
+    };
+    ctx.queue
+        .write_buffer(&patch.buffer, 0, bytemuck::cast_slice(flat.as_raw()));
+    let grained = filmr::gpu_pipelines::get_grain_pipeline(ctx)
+        .process(ctx, &patch, &film)
+        .unwrap();
+    let gpu = filmr::gpu::block_on(filmr::gpu_pipelines::read_gpu_buffer(ctx, &grained)).unwrap();
+
+    // Spread the density noise over 8-bit levels around mid gray
+    let to_u8 = |img: &image::ImageBuffer<Rgb<f32>, Vec<f32>>| {
+        RgbImage::from_fn(img.width(), img.height(), |x, y| {
+            let p = img.get_pixel(x, y);
+            Rgb(std::array::from_fn(|c| {
+                ((p[c] - 1.0) * 2000.0 + 128.0).clamp(0.0, 255.0) as u8
+            }))
+        })
+    };
+    let (cpu, gpu) = (to_u8(&cpu), to_u8(&gpu));
+    let diff = filmr::metrics::compare(&gpu, &cpu).unwrap();
+    let cpu_lv = filmr::FilmMetrics::analyze(&cpu).laplacian_variance;
+    let gpu_lv = filmr::FilmMetrics::analyze(&gpu).laplacian_variance;
+    println!("CPU LV {cpu_lv}, GPU LV {gpu_lv}, {diff:?}");
+    assert!(
+        (0.75..1.33).contains(&(gpu_lv / cpu_lv)),
+        "GPU grain texture should match CPU: {gpu_lv} vs {cpu_lv}"
+    );
+    // Same mean density, so the grain fields only differ by noise
+    assert!(diff.mean_delta_e < 20.0);
+}
+
+#[cfg(feature = "compute-gpu")]
+#[test]
+fn test_gpu_chromatic_aberration_matches_cpu() {
+    let input = RgbImage::from_fn(96, 64, |x, y| {
+        Rgb([((x * 7) % 256) as u8, ((y * 11) % 256) as u8, 128])
+    });
+    let film = STANDARD_DAYLIGHT();
+    let gpu = SimulationConfig {
+        use_gpu: true,
+        enable_grain: false,
+        lens: filmr::LensConfig {
+            chromatic_aberration: 4.0,
+            ..Default::default()
+        },
+        // Lead with the stage so the GPU runs it
+        pipeline: vec![
+            filmr::StageKind::ChromaticAberration,
+            filmr::StageKind::Develop,
+        ],
+        ..Default::default()
+    };
+    let cpu = SimulationConfig {
+        use_gpu: false,
+        ..gpu.clone()
+    };
+    let diff = filmr::metrics::compare(
+        &process_image(&input, &film, &gpu),
+        &process_image(&input, &film, &cpu),
+    )
+    .unwrap();
+    assert!(diff.max_channel_diff <= 2, "{diff:?}");
+}
+
+#[cfg(feature = "compute-gpu")]
+#[test]
+fn test_gpu_diffusion_matches_cpu() {
+    let input = RgbImage::from_fn(96, 64, |x, y| {
+        Rgb([((x * 7) % 256) as u8, ((y * 11) % 256) as u8, 128])
+    });
+    let film = STANDARD_DAYLIGHT();
+    let gpu = SimulationConfig {
+        use_gpu: true,
+        enable_grain: false,
+        lens: filmr::LensConfig {
+            diffusion: 0.8,
+            ..Default::default()
+        },
+        pipeline: vec![filmr::StageKind::Diffusion, filmr::StageKind::Develop],
+        ..Default::default()
+    };
+    let cpu = SimulationConfig {
+        use_gpu: false,
+        ..gpu.clone()
+    };
+    let diff = filmr::metrics::compare(
+        &process_image(&input, &film, &gpu),
+        &process_image(&input, &film, &cpu),
+    )
+    .unwrap();
+    // The CPU glow is three box passes and the GPU glow a true Gaussian of
+    // the same sigma; their kernels part by a few codes at the hard wraps
+    // of the ramp, but not on average.
+    assert!(diff.max_channel_diff <= 4, "{diff:?}");
+    assert!(diff.mean_delta_e < 0.5, "{diff:?}");
+}
+
+/// Upload a linear image to a GPU buffer.
+#[cfg(feature = "compute-gpu")]
+fn upload(
+    ctx: &filmr::gpu::GpuContext,
+    image: &image::ImageBuffer<Rgb<f32>, Vec<f32>>,
+) -> filmr::gpu::GpuBuffer {
+    let size = (image.as_raw().len() * 4) as u64;
+    let buffer = filmr::gpu::GpuBuffer {
+        buffer: ctx.acquire_image_buffer("Reference Input", size),
+        width: image.width(),
+        height: image.height(),
+        size,
+    };
+    ctx.queue
+        .write_buffer(&buffer.buffer, 0, bytemuck::cast_slice(image.as_raw()));
+    buffer
+}
+
+/// 8-bit image of linear values, `scale` levels per unit.
+#[cfg(all(feature = "compute-gpu", feature = "reference"))]
+fn quantize(image: &image::ImageBuffer<Rgb<f32>, Vec<f32>>, scale: f32) -> RgbImage {
+    RgbImage::from_fn(image.width(), image.height(), |x, y| {
+        let p = image.get_pixel(x, y);
+        Rgb(p.0.map(|v| (v * scale).round().clamp(0.0, 255.0) as u8))
+    })
+}
+
+/// Scene-linear ramp with a few hot highlights.
+#[cfg(all(feature = "compute-gpu", feature = "reference"))]
+fn reference_scene() -> image::ImageBuffer<Rgb<f32>, Vec<f32>> {
+    image::ImageBuffer::from_fn(96, 64, |x, y| {
+        if (x % 32, y % 32) == (16, 16) {
+            Rgb([8.0, 6.0, 4.0])
+        } else {
+            Rgb([x as f32 / 96.0, y as f32 / 64.0, 0.18])
+        }
+    })
+}
+
+#[cfg(all(feature = "compute-gpu", feature = "reference"))]
+#[test]
+fn test_gpu_develop_matches_reference() {
+    let Some(ctx) = filmr::gpu::get_gpu_context() else {
+        println!("No GPU adapter, skipping develop reference test");
+        return;
+    };
+
+    let scene = reference_scene();
+    let spectral_matrix = [[0.9, 0.08, 0.02], [0.05, 0.9, 0.05], [0.02, 0.08, 0.9]];
+    let wb_gains = [1.05, 1.0, 0.95];
+    let t_eff = 1.3;
+    for film in [
+        STANDARD_DAYLIGHT(),
+        filmr::presets::kodak::KODAK_TRI_X_400(),
+    ] {
+        let input = upload(ctx, &scene);
+        let output = filmr::gpu_pipelines::get_develop_pipeline(ctx)
+            .process(ctx, &input, &film, &spectral_matrix, wb_gains, t_eff)
+            .unwrap();
+        let gpu =
+            filmr::gpu::block_on(filmr::gpu_pipelines::read_gpu_buffer(ctx, &output)).unwrap();
+        let cpu = filmr::reference::develop(&scene, &film, &spectral_matrix, wb_gains, t_eff);
+
+        // 100 levels per unit of density
+        let diff = filmr::metrics::compare(&quantize(&gpu, 100.0), &quantize(&cpu, 100.0)).unwrap();
+        assert!(diff.max_channel_diff <= 1, "{}: {diff:?}", film.name);
+    }
+}
+
+#[cfg(all(feature = "compute-gpu", feature = "reference"))]
+#[test]
+fn test_gpu_halation_matches_reference() {
+    let Some(ctx) = filmr::gpu::get_gpu_context() else {
+        println!("No GPU adapter, skipping halation reference test");
+        return;
+    };
+
+    let scene = reference_scene();
+    let mut film = STANDARD_DAYLIGHT();
+    film.halation_strength = 0.4;
+    film.halation_sigma = 0.03;
+    film.halation_strength2 = 0.1;
+    film.halation_sigma2 = 0.2;
+
+    let input = upload(ctx, &scene);
+    let output = filmr::gpu_pipelines::get_halation_pipeline(ctx)
+        .process(ctx, &input, &film)
+        .unwrap();
+    let gpu = filmr::gpu::block_on(filmr::gpu_pipelines::read_gpu_buffer(ctx, &output)).unwrap();
+    let cpu = filmr::reference::halation(&scene, &film);
+
+    // 255 levels per unit of scene light, highlights clip
+    let diff = filmr::metrics::compare(&quantize(&gpu, 255.0), &quantize(&cpu, 255.0)).unwrap();
+    assert!(diff.max_channel_diff <= 1, "{diff:?}");
+}
+
+#[cfg(feature = "compute-gpu")]
+#[test]
+fn test_gpu_grain_moments_match_cpu() {
+    use filmr::pipeline::{GrainStage, PipelineContext, PipelineStage};
+
+    let Some(ctx) = filmr::gpu::get_gpu_context() else {
+        println!("No GPU adapter, skipping grain moments test");
+        return;
+    };
+
+    // Grain noise comes from different generators; compare its statistics
+    let film = filmr::presets::kodak::KODAK_PORTRA_400();
+    let config = SimulationConfig {
+        grain_seed: Some(5),
+        ..Default::default()
+    };
+    let flat: image::ImageBuffer<Rgb<f32>, Vec<f32>> =
+        image::ImageBuffer::from_pixel(256, 256, Rgb([1.0, 1.0, 1.0]));
+
+    let mut cpu = flat.clone();
+    GrainStage.process(
+        &mut cpu,
+        &PipelineContext {
+            film: &film,
+            config: &config,
+            depth_map: None,
+        },
+    );
+    let input = upload(ctx, &flat);
+    let output = filmr::gpu_pipelines::get_grain_pipeline(ctx)
+        .process(ctx, &input, &film)
+        .unwrap();
+    let gpu = filmr::gpu::block_on(filmr::gpu_pipelines::read_gpu_buffer(ctx, &output)).unwrap();
+
+    let moments = |img: &image::ImageBuffer<Rgb<f32>, Vec<f32>>| {
+        let n = img.as_raw().len() as f64;
+        let mean = img.as_raw().iter().map(|&v| v as f64).sum::<f64>() / n;
+        let var = img
+            .as_raw()
+            .iter()
+            .map(|&v| (v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        (mean, var.sqrt())
+    };
+    let (cpu_mean, cpu_std) = moments(&cpu);
+    let (gpu_mean, gpu_std) = moments(&gpu);
+    assert!(
+        (gpu_mean - cpu_mean).abs() < 0.1 * cpu_std,
+        "mean density {gpu_mean} vs {cpu_mean}"
+    );
+    assert!(
+        (0.8..1.25).contains(&(gpu_std / cpu_std)),
+        "grain σ_D {gpu_std} vs {cpu_std}"
+    );
+}
@@ -1,9 +1,25 @@
 #[cfg(test)]
 mod tests {
-    use filmr::presets::kodak::KODAK_TRI_X_400;
+    use filmr::pipeline::apply_grain;
+    use filmr::presets::kodak::{KODAK_PORTRA_400, KODAK_TRI_X_400};
     use filmr::processor::{process_image, OutputMode, SimulationConfig, WhiteBalanceMode};
+    use filmr::utils::{resize_to_fit, PREVIEW_FILTER};
+    use filmr::{FilmFormat, FilmMetrics, FilmStock, GrainSpace, GrainStructure};
     use image::{Rgb, RgbImage};
 
+    /// Tri-X with the given grain strength and no read noise, developed
+    /// without white balance so a flat patch keeps its level.
+    fn grain_fixture(alpha: f32) -> (FilmStock, SimulationConfig) {
+        let mut film = KODAK_TRI_X_400();
+        film.grain_model.alpha = alpha;
+        film.grain_model.sigma_read = 0.0;
+        let config = SimulationConfig {
+            white_balance_mode: WhiteBalanceMode::Off,
+            ..Default::default()
+        };
+        (film, config)
+    }
+
     #[test]
     fn test_monochrome_grain_consistency() {
         // Setup a flat gray image
@@ -16,10 +32,8 @@ mod tests {
         }
 
         // Setup Monochrome Film (modified Tri-X)
-        let mut film = KODAK_TRI_X_400();
+        let (mut film, _) = grain_fixture(0.5); // High noise to be sure
         film.grain_model.monochrome = true;
-        film.grain_model.alpha = 0.5; // High noise to be sure
-        film.grain_model.sigma_read = 0.0;
 
         let config = SimulationConfig {
             exposure_time: 1.0,
@@ -67,10 +81,8 @@ mod tests {
         }
 
         // Setup Color Film (modified Tri-X but with monochrome=false)
-        let mut film = KODAK_TRI_X_400();
+        let (mut film, _) = grain_fixture(0.5); // High noise
         film.grain_model.monochrome = false;
-        film.grain_model.alpha = 0.5; // High noise
-        film.grain_model.sigma_read = 0.0;
         // Reset color matrix to identity to avoid color shifts from the matrix itself confusing things?
         // Actually, if we use identity matrix, output should be gray *plus* independent noise.
         film.color_matrix = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
@@ -106,4 +118,157 @@ mod tests {
             width * height
         );
     }
+
+    #[test]
+    fn test_preview_grain_matches_downscaled_full_res() {
+        let (full_w, full_h) = (576, 384);
+        let downscale = 4;
+
+        let (film, config) = grain_fixture(0.005);
+
+        // Full-resolution develop, downscaled the way the app shows it
+        let full_input = RgbImage::from_pixel(full_w, full_h, Rgb([100, 100, 100]));
+        let full = process_image(&full_input, &film, &config);
+        let full_viewed = resize_to_fit(&full, full_w / downscale, PREVIEW_FILTER);
+
+        // Preview rendered directly at the small size, aware of the source width
+        let preview_input =
+            RgbImage::from_pixel(full_w / downscale, full_h / downscale, Rgb([100, 100, 100]));
+        let preview_config = SimulationConfig {
+            source_width: Some(full_w),
+            ..config.clone()
+        };
+        let preview = process_image(&preview_input, &film, &preview_config);
+
+        let full_lv = FilmMetrics::analyze(&full_viewed).laplacian_variance;
+        let preview_lv = FilmMetrics::analyze(&preview).laplacian_variance;
+        assert!(full_lv > 0.0, "grain should survive the downscale");
+        let ratio = preview_lv / full_lv;
+        assert!(
+            (1.0 / 3.0..=3.0).contains(&ratio),
+            "preview Laplacian variance {preview_lv} vs downscaled full-res {full_lv}"
+        );
+    }
+
+    #[test]
+    fn test_grain_size_follows_film_format() {
+        let input = RgbImage::from_pixel(384, 256, Rgb([100, 100, 100]));
+        let (mut film, config) = grain_fixture(0.005);
+
+        // The same picture on a larger frame holds relatively finer, weaker grain
+        let small = FilmMetrics::analyze(&process_image(&input, &film, &config));
+        film.film_format = FilmFormat::Large4x5;
+        let large = FilmMetrics::analyze(&process_image(&input, &film, &config));
+        assert!(large.laplacian_variance < small.laplacian_variance);
+    }
+
+    #[test]
+    fn test_region_grain_matches_full_frame() {
+        let (full_w, full_h) = (576, 384);
+
+        let (film, config) = grain_fixture(0.005);
+
+        // A 128px crop of the full-resolution develop
+        let full_input = RgbImage::from_pixel(full_w, full_h, Rgb([100, 100, 100]));
+        let full = process_image(&full_input, &film, &config);
+        let crop = image::imageops::crop_imm(&full, 200, 120, 128, 128).to_image();
+
+        // The same region developed without grain, grained as part of the full frame
+        let clean_config = SimulationConfig {
+            enable_grain: false,
+            ..config.clone()
+        };
+        let region = RgbImage::from_pixel(128, 128, Rgb([100, 100, 100]));
+        let clean = process_image(&region, &film, &clean_config);
+        let grained = apply_grain(&clean, &film, full_w, full_h, config.grain_space);
+
+        let full_lv = FilmMetrics::analyze(&crop).laplacian_variance;
+        let region_lv = FilmMetrics::analyze(&grained).laplacian_variance;
+        assert!(full_lv > 0.0);
+        let ratio = region_lv / full_lv;
+        assert!(
+            (1.0 / 3.0..=3.0).contains(&ratio),
+            "region Laplacian variance {region_lv} vs full-frame crop {full_lv}"
+        );
+    }
+
+    #[test]
+    fn test_grain_space_changes_shadow_grain() {
+        let (film, _) = grain_fixture(0.0002);
+
+        // Standard deviation of the 8-bit green channel of a grained flat patch
+        let grain_std = |level: u8, space: GrainSpace| {
+            let patch = RgbImage::from_pixel(128, 128, Rgb([level, level, level]));
+            let grained = apply_grain(&patch, &film, 2048, 1365, space);
+            let values: Vec<f32> = grained.pixels().map(|p| p[1] as f32).collect();
+            let mean = values.iter().sum::<f32>() / values.len() as f32;
+            let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32;
+            var.sqrt()
+        };
+
+        // 118 is 18% gray, where the spaces are matched; 20 is a deep shadow
+        let mut shadow_ratio = Vec::new();
+        for space in GrainSpace::ALL {
+            let mid = grain_std(118, space);
+            let shadow = grain_std(20, space);
+            assert!(
+                (2.0..5.0).contains(&mid),
+                "{space:?}: mid gray grain σ {mid} levels"
+            );
+            shadow_ratio.push(shadow / mid);
+        }
+        let [linear, encoded, density] = shadow_ratio[..] else {
+            unreachable!()
+        };
+        // Linear keeps a floor of linear noise that encodes large in the shadows,
+        // sRGB grain is even, density grain follows the signal down
+        assert!(linear > encoded * 1.5, "linear {linear} vs sRGB {encoded}");
+        assert!(
+            encoded > density * 1.5,
+            "sRGB {encoded} vs density {density}"
+        );
+    }
+
+    #[test]
+    fn test_clumped_grain_steepens_psd_slope() {
+        let (mut film, _) = grain_fixture(0.0005);
+        let patch = RgbImage::from_pixel(256, 256, Rgb([118, 118, 118]));
+
+        // Clumps concentrate the grain power at low frequencies
+        let mut psd_slope = |structure| {
+            film.grain_model.structure = structure;
+            let grained = apply_grain(&patch, &film, 2048, 1365, GrainSpace::Linear);
+            FilmMetrics::analyze(&grained).psd_slope
+        };
+        let per_pixel = psd_slope(GrainStructure::PerPixel);
+        let clumped = psd_slope(GrainStructure::Clumped { size: 6.0 });
+        assert!(
+            clumped > per_pixel + 0.15,
+            "clumped PSD slope {clumped} vs per-pixel {per_pixel}"
+        );
+    }
+
+    #[test]
+    fn test_seeded_grain_ignores_thread_count() {
+        let input = RgbImage::from_fn(96, 64, |x, y| Rgb([(x * 2) as u8, (y * 3) as u8, 128]));
+        let config = SimulationConfig {
+            grain_seed: Some(2024),
+            ..Default::default()
+        };
+        let develop_on = |threads: usize, film: &filmr::FilmStock| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| process_image(&input, film, &config))
+        };
+
+        let mut clumped = KODAK_TRI_X_400();
+        clumped.grain_model.structure = GrainStructure::Clumped { size: 6.0 };
+        for film in [KODAK_PORTRA_400(), KODAK_TRI_X_400(), clumped] {
+            let single = develop_on(1, &film);
+            assert_eq!(single, develop_on(4, &film), "{}", film.name);
+            assert_eq!(single, develop_on(7, &film), "{}", film.name);
+        }
+    }
 }